@@ -173,6 +173,15 @@ impl EntityType {
         matches!(self, EntityType::GlobalPackage)
     }
 
+    pub const fn is_global_account(&self) -> bool {
+        matches!(
+            self,
+            EntityType::GlobalAccount
+                | EntityType::GlobalVirtualSecp256k1Account
+                | EntityType::GlobalVirtualEd25519Account
+        )
+    }
+
     pub const fn is_global_consensus_manager(&self) -> bool {
         matches!(self, EntityType::GlobalConsensusManager)
     }
@@ -66,6 +66,10 @@ impl NodeId {
         matches!(self.entity_type(), Some(t) if t.is_global_package())
     }
 
+    pub const fn is_global_account(&self) -> bool {
+        matches!(self.entity_type(), Some(t) if t.is_global_account())
+    }
+
     pub const fn is_global_consensus_manager(&self) -> bool {
         matches!(self.entity_type(), Some(t) if t.is_global_consensus_manager())
     }
@@ -0,0 +1,38 @@
+use crate::sbor::rust::prelude::*;
+use radix_engine_common::crypto::{
+    Ed25519PublicKey, Ed25519Signature, Hash, Secp256k1PublicKey, Secp256k1Signature,
+};
+
+/// Cryptographic primitives, executed natively by the engine (and costed per call), so that
+/// blueprints do not have to compile them into WASM.
+pub trait ClientCryptoUtilsApi<E> {
+    fn blake2b_256_hash(&mut self, data: Vec<u8>) -> Result<Hash, E>;
+
+    fn keccak256_hash(&mut self, data: Vec<u8>) -> Result<Hash, E>;
+
+    fn sha256_hash(&mut self, data: Vec<u8>) -> Result<Hash, E>;
+
+    /// Verifies an ECDSA signature of the given (32-byte) message hash.
+    fn secp256k1_ecdsa_verify(
+        &mut self,
+        message_hash: Hash,
+        public_key: Secp256k1PublicKey,
+        signature: Secp256k1Signature,
+    ) -> Result<bool, E>;
+
+    /// Recovers the public key from a recoverable ECDSA signature of the given (32-byte) message
+    /// hash, or returns [`Option::None`] if the signature is invalid.
+    fn secp256k1_ecdsa_recover(
+        &mut self,
+        message_hash: Hash,
+        signature: Secp256k1Signature,
+    ) -> Result<Option<Secp256k1PublicKey>, E>;
+
+    /// Verifies an Ed25519 signature of the given (32-byte) message hash.
+    fn ed25519_verify(
+        &mut self,
+        message_hash: Hash,
+        public_key: Ed25519PublicKey,
+        signature: Ed25519Signature,
+    ) -> Result<bool, E>;
+}
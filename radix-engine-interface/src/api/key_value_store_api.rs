@@ -24,4 +24,14 @@ pub trait ClientKeyValueStoreApi<E> {
         node_id: &NodeId,
         key: &Vec<u8>,
     ) -> Result<Vec<u8>, E>;
+
+    /// Returns at most `limit` entries of a key value store, as `(key, value)` payload pairs, in an
+    /// unspecified (but stable) order, starting from the given key (inclusive), together with the
+    /// key to continue the scan from (if there are more entries)
+    fn key_value_store_scan(
+        &mut self,
+        node_id: &NodeId,
+        start_key: Option<Vec<u8>>,
+        limit: u32,
+    ) -> Result<(Vec<(Vec<u8>, Vec<u8>)>, Option<Vec<u8>>), E>;
 }
@@ -3,6 +3,7 @@ pub mod actor_index_api;
 pub mod actor_key_value_entry_api;
 pub mod actor_sorted_index_api;
 pub mod blueprint_api;
+pub mod crypto_utils_api;
 pub mod field_lock_api;
 pub mod key_value_entry_api;
 pub mod key_value_store_api;
@@ -16,6 +17,7 @@ use actor_index_api::ClientActorIndexApi;
 pub use actor_key_value_entry_api::ClientActorKeyValueEntryApi;
 pub use actor_sorted_index_api::ClientActorSortedIndexApi;
 pub use blueprint_api::ClientBlueprintApi;
+pub use crypto_utils_api::ClientCryptoUtilsApi;
 pub use field_lock_api::ClientFieldLockApi;
 pub use field_lock_api::LockFlags;
 use key_value_entry_api::ClientKeyValueEntryApi;
@@ -49,6 +51,7 @@ pub trait ClientApi<E: sbor::rust::fmt::Debug>:
     + ClientBlueprintApi<E>
     + ClientCostingApi<E>
     + ClientTransactionRuntimeApi<E>
+    + ClientCryptoUtilsApi<E>
     + ClientExecutionTraceApi<E>
     + ClientAuthApi<E>
 {
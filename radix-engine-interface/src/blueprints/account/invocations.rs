@@ -255,6 +255,32 @@ pub struct AccountConfigureResourceDepositRuleInput {
 
 pub type AccountConfigureResourceDepositRuleOutput = ();
 
+//==================================
+// Account Add Authorized Depositor
+//==================================
+
+pub const ACCOUNT_ADD_AUTHORIZED_DEPOSITOR_IDENT: &str = "add_authorized_depositor";
+
+#[derive(Debug, Eq, PartialEq, ScryptoSbor, ManifestSbor)]
+pub struct AccountAddAuthorizedDepositorInput {
+    pub badge: ResourceOrNonFungible,
+}
+
+pub type AccountAddAuthorizedDepositorOutput = ();
+
+//=====================================
+// Account Remove Authorized Depositor
+//=====================================
+
+pub const ACCOUNT_REMOVE_AUTHORIZED_DEPOSITOR_IDENT: &str = "remove_authorized_depositor";
+
+#[derive(Debug, Eq, PartialEq, ScryptoSbor, ManifestSbor)]
+pub struct AccountRemoveAuthorizedDepositorInput {
+    pub badge: ResourceOrNonFungible,
+}
+
+pub type AccountRemoveAuthorizedDepositorOutput = ();
+
 //===============================
 // Account Try Deposit Or Refund
 //===============================
@@ -264,6 +290,9 @@ pub const ACCOUNT_TRY_DEPOSIT_OR_REFUND_IDENT: &str = "try_deposit_or_refund";
 #[derive(Debug, Eq, PartialEq, ScryptoSbor)]
 pub struct AccountTryDepositOrRefundInput {
     pub bucket: Bucket,
+    /// A proof of one of the account's authorized depositor badges, which allows the deposit
+    /// of resources rejected by the account's default deposit rule.
+    pub authorized_depositor_badge: Option<Proof>,
 }
 
 pub type AccountTryDepositOrRefundOutput = Option<Bucket>;
@@ -281,6 +310,23 @@ pub struct AccountTryDepositBatchOrRefundInput {
 
 pub type AccountTryDepositBatchOrRefundOutput = Vec<Bucket>;
 
+//================================================
+// Account Try Deposit Batch Or Refund With Badge
+//================================================
+
+pub const ACCOUNT_TRY_DEPOSIT_BATCH_OR_REFUND_WITH_BADGE_IDENT: &str =
+    "try_deposit_batch_or_refund_with_badge";
+
+#[derive(Debug, Eq, PartialEq, ScryptoSbor)]
+pub struct AccountTryDepositBatchOrRefundWithBadgeInput {
+    pub buckets: Vec<Bucket>,
+    /// A proof of one of the account's authorized depositor badges, which allows the deposit
+    /// of resources rejected by the account's default deposit rule.
+    pub authorized_depositor_badge: Proof,
+}
+
+pub type AccountTryDepositBatchOrRefundWithBadgeOutput = Vec<Bucket>;
+
 //==============================
 // Account Try Deposit Or Abort
 //==============================
@@ -290,6 +336,9 @@ pub const ACCOUNT_TRY_DEPOSIT_OR_ABORT_IDENT: &str = "try_deposit_or_abort";
 #[derive(Debug, Eq, PartialEq, ScryptoSbor)]
 pub struct AccountTryDepositOrAbortInput {
     pub bucket: Bucket,
+    /// A proof of one of the account's authorized depositor badges, which allows the deposit
+    /// of resources rejected by the account's default deposit rule.
+    pub authorized_depositor_badge: Option<Proof>,
 }
 
 pub type AccountTryDepositOrAbortOutput = ();
@@ -307,6 +356,23 @@ pub struct AccountTryDepositBatchOrAbortInput {
 
 pub type AccountTryDepositBatchOrAbortOutput = ();
 
+//===============================================
+// Account Try Deposit Batch Or Abort With Badge
+//===============================================
+
+pub const ACCOUNT_TRY_DEPOSIT_BATCH_OR_ABORT_WITH_BADGE_IDENT: &str =
+    "try_deposit_batch_or_abort_with_badge";
+
+#[derive(Debug, Eq, PartialEq, ScryptoSbor)]
+pub struct AccountTryDepositBatchOrAbortWithBadgeInput {
+    pub buckets: Vec<Bucket>,
+    /// A proof of one of the account's authorized depositor badges, which allows the deposit
+    /// of resources rejected by the account's default deposit rule.
+    pub authorized_depositor_badge: Proof,
+}
+
+pub type AccountTryDepositBatchOrAbortWithBadgeOutput = ();
+
 //============================
 // Account Burn
 //============================
@@ -0,0 +1,137 @@
+use crate::blueprints::macros::*;
+use crate::blueprints::resource::*;
+use radix_engine_common::data::manifest::model::*;
+use radix_engine_common::math::*;
+use radix_engine_common::prelude::*;
+use radix_engine_common::*;
+
+pub const ACCOUNT_LOCKER_BLUEPRINT: &str = "AccountLocker";
+
+/// The resources to store for, claim by or recover from a claimant of an account locker.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor, ManifestSbor)]
+pub enum AccountLockerResources {
+    Fungible(Decimal),
+    NonFungible(BTreeSet<NonFungibleLocalId>),
+}
+
+define_invocation! {
+    blueprint_name: AccountLocker,
+    function_name: instantiate,
+    input: struct {
+        owner_role: OwnerRole
+    },
+    output: type ComponentAddress,
+    manifest_input: struct {
+        owner_role: OwnerRole
+    }
+}
+
+define_invocation! {
+    blueprint_name: AccountLocker,
+    function_name: store,
+    input: struct {
+        claimant: ComponentAddress,
+        bucket: Bucket,
+        try_direct_send: bool
+    },
+    output: type (),
+    manifest_input: struct {
+        claimant: ComponentAddress,
+        bucket: ManifestBucket,
+        try_direct_send: bool
+    }
+}
+
+define_invocation! {
+    blueprint_name: AccountLocker,
+    function_name: airdrop,
+    input: struct {
+        claimants: BTreeMap<ComponentAddress, AccountLockerResources>,
+        bucket: Bucket,
+        try_direct_send: bool
+    },
+    output: type Option<Bucket>,
+    manifest_input: struct {
+        claimants: BTreeMap<ComponentAddress, AccountLockerResources>,
+        bucket: ManifestBucket,
+        try_direct_send: bool
+    }
+}
+
+define_invocation! {
+    blueprint_name: AccountLocker,
+    function_name: recover,
+    input: struct {
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress,
+        amount: Decimal
+    },
+    output: type Bucket,
+    manifest_input: struct {
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress,
+        amount: Decimal
+    }
+}
+
+define_invocation! {
+    blueprint_name: AccountLocker,
+    function_name: recover_non_fungibles,
+    input: struct {
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress,
+        ids: BTreeSet<NonFungibleLocalId>
+    },
+    output: type Bucket,
+    manifest_input: struct {
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress,
+        ids: BTreeSet<NonFungibleLocalId>
+    }
+}
+
+define_invocation! {
+    blueprint_name: AccountLocker,
+    function_name: claim,
+    input: struct {
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress,
+        amount: Decimal
+    },
+    output: type Bucket,
+    manifest_input: struct {
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress,
+        amount: Decimal
+    }
+}
+
+define_invocation! {
+    blueprint_name: AccountLocker,
+    function_name: claim_non_fungibles,
+    input: struct {
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress,
+        ids: BTreeSet<NonFungibleLocalId>
+    },
+    output: type Bucket,
+    manifest_input: struct {
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress,
+        ids: BTreeSet<NonFungibleLocalId>
+    }
+}
+
+define_invocation! {
+    blueprint_name: AccountLocker,
+    function_name: get_amount,
+    input: struct {
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress
+    },
+    output: type Decimal,
+    manifest_input: struct {
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress
+    }
+}
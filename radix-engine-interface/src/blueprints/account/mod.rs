@@ -1,3 +1,5 @@
 mod invocations;
+mod locker_invocations;
 
 pub use invocations::*;
+pub use locker_invocations::*;
@@ -49,6 +49,11 @@ pub struct ConsensusManagerConfig {
     pub num_fee_increase_delay_epochs: u64,
 
     pub validator_creation_xrd_cost: Decimal,
+
+    /// The proportion of the stake of the active validators which needs to signal readiness for
+    /// a protocol version, for the protocol update to it to be enacted.
+    /// Should be between 0 and 1
+    pub protocol_update_readiness_threshold: Decimal,
 }
 
 impl ConsensusManagerConfig {
@@ -86,6 +91,11 @@ impl ConsensusManagerConfig {
         self.num_fee_increase_delay_epochs = new_value;
         self
     }
+
+    pub fn with_protocol_update_readiness_threshold(mut self, new_value: Decimal) -> Self {
+        self.protocol_update_readiness_threshold = new_value;
+        self
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default, ScryptoSbor, ManifestSbor)]
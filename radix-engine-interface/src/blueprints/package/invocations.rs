@@ -90,6 +90,39 @@ pub struct PackageClaimRoyaltiesInput {}
 
 pub type PackageClaimRoyaltiesOutput = Bucket;
 
+pub const PACKAGE_UPGRADE_BLUEPRINT_IDENT: &str = "upgrade_blueprint";
+
+#[derive(Debug, Clone, Eq, PartialEq, ScryptoSbor)]
+pub struct PackageUpgradeBlueprintInput {
+    pub blueprint: String,
+    pub version: BlueprintVersion,
+    pub code: Vec<u8>,
+    pub definition: BlueprintDefinitionInit,
+    /// The functions of the new version migrating the fields of the components of previous
+    /// versions, by the version they migrate from.
+    pub migrations: BTreeMap<BlueprintVersion, String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, ManifestSbor)]
+pub struct PackageUpgradeBlueprintManifestInput {
+    pub blueprint: String,
+    pub version: BlueprintVersion,
+    pub code: ManifestBlobRef,
+    pub definition: BlueprintDefinitionInit,
+    pub migrations: BTreeMap<BlueprintVersion, String>,
+}
+
+pub type PackageUpgradeBlueprintOutput = ();
+
+pub const PACKAGE_UPGRADE_COMPONENT_IDENT: &str = "upgrade_component";
+
+#[derive(Debug, Clone, Eq, PartialEq, ScryptoSbor, ManifestSbor)]
+pub struct PackageUpgradeComponentInput {
+    pub component: ComponentAddress,
+}
+
+pub type PackageUpgradeComponentOutput = BlueprintVersion;
+
 #[derive(Debug, Clone, Eq, PartialEq, Default, ScryptoSbor, ManifestSbor)]
 pub struct PackageDefinition {
     pub blueprints: BTreeMap<String, BlueprintDefinitionInit>,
@@ -33,6 +33,8 @@ pub const PACKAGE_VM_TYPE_PARTITION_OFFSET: PartitionOffset = PartitionOffset(6u
 pub const PACKAGE_ORIGINAL_CODE_PARTITION_OFFSET: PartitionOffset = PartitionOffset(7u8);
 pub const PACKAGE_INSTRUMENTED_CODE_PARTITION_OFFSET: PartitionOffset = PartitionOffset(8u8);
 
+pub const PACKAGE_BLUEPRINT_UPGRADES_PARTITION_OFFSET: PartitionOffset = PartitionOffset(9u8);
+
 #[derive(Copy, Debug, Clone, PartialEq, Eq, Sbor)]
 pub enum VmType {
     Native,
@@ -97,7 +99,7 @@ pub struct FunctionSchema {
     pub output: TypePointer,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, ScryptoSbor, Ord, PartialOrd, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ScryptoSbor, ManifestSbor, Ord, PartialOrd, Hash)]
 pub struct BlueprintVersion {
     pub major: u32,
     pub minor: u32,
@@ -134,6 +136,49 @@ impl BlueprintVersionKey {
             version: BlueprintVersion::default(),
         }
     }
+
+    pub fn new<S: ToString>(blueprint: S, version: BlueprintVersion) -> Self {
+        Self {
+            blueprint: blueprint.to_string(),
+            version,
+        }
+    }
+}
+
+/// A version added to a blueprint by an upgrade of its package.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct BlueprintUpgrade {
+    pub version: BlueprintVersion,
+    /// The functions migrating the fields of the components of a previous version to this one,
+    /// by the version they migrate from.
+    pub migrations: BTreeMap<BlueprintVersion, String>,
+}
+
+/// The upgrades of a blueprint, in order. A blueprint which has never been upgraded only has
+/// the default version.
+#[derive(Debug, Clone, PartialEq, Eq, Default, ScryptoSbor)]
+#[sbor(transparent)]
+pub struct BlueprintUpgrades {
+    pub upgrades: Vec<BlueprintUpgrade>,
+}
+
+impl BlueprintUpgrades {
+    pub fn latest_version(&self) -> BlueprintVersion {
+        self.upgrades
+            .last()
+            .map(|upgrade| upgrade.version)
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, version: &BlueprintVersion) -> Option<&BlueprintUpgrade> {
+        self.upgrades
+            .iter()
+            .find(|upgrade| upgrade.version.eq(version))
+    }
+
+    pub fn contains_version(&self, version: &BlueprintVersion) -> bool {
+        version.eq(&BlueprintVersion::default()) || self.get(version).is_some()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
@@ -10,6 +10,7 @@ mod resource;
 mod resource_manager;
 mod resource_type;
 mod vault;
+mod vesting_vault;
 mod worktop;
 
 pub use access_rules::*;
@@ -25,6 +26,7 @@ pub use resource_manager::ResourceAction::*;
 pub use resource_manager::*;
 pub use resource_type::*;
 pub use vault::*;
+pub use vesting_vault::*;
 pub use worktop::*;
 
 use radix_engine_common::math::*;
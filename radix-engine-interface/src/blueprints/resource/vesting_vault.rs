@@ -0,0 +1,128 @@
+use crate::blueprints::resource::*;
+use crate::math::*;
+use crate::*;
+#[cfg(feature = "radix_engine_fuzzing")]
+use arbitrary::Arbitrary;
+use radix_engine_common::data::manifest::model::ManifestBucket;
+use radix_engine_common::time::Instant;
+use radix_engine_common::types::*;
+use sbor::rust::prelude::*;
+
+pub const VESTING_VAULT_BLUEPRINT: &str = "VestingVault";
+
+/// The schedule on which the resources of a vesting vault are released. Nothing is released before
+/// the cliff, after which the amount released grows linearly from the start until everything is
+/// released at the end. A cliff equal to the end makes for a schedule which releases everything at
+/// once.
+#[derive(Debug, Clone, Eq, PartialEq, ScryptoSbor, ManifestSbor)]
+pub enum VestingSchedule {
+    Time {
+        start: Instant,
+        cliff: Instant,
+        end: Instant,
+    },
+    Epoch {
+        start: Epoch,
+        cliff: Epoch,
+        end: Epoch,
+    },
+}
+
+impl VestingSchedule {
+    /// Checks that the start, the cliff and the end of the schedule are in order.
+    pub fn is_valid(&self) -> bool {
+        let (start, cliff, end) = self.points();
+        start <= cliff && cliff <= end
+    }
+
+    /// Returns the amount of the total amount which is still locked at the given time or epoch,
+    /// depending on what the schedule is keyed on.
+    pub fn locked_amount(
+        &self,
+        total_amount: Decimal,
+        current_time: Instant,
+        current_epoch: Epoch,
+    ) -> Decimal {
+        let (start, cliff, end) = self.points();
+        let now = match self {
+            VestingSchedule::Time { .. } => current_time.seconds_since_unix_epoch as i128,
+            VestingSchedule::Epoch { .. } => current_epoch.number() as i128,
+        };
+
+        if now < cliff {
+            total_amount
+        } else if now >= end {
+            Decimal::ZERO
+        } else {
+            // The cliff is reached but the end is not, so the end is strictly after the start.
+            // The locked amount is a fraction of the total amount, hence it is computed on the
+            // subunits in a wider integer type (the durations never exceed 2^65), so that any
+            // schedule works with any total amount without overflowing.
+            let locked_subunits = BnumI384::from(total_amount.0) * BnumI384::from(end - now)
+                / BnumI384::from(end - start);
+            Decimal(
+                BnumI256::try_from(locked_subunits)
+                    .expect("The locked amount never exceeds the total amount"),
+            )
+        }
+    }
+
+    fn points(&self) -> (i128, i128, i128) {
+        match self {
+            VestingSchedule::Time { start, cliff, end } => (
+                start.seconds_since_unix_epoch as i128,
+                cliff.seconds_since_unix_epoch as i128,
+                end.seconds_since_unix_epoch as i128,
+            ),
+            VestingSchedule::Epoch { start, cliff, end } => (
+                start.number() as i128,
+                cliff.number() as i128,
+                end.number() as i128,
+            ),
+        }
+    }
+}
+
+pub const VESTING_VAULT_CREATE_IDENT: &str = "create";
+
+#[derive(Debug, Clone, Eq, PartialEq, ScryptoSbor)]
+pub struct VestingVaultCreateInput {
+    pub owner_role: OwnerRole,
+    pub bucket: Bucket,
+    pub schedule: VestingSchedule,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, ManifestSbor)]
+pub struct VestingVaultCreateManifestInput {
+    pub owner_role: OwnerRole,
+    pub bucket: ManifestBucket,
+    pub schedule: VestingSchedule,
+}
+
+pub type VestingVaultCreateOutput = ComponentAddress;
+
+pub const VESTING_VAULT_WITHDRAW_IDENT: &str = "withdraw";
+
+#[cfg_attr(feature = "radix_engine_fuzzing", derive(Arbitrary))]
+#[derive(Debug, Clone, Eq, PartialEq, ScryptoSbor, ManifestSbor)]
+pub struct VestingVaultWithdrawInput {
+    pub amount: Decimal,
+}
+
+pub type VestingVaultWithdrawOutput = Bucket;
+
+pub const VESTING_VAULT_GET_AMOUNT_IDENT: &str = "get_amount";
+
+#[cfg_attr(feature = "radix_engine_fuzzing", derive(Arbitrary))]
+#[derive(Debug, Clone, Eq, PartialEq, ScryptoSbor, ManifestSbor)]
+pub struct VestingVaultGetAmountInput {}
+
+pub type VestingVaultGetAmountOutput = Decimal;
+
+pub const VESTING_VAULT_GET_LOCKED_AMOUNT_IDENT: &str = "get_locked_amount";
+
+#[cfg_attr(feature = "radix_engine_fuzzing", derive(Arbitrary))]
+#[derive(Debug, Clone, Eq, PartialEq, ScryptoSbor, ManifestSbor)]
+pub struct VestingVaultGetLockedAmountInput {}
+
+pub type VestingVaultGetLockedAmountOutput = Decimal;
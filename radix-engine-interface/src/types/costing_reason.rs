@@ -15,4 +15,10 @@ pub enum ClientCostingEntry<'a> {
     PrepareWasmCode {
         size: usize,
     },
+    /// The entries written to or returned from an index or a sorted index (on top of the costs of
+    /// the underlying substate operations, which do not depend on their number).
+    AccessIndex {
+        entry_count: usize,
+        size: usize,
+    },
 }
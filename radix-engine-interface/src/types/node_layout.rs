@@ -83,6 +83,7 @@ pub enum PackagePartitionOffset {
     VmType,
     OriginalCode,
     InstrumentedCode,
+    BlueprintUpgrades,
 }
 
 impl TryFrom<u8> for PackagePartitionOffset {
@@ -1,11 +1,20 @@
 use super::{StateTreeTraverser, StateTreeVisitor};
+use radix_engine::blueprints::consensus_manager::{
+    ConsensusManagerSubstate, ProposerMinuteTimestampSubstate,
+};
+use radix_engine::blueprints::resource::VestingVaultSubstate;
+use radix_engine_interface::constants::CONSENSUS_MANAGER;
 use radix_engine_interface::{
     blueprints::resource::{LiquidFungibleResource, LiquidNonFungibleVault},
     data::scrypto::model::NonFungibleLocalId,
     math::Decimal,
-    types::{NodeId, ResourceAddress},
+    time::Instant,
+    types::{ConsensusManagerField, Epoch, NodeId, ResourceAddress, MAIN_BASE_PARTITION},
+};
+use radix_engine_store_interface::{
+    db_key_mapper::{MappedSubstateDatabase, SpreadPrefixKeyMapper},
+    interface::SubstateDatabase,
 };
-use radix_engine_store_interface::interface::SubstateDatabase;
 use sbor::rust::ops::AddAssign;
 use sbor::rust::prelude::*;
 
@@ -16,9 +25,31 @@ pub struct ResourceAccounter<'s, S: SubstateDatabase> {
 
 impl<'s, S: SubstateDatabase> ResourceAccounter<'s, S> {
     pub fn new(substate_db: &'s S) -> Self {
+        // Vesting vaults are accounted for as of the time and epoch of the state being traversed.
+        let current_epoch = substate_db
+            .get_mapped::<SpreadPrefixKeyMapper, ConsensusManagerSubstate>(
+                CONSENSUS_MANAGER.as_node_id(),
+                MAIN_BASE_PARTITION,
+                &ConsensusManagerField::ConsensusManager.into(),
+            )
+            .map(|substate| substate.epoch);
+        let current_time = substate_db
+            .get_mapped::<SpreadPrefixKeyMapper, ProposerMinuteTimestampSubstate>(
+                CONSENSUS_MANAGER.as_node_id(),
+                MAIN_BASE_PARTITION,
+                &ConsensusManagerField::CurrentTimeRoundedToMinutes.into(),
+            )
+            .map(|substate| Instant::new(substate.epoch_minute as i64 * 60));
+
+        let accounting = match (current_time, current_epoch) {
+            (Some(current_time), Some(current_epoch)) => {
+                Accounting::with_time(current_time, current_epoch)
+            }
+            _ => Accounting::new(),
+        };
         ResourceAccounter {
             substate_db,
-            accounting: Accounting::new(),
+            accounting,
         }
     }
 
@@ -36,6 +67,12 @@ impl<'s, S: SubstateDatabase> ResourceAccounter<'s, S> {
 pub struct Accounting {
     pub balances: HashMap<ResourceAddress, Decimal>,
     pub non_fungibles: HashMap<ResourceAddress, HashSet<NonFungibleLocalId>>,
+    /// The part of the balances which is held in vesting vaults and has not vested yet.
+    pub locked_balances: HashMap<ResourceAddress, Decimal>,
+    /// The resources held in vesting vaults whose locked part could not be determined, since the
+    /// current time and epoch are not known (see [`Accounting::with_time()`]).
+    pub unknown_locked_balances: HashSet<ResourceAddress>,
+    current_time_and_epoch: Option<(Instant, Epoch)>,
 }
 
 impl Accounting {
@@ -43,6 +80,18 @@ impl Accounting {
         Accounting {
             balances: hash_map_new(),
             non_fungibles: hash_map_new(),
+            locked_balances: hash_map_new(),
+            unknown_locked_balances: hash_set_new(),
+            current_time_and_epoch: None,
+        }
+    }
+
+    /// Creates an accounting which also determines the locked part of the vesting vaults, as of
+    /// the given time and epoch.
+    pub fn with_time(current_time: Instant, current_epoch: Epoch) -> Self {
+        Accounting {
+            current_time_and_epoch: Some((current_time, current_epoch)),
+            ..Self::new()
         }
     }
 
@@ -74,6 +123,20 @@ impl Accounting {
             .or_default()
             .insert(id.clone());
     }
+
+    pub fn add_vesting_vault(&mut self, vesting_vault: &VestingVaultSubstate) {
+        match self.current_time_and_epoch {
+            Some((current_time, current_epoch)) => self
+                .locked_balances
+                .entry(vesting_vault.resource_address)
+                .or_default()
+                .add_assign(vesting_vault.locked_amount(current_time, current_epoch)),
+            None => {
+                self.unknown_locked_balances
+                    .insert(vesting_vault.resource_address);
+            }
+        }
+    }
 }
 
 impl StateTreeVisitor for Accounting {
@@ -102,4 +165,8 @@ impl StateTreeVisitor for Accounting {
     ) {
         self.add_non_fungible(address, id);
     }
+
+    fn visit_vesting_vault(&mut self, _node_id: NodeId, vesting_vault: &VestingVaultSubstate) {
+        self.add_vesting_vault(vesting_vault);
+    }
 }
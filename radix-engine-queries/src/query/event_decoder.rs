@@ -0,0 +1,281 @@
+use radix_engine::system::node_modules::type_info::TypeInfoSubstate;
+use radix_engine::system::system::KeyValueEntrySubstate;
+use radix_engine::types::*;
+use radix_engine_interface::api::node_modules::auth::ACCESS_RULES_BLUEPRINT;
+use radix_engine_interface::api::node_modules::metadata::METADATA_BLUEPRINT;
+use radix_engine_interface::api::node_modules::royalty::COMPONENT_ROYALTY_BLUEPRINT;
+use radix_engine_interface::api::ObjectModuleId;
+use radix_engine_interface::blueprints::package::{TypePointer, PACKAGE_SCHEMAS_PARTITION_OFFSET};
+use radix_engine_store_interface::{
+    db_key_mapper::{MappedSubstateDatabase, SpreadPrefixKeyMapper},
+    interface::SubstateDatabase,
+};
+use sbor::representations::{SerializationMode, SerializationParameters};
+use sbor::rust::prelude::*;
+use utils::ContextualSerialize;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventDecoderError {
+    NodeNotFound(NodeId),
+    /// The emitter of a method event is not an object.
+    NotAnObject(NodeId),
+    SchemaNotFound(PackageAddress, Hash),
+    /// The emitter of an event of a generic type has no such instance type.
+    InstanceTypeNotFound(NodeId, u8),
+    /// The event type has no name in its schema.
+    UnnamedEventType(LocalTypeIndex),
+    /// The event payload doesn't match its schema.
+    SerializationError(String),
+}
+
+/// An application event, with the (schema-annotated) programmatic JSON representation of its
+/// payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedEvent {
+    pub event_type_identifier: EventTypeIdentifier,
+    /// The blueprint which emitted the event (the module's blueprint, for the events of object
+    /// modules such as metadata).
+    pub blueprint_id: BlueprintId,
+    pub event_name: String,
+    pub payload: serde_json::Value,
+}
+
+/// Selects events by their emitter, blueprint and name. An empty filter selects all events.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventFilter {
+    /// The node emitting the event, i.e. the object (for method events) or the package (for
+    /// function events).
+    pub emitter: Option<NodeId>,
+    pub blueprint_id: Option<BlueprintId>,
+    pub event_name: Option<String>,
+}
+
+impl EventFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn emitter(mut self, node_id: NodeId) -> Self {
+        self.emitter = Some(node_id);
+        self
+    }
+
+    pub fn blueprint(mut self, package_address: PackageAddress, blueprint_name: &str) -> Self {
+        self.blueprint_id = Some(BlueprintId::new(&package_address, blueprint_name));
+        self
+    }
+
+    pub fn event_name(mut self, event_name: &str) -> Self {
+        self.event_name = Some(event_name.to_string());
+        self
+    }
+
+    fn matches_emitter(&self, event_type_identifier: &EventTypeIdentifier) -> bool {
+        let node_id = match &event_type_identifier.0 {
+            Emitter::Function(node_id, ..) | Emitter::Method(node_id, ..) => node_id,
+        };
+        self.emitter.map_or(true, |emitter| emitter == *node_id)
+    }
+}
+
+/// Decodes application events (e.g. those of [`CommitResult::application_events`](radix_engine::transaction::CommitResult))
+/// against the schemas of the packages defining them, read from a substate database.
+pub struct EventDecoder<'s, S: SubstateDatabase> {
+    substate_db: &'s S,
+    address_bech32_encoder: Option<&'s AddressBech32Encoder>,
+}
+
+impl<'s, S: SubstateDatabase> EventDecoder<'s, S> {
+    pub fn new(substate_db: &'s S) -> Self {
+        EventDecoder {
+            substate_db,
+            address_bech32_encoder: None,
+        }
+    }
+
+    /// Renders the addresses of the decoded events in Bech32 (rather than in hex).
+    pub fn with_address_bech32_encoder(
+        mut self,
+        address_bech32_encoder: &'s AddressBech32Encoder,
+    ) -> Self {
+        self.address_bech32_encoder = Some(address_bech32_encoder);
+        self
+    }
+
+    /// Returns the blueprint which emitted the event.
+    pub fn event_blueprint(
+        &self,
+        event_type_identifier: &EventTypeIdentifier,
+    ) -> Result<BlueprintId, EventDecoderError> {
+        match &event_type_identifier.0 {
+            Emitter::Function(node_id, _, blueprint_name) => Ok(BlueprintId::new(
+                &PackageAddress::new_or_panic(node_id.0),
+                blueprint_name,
+            )),
+            Emitter::Method(_, ObjectModuleId::AccessRules) => Ok(BlueprintId::new(
+                &ACCESS_RULES_MODULE_PACKAGE,
+                ACCESS_RULES_BLUEPRINT,
+            )),
+            Emitter::Method(_, ObjectModuleId::Royalty) => Ok(BlueprintId::new(
+                &ROYALTY_MODULE_PACKAGE,
+                COMPONENT_ROYALTY_BLUEPRINT,
+            )),
+            Emitter::Method(_, ObjectModuleId::Metadata) => Ok(BlueprintId::new(
+                &METADATA_MODULE_PACKAGE,
+                METADATA_BLUEPRINT,
+            )),
+            Emitter::Method(node_id, ObjectModuleId::Main) => {
+                Ok(self.object_info(node_id)?.blueprint_id)
+            }
+        }
+    }
+
+    /// Returns the schema of the event, and the index of the event type in it.
+    pub fn event_schema(
+        &self,
+        event_type_identifier: &EventTypeIdentifier,
+    ) -> Result<(LocalTypeIndex, ScryptoSchema), EventDecoderError> {
+        match (&event_type_identifier.0, &event_type_identifier.1) {
+            (_, TypePointer::Package(schema_hash, type_index)) => {
+                let package_address = self.event_blueprint(event_type_identifier)?.package_address;
+                let schema = self
+                    .substate_db
+                    .get_mapped::<SpreadPrefixKeyMapper, KeyValueEntrySubstate<ScryptoSchema>>(
+                        package_address.as_node_id(),
+                        MAIN_BASE_PARTITION
+                            .at_offset(PACKAGE_SCHEMAS_PARTITION_OFFSET)
+                            .unwrap(),
+                        &SubstateKey::Map(scrypto_encode(schema_hash).unwrap()),
+                    )
+                    .and_then(|entry| entry.value)
+                    .ok_or(EventDecoderError::SchemaNotFound(
+                        package_address,
+                        *schema_hash,
+                    ))?;
+                Ok((*type_index, schema))
+            }
+            (Emitter::Method(node_id, ObjectModuleId::Main), TypePointer::Instance(index)) => {
+                let instance_schema = self.object_info(node_id)?.instance_schema;
+                instance_schema
+                    .and_then(|instance_schema| {
+                        instance_schema
+                            .type_index
+                            .get(*index as usize)
+                            .map(|type_index| (*type_index, instance_schema.schema.clone()))
+                    })
+                    .ok_or(EventDecoderError::InstanceTypeNotFound(*node_id, *index))
+            }
+            (
+                Emitter::Function(node_id, ..) | Emitter::Method(node_id, ..),
+                TypePointer::Instance(index),
+            ) => Err(EventDecoderError::InstanceTypeNotFound(*node_id, *index)),
+        }
+    }
+
+    /// Returns the name of the event type, e.g. `WithdrawResourceEvent`.
+    pub fn event_name(
+        &self,
+        event_type_identifier: &EventTypeIdentifier,
+    ) -> Result<String, EventDecoderError> {
+        let (type_index, schema) = self.event_schema(event_type_identifier)?;
+        Self::type_name(type_index, &schema)
+    }
+
+    pub fn decode_event(
+        &self,
+        event_type_identifier: &EventTypeIdentifier,
+        event_data: &[u8],
+    ) -> Result<DecodedEvent, EventDecoderError> {
+        self.decode_filtered_event(event_type_identifier, event_data, &EventFilter::new())
+            .map(|event| event.expect("An empty filter selects all events"))
+    }
+
+    /// Decodes the events selected by the filter, in order. Each event is only decoded as far as
+    /// needed to filter it.
+    pub fn decode_events<'a>(
+        &'a self,
+        events: &'a [(EventTypeIdentifier, Vec<u8>)],
+        filter: &'a EventFilter,
+    ) -> impl Iterator<Item = Result<DecodedEvent, EventDecoderError>> + 'a {
+        events
+            .iter()
+            .filter_map(move |(event_type_identifier, event_data)| {
+                self.decode_filtered_event(event_type_identifier, event_data, filter)
+                    .transpose()
+            })
+    }
+
+    fn decode_filtered_event(
+        &self,
+        event_type_identifier: &EventTypeIdentifier,
+        event_data: &[u8],
+        filter: &EventFilter,
+    ) -> Result<Option<DecodedEvent>, EventDecoderError> {
+        if !filter.matches_emitter(event_type_identifier) {
+            return Ok(None);
+        }
+        let blueprint_id = self.event_blueprint(event_type_identifier)?;
+        if filter
+            .blueprint_id
+            .as_ref()
+            .map_or(false, |expected| *expected != blueprint_id)
+        {
+            return Ok(None);
+        }
+        let (type_index, schema) = self.event_schema(event_type_identifier)?;
+        let event_name = Self::type_name(type_index, &schema)?;
+        if filter
+            .event_name
+            .as_ref()
+            .map_or(false, |expected| *expected != event_name)
+        {
+            return Ok(None);
+        }
+
+        let payload = serde_json::to_value(
+            ScryptoRawPayload::new_from_valid_slice(event_data).serializable(
+                SerializationParameters::WithSchema {
+                    mode: SerializationMode::Programmatic,
+                    custom_context: ScryptoValueDisplayContext::with_optional_bech32(
+                        self.address_bech32_encoder,
+                    ),
+                    schema: &schema,
+                    type_index,
+                },
+            ),
+        )
+        .map_err(|error| EventDecoderError::SerializationError(error.to_string()))?;
+
+        Ok(Some(DecodedEvent {
+            event_type_identifier: event_type_identifier.clone(),
+            blueprint_id,
+            event_name,
+            payload,
+        }))
+    }
+
+    fn object_info(&self, node_id: &NodeId) -> Result<ObjectInfo, EventDecoderError> {
+        let type_info = self
+            .substate_db
+            .get_mapped::<SpreadPrefixKeyMapper, TypeInfoSubstate>(
+                node_id,
+                TYPE_INFO_FIELD_PARTITION,
+                &TypeInfoField::TypeInfo.into(),
+            )
+            .ok_or(EventDecoderError::NodeNotFound(*node_id))?;
+        match type_info {
+            TypeInfoSubstate::Object(object_info) => Ok(object_info),
+            _ => Err(EventDecoderError::NotAnObject(*node_id)),
+        }
+    }
+
+    fn type_name(
+        type_index: LocalTypeIndex,
+        schema: &ScryptoSchema,
+    ) -> Result<String, EventDecoderError> {
+        schema
+            .resolve_type_metadata(type_index)
+            .and_then(|metadata| metadata.get_name_string())
+            .ok_or(EventDecoderError::UnnamedEventType(type_index))
+    }
+}
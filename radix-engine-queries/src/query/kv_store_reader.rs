@@ -0,0 +1,156 @@
+use radix_engine::system::node_modules::type_info::TypeInfoSubstate;
+use radix_engine::system::system::KeyValueEntrySubstate;
+use radix_engine::types::{MapKey, ScryptoValue};
+use radix_engine_interface::data::scrypto::{
+    scrypto_decode, scrypto_encode, ScryptoCustomExtension,
+};
+use radix_engine_interface::schema::KeyValueStoreSchema;
+use radix_engine_interface::types::{
+    NodeId, TypeInfoField, MAIN_BASE_PARTITION, TYPE_INFO_FIELD_PARTITION,
+};
+use radix_engine_store_interface::{
+    db_key_mapper::{DatabaseKeyMapper, MappedSubstateDatabase, SpreadPrefixKeyMapper},
+    interface::SubstateDatabase,
+};
+use sbor::rust::prelude::*;
+use sbor::{validate_payload_against_schema, DecodeError, LocalTypeIndex};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyValueStoreReaderError {
+    NodeNotFound(NodeId),
+    NotAKeyValueStore(NodeId),
+    DecodeError(DecodeError),
+    /// An entry's key or value does not match the store's schema.
+    SchemaValidationError(String),
+}
+
+/// A decoded entry of a key value store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyValueStoreEntry {
+    pub key: ScryptoValue,
+    pub value: ScryptoValue,
+}
+
+/// A page of the entries of a key value store, together with the store's schema (which describes
+/// the types of the keys and values).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyValueStoreContents {
+    pub schema: KeyValueStoreSchema,
+    pub entries: Vec<KeyValueStoreEntry>,
+    /// The key to pass to [`KeyValueStoreReader::list_entries()`] for the next page, or
+    /// [`Option::None`] if this is the last page.
+    pub next_key: Option<MapKey>,
+}
+
+/// Reads the contents of any key value store directly from a substate database (i.e. outside of
+/// a transaction, so nothing is costed nor limited).
+///
+/// Entries are listed in the database's order (i.e. the order of the hashed keys), which is
+/// stable, but unrelated to the order of insertion or to the keys' values.
+pub struct KeyValueStoreReader<'s, S: SubstateDatabase> {
+    substate_db: &'s S,
+}
+
+impl<'s, S: SubstateDatabase> KeyValueStoreReader<'s, S> {
+    pub fn new(substate_db: &'s S) -> Self {
+        KeyValueStoreReader { substate_db }
+    }
+
+    pub fn get_schema(
+        &self,
+        node_id: &NodeId,
+    ) -> Result<KeyValueStoreSchema, KeyValueStoreReaderError> {
+        let type_info = self
+            .substate_db
+            .get_mapped::<SpreadPrefixKeyMapper, TypeInfoSubstate>(
+                node_id,
+                TYPE_INFO_FIELD_PARTITION,
+                &TypeInfoField::TypeInfo.into(),
+            )
+            .ok_or(KeyValueStoreReaderError::NodeNotFound(*node_id))?;
+        match type_info {
+            TypeInfoSubstate::KeyValueStore(info) => Ok(info.schema),
+            _ => Err(KeyValueStoreReaderError::NotAKeyValueStore(*node_id)),
+        }
+    }
+
+    /// Counts the entries of the store (removed entries, which may still be present in the
+    /// database, are not counted).
+    pub fn count_entries(&self, node_id: &NodeId) -> Result<usize, KeyValueStoreReaderError> {
+        self.get_schema(node_id)?;
+        let partition_key =
+            SpreadPrefixKeyMapper::to_db_partition_key(node_id, MAIN_BASE_PARTITION);
+        let mut count = 0;
+        for (_, value) in self.substate_db.list_entries(&partition_key) {
+            let entry: KeyValueEntrySubstate<ScryptoValue> =
+                scrypto_decode(&value).map_err(KeyValueStoreReaderError::DecodeError)?;
+            if entry.value.is_some() {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Lists at most `limit` entries of the store, starting from the given key (inclusive), or
+    /// from the first entry if [`Option::None`].
+    ///
+    /// Every returned key and value is validated against the store's schema.
+    pub fn list_entries(
+        &self,
+        node_id: &NodeId,
+        from_key: Option<&MapKey>,
+        limit: usize,
+    ) -> Result<KeyValueStoreContents, KeyValueStoreReaderError> {
+        let schema = self.get_schema(node_id)?;
+        let from_sort_key = from_key.map(SpreadPrefixKeyMapper::map_to_db_sort_key);
+        let db_entries = self.substate_db.list_entries_from(
+            &SpreadPrefixKeyMapper::to_db_partition_key(node_id, MAIN_BASE_PARTITION),
+            from_sort_key.as_ref(),
+        );
+
+        let mut entries = Vec::new();
+        let mut next_key = None;
+        for (db_sort_key, db_value) in db_entries {
+            let key = SpreadPrefixKeyMapper::map_from_db_sort_key(&db_sort_key);
+            if entries.len() >= limit {
+                next_key = Some(key);
+                break;
+            }
+            let entry: KeyValueEntrySubstate<ScryptoValue> =
+                scrypto_decode(&db_value).map_err(KeyValueStoreReaderError::DecodeError)?;
+            let value = match entry.value {
+                Some(value) => value,
+                None => continue,
+            };
+            Self::validate(&schema, &key, schema.key)?;
+            let value = scrypto_encode(&value).unwrap();
+            Self::validate(&schema, &value, schema.value)?;
+            entries.push(KeyValueStoreEntry {
+                key: scrypto_decode(&key).map_err(KeyValueStoreReaderError::DecodeError)?,
+                value: scrypto_decode(&value).map_err(KeyValueStoreReaderError::DecodeError)?,
+            });
+        }
+
+        Ok(KeyValueStoreContents {
+            schema,
+            entries,
+            next_key,
+        })
+    }
+
+    fn validate(
+        schema: &KeyValueStoreSchema,
+        payload: &[u8],
+        type_index: LocalTypeIndex,
+    ) -> Result<(), KeyValueStoreReaderError> {
+        validate_payload_against_schema::<ScryptoCustomExtension, _>(
+            payload,
+            &schema.schema,
+            type_index,
+            &(),
+        )
+        .map_err(|error| {
+            KeyValueStoreReaderError::SchemaValidationError(error.error_message(&schema.schema))
+        })
+    }
+}
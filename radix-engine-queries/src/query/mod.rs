@@ -1,7 +1,11 @@
 mod accounter;
+mod event_decoder;
+mod kv_store_reader;
 mod traverse;
 mod vault_finder;
 
 pub use accounter::*;
+pub use event_decoder::*;
+pub use kv_store_reader::*;
 pub use traverse::*;
 pub use vault_finder::*;
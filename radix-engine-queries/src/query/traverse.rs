@@ -1,8 +1,10 @@
+use radix_engine::blueprints::resource::{VestingVaultSubstate, VESTING_VAULT_FIELD};
 use radix_engine::system::node_modules::type_info::TypeInfoSubstate;
 use radix_engine::types::{FieldKey, MapKey, ScryptoValue, SubstateKey};
 use radix_engine_interface::blueprints::account::ACCOUNT_BLUEPRINT;
 use radix_engine_interface::blueprints::resource::{
     LiquidNonFungibleVault, FUNGIBLE_VAULT_BLUEPRINT, NON_FUNGIBLE_VAULT_BLUEPRINT,
+    VESTING_VAULT_BLUEPRINT,
 };
 use radix_engine_interface::constants::{ACCOUNT_PACKAGE, RESOURCE_PACKAGE};
 use radix_engine_interface::data::scrypto::model::NonFungibleLocalId;
@@ -50,6 +52,8 @@ pub trait StateTreeVisitor {
     ) {
     }
 
+    fn visit_vesting_vault(&mut self, _node_id: NodeId, _vesting_vault: &VestingVaultSubstate) {}
+
     fn visit_node_id(
         &mut self,
         _parent_id: Option<&(NodeId, PartitionNumber, SubstateKey)>,
@@ -175,6 +179,21 @@ impl<'s, 'v, S: SubstateDatabase, V: StateTreeVisitor> StateTreeTraverser<'s, 'v
                         );
                     }
                 } else {
+                    if info.blueprint_id.package_address.eq(&RESOURCE_PACKAGE)
+                        && info.blueprint_id.blueprint_name.eq(VESTING_VAULT_BLUEPRINT)
+                    {
+                        let vesting_vault = self
+                            .substate_db
+                            .get_mapped::<SpreadPrefixKeyMapper, VestingVaultSubstate>(
+                                &node_id,
+                                MAIN_BASE_PARTITION,
+                                &SubstateKey::Field(VESTING_VAULT_FIELD),
+                            )
+                            .expect("Broken database");
+
+                        self.visitor.visit_vesting_vault(node_id, &vesting_vault);
+                    }
+
                     for partition_num in [
                         TYPE_INFO_FIELD_PARTITION,
                         ROYALTY_BASE_PARTITION,
@@ -126,6 +126,7 @@ pub enum TypedMainModuleSubstateKey {
     PackageVmTypeKey(Hash),
     PackageOriginalCodeKey(Hash),
     PackageInstrumentedCodeKey(Hash),
+    PackageBlueprintUpgradesKey(String),
     FungibleResourceField(FungibleResourceManagerField),
     NonFungibleResourceField(NonFungibleResourceManagerField),
     NonFungibleResourceData(NonFungibleLocalId),
@@ -312,6 +313,12 @@ fn to_typed_object_substate_key_internal(
                         scrypto_decode(&key).map_err(|_| ())?,
                     )
                 }
+                PackagePartitionOffset::BlueprintUpgrades => {
+                    let key = substate_key.for_map().ok_or(())?;
+                    TypedMainModuleSubstateKey::PackageBlueprintUpgradesKey(
+                        scrypto_decode(&key).map_err(|_| ())?,
+                    )
+                }
             }
         }
         EntityType::GlobalFungibleResourceManager => {
@@ -482,6 +489,7 @@ pub enum TypedMainModuleSubstateValue {
     PackageVmType(KeyValueEntrySubstate<PackageVmTypeSubstate>),
     PackageOriginalCode(KeyValueEntrySubstate<PackageOriginalCodeSubstate>),
     PackageInstrumentedCode(KeyValueEntrySubstate<PackageInstrumentedCodeSubstate>),
+    PackageBlueprintUpgrades(KeyValueEntrySubstate<BlueprintUpgrades>),
     FungibleResource(TypedFungibleResourceManagerFieldValue),
     NonFungibleResource(TypedNonFungibleResourceManagerFieldValue),
     NonFungibleResourceData(KeyValueEntrySubstate<ScryptoOwnedRawValue>),
@@ -686,6 +694,9 @@ fn to_typed_object_substate_value(
         TypedMainModuleSubstateKey::PackageInstrumentedCodeKey(..) => {
             TypedMainModuleSubstateValue::PackageInstrumentedCode(scrypto_decode(data)?)
         }
+        TypedMainModuleSubstateKey::PackageBlueprintUpgradesKey(..) => {
+            TypedMainModuleSubstateValue::PackageBlueprintUpgrades(scrypto_decode(data)?)
+        }
         TypedMainModuleSubstateKey::FungibleResourceField(offset) => {
             TypedMainModuleSubstateValue::FungibleResource(match offset {
                 FungibleResourceManagerField::Divisibility => {
@@ -48,12 +48,48 @@ pub trait SubstateDatabase {
         sort_key: &DbSortKey,
     ) -> Option<DbSubstateValue>;
 
+    /// Iterates over entries of the given partition, in a lexicographical order (ascending) of the
+    /// [`DbSortKey`]s, starting from the given sort key (inclusive), or from the first entry if
+    /// [`Option::None`].
+    fn list_entries_from(
+        &self,
+        partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
+    ) -> Box<dyn Iterator<Item = PartitionEntry> + '_>;
+
+    /// Iterates over entries of the given partition, in a reverse lexicographical order
+    /// (descending) of the [`DbSortKey`]s, starting from the given sort key (inclusive), or from
+    /// the last entry if [`Option::None`].
+    fn list_entries_rev_from(
+        &self,
+        partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
+    ) -> Box<dyn Iterator<Item = PartitionEntry> + '_>;
+
     /// Iterates over all entries of the given partition, in a lexicographical order (ascending)
     /// of the [`DbSortKey`]s.
     fn list_entries(
         &self,
         partition_key: &DbPartitionKey,
-    ) -> Box<dyn Iterator<Item = PartitionEntry> + '_>;
+    ) -> Box<dyn Iterator<Item = PartitionEntry> + '_> {
+        self.list_entries_from(partition_key, None)
+    }
+
+    /// Iterates over entries of the given partition which have a [`DbSortKey`] starting with the
+    /// given prefix, in a lexicographical order (ascending).
+    /// The iteration can be bounded by the caller with [`Iterator::take()`] - the underlying
+    /// database is only read lazily.
+    fn list_entries_with_prefix(
+        &self,
+        partition_key: &DbPartitionKey,
+        sort_key_prefix: &[u8],
+    ) -> Box<dyn Iterator<Item = PartitionEntry> + '_> {
+        let sort_key_prefix = sort_key_prefix.to_vec();
+        let iter = self
+            .list_entries_from(partition_key, Some(&DbSortKey(sort_key_prefix.clone())))
+            .take_while(move |(sort_key, _)| sort_key.0.starts_with(&sort_key_prefix));
+        Box::new(iter)
+    }
 }
 
 /// A write interface between Track and a database vendor.
@@ -1,5 +1,6 @@
+use crate::hash_tree::proof::{SubstateProof, TreeProof, TreeProofLeaf};
 use crate::hash_tree::tree_store::PartitionPayload;
-use crate::hash_tree::types::LeafKey;
+use crate::hash_tree::types::{LeafKey, SparseMerkleProof};
 use jellyfish::JellyfishMerkleTree;
 use radix_engine_common::crypto::Hash;
 use radix_engine_store_interface::interface::{DbPartitionKey, DbSortKey, DbSubstateKey};
@@ -9,6 +10,7 @@ use utils::rust::collections::{index_map_new, IndexMap};
 use utils::rust::vec::Vec;
 
 pub mod hash_tree_facade;
+pub mod proof;
 pub mod tree_store;
 
 // The sources copied from Aptos (the `jellyfish` and `types` modules) contain support for
@@ -73,8 +75,56 @@ pub fn put_at_next_version<S: TreeStore<PartitionPayload> + TreeStore<()>>(
     put_partition_changes(store, current_version, nested_root_changes)
 }
 
+/// Returns the root hash of the "nested JMT" at the given version, or `None` if the version does
+/// not exist in the `store` (e.g. was never created or was already pruned).
+pub fn get_state_root_hash<S: ReadableTreeStore<PartitionPayload>>(
+    store: &S,
+    version: Version,
+) -> Option<Hash> {
+    JellyfishMerkleTree::new(store).get_root_hash(version).ok()
+}
+
+/// Creates a proof of the given substate's value hash (or its absence) at the given version of the
+/// "nested JMT" persisted within the given `store`.
+/// The proof may be verified against the state root hash (see `get_state_root_hash()`) using the
+/// `SubstateProof::verify()`, which does not require any access to the store.
+///
+/// # Panics
+/// Panics if any node required by the proof does not exist (e.g. the version was already pruned).
+pub fn get_substate_proof<S: ReadableTreeStore<PartitionPayload> + ReadableTreeStore<()>>(
+    store: &S,
+    version: Version,
+    db_partition_key: &DbPartitionKey,
+    db_sort_key: &DbSortKey,
+) -> SubstateProof {
+    let (partition_leaf, partition_proof) = JellyfishMerkleTree::new(store)
+        .get_with_proof(&LeafKey::new(&db_partition_key.0), version)
+        .expect("error while reading the upper-layer tree during proof creation");
+    let substate_proof = partition_leaf.map(|(_hash, subtree_root, subtree_version)| {
+        let subtree_store = NestedTreeReader::new(store, db_partition_key, subtree_root);
+        let (_substate_leaf, substate_proof) = JellyfishMerkleTree::new(&subtree_store)
+            .get_with_proof(&LeafKey::new(&db_sort_key.0), subtree_version)
+            .expect("error while reading the lower-layer tree during proof creation");
+        to_tree_proof(substate_proof)
+    });
+    SubstateProof {
+        partition_proof: to_tree_proof(partition_proof),
+        substate_proof,
+    }
+}
+
 // only internals below
 
+fn to_tree_proof(proof: SparseMerkleProof) -> TreeProof {
+    TreeProof {
+        leaf: proof.leaf().map(|leaf| TreeProofLeaf {
+            key: leaf.key().bytes.clone(),
+            value_hash: *leaf.value_hash(),
+        }),
+        siblings: proof.siblings().to_vec(),
+    }
+}
+
 fn index_by_db_partition_key(
     changes: Vec<SubstateHashChange>,
 ) -> IndexMap<DbPartitionKey, Vec<IdChange<DbSortKey, Hash>>> {
@@ -186,7 +236,7 @@ fn put_changes<S: TreeStore<P>, P: Payload>(
         store.insert_node(key, node)
     }
     for key in update_result.stale_node_index_batch.into_iter().flatten() {
-        store.record_stale_node(key.node_key);
+        store.record_stale_node(key.node_key, key.stale_since_version);
     }
     root_hash
 }
@@ -235,14 +285,7 @@ impl<'s, S> NestedTreeStore<'s, S> {
     }
 
     fn prefixed(&self, key: &NodeKey) -> NodeKey {
-        NodeKey::new(
-            key.version(),
-            NibblePath::from_iter(
-                NibblePath::new_even(self.parent_key.bytes.clone())
-                    .nibbles()
-                    .chain(key.nibble_path().nibbles()),
-            ),
-        )
+        prefixed(&self.parent_key, key)
     }
 }
 
@@ -256,6 +299,37 @@ impl<'s, S: ReadableTreeStore<()>> ReadableTreeStore<()> for NestedTreeStore<'s,
     }
 }
 
+/// A read-only counterpart of the `NestedTreeStore`.
+struct NestedTreeReader<'s, S> {
+    underlying: &'s S,
+    parent_key: LeafKey,
+    root: TreeNode<()>,
+}
+
+impl<'s, S> NestedTreeReader<'s, S> {
+    pub fn new(
+        underlying: &'s S,
+        db_partition_key: &DbPartitionKey,
+        root: TreeNode<()>,
+    ) -> NestedTreeReader<'s, S> {
+        NestedTreeReader {
+            underlying,
+            parent_key: LeafKey::new(&db_partition_key.0),
+            root,
+        }
+    }
+}
+
+impl<'s, S: ReadableTreeStore<()>> ReadableTreeStore<()> for NestedTreeReader<'s, S> {
+    fn get_node(&self, key: &NodeKey) -> Option<TreeNode<()>> {
+        if key.nibble_path().is_empty() {
+            Some(self.root.clone())
+        } else {
+            self.underlying.get_node(&prefixed(&self.parent_key, key))
+        }
+    }
+}
+
 impl<'s, S: WriteableTreeStore<()>> WriteableTreeStore<()> for NestedTreeStore<'s, S> {
     fn insert_node(&mut self, key: NodeKey, node: TreeNode<()>) {
         if key.nibble_path().is_empty() {
@@ -265,11 +339,23 @@ impl<'s, S: WriteableTreeStore<()>> WriteableTreeStore<()> for NestedTreeStore<'
         }
     }
 
-    fn record_stale_node(&mut self, key: NodeKey) {
+    fn record_stale_node(&mut self, key: NodeKey, stale_since_version: Version) {
         if key.nibble_path().is_empty() {
             self.current_root = None;
         } else {
-            self.underlying.record_stale_node(self.prefixed(&key));
+            self.underlying
+                .record_stale_node(self.prefixed(&key), stale_since_version);
         }
     }
 }
+
+fn prefixed(parent_key: &LeafKey, key: &NodeKey) -> NodeKey {
+    NodeKey::new(
+        key.version(),
+        NibblePath::from_iter(
+            NibblePath::new_even(parent_key.bytes.clone())
+                .nibbles()
+                .chain(key.nibble_path().nibbles()),
+        ),
+    )
+}
@@ -0,0 +1,198 @@
+use radix_engine_common::crypto::{hash, Hash};
+use radix_engine_derive::ScryptoSbor;
+use radix_engine_store_interface::interface::{DbPartitionKey, DbSortKey, DbSubstateValue};
+use utils::rust::vec::Vec;
+
+/// A hash representing an empty subtree (see the Sparse Merkle Tree design of the JMT).
+const PLACEHOLDER_HASH: Hash = Hash([0u8; Hash::LENGTH]);
+
+/// A self-contained proof of inclusion (or exclusion) of a single key within a single tree (i.e.
+/// within a single layer of our two-layered JMT).
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct TreeProof {
+    /// A leaf found at the end of the key's path:
+    /// - if its key equals the proven key, then this is an inclusion proof;
+    /// - if its key differs, then it is an exclusion proof (the leaf is the only key that exists in
+    ///   the subtree where the proven key would be);
+    /// - if [`None`], then it is an exclusion proof (the subtree where the key would be is empty).
+    pub leaf: Option<TreeProofLeaf>,
+    /// Hashes of all siblings on the key's path, ordered from the bottom level to the root level.
+    pub siblings: Vec<Hash>,
+}
+
+/// A leaf's information needed to re-compute its hash.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct TreeProofLeaf {
+    pub key: Vec<u8>,
+    pub value_hash: Hash,
+}
+
+/// A proof that a substate of the given [`DbPartitionKey`] and [`DbSortKey`] has some value (or
+/// does not exist) at a specific state version, i.e. under a specific state root hash.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct SubstateProof {
+    /// A proof of the partition's leaf within the upper-layer tree (its value hash is the root hash
+    /// of the partition's lower-layer tree).
+    pub partition_proof: TreeProof,
+    /// A proof of the substate's leaf within the partition's lower-layer tree, or [`None`] if the
+    /// entire partition does not exist.
+    pub substate_proof: Option<TreeProof>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofVerificationError {
+    /// The proof's leaf claims a different value hash than the expected one.
+    ValueHashMismatch { expected: Hash, actual: Hash },
+    /// The proof's leaf is of the proven key, but the key was expected to be absent.
+    UnexpectedInclusion,
+    /// The proof has no leaf of the proven key, but the key was expected to be present.
+    UnexpectedExclusion,
+    /// The proof's leaf of a different key does not share the path prefix with the proven key.
+    InvalidExclusionLeaf,
+    /// The proof has more siblings than the key's bit length.
+    TooManySiblings,
+    /// The root hash re-computed from the proof does not match the expected one.
+    RootHashMismatch { expected: Hash, actual: Hash },
+    /// The lower-layer proof is missing, while the partition exists (or vice versa).
+    InconsistentLayers,
+}
+
+impl TreeProof {
+    /// Verifies that the given key has the given value hash (or is absent, if [`None`]) in the tree
+    /// of the given root hash.
+    pub fn verify(
+        &self,
+        expected_root_hash: &Hash,
+        key: &[u8],
+        expected_value_hash: Option<&Hash>,
+    ) -> Result<(), ProofVerificationError> {
+        if self.siblings.len() > key.len() * 8 {
+            return Err(ProofVerificationError::TooManySiblings);
+        }
+
+        match (&self.leaf, expected_value_hash) {
+            (Some(leaf), Some(expected_value_hash)) => {
+                if leaf.key != key {
+                    return Err(ProofVerificationError::UnexpectedExclusion);
+                }
+                if leaf.value_hash != *expected_value_hash {
+                    return Err(ProofVerificationError::ValueHashMismatch {
+                        expected: *expected_value_hash,
+                        actual: leaf.value_hash,
+                    });
+                }
+            }
+            (Some(leaf), None) => {
+                if leaf.key == key {
+                    return Err(ProofVerificationError::UnexpectedInclusion);
+                }
+                // the leaf must be "in the way", i.e. on the path where our key would be
+                let common_prefix_bits = common_prefix_bits_len(&leaf.key, key);
+                if common_prefix_bits < self.siblings.len() {
+                    return Err(ProofVerificationError::InvalidExclusionLeaf);
+                }
+            }
+            (None, Some(_)) => return Err(ProofVerificationError::UnexpectedExclusion),
+            (None, None) => {}
+        }
+
+        let actual_root_hash = self.compute_root_hash(key);
+        if actual_root_hash != *expected_root_hash {
+            return Err(ProofVerificationError::RootHashMismatch {
+                expected: *expected_root_hash,
+                actual: actual_root_hash,
+            });
+        }
+        Ok(())
+    }
+
+    fn compute_root_hash(&self, key: &[u8]) -> Hash {
+        let mut current_hash = self
+            .leaf
+            .as_ref()
+            .map(|leaf| leaf_hash(&leaf.key, &leaf.value_hash))
+            .unwrap_or(PLACEHOLDER_HASH);
+        // the siblings are ordered bottom-up, so the last one corresponds to the key's first bit
+        for (index, sibling_hash) in self.siblings.iter().enumerate() {
+            current_hash = if get_bit(key, self.siblings.len() - 1 - index) {
+                internal_hash(sibling_hash, &current_hash)
+            } else {
+                internal_hash(&current_hash, sibling_hash)
+            };
+        }
+        current_hash
+    }
+}
+
+impl SubstateProof {
+    /// Verifies that the substate has the given value (or is absent, if [`None`]) in the state of
+    /// the given root hash.
+    pub fn verify(
+        &self,
+        expected_root_hash: &Hash,
+        partition_key: &DbPartitionKey,
+        sort_key: &DbSortKey,
+        expected_value: Option<&DbSubstateValue>,
+    ) -> Result<(), ProofVerificationError> {
+        self.verify_hash(
+            expected_root_hash,
+            partition_key,
+            sort_key,
+            expected_value.map(|value| hash(value)).as_ref(),
+        )
+    }
+
+    /// Verifies that the substate has the given value hash (or is absent, if [`None`]) in the
+    /// state of the given root hash.
+    pub fn verify_hash(
+        &self,
+        expected_root_hash: &Hash,
+        partition_key: &DbPartitionKey,
+        sort_key: &DbSortKey,
+        expected_value_hash: Option<&Hash>,
+    ) -> Result<(), ProofVerificationError> {
+        let partition_leaf = self
+            .partition_proof
+            .leaf
+            .as_ref()
+            .filter(|leaf| leaf.key == partition_key.0);
+        match (partition_leaf, &self.substate_proof) {
+            (Some(partition_leaf), Some(substate_proof)) => {
+                self.partition_proof.verify(
+                    expected_root_hash,
+                    &partition_key.0,
+                    Some(&partition_leaf.value_hash),
+                )?;
+                substate_proof.verify(&partition_leaf.value_hash, &sort_key.0, expected_value_hash)
+            }
+            (None, None) => {
+                if expected_value_hash.is_some() {
+                    return Err(ProofVerificationError::UnexpectedExclusion);
+                }
+                self.partition_proof
+                    .verify(expected_root_hash, &partition_key.0, None)
+            }
+            _ => Err(ProofVerificationError::InconsistentLayers),
+        }
+    }
+}
+
+fn leaf_hash(key: &[u8], value_hash: &Hash) -> Hash {
+    hash([key, &value_hash.0].concat())
+}
+
+fn internal_hash(left_child: &Hash, right_child: &Hash) -> Hash {
+    hash([left_child.0, right_child.0].concat())
+}
+
+/// Returns the `index`-th bit of the given bytes (counting from the most significant one).
+fn get_bit(bytes: &[u8], index: usize) -> bool {
+    (bytes[index / 8] >> (7 - index % 8)) & 1 != 0
+}
+
+fn common_prefix_bits_len(left: &[u8], right: &[u8]) -> usize {
+    let max_len = left.len().min(right.len()) * 8;
+    (0..max_len)
+        .take_while(|index| get_bit(left, *index) == get_bit(right, *index))
+        .count()
+}
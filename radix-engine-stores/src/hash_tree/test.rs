@@ -1,10 +1,13 @@
 use super::types::{Nibble, NibblePath, Version, SPARSE_MERKLE_PLACEHOLDER_HASH};
+use crate::hash_tree::proof::ProofVerificationError;
 use crate::hash_tree::tree_store::{
     SerializedInMemoryTreeStore, TreeChildEntry, TreeInternalNode, TreeLeafNode, TreeNode,
     TypedInMemoryTreeStore,
 };
 use crate::hash_tree::types::{LeafKey, NodeKey};
-use crate::hash_tree::{put_at_next_version, SubstateHashChange};
+use crate::hash_tree::{
+    get_state_root_hash, get_substate_proof, put_at_next_version, SubstateHashChange,
+};
 use itertools::Itertools;
 use radix_engine_common::crypto::{hash, Hash};
 use radix_engine_common::data::scrypto::{scrypto_decode, scrypto_encode};
@@ -219,7 +222,12 @@ fn deletes_partition_layer_leaf_when_all_its_entries_deleted() {
         store
             .root_tree_nodes
             .iter()
-            .filter(|(key, _)| !store.stale_key_buffer.contains(key))
+            .filter(|(key, _)| {
+                !store
+                    .stale_key_buffer
+                    .iter()
+                    .any(|stale_key| stale_key.node_key == **key)
+            })
             .filter(|(_, node)| matches!(node, TreeNode::Leaf(TreeLeafNode { .. })))
             .count()
     }
@@ -265,7 +273,7 @@ fn records_stale_tree_node_keys() {
     let stale_versions = store
         .stale_key_buffer
         .iter()
-        .map(|key| key.version())
+        .map(|stale_key| stale_key.node_key.version())
         .unique()
         .sorted()
         .collect::<Vec<Version>>();
@@ -334,16 +342,175 @@ fn serialized_keys_are_strictly_increasing() {
     assert!(next_key > previous_key);
 }
 
+#[test]
+fn substate_proof_verifies_inclusion_of_existing_substates() {
+    let mut store = TypedInMemoryTreeStore::new();
+    put_at_next_version(
+        &mut store,
+        None,
+        vec![
+            change(1, 2, Some(30)),
+            change(1, 7, Some(40)),
+            change(4, 6, Some(50)),
+        ],
+    );
+    let root_hash = get_state_root_hash(&store, 1).unwrap();
+    for (partition_key_seed, sort_key_seed, value_seed) in [(1, 2, 30), (1, 7, 40), (4, 6, 50)] {
+        let (partition_key, sort_key) = substate_key(partition_key_seed, sort_key_seed);
+        let proof = get_substate_proof(&store, 1, &partition_key, &sort_key);
+        assert_eq!(
+            proof.verify_hash(
+                &root_hash,
+                &partition_key,
+                &sort_key,
+                Some(&value_hash(value_seed))
+            ),
+            Ok(())
+        );
+    }
+}
+
+#[test]
+fn substate_proof_verifies_exclusion_of_missing_substates() {
+    let mut store = TypedInMemoryTreeStore::new();
+    put_at_next_version(
+        &mut store,
+        None,
+        vec![change(1, 2, Some(30)), change(4, 6, Some(50))],
+    );
+    let root_hash = get_state_root_hash(&store, 1).unwrap();
+
+    // a missing substate within an existing partition
+    let (partition_key, sort_key) = substate_key(1, 3);
+    let proof = get_substate_proof(&store, 1, &partition_key, &sort_key);
+    assert!(proof.substate_proof.is_some());
+    assert_eq!(
+        proof.verify_hash(&root_hash, &partition_key, &sort_key, None),
+        Ok(())
+    );
+
+    // a substate within an entirely missing partition
+    let (partition_key, sort_key) = substate_key(9, 2);
+    let proof = get_substate_proof(&store, 1, &partition_key, &sort_key);
+    assert!(proof.substate_proof.is_none());
+    assert_eq!(
+        proof.verify_hash(&root_hash, &partition_key, &sort_key, None),
+        Ok(())
+    );
+}
+
+#[test]
+fn substate_proof_rejects_wrong_value_and_wrong_root() {
+    let mut store = TypedInMemoryTreeStore::new();
+    put_at_next_version(
+        &mut store,
+        None,
+        vec![change(1, 2, Some(30)), change(1, 5, Some(20))],
+    );
+    let root_hash_v1 = get_state_root_hash(&store, 1).unwrap();
+    put_at_next_version(&mut store, Some(1), vec![change(1, 2, Some(70))]);
+    let root_hash_v2 = get_state_root_hash(&store, 2).unwrap();
+    let (partition_key, sort_key) = substate_key(1, 2);
+    let proof_v1 = get_substate_proof(&store, 1, &partition_key, &sort_key);
+
+    assert_eq!(
+        proof_v1.verify_hash(
+            &root_hash_v1,
+            &partition_key,
+            &sort_key,
+            Some(&value_hash(30))
+        ),
+        Ok(())
+    );
+    assert!(matches!(
+        proof_v1.verify_hash(
+            &root_hash_v1,
+            &partition_key,
+            &sort_key,
+            Some(&value_hash(70))
+        ),
+        Err(ProofVerificationError::ValueHashMismatch { .. })
+    ));
+    assert!(matches!(
+        proof_v1.verify_hash(&root_hash_v1, &partition_key, &sort_key, None),
+        Err(ProofVerificationError::UnexpectedInclusion)
+    ));
+    assert!(matches!(
+        proof_v1.verify_hash(
+            &root_hash_v2,
+            &partition_key,
+            &sort_key,
+            Some(&value_hash(30))
+        ),
+        Err(ProofVerificationError::RootHashMismatch { .. })
+    ));
+}
+
+#[test]
+fn pruning_stale_nodes_keeps_retained_versions_provable() {
+    let mut store = TypedInMemoryTreeStore::new();
+    put_at_next_version(
+        &mut store,
+        None,
+        vec![change(1, 2, Some(30)), change(4, 6, Some(50))],
+    );
+    put_at_next_version(&mut store, Some(1), vec![change(1, 2, Some(31))]);
+    put_at_next_version(&mut store, Some(2), vec![change(1, 2, Some(32))]);
+    put_at_next_version(&mut store, Some(3), vec![change(4, 6, Some(51))]);
+    let node_count_before = store.root_tree_nodes.len() + store.sub_tree_nodes.len();
+
+    let processed_stale_versions = store.prune_stale_nodes(3, usize::MAX);
+
+    assert_eq!(processed_stale_versions, 2);
+    assert!(store.root_tree_nodes.len() + store.sub_tree_nodes.len() < node_count_before);
+    assert_eq!(get_state_root_hash(&store, 1), None);
+    assert_eq!(get_state_root_hash(&store, 2), None);
+    for (version, value_seed) in [(3, 32), (4, 32)] {
+        let root_hash = get_state_root_hash(&store, version).unwrap();
+        let (partition_key, sort_key) = substate_key(1, 2);
+        let proof = get_substate_proof(&store, version, &partition_key, &sort_key);
+        assert_eq!(
+            proof.verify_hash(
+                &root_hash,
+                &partition_key,
+                &sort_key,
+                Some(&value_hash(value_seed))
+            ),
+            Ok(())
+        );
+    }
+}
+
+#[test]
+fn pruning_stale_nodes_runs_incrementally() {
+    let mut store = TypedInMemoryTreeStore::new();
+    put_at_next_version(&mut store, None, vec![change(1, 2, Some(30))]);
+    put_at_next_version(&mut store, Some(1), vec![change(1, 2, Some(31))]);
+    put_at_next_version(&mut store, Some(2), vec![change(1, 2, Some(32))]);
+
+    assert_eq!(store.prune_stale_nodes(3, 1), 1);
+    assert_eq!(get_state_root_hash(&store, 1), None);
+    assert!(get_state_root_hash(&store, 2).is_some());
+    assert_eq!(store.prune_stale_nodes(3, 1), 1);
+    assert_eq!(get_state_root_hash(&store, 2), None);
+    assert_eq!(store.prune_stale_nodes(3, 1), 0);
+    assert!(get_state_root_hash(&store, 3).is_some());
+}
+
+fn substate_key(partition_key_seed: u8, sort_key_seed: u8) -> (DbPartitionKey, DbSortKey) {
+    (
+        DbPartitionKey(vec![partition_key_seed; Hash::LENGTH]),
+        DbSortKey(vec![sort_key_seed; sort_key_seed as usize]),
+    )
+}
+
 fn change(
     partition_key_seed: u8,
     sort_key_seed: u8,
     value_hash_seed: Option<u8>,
 ) -> SubstateHashChange {
     SubstateHashChange::new(
-        (
-            DbPartitionKey(vec![partition_key_seed; Hash::LENGTH]),
-            DbSortKey(vec![sort_key_seed; sort_key_seed as usize]),
-        ),
+        substate_key(partition_key_seed, sort_key_seed),
         value_hash_seed.map(|value_seed| value_hash(value_seed)),
     )
 }
@@ -84,19 +84,30 @@ pub trait WriteableTreeStore<P: Payload> {
 
     /// Marks the given node for a (potential) future removal by an arbitrary
     /// external pruning process.
-    fn record_stale_node(&mut self, key: NodeKey);
+    /// The node is not a part of the tree since the given version (i.e. it is only needed for
+    /// reading the versions preceding it).
+    fn record_stale_node(&mut self, key: NodeKey, stale_since_version: Version);
 }
 
 /// A complete tree node storage SPI.
 pub trait TreeStore<P: Payload>: ReadableTreeStore<P> + WriteableTreeStore<P> {}
 impl<S: ReadableTreeStore<P> + WriteableTreeStore<P>, P: Payload> TreeStore<P> for S {}
 
+/// A key of a node which is not a part of the tree since a specific version.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct StaleTreeNodeKey {
+    pub node_key: NodeKey,
+    pub stale_since_version: Version,
+}
+
 /// A `TreeStore` based on memory object copies (i.e. no serialization).
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct TypedInMemoryTreeStore {
     pub root_tree_nodes: HashMap<NodeKey, TreeNode<PartitionPayload>>,
     pub sub_tree_nodes: HashMap<NodeKey, TreeNode<()>>,
-    pub stale_key_buffer: Vec<NodeKey>,
+    /// Keys of stale nodes which were not pruned yet, in the order of their recording (i.e. also
+    /// by their `stale_since_version`).
+    pub stale_key_buffer: Vec<StaleTreeNodeKey>,
 }
 
 impl TypedInMemoryTreeStore {
@@ -108,6 +119,39 @@ impl TypedInMemoryTreeStore {
             stale_key_buffer: Vec::new(),
         }
     }
+
+    /// Deletes the nodes which are not needed for reading any version since the given
+    /// `oldest_retained_version`, processing at most `max_stale_versions` (to allow running it
+    /// incrementally).
+    /// Returns the number of processed stale versions (i.e. `0` means that nothing more can be
+    /// pruned until the retention horizon moves).
+    pub fn prune_stale_nodes(
+        &mut self,
+        oldest_retained_version: Version,
+        max_stale_versions: usize,
+    ) -> usize {
+        let mut processed_stale_versions = 0;
+        let mut last_stale_since_version = None;
+        let mut pruned_count = 0;
+        for stale_key in &self.stale_key_buffer {
+            if stale_key.stale_since_version > oldest_retained_version {
+                break;
+            }
+            if last_stale_since_version != Some(stale_key.stale_since_version) {
+                if processed_stale_versions == max_stale_versions {
+                    break;
+                }
+                processed_stale_versions += 1;
+                last_stale_since_version = Some(stale_key.stale_since_version);
+            }
+            // the keys are unique across both layers, so we can simply try both
+            self.root_tree_nodes.remove(&stale_key.node_key);
+            self.sub_tree_nodes.remove(&stale_key.node_key);
+            pruned_count += 1;
+        }
+        self.stale_key_buffer.drain(..pruned_count);
+        processed_stale_versions
+    }
 }
 
 impl ReadableTreeStore<()> for TypedInMemoryTreeStore {
@@ -121,8 +165,11 @@ impl WriteableTreeStore<()> for TypedInMemoryTreeStore {
         self.sub_tree_nodes.insert(key, node);
     }
 
-    fn record_stale_node(&mut self, key: NodeKey) {
-        self.stale_key_buffer.push(key);
+    fn record_stale_node(&mut self, key: NodeKey, stale_since_version: Version) {
+        self.stale_key_buffer.push(StaleTreeNodeKey {
+            node_key: key,
+            stale_since_version,
+        });
     }
 }
 
@@ -137,8 +184,11 @@ impl WriteableTreeStore<PartitionPayload> for TypedInMemoryTreeStore {
         self.root_tree_nodes.insert(key, node);
     }
 
-    fn record_stale_node(&mut self, key: NodeKey) {
-        self.stale_key_buffer.push(key);
+    fn record_stale_node(&mut self, key: NodeKey, stale_since_version: Version) {
+        self.stale_key_buffer.push(StaleTreeNodeKey {
+            node_key: key,
+            stale_since_version,
+        });
     }
 }
 
@@ -173,7 +223,7 @@ impl<P: Payload> WriteableTreeStore<P> for SerializedInMemoryTreeStore {
             .insert(encode_key(&key), scrypto_encode(&node).unwrap());
     }
 
-    fn record_stale_node(&mut self, key: NodeKey) {
+    fn record_stale_node(&mut self, key: NodeKey, _stale_since_version: Version) {
         self.stale_key_buffer.push(encode_key(&key));
     }
 }
@@ -11,3 +11,5 @@ pub mod memory_db;
 pub mod rocks_db;
 #[cfg(feature = "rocksdb")]
 pub mod rocks_db_with_merkle_tree;
+#[cfg(feature = "std")]
+pub mod snapshot;
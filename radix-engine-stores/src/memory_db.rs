@@ -26,15 +26,39 @@ impl SubstateDatabase for InMemorySubstateDatabase {
             .cloned()
     }
 
-    fn list_entries(
+    fn list_entries_from(
         &self,
         partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
     ) -> Box<dyn Iterator<Item = PartitionEntry> + '_> {
+        let from_sort_key = from_sort_key.cloned();
         let iter = self
             .partitions
             .get(partition_key)
             .into_iter()
-            .flat_map(|partition| partition.iter())
+            .flat_map(move |partition| match &from_sort_key {
+                Some(from_sort_key) => partition.range(from_sort_key.clone()..),
+                None => partition.range(..),
+            })
+            .map(|(key, substate)| (key.clone(), substate.clone()));
+
+        Box::new(iter)
+    }
+
+    fn list_entries_rev_from(
+        &self,
+        partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
+    ) -> Box<dyn Iterator<Item = PartitionEntry> + '_> {
+        let from_sort_key = from_sort_key.cloned();
+        let iter = self
+            .partitions
+            .get(partition_key)
+            .into_iter()
+            .flat_map(move |partition| match &from_sort_key {
+                Some(from_sort_key) => partition.range(..=from_sort_key.clone()).rev(),
+                None => partition.range(..).rev(),
+            })
             .map(|(key, substate)| (key.clone(), substate.clone()));
 
         Box::new(iter)
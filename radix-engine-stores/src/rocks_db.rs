@@ -33,12 +33,15 @@ impl SubstateDatabase for RocksdbSubstateStore {
         self.db.get(&key_bytes).expect("IO Error")
     }
 
-    fn list_entries(
+    fn list_entries_from(
         &self,
         partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
     ) -> Box<dyn Iterator<Item = PartitionEntry> + '_> {
         let partition_key = partition_key.clone();
-        let start_key_bytes = encode_to_rocksdb_bytes(&partition_key, &DbSortKey(vec![]));
+        let empty_sort_key = DbSortKey(vec![]);
+        let from_sort_key = from_sort_key.unwrap_or(&empty_sort_key);
+        let start_key_bytes = encode_to_rocksdb_bytes(&partition_key, from_sort_key);
         let iter = self
             .db
             .iterator(IteratorMode::From(&start_key_bytes, Direction::Forward))
@@ -52,6 +55,46 @@ impl SubstateDatabase for RocksdbSubstateStore {
 
         Box::new(iter)
     }
+
+    fn list_entries_rev_from(
+        &self,
+        partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
+    ) -> Box<dyn Iterator<Item = PartitionEntry> + '_> {
+        let partition_key = partition_key.clone();
+        // When no sort key is given, we seek (backwards) from the first key *after* the partition
+        // (which itself may exist and belong to some other partition - hence the `skip_while()`).
+        let start_key_bytes = match from_sort_key {
+            Some(from_sort_key) => Some(encode_to_rocksdb_bytes(&partition_key, from_sort_key)),
+            None => encode_partition_upper_bound(&partition_key),
+        };
+        let raw_iter = match &start_key_bytes {
+            Some(start_key_bytes) => self
+                .db
+                .iterator(IteratorMode::From(start_key_bytes, Direction::Reverse)),
+            None => self.db.iterator(IteratorMode::End),
+        };
+        let upper_bound_key_bytes = if from_sort_key.is_none() {
+            start_key_bytes
+        } else {
+            None
+        };
+        let iter = raw_iter
+            .map(|kv| {
+                let (iter_key_bytes, iter_value) = kv.unwrap();
+                (iter_key_bytes.to_vec(), iter_value.to_vec())
+            })
+            .skip_while(move |(iter_key_bytes, _)| {
+                Some(iter_key_bytes) == upper_bound_key_bytes.as_ref()
+            })
+            .map(|(iter_key_bytes, iter_value)| {
+                (decode_from_rocksdb_bytes(&iter_key_bytes), iter_value)
+            })
+            .take_while(move |((iter_partition_key, _), _)| *iter_partition_key == partition_key)
+            .map(|((_, iter_sort_key), iter_value)| (iter_sort_key, iter_value));
+
+        Box::new(iter)
+    }
 }
 
 impl CommittableSubstateDatabase for RocksdbSubstateStore {
@@ -94,6 +137,20 @@ fn encode_to_rocksdb_bytes(partition_key: &DbPartitionKey, sort_key: &DbSortKey)
     buffer
 }
 
+/// Returns the smallest key (in the encoding of [`encode_to_rocksdb_bytes()`], which is shared by
+/// all RocksDB-based stores) which is greater than all keys of the given partition, or [`None`] if
+/// no such key exists.
+pub(crate) fn encode_partition_upper_bound(partition_key: &DbPartitionKey) -> Option<Vec<u8>> {
+    let mut buffer = encode_to_rocksdb_bytes(partition_key, &DbSortKey(vec![]));
+    while let Some(last_byte) = buffer.pop() {
+        if last_byte < u8::MAX {
+            buffer.push(last_byte + 1);
+            return Some(buffer);
+        }
+    }
+    None
+}
+
 fn decode_from_rocksdb_bytes(buffer: &[u8]) -> DbSubstateKey {
     let partition_key_len =
         usize::try_from(u32::from_be_bytes(copy_u8_array(&buffer[..4]))).unwrap();
@@ -0,0 +1,287 @@
+use super::{
+    encode_partition_upper_bound, RocksDBWithMerkleTreeSubstateStore, SUBSTATE_HISTORY_CF,
+};
+use radix_engine_common::data::scrypto::{scrypto_decode, scrypto_encode};
+use radix_engine_derive::ScryptoSbor;
+use radix_engine_store_interface::interface::*;
+use rocksdb::{Direction, IteratorMode};
+use sbor::rust::iter::Peekable;
+use sbor::rust::prelude::*;
+use utils::copy_u8_array;
+
+/// A configuration of the (optional) per-version substate history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateHistoryConfig {
+    /// The number of most recent state versions (not counting the current one) which remain
+    /// readable, or [`None`] if the history should never be pruned.
+    pub retained_versions: Option<u64>,
+}
+
+impl StateHistoryConfig {
+    pub fn unpruned() -> Self {
+        Self {
+            retained_versions: None,
+        }
+    }
+
+    pub fn retaining_versions(retained_versions: u64) -> Self {
+        Self {
+            retained_versions: Some(retained_versions),
+        }
+    }
+}
+
+/// An error when opening a store with the substate history enabled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateHistoryError {
+    /// The history can only be enabled on an empty database, since it must cover all versions.
+    DatabaseNotEmpty { current_state_version: u64 },
+}
+
+/// A persisted information about the range of state versions available in the substate history.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, ScryptoSbor)]
+pub(super) struct HistoryMetadata {
+    pub earliest_state_version: u64,
+}
+
+/// A read-only [`SubstateDatabase`] view of the [`RocksDBWithMerkleTreeSubstateStore`], as it
+/// was at a specific (historical) state version.
+pub struct HistoricalSubstateDatabase<'s> {
+    store: &'s RocksDBWithMerkleTreeSubstateStore,
+    state_version: u64,
+}
+
+impl<'s> HistoricalSubstateDatabase<'s> {
+    pub(super) fn new(store: &'s RocksDBWithMerkleTreeSubstateStore, state_version: u64) -> Self {
+        Self {
+            store,
+            state_version,
+        }
+    }
+
+    pub fn state_version(&self) -> u64 {
+        self.state_version
+    }
+
+    fn iterate_history(
+        &self,
+        start_key_bytes: Option<Vec<u8>>,
+        direction: Direction,
+    ) -> Box<dyn Iterator<Item = (DbSubstateKey, u64, Option<DbSubstateValue>)> + 's> {
+        let history_cf = self.store.cf(SUBSTATE_HISTORY_CF);
+        let raw_iter = match &start_key_bytes {
+            Some(start_key_bytes) => self
+                .store
+                .db
+                .iterator_cf(history_cf, IteratorMode::From(start_key_bytes, direction)),
+            None => self.store.db.iterator_cf(history_cf, IteratorMode::End),
+        };
+        Box::new(raw_iter.map(|kv| {
+            let (iter_key_bytes, iter_value) = kv.unwrap();
+            let (substate_key, state_version) = decode_history_key(&iter_key_bytes);
+            (
+                substate_key,
+                state_version,
+                scrypto_decode::<Option<DbSubstateValue>>(&iter_value).unwrap(),
+            )
+        }))
+    }
+}
+
+impl<'s> SubstateDatabase for HistoricalSubstateDatabase<'s> {
+    fn get_substate(
+        &self,
+        partition_key: &DbPartitionKey,
+        sort_key: &DbSortKey,
+    ) -> Option<DbSubstateValue> {
+        get_historical_entry(self.store, partition_key, sort_key, self.state_version)
+            .and_then(|(_, value)| value)
+    }
+
+    fn list_entries_from(
+        &self,
+        partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
+    ) -> Box<dyn Iterator<Item = PartitionEntry> + '_> {
+        let start_key_bytes = match from_sort_key {
+            Some(from_sort_key) => encode_history_key_prefix(partition_key, from_sort_key, false),
+            None => encode_history_partition_prefix(partition_key),
+        };
+        let partition_key = partition_key.clone();
+        let raw_iter = self
+            .iterate_history(Some(start_key_bytes), Direction::Forward)
+            .take_while(move |((iter_partition_key, _), _, _)| *iter_partition_key == partition_key)
+            .map(|((_, sort_key), state_version, value)| (sort_key, state_version, value));
+        Box::new(VersionResolvingIterator::new(raw_iter, self.state_version))
+    }
+
+    fn list_entries_rev_from(
+        &self,
+        partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
+    ) -> Box<dyn Iterator<Item = PartitionEntry> + '_> {
+        let start_key_bytes = match from_sort_key {
+            Some(from_sort_key) => Some(encode_history_key(partition_key, from_sort_key, u64::MAX)),
+            // Note: the upper bound is never equal to an actual history key (since it misses the
+            // encoded state version), so there is nothing to skip here.
+            None => encode_partition_upper_bound(partition_key),
+        };
+        let partition_key = partition_key.clone();
+        let raw_iter = self
+            .iterate_history(start_key_bytes, Direction::Reverse)
+            .take_while(move |((iter_partition_key, _), _, _)| *iter_partition_key == partition_key)
+            .map(|((_, sort_key), state_version, value)| (sort_key, state_version, value));
+        Box::new(VersionResolvingIterator::new(raw_iter, self.state_version))
+    }
+}
+
+/// An iterator which groups the consecutive history entries of the same sort key and only returns
+/// the one which was current at the given state version (unless it represents a deletion).
+struct VersionResolvingIterator<I: Iterator<Item = (DbSortKey, u64, Option<DbSubstateValue>)>> {
+    history_entries: Peekable<I>,
+    state_version: u64,
+}
+
+impl<I: Iterator<Item = (DbSortKey, u64, Option<DbSubstateValue>)>> VersionResolvingIterator<I> {
+    fn new(history_entries: I, state_version: u64) -> Self {
+        Self {
+            history_entries: history_entries.peekable(),
+            state_version,
+        }
+    }
+}
+
+impl<I: Iterator<Item = (DbSortKey, u64, Option<DbSubstateValue>)>> Iterator
+    for VersionResolvingIterator<I>
+{
+    type Item = PartitionEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (sort_key, state_version, value) = self.history_entries.next()?;
+            let mut resolved = if state_version <= self.state_version {
+                Some((state_version, value))
+            } else {
+                None
+            };
+            while let Some((next_sort_key, _, _)) = self.history_entries.peek() {
+                if *next_sort_key != sort_key {
+                    break;
+                }
+                let (_, next_state_version, next_value) = self.history_entries.next().unwrap();
+                let is_more_recent = resolved
+                    .as_ref()
+                    .map(|(resolved_state_version, _)| next_state_version > *resolved_state_version)
+                    .unwrap_or(true);
+                if next_state_version <= self.state_version && is_more_recent {
+                    resolved = Some((next_state_version, next_value));
+                }
+            }
+            if let Some((_, Some(value))) = resolved {
+                return Some((sort_key, value));
+            }
+        }
+    }
+}
+
+/// Returns the state version and the value (or [`None`] if deleted) of the history entry of the
+/// given substate which was current at the given state version.
+pub(super) fn get_historical_entry(
+    store: &RocksDBWithMerkleTreeSubstateStore,
+    partition_key: &DbPartitionKey,
+    sort_key: &DbSortKey,
+    state_version: u64,
+) -> Option<(u64, Option<DbSubstateValue>)> {
+    let seek_key_bytes = encode_history_key(partition_key, sort_key, state_version);
+    let key_prefix_bytes = encode_history_key_prefix(partition_key, sort_key, true);
+    store
+        .db
+        .iterator_cf(
+            store.cf(SUBSTATE_HISTORY_CF),
+            IteratorMode::From(&seek_key_bytes, Direction::Reverse),
+        )
+        .next()
+        .map(|kv| kv.unwrap())
+        .filter(|(iter_key_bytes, _)| {
+            iter_key_bytes.len() == key_prefix_bytes.len() + 8
+                && iter_key_bytes.starts_with(&key_prefix_bytes)
+        })
+        .map(|(iter_key_bytes, iter_value)| {
+            let (_, entry_state_version) = decode_history_key(&iter_key_bytes);
+            (
+                entry_state_version,
+                scrypto_decode::<Option<DbSubstateValue>>(&iter_value).unwrap(),
+            )
+        })
+}
+
+pub(super) fn encode_history_value(database_update: &DatabaseUpdate) -> Vec<u8> {
+    let value = match database_update {
+        DatabaseUpdate::Set(value) => Some(value.clone()),
+        DatabaseUpdate::Delete => None,
+    };
+    scrypto_encode(&value).unwrap()
+}
+
+/// Encodes a key of the substate history, i.e. a partition key (length-prefixed), a sort key (in
+/// an order-preserving, self-delimiting escaped form) and a state version (big-endian).
+pub(super) fn encode_history_key(
+    partition_key: &DbPartitionKey,
+    sort_key: &DbSortKey,
+    state_version: u64,
+) -> Vec<u8> {
+    let mut buffer = encode_history_key_prefix(partition_key, sort_key, true);
+    buffer.extend(state_version.to_be_bytes());
+    buffer
+}
+
+fn encode_history_partition_prefix(partition_key: &DbPartitionKey) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    buffer.extend(u32::try_from(partition_key.0.len()).unwrap().to_be_bytes());
+    buffer.extend(partition_key.0.clone());
+    buffer
+}
+
+fn encode_history_key_prefix(
+    partition_key: &DbPartitionKey,
+    sort_key: &DbSortKey,
+    terminated: bool,
+) -> Vec<u8> {
+    let mut buffer = encode_history_partition_prefix(partition_key);
+    // Each `0x00` byte is escaped as `0x00 0x01`, and the key is terminated with `0x00 0x00`, which
+    // preserves the lexicographical order of sort keys (needed by range iteration).
+    for byte in &sort_key.0 {
+        buffer.push(*byte);
+        if *byte == 0 {
+            buffer.push(1);
+        }
+    }
+    if terminated {
+        buffer.extend([0, 0]);
+    }
+    buffer
+}
+
+fn decode_history_key(buffer: &[u8]) -> (DbSubstateKey, u64) {
+    let partition_key_len =
+        usize::try_from(u32::from_be_bytes(copy_u8_array(&buffer[..4]))).unwrap();
+    let sort_key_offset = 4 + partition_key_len;
+    let partition_key = DbPartitionKey(buffer[4..sort_key_offset].to_vec());
+    let mut sort_key_bytes = Vec::new();
+    let mut offset = sort_key_offset;
+    loop {
+        let byte = buffer[offset];
+        if byte == 0 {
+            let escaped = buffer[offset + 1];
+            offset += 2;
+            if escaped == 0 {
+                break;
+            }
+        } else {
+            offset += 1;
+        }
+        sort_key_bytes.push(byte);
+    }
+    let state_version = u64::from_be_bytes(copy_u8_array(&buffer[offset..]));
+    ((partition_key, DbSortKey(sort_key_bytes)), state_version)
+}
@@ -1,6 +1,10 @@
 //use super::compute_state_tree_update;
+use crate::hash_tree::proof::SubstateProof;
 use crate::hash_tree::tree_store::{encode_key, NodeKey, Payload, ReadableTreeStore, TreeNode};
+use crate::hash_tree::{get_state_root_hash, get_substate_proof};
+use crate::rocks_db::encode_partition_upper_bound;
 use itertools::Itertools;
+use radix_engine_common::crypto::Hash;
 use radix_engine_common::data::scrypto::{scrypto_decode, scrypto_encode};
 use radix_engine_derive::ScryptoSbor;
 use radix_engine_store_interface::interface::*;
@@ -12,16 +16,25 @@ use rocksdb::{
 use sbor::rust::prelude::*;
 use std::path::PathBuf;
 use utils::copy_u8_array;
+mod history;
 mod state_tree;
+use history::*;
+pub use history::{HistoricalSubstateDatabase, StateHistoryConfig, StateHistoryError};
 use state_tree::*;
 
 const META_CF: &str = "meta";
 const SUBSTATES_CF: &str = "substates";
 const MERKLE_NODES_CF: &str = "merkle_nodes";
 const STALE_MERKLE_NODE_KEYS_CF: &str = "stale_merkle_node_keys";
+const SUBSTATE_HISTORY_CF: &str = "substate_history";
+const SUPERSEDED_SUBSTATE_HISTORY_KEYS_CF: &str = "superseded_substate_history_keys";
+
+const METADATA_KEY: [u8; 0] = [];
+const HISTORY_METADATA_KEY: [u8; 1] = [1];
 
 pub struct RocksDBWithMerkleTreeSubstateStore {
     db: DBWithThreadMode<SingleThreaded>,
+    history_config: Option<StateHistoryConfig>,
 }
 
 impl RocksDBWithMerkleTreeSubstateStore {
@@ -33,6 +46,36 @@ impl RocksDBWithMerkleTreeSubstateStore {
     }
 
     pub fn with_options(options: &Options, root: PathBuf) -> Self {
+        let store = Self::open(options, root, None);
+        store.forget_history();
+        store
+    }
+
+    /// Opens a store which additionally keeps the substate history (see [`StateHistoryConfig`]),
+    /// allowing to read the state at past versions via [`Self::historical_view()`].
+    /// The history has to be enabled since the very first commit, and consistently kept enabled -
+    /// otherwise, a [`StateHistoryError`] is returned.
+    pub fn with_history(
+        root: PathBuf,
+        history_config: StateHistoryConfig,
+    ) -> Result<Self, StateHistoryError> {
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+        Self::with_options_and_history(&options, root, history_config)
+    }
+
+    pub fn with_options_and_history(
+        options: &Options,
+        root: PathBuf,
+        history_config: StateHistoryConfig,
+    ) -> Result<Self, StateHistoryError> {
+        let store = Self::open(options, root, Some(history_config));
+        store.initialize_history()?;
+        Ok(store)
+    }
+
+    fn open(options: &Options, root: PathBuf, history_config: Option<StateHistoryConfig>) -> Self {
         let db = DB::open_cf_descriptors(
             options,
             root.as_path(),
@@ -41,13 +84,224 @@ impl RocksDBWithMerkleTreeSubstateStore {
                 SUBSTATES_CF,
                 MERKLE_NODES_CF,
                 STALE_MERKLE_NODE_KEYS_CF,
+                SUBSTATE_HISTORY_CF,
+                SUPERSEDED_SUBSTATE_HISTORY_KEYS_CF,
             ]
             .into_iter()
             .map(|name| ColumnFamilyDescriptor::new(name, Options::default()))
             .collect::<Vec<_>>(),
         )
         .unwrap();
-        Self { db }
+        Self { db, history_config }
+    }
+
+    /// Returns the version of the latest committed state (or 0 for an empty database).
+    pub fn get_current_version(&self) -> u64 {
+        self.read_metadata().current_state_version
+    }
+
+    /// Returns the earliest state version which can be read via [`Self::historical_view()`], or
+    /// [`None`] if the substate history is not enabled.
+    pub fn get_earliest_historical_version(&self) -> Option<u64> {
+        self.history_config?;
+        self.read_history_metadata()
+            .map(|history_metadata| history_metadata.earliest_state_version)
+    }
+
+    /// Returns a read-only view of the state as it was at the given version, or [`None`] if
+    /// the version is not (or no longer) available in the substate history.
+    pub fn historical_view(&self, state_version: u64) -> Option<HistoricalSubstateDatabase<'_>> {
+        let earliest_state_version = self.get_earliest_historical_version()?;
+        if state_version < earliest_state_version || state_version > self.get_current_version() {
+            return None;
+        }
+        Some(HistoricalSubstateDatabase::new(self, state_version))
+    }
+
+    /// Returns the root hash of the state tree at the given version, or [`None`] if the version
+    /// does not exist (or its tree nodes were already pruned).
+    pub fn get_state_root_hash(&self, state_version: u64) -> Option<Hash> {
+        get_state_root_hash(self, state_version)
+    }
+
+    /// Returns a proof of the given substate's value (or its absence) at the given state
+    /// version, to be verified against [`Self::get_state_root_hash()`] (see
+    /// [`SubstateProof::verify()`]), or [`None`] if the version does not exist.
+    pub fn get_substate_proof(
+        &self,
+        state_version: u64,
+        partition_key: &DbPartitionKey,
+        sort_key: &DbSortKey,
+    ) -> Option<SubstateProof> {
+        self.get_state_root_hash(state_version)?;
+        Some(get_substate_proof(
+            self,
+            state_version,
+            partition_key,
+            sort_key,
+        ))
+    }
+
+    /// Deletes the state tree nodes which are not needed for reading any version since the given
+    /// `oldest_retained_version`, processing at most `max_stale_versions` (to allow running it
+    /// incrementally, e.g. as an amortized background GC).
+    /// Returns the number of processed stale versions (i.e. `0` means that nothing more can be
+    /// pruned until the retention horizon moves).
+    pub fn prune_stale_tree_nodes(
+        &mut self,
+        oldest_retained_version: u64,
+        max_stale_versions: usize,
+    ) -> usize {
+        let mut batch = WriteBatch::default();
+        let mut processed_stale_versions = 0;
+        for kv in self
+            .db
+            .iterator_cf(self.cf(STALE_MERKLE_NODE_KEYS_CF), IteratorMode::Start)
+            .take(max_stale_versions)
+        {
+            let (version_bytes, encoded_node_keys) = kv.unwrap();
+            let stale_since_version = u64::from_be_bytes(copy_u8_array(&version_bytes));
+            if stale_since_version > oldest_retained_version {
+                break;
+            }
+            for encoded_node_key in scrypto_decode::<Vec<Vec<u8>>>(&encoded_node_keys).unwrap() {
+                batch.delete_cf(self.cf(MERKLE_NODES_CF), encoded_node_key);
+            }
+            batch.delete_cf(self.cf(STALE_MERKLE_NODE_KEYS_CF), version_bytes);
+            processed_stale_versions += 1;
+        }
+        self.db.write(batch).unwrap();
+        processed_stale_versions
+    }
+
+    fn initialize_history(&self) -> Result<(), StateHistoryError> {
+        if self.read_history_metadata().is_some() {
+            return Ok(());
+        }
+        let current_state_version = self.get_current_version();
+        if current_state_version != 0 {
+            return Err(StateHistoryError::DatabaseNotEmpty {
+                current_state_version,
+            });
+        }
+        self.db
+            .put_cf(
+                self.cf(META_CF),
+                HISTORY_METADATA_KEY,
+                scrypto_encode(&HistoryMetadata {
+                    earliest_state_version: 0,
+                })
+                .unwrap(),
+            )
+            .unwrap();
+        Ok(())
+    }
+
+    fn forget_history(&self) {
+        if self.read_history_metadata().is_some() {
+            // the history would not be maintained anymore, so it must not be trusted later
+            self.db
+                .delete_cf(self.cf(META_CF), HISTORY_METADATA_KEY)
+                .unwrap();
+        }
+    }
+
+    fn read_metadata(&self) -> Metadata {
+        self.db
+            .get_cf(self.cf(META_CF), METADATA_KEY)
+            .unwrap()
+            .map(|bytes| scrypto_decode::<Metadata>(&bytes).unwrap())
+            .unwrap_or_else(|| Metadata {
+                current_state_version: 0,
+            })
+    }
+
+    fn read_history_metadata(&self) -> Option<HistoryMetadata> {
+        self.db
+            .get_cf(self.cf(META_CF), HISTORY_METADATA_KEY)
+            .unwrap()
+            .map(|bytes| scrypto_decode::<HistoryMetadata>(&bytes).unwrap())
+    }
+
+    /// Puts the history entries of the given updates (made at the given version) into the batch,
+    /// and deletes the entries which are no longer reachable from any retained version.
+    fn put_history(
+        &self,
+        batch: &mut WriteBatch,
+        next_state_version: u64,
+        database_updates: &DatabaseUpdates,
+    ) {
+        let Some(history_config) = self.history_config else {
+            return;
+        };
+        let parent_state_version = next_state_version - 1;
+
+        // put the new entries, recording the previous ones as superseded
+        let mut superseded_history_keys = Vec::new();
+        for (partition_key, partition_updates) in database_updates {
+            for (sort_key, database_update) in partition_updates {
+                if let Some((previous_state_version, _)) =
+                    get_historical_entry(self, partition_key, sort_key, parent_state_version)
+                {
+                    superseded_history_keys.push(encode_history_key(
+                        partition_key,
+                        sort_key,
+                        previous_state_version,
+                    ));
+                }
+                batch.put_cf(
+                    self.cf(SUBSTATE_HISTORY_CF),
+                    encode_history_key(partition_key, sort_key, next_state_version),
+                    encode_history_value(database_update),
+                );
+            }
+        }
+        batch.put_cf(
+            self.cf(SUPERSEDED_SUBSTATE_HISTORY_KEYS_CF),
+            next_state_version.to_be_bytes(),
+            scrypto_encode(&superseded_history_keys).unwrap(),
+        );
+
+        // prune: an entry superseded at version V is only needed for reading versions before V
+        let Some(retained_versions) = history_config.retained_versions else {
+            return;
+        };
+        let history_metadata = self.read_history_metadata().unwrap();
+        let new_earliest_state_version = next_state_version.saturating_sub(retained_versions);
+        if new_earliest_state_version <= history_metadata.earliest_state_version {
+            return;
+        }
+        for superseded_at_version in
+            (history_metadata.earliest_state_version + 1)..=new_earliest_state_version
+        {
+            let superseded_history_keys = if superseded_at_version == next_state_version {
+                superseded_history_keys.clone()
+            } else {
+                self.db
+                    .get_cf(
+                        self.cf(SUPERSEDED_SUBSTATE_HISTORY_KEYS_CF),
+                        superseded_at_version.to_be_bytes(),
+                    )
+                    .unwrap()
+                    .map(|bytes| scrypto_decode::<Vec<Vec<u8>>>(&bytes).unwrap())
+                    .unwrap_or_default()
+            };
+            for history_key in superseded_history_keys {
+                batch.delete_cf(self.cf(SUBSTATE_HISTORY_CF), history_key);
+            }
+            batch.delete_cf(
+                self.cf(SUPERSEDED_SUBSTATE_HISTORY_KEYS_CF),
+                superseded_at_version.to_be_bytes(),
+            );
+        }
+        batch.put_cf(
+            self.cf(META_CF),
+            HISTORY_METADATA_KEY,
+            scrypto_encode(&HistoryMetadata {
+                earliest_state_version: new_earliest_state_version,
+            })
+            .unwrap(),
+        );
     }
 
     fn cf(&self, cf: &str) -> &ColumnFamily {
@@ -67,12 +321,15 @@ impl SubstateDatabase for RocksDBWithMerkleTreeSubstateStore {
             .expect("IO Error")
     }
 
-    fn list_entries(
+    fn list_entries_from(
         &self,
         partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
     ) -> Box<dyn Iterator<Item = PartitionEntry> + '_> {
         let partition_key = partition_key.clone();
-        let start_key_bytes = encode_to_rocksdb_bytes(&partition_key, &DbSortKey(vec![]));
+        let empty_sort_key = DbSortKey(vec![]);
+        let from_sort_key = from_sort_key.unwrap_or(&empty_sort_key);
+        let start_key_bytes = encode_to_rocksdb_bytes(&partition_key, from_sort_key);
         let iter = self
             .db
             .iterator_cf(
@@ -89,19 +346,55 @@ impl SubstateDatabase for RocksDBWithMerkleTreeSubstateStore {
 
         Box::new(iter)
     }
+
+    fn list_entries_rev_from(
+        &self,
+        partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
+    ) -> Box<dyn Iterator<Item = PartitionEntry> + '_> {
+        let partition_key = partition_key.clone();
+        // When no sort key is given, we seek (backwards) from the first key *after* the partition
+        // (which itself may exist and belong to some other partition - hence the `skip_while()`).
+        let start_key_bytes = match from_sort_key {
+            Some(from_sort_key) => Some(encode_to_rocksdb_bytes(&partition_key, from_sort_key)),
+            None => encode_partition_upper_bound(&partition_key),
+        };
+        let raw_iter = match &start_key_bytes {
+            Some(start_key_bytes) => self.db.iterator_cf(
+                self.cf(SUBSTATES_CF),
+                IteratorMode::From(start_key_bytes, Direction::Reverse),
+            ),
+            None => self
+                .db
+                .iterator_cf(self.cf(SUBSTATES_CF), IteratorMode::End),
+        };
+        let upper_bound_key_bytes = if from_sort_key.is_none() {
+            start_key_bytes
+        } else {
+            None
+        };
+        let iter = raw_iter
+            .map(|kv| {
+                let (iter_key_bytes, iter_value) = kv.unwrap();
+                (iter_key_bytes.to_vec(), iter_value.to_vec())
+            })
+            .skip_while(move |(iter_key_bytes, _)| {
+                Some(iter_key_bytes) == upper_bound_key_bytes.as_ref()
+            })
+            .map(|(iter_key_bytes, iter_value)| {
+                (decode_from_rocksdb_bytes(&iter_key_bytes), iter_value)
+            })
+            .take_while(move |((iter_partition_key, _), _)| *iter_partition_key == partition_key)
+            .map(|((_, iter_sort_key), iter_value)| (iter_sort_key, iter_value));
+
+        Box::new(iter)
+    }
 }
 
 impl CommittableSubstateDatabase for RocksDBWithMerkleTreeSubstateStore {
     fn commit(&mut self, database_updates: &DatabaseUpdates) {
         // read required info about current database state (here I fake it a bit)
-        let metadata = self
-            .db
-            .get_cf(self.cf(META_CF), [])
-            .unwrap()
-            .map(|bytes| scrypto_decode::<Metadata>(&bytes).unwrap())
-            .unwrap_or_else(|| Metadata {
-                current_state_version: 0,
-            });
+        let metadata = self.read_metadata();
         let parent_state_version = metadata.current_state_version;
         let next_state_version = parent_state_version + 1;
 
@@ -121,7 +414,10 @@ impl CommittableSubstateDatabase for RocksDBWithMerkleTreeSubstateStore {
             }
         }
 
-        // derive and put new JMT nodes (also record keys of stale nodes, for later amortized background GC [not implemented here!])
+        // put the substate history entries (if enabled)
+        self.put_history(&mut batch, next_state_version, database_updates);
+
+        // derive and put new JMT nodes (also record keys of stale nodes, for later amortized background GC - see `prune_stale_tree_nodes()`)
         let state_hash_tree_update =
             compute_state_tree_update(self, parent_state_version, database_updates);
         for (key, node) in state_hash_tree_update.new_re_node_layer_nodes {
@@ -152,7 +448,7 @@ impl CommittableSubstateDatabase for RocksDBWithMerkleTreeSubstateStore {
         // update the metadata
         batch.put_cf(
             self.cf(META_CF),
-            [],
+            METADATA_KEY,
             scrypto_encode(&Metadata {
                 current_state_version: next_state_version,
             })
@@ -168,7 +464,7 @@ impl ListableSubstateDatabase for RocksDBWithMerkleTreeSubstateStore {
     fn list_partition_keys(&self) -> Box<dyn Iterator<Item = DbPartitionKey> + '_> {
         Box::new(
             self.db
-                .iterator(IteratorMode::Start)
+                .iterator_cf(self.cf(SUBSTATES_CF), IteratorMode::Start)
                 .map(|kv| {
                     let (iter_key_bytes, _) = kv.as_ref().unwrap();
                     let (iter_key, _) = decode_from_rocksdb_bytes(iter_key_bytes);
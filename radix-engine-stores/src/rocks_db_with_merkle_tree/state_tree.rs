@@ -1,5 +1,5 @@
 use crate::hash_tree::tree_store::{
-    NodeKey, PartitionPayload, Payload, ReadableTreeStore, TreeNode, WriteableTreeStore,
+    NodeKey, PartitionPayload, Payload, ReadableTreeStore, TreeNode, Version, WriteableTreeStore,
 };
 use crate::hash_tree::{put_at_next_version, SubstateHashChange};
 use radix_engine_common::crypto::hash;
@@ -43,7 +43,7 @@ impl<'s, S> WriteableTreeStore<PartitionPayload> for CollectingTreeStore<'s, S>
         self.diff.new_re_node_layer_nodes.push((key, node));
     }
 
-    fn record_stale_node(&mut self, key: NodeKey) {
+    fn record_stale_node(&mut self, key: NodeKey, _stale_since_version: Version) {
         self.diff.stale_hash_tree_node_keys.push(key);
     }
 }
@@ -53,7 +53,7 @@ impl<'s, S> WriteableTreeStore<()> for CollectingTreeStore<'s, S> {
         self.diff.new_substate_layer_nodes.push((key, node));
     }
 
-    fn record_stale_node(&mut self, key: NodeKey) {
+    fn record_stale_node(&mut self, key: NodeKey, _stale_since_version: Version) {
         self.diff.stale_hash_tree_node_keys.push(key);
     }
 }
@@ -0,0 +1,373 @@
+use crate::hash_tree::tree_store::TypedInMemoryTreeStore;
+use crate::hash_tree::{put_at_next_version, SubstateHashChange};
+use radix_engine_common::crypto::{hash, Hash};
+use radix_engine_common::data::scrypto::{scrypto_decode, scrypto_encode};
+use radix_engine_derive::ScryptoSbor;
+use radix_engine_store_interface::interface::*;
+use sbor::rust::prelude::*;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+/// The bytes starting every snapshot file.
+pub const SNAPSHOT_MAGIC_BYTES: [u8; 4] = *b"RESS";
+
+/// The version of the snapshot format produced by [`export_snapshot()`].
+pub const SNAPSHOT_FORMAT_VERSION: u8 = 1;
+
+/// The number of substates accumulated in memory before each commit during [`import_snapshot()`]
+/// (and before each state tree update during [`verify_snapshot()`]).
+pub const SNAPSHOT_IMPORT_BATCH_SIZE: usize = 10_000;
+
+/// The maximum length of a single encoded record (or header) accepted by [`import_snapshot()`].
+/// It comfortably exceeds the largest possible substate, while preventing a malformed snapshot
+/// from making the importer allocate an arbitrary amount of memory.
+pub const SNAPSHOT_MAX_RECORD_LENGTH: usize = 16 * 1024 * 1024;
+
+/// An optional information about the state tree of the exported database (only applicable to the
+/// databases maintaining a state tree).
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct SnapshotStateTree {
+    pub state_version: u64,
+    pub root_hash: Hash,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct SnapshotHeaderV1 {
+    pub state_tree: Option<SnapshotStateTree>,
+}
+
+/// A single record of the snapshot's body.
+/// The body consists of a sequence of partitions, each followed by all its entries, and is
+/// terminated by a summary allowing to check its integrity.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum SnapshotRecordV1 {
+    Partition(DbPartitionKey),
+    Entry(DbSortKey, DbSubstateValue),
+    End(SnapshotSummary),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct SnapshotSummary {
+    pub partition_count: u64,
+    pub entry_count: u64,
+    /// A hash chained over the header and all the preceding records (see [`SnapshotHasher`]).
+    pub content_hash: Hash,
+}
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    IoError(std::io::Error),
+    InvalidMagicBytes,
+    UnsupportedFormatVersion(u8),
+    DecodeError(sbor::DecodeError),
+    EncodeError(sbor::EncodeError),
+    EntryOutsideOfPartition,
+    /// A partition appears more than once in the snapshot.
+    DuplicatePartition(DbPartitionKey),
+    /// An entry does not follow the preceding entry of its partition in the [`DbSortKey`] order
+    /// (which also rules out duplicate entries).
+    EntryOutOfOrder {
+        partition_key: DbPartitionKey,
+        sort_key: DbSortKey,
+    },
+    UnexpectedEndOfSnapshot,
+    RecordTooLarge(usize),
+    SummaryMismatch {
+        expected: SnapshotSummary,
+        actual: SnapshotSummary,
+    },
+    /// The root hash of the state tree computed from the snapshot's entries differs from the one
+    /// declared by the snapshot's header.
+    StateRootMismatch {
+        expected: Hash,
+        actual: Hash,
+    },
+    /// The database to import into already contains some substates.
+    TargetDatabaseNotEmpty,
+}
+
+impl From<std::io::Error> for SnapshotError {
+    fn from(value: std::io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
+
+impl From<sbor::DecodeError> for SnapshotError {
+    fn from(value: sbor::DecodeError) -> Self {
+        Self::DecodeError(value)
+    }
+}
+
+impl From<sbor::EncodeError> for SnapshotError {
+    fn from(value: sbor::EncodeError) -> Self {
+        Self::EncodeError(value)
+    }
+}
+
+/// A result of a successful export or import.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotOutcome {
+    pub header: SnapshotHeaderV1,
+    pub summary: SnapshotSummary,
+}
+
+/// Writes a snapshot of the entire given database to the given writer.
+/// The partitions are written in the order returned by the database, and the entries of each
+/// partition in their [`DbSortKey`] order.
+pub fn export_snapshot<S: SubstateDatabase + ListableSubstateDatabase, W: Write>(
+    substate_db: &S,
+    state_tree: Option<SnapshotStateTree>,
+    writer: &mut W,
+) -> Result<SnapshotOutcome, SnapshotError> {
+    writer.write_all(&SNAPSHOT_MAGIC_BYTES)?;
+    writer.write_all(&[SNAPSHOT_FORMAT_VERSION])?;
+    let header = SnapshotHeaderV1 { state_tree };
+    let header_bytes = scrypto_encode(&header)?;
+    write_length_prefixed(writer, &header_bytes)?;
+
+    let mut hasher = SnapshotHasher::new(&header_bytes);
+    for partition_key in substate_db.list_partition_keys() {
+        let record_bytes = scrypto_encode(&SnapshotRecordV1::Partition(partition_key.clone()))?;
+        hasher.partition(&record_bytes);
+        write_length_prefixed(writer, &record_bytes)?;
+        for (sort_key, value) in substate_db.list_entries(&partition_key) {
+            let record_bytes = scrypto_encode(&SnapshotRecordV1::Entry(sort_key, value))?;
+            hasher.entry(&record_bytes);
+            write_length_prefixed(writer, &record_bytes)?;
+        }
+    }
+
+    let summary = hasher.into_summary();
+    write_length_prefixed(
+        writer,
+        &scrypto_encode(&SnapshotRecordV1::End(summary.clone()))?,
+    )?;
+    writer.flush()?;
+    Ok(SnapshotOutcome { header, summary })
+}
+
+/// Reads a snapshot from the given reader and commits all its entries to the given database,
+/// which must be empty.
+/// The entire snapshot is first verified (see [`verify_snapshot()`]), and only then read again
+/// and committed in batches (see [`SNAPSHOT_IMPORT_BATCH_SIZE`]), so that a corrupted or truncated
+/// snapshot leaves the database untouched.
+pub fn import_snapshot<
+    S: CommittableSubstateDatabase + ListableSubstateDatabase,
+    R: Read + Seek,
+>(
+    substate_db: &mut S,
+    reader: &mut R,
+) -> Result<SnapshotOutcome, SnapshotError> {
+    if substate_db.list_partition_keys().next().is_some() {
+        return Err(SnapshotError::TargetDatabaseNotEmpty);
+    }
+
+    let start_position = reader.stream_position()?;
+    let outcome = verify_snapshot(reader)?;
+    reader.seek(SeekFrom::Start(start_position))?;
+    read_snapshot(reader, |database_updates| {
+        substate_db.commit(database_updates)
+    })?;
+    Ok(outcome)
+}
+
+/// Reads the entire snapshot from the given reader and checks its integrity, without committing
+/// anything: apart from the summary, the partitions must be unique, the entries of each partition
+/// must be in their [`DbSortKey`] order, and the root hash of the state tree computed from all the
+/// entries must match the header's (if present).
+pub fn verify_snapshot<R: Read>(reader: &mut R) -> Result<SnapshotOutcome, SnapshotError> {
+    // only the latest version of the tree is needed, hence the stale nodes are pruned right away
+    let mut tree_store = TypedInMemoryTreeStore::new();
+    let mut tree_version = 0;
+    let mut root_hash = None;
+    let outcome = read_snapshot(reader, |database_updates| {
+        let hash_changes = database_updates
+            .iter()
+            .flat_map(|(partition_key, partition_updates)| {
+                partition_updates.iter().map(|(sort_key, update)| {
+                    let value_hash = match update {
+                        DatabaseUpdate::Set(value) => Some(hash(value)),
+                        DatabaseUpdate::Delete => None,
+                    };
+                    SubstateHashChange::new((partition_key.clone(), sort_key.clone()), value_hash)
+                })
+            })
+            .collect();
+        root_hash = Some(put_at_next_version(
+            &mut tree_store,
+            Some(tree_version).filter(|version| *version > 0),
+            hash_changes,
+        ));
+        tree_version += 1;
+        tree_store.prune_stale_nodes(tree_version, usize::MAX);
+    })?;
+
+    if let Some(state_tree) = &outcome.header.state_tree {
+        let actual =
+            root_hash.unwrap_or_else(|| put_at_next_version(&mut tree_store, None, Vec::new()));
+        if actual != state_tree.root_hash {
+            return Err(SnapshotError::StateRootMismatch {
+                expected: state_tree.root_hash,
+                actual,
+            });
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// Reads the snapshot, checking the integrity of its records, and passes its entries to the given
+/// callback in batches of at most [`SNAPSHOT_IMPORT_BATCH_SIZE`].
+fn read_snapshot<R: Read, F: FnMut(&DatabaseUpdates)>(
+    reader: &mut R,
+    mut on_batch: F,
+) -> Result<SnapshotOutcome, SnapshotError> {
+    let mut magic_bytes = [0u8; 4];
+    reader.read_exact(&mut magic_bytes)?;
+    if magic_bytes != SNAPSHOT_MAGIC_BYTES {
+        return Err(SnapshotError::InvalidMagicBytes);
+    }
+    let mut format_version = [0u8; 1];
+    reader.read_exact(&mut format_version)?;
+    if format_version[0] != SNAPSHOT_FORMAT_VERSION {
+        return Err(SnapshotError::UnsupportedFormatVersion(format_version[0]));
+    }
+    let header_bytes = read_length_prefixed(reader)?;
+    let header = scrypto_decode::<SnapshotHeaderV1>(&header_bytes)?;
+
+    let mut hasher = SnapshotHasher::new(&header_bytes);
+    let mut database_updates: DatabaseUpdates = index_map_new();
+    let mut batch_size = 0;
+    let mut seen_partition_keys = hash_set_new();
+    let mut current_partition_key = None;
+    let mut last_sort_key: Option<DbSortKey> = None;
+    loop {
+        let record_bytes = read_length_prefixed(reader)?;
+        match scrypto_decode::<SnapshotRecordV1>(&record_bytes)? {
+            SnapshotRecordV1::Partition(partition_key) => {
+                hasher.partition(&record_bytes);
+                if !seen_partition_keys.insert(partition_key.clone()) {
+                    return Err(SnapshotError::DuplicatePartition(partition_key));
+                }
+                current_partition_key = Some(partition_key);
+                last_sort_key = None;
+            }
+            SnapshotRecordV1::Entry(sort_key, value) => {
+                hasher.entry(&record_bytes);
+                let partition_key = current_partition_key
+                    .as_ref()
+                    .ok_or(SnapshotError::EntryOutsideOfPartition)?;
+                if last_sort_key
+                    .as_ref()
+                    .map_or(false, |last_sort_key| &sort_key <= last_sort_key)
+                {
+                    return Err(SnapshotError::EntryOutOfOrder {
+                        partition_key: partition_key.clone(),
+                        sort_key,
+                    });
+                }
+                last_sort_key = Some(sort_key.clone());
+                database_updates
+                    .entry(partition_key.clone())
+                    .or_insert_with(|| index_map_new())
+                    .insert(sort_key, DatabaseUpdate::Set(value));
+                batch_size += 1;
+                if batch_size == SNAPSHOT_IMPORT_BATCH_SIZE {
+                    on_batch(&database_updates);
+                    database_updates.clear();
+                    batch_size = 0;
+                }
+            }
+            SnapshotRecordV1::End(expected_summary) => {
+                if !database_updates.is_empty() {
+                    on_batch(&database_updates);
+                }
+                let actual_summary = hasher.into_summary();
+                if actual_summary != expected_summary {
+                    return Err(SnapshotError::SummaryMismatch {
+                        expected: expected_summary,
+                        actual: actual_summary,
+                    });
+                }
+                return Ok(SnapshotOutcome {
+                    header,
+                    summary: actual_summary,
+                });
+            }
+        }
+    }
+}
+
+/// A helper computing the [`SnapshotSummary`] from the encoded header and body records.
+pub struct SnapshotHasher {
+    partition_count: u64,
+    entry_count: u64,
+    content_hash: Hash,
+}
+
+impl SnapshotHasher {
+    pub fn new(header_bytes: &[u8]) -> Self {
+        let mut hasher = Self {
+            partition_count: 0,
+            entry_count: 0,
+            content_hash: Hash([0; Hash::LENGTH]),
+        };
+        hasher.chain(header_bytes);
+        hasher
+    }
+
+    pub fn partition(&mut self, record_bytes: &[u8]) {
+        self.partition_count += 1;
+        self.chain(record_bytes);
+    }
+
+    pub fn entry(&mut self, record_bytes: &[u8]) {
+        self.entry_count += 1;
+        self.chain(record_bytes);
+    }
+
+    pub fn into_summary(self) -> SnapshotSummary {
+        SnapshotSummary {
+            partition_count: self.partition_count,
+            entry_count: self.entry_count,
+            content_hash: self.content_hash,
+        }
+    }
+
+    fn chain(&mut self, record_bytes: &[u8]) {
+        self.content_hash = hash([self.content_hash.as_ref(), record_bytes].concat());
+    }
+}
+
+fn write_length_prefixed<W: Write>(writer: &mut W, bytes: &[u8]) -> Result<(), SnapshotError> {
+    if bytes.len() > SNAPSHOT_MAX_RECORD_LENGTH {
+        return Err(SnapshotError::RecordTooLarge(bytes.len()));
+    }
+    writer.write_all(&u32::try_from(bytes.len()).unwrap().to_be_bytes())?;
+    writer.write_all(bytes)?;
+    Ok(())
+}
+
+fn read_length_prefixed<R: Read>(reader: &mut R) -> Result<Vec<u8>, SnapshotError> {
+    let mut length_bytes = [0u8; 4];
+    reader.read_exact(&mut length_bytes).map_err(|error| {
+        if error.kind() == std::io::ErrorKind::UnexpectedEof {
+            SnapshotError::UnexpectedEndOfSnapshot
+        } else {
+            SnapshotError::IoError(error)
+        }
+    })?;
+    let length = u32::from_be_bytes(length_bytes) as usize;
+    if length > SNAPSHOT_MAX_RECORD_LENGTH {
+        return Err(SnapshotError::RecordTooLarge(length));
+    }
+    // the buffer grows with the bytes actually read, rather than trusting the declared length
+    let mut bytes = Vec::new();
+    reader
+        .by_ref()
+        .take(length as u64)
+        .read_to_end(&mut bytes)?;
+    if bytes.len() != length {
+        return Err(SnapshotError::UnexpectedEndOfSnapshot);
+    }
+    Ok(bytes)
+}
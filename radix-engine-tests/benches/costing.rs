@@ -11,8 +11,8 @@ use radix_engine::{
 use radix_engine_queries::typed_substate_layout::PackageDefinition;
 use sbor::rust::iter;
 use transaction::{
-    prelude::Secp256k1PrivateKey,
-    validation::{recover_secp256k1, verify_secp256k1},
+    prelude::{Ed25519PrivateKey, Secp256k1PrivateKey},
+    validation::{recover_secp256k1, verify_ed25519, verify_secp256k1},
 };
 use wabt::wat2wasm;
 
@@ -59,6 +59,62 @@ fn bench_validate_secp256k1(c: &mut Criterion) {
     });
 }
 
+fn bench_verify_secp256k1(c: &mut Criterion) {
+    let message_hash = hash("m".repeat(1_000_000).as_bytes());
+    let signer = Secp256k1PrivateKey::from_u64(123123123123).unwrap();
+    let public_key = signer.public_key();
+    let signature = signer.sign(&message_hash);
+
+    c.bench_function("costing::verify_secp256k1", |b| {
+        b.iter(|| verify_secp256k1(&message_hash, &public_key, &signature))
+    });
+}
+
+fn bench_recover_secp256k1(c: &mut Criterion) {
+    let message_hash = hash("m".repeat(1_000_000).as_bytes());
+    let signer = Secp256k1PrivateKey::from_u64(123123123123).unwrap();
+    let signature = signer.sign(&message_hash);
+
+    c.bench_function("costing::recover_secp256k1", |b| {
+        b.iter(|| recover_secp256k1(&message_hash, &signature).unwrap())
+    });
+}
+
+fn bench_verify_ed25519(c: &mut Criterion) {
+    let message_hash = hash("m".repeat(1_000_000).as_bytes());
+    let signer = Ed25519PrivateKey::from_u64(123123123123).unwrap();
+    let public_key = signer.public_key();
+    let signature = signer.sign(&message_hash);
+
+    c.bench_function("costing::verify_ed25519", |b| {
+        b.iter(|| verify_ed25519(&message_hash, &public_key, &signature))
+    });
+}
+
+fn bench_blake2b_256_hash(c: &mut Criterion) {
+    let data = vec![0xab; 1024 * 1024];
+
+    c.bench_function("costing::blake2b_256_hash_1mib", |b| {
+        b.iter(|| blake2b_256_hash(&data))
+    });
+}
+
+fn bench_keccak256_hash(c: &mut Criterion) {
+    let data = vec![0xab; 1024 * 1024];
+
+    c.bench_function("costing::keccak256_hash_1mib", |b| {
+        b.iter(|| keccak256_hash(&data))
+    });
+}
+
+fn bench_sha256_hash(c: &mut Criterion) {
+    let data = vec![0xab; 1024 * 1024];
+
+    c.bench_function("costing::sha256_hash_1mib", |b| {
+        b.iter(|| sha256_hash(&data))
+    });
+}
+
 fn bench_spin_loop(c: &mut Criterion) {
     // Prepare code
     let code = wat2wasm(&include_str!("../tests/wasm/loop.wat").replace("${n}", "100000")).unwrap();
@@ -140,6 +196,12 @@ criterion_group!(
     bench_decode_sbor,
     bench_validate_sbor_payload,
     bench_validate_secp256k1,
+    bench_verify_secp256k1,
+    bench_recover_secp256k1,
+    bench_verify_ed25519,
+    bench_blake2b_256_hash,
+    bench_keccak256_hash,
+    bench_sha256_hash,
     bench_spin_loop,
     bench_instantiate_radiswap,
     bench_validate_wasm,
@@ -227,7 +227,7 @@ fn account_to_bucket_to_account_internal(use_virtual: bool) {
                 .add_instruction(InstructionV1::CallMethod {
                     address: account.into(),
                     method_name: "try_deposit_or_abort".to_string(),
-                    args: manifest_args!(bucket_id),
+                    args: manifest_args!(bucket_id, Option::<ManifestProof>::None),
                 })
                 .0
         })
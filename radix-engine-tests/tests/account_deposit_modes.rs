@@ -1,8 +1,10 @@
+use radix_engine::blueprints::account::AddAuthorizedDepositorEvent;
 use radix_engine::errors::{ApplicationError, RuntimeError, SystemModuleError};
 use radix_engine::system::system_modules::auth::AuthError;
 use radix_engine::transaction::TransactionReceipt;
 use radix_engine::types::*;
 use radix_engine_interface::blueprints::account::*;
+use radix_engine_interface::blueprints::resource::FromPublicKey;
 use radix_engine_queries::typed_substate_layout::AccountError;
 use scrypto_unit::TestRunner;
 use transaction::builder::{ManifestBuilder, TransactionManifestV1};
@@ -439,6 +441,227 @@ fn disallow_all_permits_deposit_of_resource_in_allow_list() {
     }
 }
 
+#[test]
+fn authorized_depositor_non_fungible_badge_permits_deposits_rejected_by_default() {
+    // Arrange
+    for is_virtual in [true, false] {
+        let mut test_runner = AccountDepositModesTestRunner::new(is_virtual);
+        let resource_address = test_runner.freely_mintable_resource();
+        let badge_holder = test_runner.new_badge_holder();
+        test_runner
+            .transition_account_default_deposit_rule(AccountDefaultDepositRule::Reject, true)
+            .expect_commit_success();
+        test_runner
+            .add_authorized_depositor(badge_holder.badge.clone().into(), true)
+            .expect_commit_success();
+
+        // Act
+        let receipt = test_runner.mint_and_deposit_with_badge(
+            resource_address,
+            DepositMethod::TryDepositOrAbort,
+            &badge_holder,
+        );
+
+        // Assert
+        receipt.expect_commit_success();
+    }
+}
+
+#[test]
+fn authorized_depositor_resource_badge_permits_deposits_rejected_by_default() {
+    // Arrange
+    for is_virtual in [true, false] {
+        let mut test_runner = AccountDepositModesTestRunner::new(is_virtual);
+        let resource_address = test_runner.freely_mintable_resource();
+        let badge_holder = test_runner.new_badge_holder();
+        test_runner
+            .transition_account_default_deposit_rule(AccountDefaultDepositRule::Reject, true)
+            .expect_commit_success();
+        test_runner
+            .add_authorized_depositor(badge_holder.badge.resource_address().into(), true)
+            .expect_commit_success();
+
+        // Act
+        let receipt = test_runner.mint_and_deposit_with_badge(
+            resource_address,
+            DepositMethod::TryDeposit,
+            &badge_holder,
+        );
+
+        // Assert
+        receipt.expect_commit_success();
+    }
+}
+
+#[test]
+fn badge_of_unauthorized_depositor_does_not_permit_deposits_rejected_by_default() {
+    // Arrange
+    for is_virtual in [true, false] {
+        let mut test_runner = AccountDepositModesTestRunner::new(is_virtual);
+        let resource_address = test_runner.freely_mintable_resource();
+        let badge_holder = test_runner.new_badge_holder();
+        test_runner
+            .transition_account_default_deposit_rule(AccountDefaultDepositRule::Reject, true)
+            .expect_commit_success();
+
+        // Act
+        let receipt = test_runner.mint_and_deposit_with_badge(
+            resource_address,
+            DepositMethod::TryDepositOrAbort,
+            &badge_holder,
+        );
+
+        // Assert
+        receipt.expect_specific_failure(is_account_deposit_not_allowed_error);
+    }
+}
+
+#[test]
+fn authorized_depositor_badge_permits_batch_deposits_rejected_by_default() {
+    for deposit_method in [
+        DepositMethod::TryDepositBatchOrRefundWithBadge,
+        DepositMethod::TryDepositBatchOrAbortWithBadge,
+    ] {
+        // Arrange
+        let mut test_runner = AccountDepositModesTestRunner::new(true);
+        let resource_address = test_runner.freely_mintable_resource();
+        let badge_holder = test_runner.new_badge_holder();
+        test_runner
+            .transition_account_default_deposit_rule(AccountDefaultDepositRule::Reject, true)
+            .expect_commit_success();
+        test_runner
+            .add_authorized_depositor(badge_holder.badge.clone().into(), true)
+            .expect_commit_success();
+
+        // Act
+        let receipt = test_runner.mint_and_deposit_with_badge(
+            resource_address,
+            deposit_method,
+            &badge_holder,
+        );
+
+        // Assert
+        receipt.expect_commit_success();
+        assert_eq!(
+            test_runner
+                .test_runner
+                .account_balance(test_runner.component_address, resource_address),
+            Some(dec!("1"))
+        );
+    }
+}
+
+#[test]
+fn badge_of_unauthorized_depositor_does_not_permit_batch_deposits_rejected_by_default() {
+    // Arrange
+    let mut test_runner = AccountDepositModesTestRunner::new(true);
+    let resource_address = test_runner.freely_mintable_resource();
+    let badge_holder = test_runner.new_badge_holder();
+    test_runner
+        .transition_account_default_deposit_rule(AccountDefaultDepositRule::Reject, true)
+        .expect_commit_success();
+
+    // Act
+    let receipt = test_runner.mint_and_deposit_with_badge(
+        resource_address,
+        DepositMethod::TryDepositBatchOrAbortWithBadge,
+        &badge_holder,
+    );
+
+    // Assert
+    receipt.expect_specific_failure(|error| {
+        matches!(
+            error,
+            RuntimeError::ApplicationError(ApplicationError::AccountError(
+                AccountError::NotAllBucketsCouldBeDeposited
+            ))
+        )
+    });
+}
+
+#[test]
+fn removed_authorized_depositor_badge_no_longer_permits_deposits() {
+    // Arrange
+    for is_virtual in [true, false] {
+        let mut test_runner = AccountDepositModesTestRunner::new(is_virtual);
+        let resource_address = test_runner.freely_mintable_resource();
+        let badge_holder = test_runner.new_badge_holder();
+        test_runner
+            .transition_account_default_deposit_rule(AccountDefaultDepositRule::Reject, true)
+            .expect_commit_success();
+        test_runner
+            .add_authorized_depositor(badge_holder.badge.clone().into(), true)
+            .expect_commit_success();
+        test_runner
+            .remove_authorized_depositor(badge_holder.badge.clone().into(), true)
+            .expect_commit_success();
+
+        // Act
+        let receipt = test_runner.mint_and_deposit_with_badge(
+            resource_address,
+            DepositMethod::TryDepositOrAbort,
+            &badge_holder,
+        );
+
+        // Assert
+        receipt.expect_specific_failure(is_account_deposit_not_allowed_error);
+    }
+}
+
+#[test]
+fn authorized_depositor_badge_does_not_permit_deposit_of_resource_in_deny_list() {
+    // Arrange
+    for is_virtual in [true, false] {
+        let mut test_runner = AccountDepositModesTestRunner::new(is_virtual);
+        let resource_address = test_runner.freely_mintable_resource();
+        let badge_holder = test_runner.new_badge_holder();
+        test_runner
+            .add_to_deny_list(resource_address, true)
+            .expect_commit_success();
+        test_runner
+            .add_authorized_depositor(badge_holder.badge.clone().into(), true)
+            .expect_commit_success();
+
+        // Act
+        let receipt = test_runner.mint_and_deposit_with_badge(
+            resource_address,
+            DepositMethod::TryDepositOrAbort,
+            &badge_holder,
+        );
+
+        // Assert
+        receipt.expect_specific_failure(is_account_deposit_not_allowed_error);
+    }
+}
+
+#[test]
+fn adding_authorized_depositor_requires_owner_signature_and_emits_event() {
+    // Arrange
+    for is_virtual in [true, false] {
+        let mut test_runner = AccountDepositModesTestRunner::new(is_virtual);
+        let badge_holder = test_runner.new_badge_holder();
+
+        // Act
+        let unsigned_receipt =
+            test_runner.add_authorized_depositor(badge_holder.badge.clone().into(), false);
+        let signed_receipt =
+            test_runner.add_authorized_depositor(badge_holder.badge.clone().into(), true);
+
+        // Assert
+        unsigned_receipt.expect_specific_failure(is_auth_unauthorized_error);
+        let events = &signed_receipt.expect_commit_success().application_events;
+        assert!(events.iter().any(|(event_type_identifier, event_data)| {
+            test_runner
+                .test_runner
+                .is_event_name_equal::<AddAuthorizedDepositorEvent>(event_type_identifier)
+                && scrypto_decode::<AddAuthorizedDepositorEvent>(event_data).unwrap()
+                    == AddAuthorizedDepositorEvent {
+                        badge: badge_holder.badge.clone().into(),
+                    }
+        }));
+    }
+}
+
 struct AccountDepositModesTestRunner {
     test_runner: TestRunner,
     public_key: PublicKey,
@@ -463,24 +686,15 @@ impl AccountDepositModesTestRunner {
         deposit_method: DepositMethod,
         sign: bool,
     ) -> TransactionReceipt {
-        let (method, is_vec) = match deposit_method {
-            DepositMethod::Deposit => (ACCOUNT_DEPOSIT_IDENT, false),
-            DepositMethod::TryDeposit => (ACCOUNT_TRY_DEPOSIT_OR_REFUND_IDENT, false),
-            DepositMethod::TryDepositOrAbort => (ACCOUNT_TRY_DEPOSIT_OR_ABORT_IDENT, false),
-            DepositMethod::DepositBatch => (ACCOUNT_DEPOSIT_BATCH_IDENT, true),
-            DepositMethod::TryDepositBatchOrRefund => {
-                (ACCOUNT_TRY_DEPOSIT_BATCH_OR_REFUND_IDENT, true)
-            }
-            DepositMethod::TryDepositBatchOrAbort => {
-                (ACCOUNT_TRY_DEPOSIT_BATCH_OR_ABORT_IDENT, true)
-            }
-        };
+        let (method, is_vec) = deposit_method.method();
 
         let manifest = ManifestBuilder::new()
             .mint_fungible(resource_address, 1.into())
             .take_all_from_worktop(resource_address, |builder, bucket| {
                 let args = if is_vec {
                     manifest_args!(vec![bucket])
+                } else if deposit_method.takes_authorized_depositor_badge() {
+                    manifest_args!(bucket, Option::<ManifestProof>::None)
                 } else {
                     manifest_args!(bucket)
                 };
@@ -495,24 +709,15 @@ impl AccountDepositModesTestRunner {
         deposit_method: DepositMethod,
         sign: bool,
     ) -> TransactionReceipt {
-        let (method, is_vec) = match deposit_method {
-            DepositMethod::Deposit => (ACCOUNT_DEPOSIT_IDENT, false),
-            DepositMethod::TryDeposit => (ACCOUNT_TRY_DEPOSIT_OR_REFUND_IDENT, false),
-            DepositMethod::TryDepositOrAbort => (ACCOUNT_TRY_DEPOSIT_OR_ABORT_IDENT, false),
-            DepositMethod::DepositBatch => (ACCOUNT_DEPOSIT_BATCH_IDENT, true),
-            DepositMethod::TryDepositBatchOrRefund => {
-                (ACCOUNT_TRY_DEPOSIT_BATCH_OR_REFUND_IDENT, true)
-            }
-            DepositMethod::TryDepositBatchOrAbort => {
-                (ACCOUNT_TRY_DEPOSIT_BATCH_OR_ABORT_IDENT, true)
-            }
-        };
+        let (method, is_vec) = deposit_method.method();
 
         let manifest = ManifestBuilder::new()
             .call_method(FAUCET, "free", manifest_args!())
             .take_all_from_worktop(RADIX_TOKEN, |builder, bucket| {
                 let args = if is_vec {
                     manifest_args!(vec![bucket])
+                } else if deposit_method.takes_authorized_depositor_badge() {
+                    manifest_args!(bucket, Option::<ManifestProof>::None)
                 } else {
                     manifest_args!(bucket)
                 };
@@ -594,6 +799,86 @@ impl AccountDepositModesTestRunner {
         self.configure_resource_deposit_rule(resource_address, ResourceDepositRule::Neither, sign)
     }
 
+    pub fn add_authorized_depositor(
+        &mut self,
+        badge: ResourceOrNonFungible,
+        sign: bool,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                self.component_address,
+                ACCOUNT_ADD_AUTHORIZED_DEPOSITOR_IDENT,
+                to_manifest_value_and_unwrap!(&AccountAddAuthorizedDepositorInput { badge }),
+            )
+            .build();
+        self.execute_manifest(manifest, sign)
+    }
+
+    pub fn remove_authorized_depositor(
+        &mut self,
+        badge: ResourceOrNonFungible,
+        sign: bool,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                self.component_address,
+                ACCOUNT_REMOVE_AUTHORIZED_DEPOSITOR_IDENT,
+                to_manifest_value_and_unwrap!(&AccountRemoveAuthorizedDepositorInput { badge }),
+            )
+            .build();
+        self.execute_manifest(manifest, sign)
+    }
+
+    /// Creates an account holding a non-fungible badge.
+    pub fn new_badge_holder(&mut self) -> BadgeHolder {
+        let (public_key, _, account) = self.test_runner.new_allocated_account();
+        let badge_resource = self.test_runner.create_non_fungible_resource(account);
+
+        BadgeHolder {
+            public_key,
+            account,
+            badge: NonFungibleGlobalId::new(badge_resource, NonFungibleLocalId::integer(1)),
+        }
+    }
+
+    /// Mints and deposits a resource with a proof of the holder's badge.
+    pub fn mint_and_deposit_with_badge(
+        &mut self,
+        resource_address: ResourceAddress,
+        deposit_method: DepositMethod,
+        badge_holder: &BadgeHolder,
+    ) -> TransactionReceipt {
+        let (method, is_vec) = deposit_method.method();
+        let component_address = self.component_address;
+
+        let manifest = ManifestBuilder::new()
+            .create_proof_from_account_of_non_fungibles(
+                badge_holder.account,
+                badge_holder.badge.resource_address(),
+                &btreeset!(badge_holder.badge.local_id().clone()),
+            )
+            .pop_from_auth_zone(|builder, proof| {
+                builder
+                    .mint_fungible(resource_address, 1.into())
+                    .take_all_from_worktop(resource_address, |builder, bucket| {
+                        // The batch methods taking a badge require it, rather than an optional one
+                        let args = if is_vec {
+                            manifest_args!(vec![bucket], proof)
+                        } else {
+                            manifest_args!(bucket, Some(proof))
+                        };
+                        builder.call_method(component_address, method, args)
+                    })
+            })
+            .build();
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(
+                &badge_holder.public_key,
+            )],
+        )
+    }
+
     pub fn virtual_signature_badge(&self) -> NonFungibleGlobalId {
         NonFungibleGlobalId::from_public_key(&self.public_key)
     }
@@ -641,6 +926,12 @@ impl AccountDepositModesTestRunner {
     }
 }
 
+struct BadgeHolder {
+    public_key: Secp256k1PublicKey,
+    account: ComponentAddress,
+    badge: NonFungibleGlobalId,
+}
+
 enum DepositMethod {
     Deposit,
     TryDeposit,
@@ -649,6 +940,38 @@ enum DepositMethod {
     DepositBatch,
     TryDepositBatchOrRefund,
     TryDepositBatchOrAbort,
+    TryDepositBatchOrRefundWithBadge,
+    TryDepositBatchOrAbortWithBadge,
+}
+
+impl DepositMethod {
+    fn method(&self) -> (&'static str, bool) {
+        match self {
+            DepositMethod::Deposit => (ACCOUNT_DEPOSIT_IDENT, false),
+            DepositMethod::TryDeposit => (ACCOUNT_TRY_DEPOSIT_OR_REFUND_IDENT, false),
+            DepositMethod::TryDepositOrAbort => (ACCOUNT_TRY_DEPOSIT_OR_ABORT_IDENT, false),
+            DepositMethod::DepositBatch => (ACCOUNT_DEPOSIT_BATCH_IDENT, true),
+            DepositMethod::TryDepositBatchOrRefund => {
+                (ACCOUNT_TRY_DEPOSIT_BATCH_OR_REFUND_IDENT, true)
+            }
+            DepositMethod::TryDepositBatchOrAbort => {
+                (ACCOUNT_TRY_DEPOSIT_BATCH_OR_ABORT_IDENT, true)
+            }
+            DepositMethod::TryDepositBatchOrRefundWithBadge => {
+                (ACCOUNT_TRY_DEPOSIT_BATCH_OR_REFUND_WITH_BADGE_IDENT, true)
+            }
+            DepositMethod::TryDepositBatchOrAbortWithBadge => {
+                (ACCOUNT_TRY_DEPOSIT_BATCH_OR_ABORT_WITH_BADGE_IDENT, true)
+            }
+        }
+    }
+
+    fn takes_authorized_depositor_badge(&self) -> bool {
+        matches!(
+            self,
+            DepositMethod::TryDeposit | DepositMethod::TryDepositOrAbort
+        )
+    }
 }
 
 fn is_auth_unauthorized_error(runtime_error: &RuntimeError) -> bool {
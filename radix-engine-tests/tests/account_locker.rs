@@ -0,0 +1,513 @@
+use radix_engine::blueprints::account::{AccountLockerError, ClaimEvent, RecoverEvent, StoreEvent};
+use radix_engine::errors::{ApplicationError, RuntimeError, SystemError, SystemModuleError};
+use radix_engine::system::system_modules::auth::AuthError;
+use radix_engine::transaction::TransactionReceipt;
+use radix_engine::types::*;
+use radix_engine_interface::blueprints::account::*;
+use radix_engine_interface::blueprints::resource::FromPublicKey;
+use scrypto_unit::TestRunner;
+use transaction::builder::{ManifestBuilder, TransactionManifestV1};
+
+#[test]
+fn stored_resources_can_be_claimed_by_the_claimant() {
+    // Arrange
+    for is_virtual in [true, false] {
+        let mut test_runner = AccountLockerTestRunner::new();
+        let claimant = test_runner.new_claimant(is_virtual);
+        test_runner
+            .store(claimant.account, 10.into(), false, true)
+            .expect_commit_success();
+
+        // Act
+        let receipt = test_runner.claim(&claimant, 10.into());
+
+        // Assert
+        let events = &receipt.expect_commit_success().application_events;
+        assert!(events.iter().any(|(event_type_identifier, event_data)| {
+            test_runner
+                .test_runner
+                .is_event_name_equal::<ClaimEvent>(event_type_identifier)
+                && scrypto_decode::<ClaimEvent>(event_data).unwrap()
+                    == ClaimEvent {
+                        claimant: claimant.account,
+                        resource_address: test_runner.resource_address,
+                        resources: AccountLockerResources::Fungible(10.into()),
+                    }
+        }));
+        assert_eq!(test_runner.claimant_balance(&claimant), 10.into());
+        assert_eq!(test_runner.locker_amount(claimant.account), Decimal::ZERO);
+    }
+}
+
+#[test]
+fn stored_resources_can_not_be_claimed_without_the_claimant_signature() {
+    // Arrange
+    let mut test_runner = AccountLockerTestRunner::new();
+    let claimant = test_runner.new_claimant(false);
+    let impostor = test_runner.new_claimant(false);
+    test_runner
+        .store(claimant.account, 10.into(), false, true)
+        .expect_commit_success();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .claim_from_account_locker(
+            test_runner.locker_address,
+            claimant.account,
+            test_runner.resource_address,
+            10.into(),
+        )
+        .try_deposit_batch_or_abort(impostor.account)
+        .build();
+    let receipt = test_runner.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&impostor.public_key)],
+    );
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::SystemError(SystemError::AssertAccessRuleFailed)
+        )
+    });
+}
+
+#[test]
+fn store_requires_the_locker_owner_signature() {
+    // Arrange
+    let mut test_runner = AccountLockerTestRunner::new();
+    let claimant = test_runner.new_claimant(false);
+
+    // Act
+    let receipt = test_runner.store(claimant.account, 10.into(), false, false);
+
+    // Assert
+    receipt.expect_specific_failure(is_auth_unauthorized_error);
+}
+
+#[test]
+fn store_for_a_claimant_which_is_not_an_account_fails() {
+    // Arrange
+    let mut test_runner = AccountLockerTestRunner::new();
+
+    // Act
+    let receipt = test_runner.store(FAUCET, 10.into(), false, true);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::AccountLockerError(
+                AccountLockerError::ClaimantIsNotAnAccount { .. }
+            ))
+        )
+    });
+}
+
+#[test]
+fn store_with_direct_send_deposits_into_the_claimant_account() {
+    // Arrange
+    let mut test_runner = AccountLockerTestRunner::new();
+    let claimant = test_runner.new_claimant(false);
+
+    // Act
+    let receipt = test_runner.store(claimant.account, 10.into(), true, true);
+
+    // Assert
+    let events = &receipt.expect_commit_success().application_events;
+    assert!(!events.iter().any(|(event_type_identifier, _)| {
+        test_runner
+            .test_runner
+            .is_event_name_equal::<StoreEvent>(event_type_identifier)
+    }));
+    assert_eq!(test_runner.claimant_balance(&claimant), 10.into());
+    assert_eq!(test_runner.locker_amount(claimant.account), Decimal::ZERO);
+}
+
+#[test]
+fn store_with_direct_send_falls_back_to_the_locker_when_the_deposit_is_rejected() {
+    // Arrange
+    let mut test_runner = AccountLockerTestRunner::new();
+    let claimant = test_runner.new_claimant(false);
+    test_runner.reject_deposits(&claimant);
+
+    // Act
+    let receipt = test_runner.store(claimant.account, 10.into(), true, true);
+
+    // Assert
+    let events = &receipt.expect_commit_success().application_events;
+    assert!(events.iter().any(|(event_type_identifier, event_data)| {
+        test_runner
+            .test_runner
+            .is_event_name_equal::<StoreEvent>(event_type_identifier)
+            && scrypto_decode::<StoreEvent>(event_data).unwrap()
+                == StoreEvent {
+                    claimant: claimant.account,
+                    resource_address: test_runner.resource_address,
+                    resources: AccountLockerResources::Fungible(10.into()),
+                }
+    }));
+    assert_eq!(test_runner.claimant_balance(&claimant), Decimal::ZERO);
+    assert_eq!(test_runner.locker_amount(claimant.account), 10.into());
+}
+
+#[test]
+fn airdrop_stores_for_each_claimant_and_returns_the_remainder() {
+    // Arrange
+    let mut test_runner = AccountLockerTestRunner::new();
+    let accepting_claimant = test_runner.new_claimant(false);
+    let rejecting_claimant = test_runner.new_claimant(true);
+    test_runner.reject_deposits(&rejecting_claimant);
+
+    // Act
+    let claimants = btreemap!(
+        accepting_claimant.account => AccountLockerResources::Fungible(10.into()),
+        rejecting_claimant.account => AccountLockerResources::Fungible(20.into()),
+    );
+    let manifest = ManifestBuilder::new()
+        .withdraw_from_account(
+            test_runner.owner.account,
+            test_runner.resource_address,
+            50.into(),
+        )
+        .take_all_from_worktop(test_runner.resource_address, |builder, bucket| {
+            builder.airdrop_from_account_locker(test_runner.locker_address, claimants, bucket, true)
+        })
+        .try_deposit_batch_or_abort(test_runner.owner.account)
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, true);
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(test_runner.claimant_balance(&accepting_claimant), 10.into());
+    assert_eq!(
+        test_runner.locker_amount(accepting_claimant.account),
+        Decimal::ZERO
+    );
+    assert_eq!(
+        test_runner.claimant_balance(&rejecting_claimant),
+        Decimal::ZERO
+    );
+    assert_eq!(
+        test_runner.locker_amount(rejecting_claimant.account),
+        20.into()
+    );
+    let owner = test_runner.owner.clone();
+    assert_eq!(test_runner.claimant_balance(&owner), dec!("970"));
+}
+
+#[test]
+fn stored_resources_can_be_recovered_by_the_locker_owner() {
+    // Arrange
+    let mut test_runner = AccountLockerTestRunner::new();
+    let claimant = test_runner.new_claimant(false);
+    test_runner
+        .store(claimant.account, 10.into(), false, true)
+        .expect_commit_success();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .recover_from_account_locker(
+            test_runner.locker_address,
+            claimant.account,
+            test_runner.resource_address,
+            4.into(),
+        )
+        .try_deposit_batch_or_abort(test_runner.owner.account)
+        .build();
+    let unsigned_receipt = test_runner.execute_manifest(manifest.clone(), false);
+    let signed_receipt = test_runner.execute_manifest(manifest, true);
+
+    // Assert
+    unsigned_receipt.expect_specific_failure(is_auth_unauthorized_error);
+    let events = &signed_receipt.expect_commit_success().application_events;
+    assert!(events.iter().any(|(event_type_identifier, event_data)| {
+        test_runner
+            .test_runner
+            .is_event_name_equal::<RecoverEvent>(event_type_identifier)
+            && scrypto_decode::<RecoverEvent>(event_data).unwrap()
+                == RecoverEvent {
+                    claimant: claimant.account,
+                    resource_address: test_runner.resource_address,
+                    resources: AccountLockerResources::Fungible(4.into()),
+                }
+    }));
+    assert_eq!(test_runner.locker_amount(claimant.account), 6.into());
+}
+
+#[test]
+fn claiming_resources_which_were_never_stored_fails() {
+    // Arrange
+    let mut test_runner = AccountLockerTestRunner::new();
+    let claimant = test_runner.new_claimant(false);
+
+    // Act
+    let receipt = test_runner.claim(&claimant, 10.into());
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::AccountLockerError(
+                AccountLockerError::NothingStoredForClaimant { .. }
+            ))
+        )
+    });
+}
+
+#[test]
+fn resources_can_not_be_claimed_again_once_everything_was_claimed() {
+    // Arrange
+    let mut test_runner = AccountLockerTestRunner::new();
+    let claimant = test_runner.new_claimant(false);
+    test_runner
+        .store(claimant.account, 10.into(), false, true)
+        .expect_commit_success();
+    test_runner
+        .claim(&claimant, 10.into())
+        .expect_commit_success();
+
+    // Act
+    let receipt = test_runner.claim(&claimant, 1.into());
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::AccountLockerError(
+                AccountLockerError::NothingStoredForClaimant { .. }
+            ))
+        )
+    });
+    assert_eq!(test_runner.locker_amount(claimant.account), Decimal::ZERO);
+}
+
+#[test]
+fn claimant_can_not_claim_resources_stored_for_another_claimant() {
+    // Arrange
+    let mut test_runner = AccountLockerTestRunner::new();
+    let claimant = test_runner.new_claimant(false);
+    let other_claimant = test_runner.new_claimant(false);
+    test_runner
+        .store(claimant.account, 5.into(), false, true)
+        .expect_commit_success();
+    test_runner
+        .store(other_claimant.account, 10.into(), false, true)
+        .expect_commit_success();
+
+    // Act
+    let receipt = test_runner.claim(&claimant, 6.into());
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::AccountLockerError(
+                AccountLockerError::NotStoredForClaimant { .. }
+            ))
+        )
+    });
+    assert_eq!(test_runner.locker_amount(claimant.account), 5.into());
+    assert_eq!(test_runner.locker_amount(other_claimant.account), 10.into());
+}
+
+#[test]
+fn stored_non_fungibles_can_be_claimed_by_the_claimant() {
+    // Arrange
+    let mut test_runner = AccountLockerTestRunner::new();
+    let claimant = test_runner.new_claimant(false);
+    let resource_address = test_runner
+        .test_runner
+        .create_non_fungible_resource(test_runner.owner.account);
+    let ids = btreeset!(
+        NonFungibleLocalId::integer(1),
+        NonFungibleLocalId::integer(2)
+    );
+
+    let manifest = ManifestBuilder::new()
+        .withdraw_non_fungibles_from_account(test_runner.owner.account, resource_address, &ids)
+        .take_all_from_worktop(resource_address, |builder, bucket| {
+            builder.store_in_account_locker(
+                test_runner.locker_address,
+                claimant.account,
+                bucket,
+                false,
+            )
+        })
+        .build();
+    test_runner
+        .execute_manifest(manifest, true)
+        .expect_commit_success();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .claim_non_fungibles_from_account_locker(
+            test_runner.locker_address,
+            claimant.account,
+            resource_address,
+            &btreeset!(NonFungibleLocalId::integer(1)),
+        )
+        .try_deposit_batch_or_abort(claimant.account)
+        .build();
+    let receipt = test_runner.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&claimant.public_key)],
+    );
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(
+        test_runner
+            .test_runner
+            .account_balance(claimant.account, resource_address),
+        Some(Decimal::ONE)
+    );
+}
+
+#[derive(Clone)]
+struct Claimant {
+    public_key: Secp256k1PublicKey,
+    account: ComponentAddress,
+}
+
+struct AccountLockerTestRunner {
+    test_runner: TestRunner,
+    owner: Claimant,
+    locker_address: ComponentAddress,
+    resource_address: ResourceAddress,
+}
+
+impl AccountLockerTestRunner {
+    pub fn new() -> Self {
+        let mut test_runner = TestRunner::builder().without_trace().build();
+        let (public_key, _, account) = test_runner.new_allocated_account();
+        let resource_address = test_runner.create_fungible_resource(1000.into(), 18, account);
+
+        let manifest = ManifestBuilder::new()
+            .new_account_locker(OwnerRole::Fixed(rule!(require(
+                NonFungibleGlobalId::from_public_key(&public_key)
+            ))))
+            .build();
+        let receipt = test_runner.execute_manifest_ignoring_fee(manifest, vec![]);
+        let locker_address = receipt.expect_commit_success().new_component_addresses()[0];
+
+        Self {
+            test_runner,
+            owner: Claimant {
+                public_key,
+                account,
+            },
+            locker_address,
+            resource_address,
+        }
+    }
+
+    pub fn new_claimant(&mut self, is_virtual: bool) -> Claimant {
+        let (public_key, _, account) = self.test_runner.new_account(is_virtual);
+        Claimant {
+            public_key,
+            account,
+        }
+    }
+
+    pub fn store(
+        &mut self,
+        claimant: ComponentAddress,
+        amount: Decimal,
+        try_direct_send: bool,
+        sign: bool,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(self.owner.account, self.resource_address, amount)
+            .take_all_from_worktop(self.resource_address, |builder, bucket| {
+                builder.store_in_account_locker(
+                    self.locker_address,
+                    claimant,
+                    bucket,
+                    try_direct_send,
+                )
+            })
+            .build();
+        self.execute_manifest(manifest, sign)
+    }
+
+    pub fn claim(&mut self, claimant: &Claimant, amount: Decimal) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .claim_from_account_locker(
+                self.locker_address,
+                claimant.account,
+                self.resource_address,
+                amount,
+            )
+            .try_deposit_batch_or_abort(claimant.account)
+            .build();
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&claimant.public_key)],
+        )
+    }
+
+    pub fn reject_deposits(&mut self, claimant: &Claimant) {
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                claimant.account,
+                ACCOUNT_CHANGE_DEFAULT_DEPOSIT_RULE_IDENT,
+                to_manifest_value_and_unwrap!(&AccountChangeDefaultDepositRuleInput {
+                    default_deposit_rule: AccountDefaultDepositRule::Reject,
+                }),
+            )
+            .build();
+        self.test_runner
+            .execute_manifest_ignoring_fee(
+                manifest,
+                vec![NonFungibleGlobalId::from_public_key(&claimant.public_key)],
+            )
+            .expect_commit_success();
+    }
+
+    pub fn claimant_balance(&mut self, claimant: &Claimant) -> Decimal {
+        self.test_runner
+            .account_balance(claimant.account, self.resource_address)
+            .unwrap_or_default()
+    }
+
+    pub fn locker_amount(&mut self, claimant: ComponentAddress) -> Decimal {
+        let manifest = ManifestBuilder::new()
+            .call_method(
+                self.locker_address,
+                ACCOUNT_LOCKER_GET_AMOUNT_IDENT,
+                to_manifest_value_and_unwrap!(&AccountLockerGetAmountManifestInput {
+                    claimant,
+                    resource_address: self.resource_address,
+                }),
+            )
+            .build();
+        let receipt = self
+            .test_runner
+            .execute_manifest_ignoring_fee(manifest, vec![]);
+        receipt.expect_commit_success().output(1)
+    }
+
+    pub fn execute_manifest(
+        &mut self,
+        manifest: TransactionManifestV1,
+        sign: bool,
+    ) -> TransactionReceipt {
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            if sign {
+                vec![NonFungibleGlobalId::from_public_key(&self.owner.public_key)]
+            } else {
+                vec![]
+            },
+        )
+    }
+}
+
+fn is_auth_unauthorized_error(runtime_error: &RuntimeError) -> bool {
+    matches!(
+        runtime_error,
+        RuntimeError::SystemModuleError(SystemModuleError::AuthError(AuthError::Unauthorized(..)))
+    )
+}
@@ -110,7 +110,11 @@ fn test_resource_auth(action: Action, update_auth: bool, use_other_auth: bool, e
             .create_proof_from_account(account, withdraw_auth)
             .withdraw_from_account(account, token_address, dec!("1.0"))
             .take_all_from_worktop(token_address, |builder, bucket_id| {
-                builder.call_method(account, "try_deposit_or_abort", manifest_args!(bucket_id))
+                builder.call_method(
+                    account,
+                    "try_deposit_or_abort",
+                    manifest_args!(bucket_id, Option::<ManifestProof>::None),
+                )
             })
             .call_method(
                 account,
@@ -0,0 +1,53 @@
+use scrypto::prelude::*;
+
+#[blueprint]
+#[collections(
+    players: Index<String, u16>,
+    scores: SortedIndex<(String, u16)>,
+)]
+mod leaderboard {
+    struct Leaderboard {
+        submissions: u32,
+    }
+
+    impl Leaderboard {
+        pub fn new() -> Global<Leaderboard> {
+            Self { submissions: 0 }
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::None)
+                .globalize()
+        }
+
+        pub fn submit(&mut self, player: String, score: u16) {
+            if let Some(previous_score) = Self::players().remove(&player) {
+                Self::scores().remove(&Self::sorted_key(&player, previous_score));
+            }
+            Self::players().insert(player.clone(), score);
+            Self::scores().insert(Self::sorted_key(&player, score), (player, score));
+            self.submissions += 1;
+        }
+
+        pub fn remove_player(&mut self, player: String) -> Option<u16> {
+            let score = Self::players().remove(&player)?;
+            Self::scores().remove(&Self::sorted_key(&player, score));
+            Some(score)
+        }
+
+        pub fn top(&self, count: u32) -> Vec<(String, u16)> {
+            Self::scores().scan(count)
+        }
+
+        pub fn player_count(&self) -> u32 {
+            Self::players().scan(u32::MAX).len() as u32
+        }
+
+        pub fn take_players(&mut self, count: u32) -> Vec<u16> {
+            Self::players().take(count)
+        }
+
+        fn sorted_key(player: &String, score: u16) -> SortedKey {
+            // The highest scores come first
+            SortedKey::new(u16::MAX - score, scrypto_encode(player).unwrap())
+        }
+    }
+}
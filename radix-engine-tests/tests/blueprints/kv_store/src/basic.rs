@@ -1,3 +1,6 @@
+use scrypto::api::field_lock_api::LockFlags;
+use scrypto::api::key_value_store_api::ClientKeyValueStoreApi;
+use scrypto::engine::scrypto_env::*;
 use scrypto::prelude::*;
 
 #[blueprint]
@@ -63,6 +66,25 @@ mod basic {
         pub fn remove(&mut self, key: String) -> Option<String> {
             self.map.remove(&key)
         }
+
+        pub fn scan(
+            &self,
+            start_key: Option<String>,
+            limit: u32,
+        ) -> (Vec<(String, String)>, Option<String>) {
+            self.map.scan(start_key.as_ref(), limit)
+        }
+
+        pub fn scan_while_writing(&mut self, key: String, limit: u32) -> Vec<(String, String)> {
+            ScryptoEnv
+                .key_value_store_open_entry(
+                    self.map.id.as_node_id(),
+                    &scrypto_encode(&key).unwrap(),
+                    LockFlags::MUTABLE,
+                )
+                .unwrap();
+            self.map.scan(None, limit).0
+        }
     }
 }
 
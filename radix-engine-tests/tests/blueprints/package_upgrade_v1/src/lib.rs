@@ -0,0 +1,41 @@
+use scrypto::prelude::*;
+
+#[blueprint]
+mod counter {
+    struct Counter {
+        count: u32,
+        /// The step of each increment, by the count it resulted in
+        history: KeyValueStore<u32, u32>,
+    }
+
+    impl Counter {
+        pub fn instantiate() -> Global<Counter> {
+            Self {
+                count: 0,
+                history: KeyValueStore::new(),
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Fixed(rule!(allow_all)))
+            .globalize()
+        }
+
+        pub fn instantiate_without_owner() -> Global<Counter> {
+            Self {
+                count: 0,
+                history: KeyValueStore::new(),
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Fixed(rule!(deny_all)))
+            .globalize()
+        }
+
+        pub fn increment(&mut self) {
+            self.count += 1;
+            self.history.insert(self.count, 1);
+        }
+
+        pub fn get(&self) -> u32 {
+            self.count
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use scrypto::prelude::*;
+
+/// The state of a `Counter` of the previous version of the package.
+#[derive(ScryptoSbor)]
+pub struct CounterV1 {
+    count: u32,
+    history: KeyValueStore<u32, u32>,
+}
+
+#[blueprint]
+mod counter {
+    struct Counter {
+        count: u32,
+        /// The step of each increment, by the count it resulted in
+        history: KeyValueStore<u32, u32>,
+        step: u32,
+    }
+
+    impl Counter {
+        pub fn instantiate() -> Global<Counter> {
+            Self {
+                count: 0,
+                history: KeyValueStore::new(),
+                step: 1,
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Fixed(rule!(allow_all)))
+            .globalize()
+        }
+
+        pub fn instantiate_without_owner() -> Global<Counter> {
+            Self {
+                count: 0,
+                history: KeyValueStore::new(),
+                step: 1,
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Fixed(rule!(deny_all)))
+            .globalize()
+        }
+
+        pub fn migrate(old: CounterV1) -> (Counter,) {
+            (Counter {
+                count: old.count,
+                history: old.history,
+                step: 10,
+            },)
+        }
+
+        pub fn increment(&mut self) {
+            self.count += self.step;
+            self.history.insert(self.count, self.step);
+        }
+
+        /// The sum of the recorded steps, which is the count unless some history was lost.
+        pub fn get_recorded_steps(&self) -> u32 {
+            (1..=self.count)
+                .filter_map(|count| self.history.get(&count).map(|step| *step))
+                .sum()
+        }
+
+        pub fn get(&self) -> u32 {
+            self.count
+        }
+    }
+}
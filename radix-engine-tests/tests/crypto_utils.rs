@@ -0,0 +1,109 @@
+use radix_engine::types::*;
+use scrypto_unit::*;
+use transaction::builder::ManifestBuilder;
+use transaction::signing::ed25519::Ed25519PrivateKey;
+use transaction::signing::secp256k1::Secp256k1PrivateKey;
+
+fn call_crypto_utils<T: ScryptoDecode>(
+    test_runner: &mut TestRunner,
+    package_address: PackageAddress,
+    function_name: &str,
+    args: ManifestValue,
+) -> T {
+    let manifest = ManifestBuilder::new()
+        .lock_fee(test_runner.faucet_component(), 500u32.into())
+        .call_function(package_address, "CryptoUtilsTest", function_name, args)
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    receipt.expect_commit_success().output(1)
+}
+
+#[test]
+fn crypto_utils_hashes_match_native_implementations() {
+    // Arrange
+    let mut test_runner = TestRunner::builder().build();
+    let package_address = test_runner.compile_and_publish("./tests/blueprints/crypto_utils");
+    let data = b"Hello Radix".to_vec();
+
+    // Act
+    let hashes: (Hash, Hash, Hash) = call_crypto_utils(
+        &mut test_runner,
+        package_address,
+        "hashes",
+        manifest_args!(data.clone()),
+    );
+
+    // Assert
+    assert_eq!(
+        hashes,
+        (
+            blake2b_256_hash(&data),
+            keccak256_hash(&data),
+            sha256_hash(&data)
+        )
+    );
+}
+
+#[test]
+fn crypto_utils_verifies_and_recovers_secp256k1_signatures() {
+    // Arrange
+    let mut test_runner = TestRunner::builder().build();
+    let package_address = test_runner.compile_and_publish("./tests/blueprints/crypto_utils");
+    let private_key = Secp256k1PrivateKey::from_u64(1).unwrap();
+    let other_public_key = Secp256k1PrivateKey::from_u64(2).unwrap().public_key();
+    let message_hash = hash("message");
+    let signature = private_key.sign(&message_hash);
+
+    // Act
+    let valid: bool = call_crypto_utils(
+        &mut test_runner,
+        package_address,
+        "secp256k1_ecdsa_verify",
+        manifest_args!(message_hash, private_key.public_key(), signature),
+    );
+    let invalid: bool = call_crypto_utils(
+        &mut test_runner,
+        package_address,
+        "secp256k1_ecdsa_verify",
+        manifest_args!(message_hash, other_public_key, signature),
+    );
+    let recovered: Option<Secp256k1PublicKey> = call_crypto_utils(
+        &mut test_runner,
+        package_address,
+        "secp256k1_ecdsa_recover",
+        manifest_args!(message_hash, signature),
+    );
+
+    // Assert
+    assert!(valid);
+    assert!(!invalid);
+    assert_eq!(recovered, Some(private_key.public_key()));
+}
+
+#[test]
+fn crypto_utils_verifies_ed25519_signatures() {
+    // Arrange
+    let mut test_runner = TestRunner::builder().build();
+    let package_address = test_runner.compile_and_publish("./tests/blueprints/crypto_utils");
+    let private_key = Ed25519PrivateKey::from_u64(1).unwrap();
+    let message_hash = hash("message");
+    let signature = private_key.sign(&message_hash);
+
+    // Act
+    let valid: bool = call_crypto_utils(
+        &mut test_runner,
+        package_address,
+        "ed25519_verify",
+        manifest_args!(message_hash, private_key.public_key(), signature),
+    );
+    let invalid: bool = call_crypto_utils(
+        &mut test_runner,
+        package_address,
+        "ed25519_verify",
+        manifest_args!(hash("other message"), private_key.public_key(), signature),
+    );
+
+    // Assert
+    assert!(valid);
+    assert!(!invalid);
+}
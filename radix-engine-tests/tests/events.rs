@@ -9,7 +9,7 @@ use radix_engine::errors::{
 };
 use radix_engine::system::node_modules::metadata::SetMetadataEvent;
 use radix_engine::types::*;
-use radix_engine_interface::api::node_modules::metadata::MetadataValue;
+use radix_engine_interface::api::node_modules::metadata::{MetadataValue, METADATA_BLUEPRINT};
 use radix_engine_interface::api::node_modules::ModuleConfig;
 use radix_engine_interface::api::ObjectModuleId;
 use radix_engine_interface::blueprints::account::*;
@@ -18,6 +18,7 @@ use radix_engine_interface::blueprints::consensus_manager::{
     CONSENSUS_MANAGER_NEXT_ROUND_IDENT, VALIDATOR_UPDATE_ACCEPT_DELEGATED_STAKE_IDENT,
 };
 use radix_engine_interface::{metadata, metadata_init};
+use radix_engine_queries::query::{EventDecoder, EventFilter};
 use scrypto::prelude::Mutability::LOCKED;
 use scrypto::prelude::{AccessRule, FromPublicKey, ResourceAction};
 use scrypto::NonFungibleData;
@@ -1444,6 +1445,112 @@ fn create_account_events_can_be_looked_up() {
     }
 }
 
+//===============
+// Event Decoder
+//===============
+
+#[test]
+fn event_decoder_decodes_and_filters_events() {
+    // Arrange
+    let mut test_runner = TestRunner::builder().without_trace().build();
+    let (_, _, account) = test_runner.new_account(false);
+    let recallable_resource_address = test_runner.create_recallable_token(account);
+    let vault_id = test_runner.get_component_vaults(account, recallable_resource_address)[0];
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee(test_runner.faucet_component(), 500u32.into())
+        .recall(InternalAddress::new_or_panic(vault_id.into()), 1.into())
+        .call_method(
+            account,
+            ACCOUNT_TRY_DEPOSIT_BATCH_OR_ABORT_IDENT,
+            manifest_args!(ManifestExpression::EntireWorktop),
+        )
+        .build();
+
+    // Act
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let events = receipt.expect_commit(true).clone().application_events;
+    let decoder = EventDecoder::new(test_runner.substate_db());
+    let decode = |filter: EventFilter| -> Vec<(String, serde_json::Value)> {
+        decoder
+            .decode_events(&events, &filter)
+            .map(|event| {
+                let event = event.unwrap();
+                (event.event_name, event.payload)
+            })
+            .collect()
+    };
+
+    let all_events = decode(EventFilter::new());
+    assert_eq!(
+        all_events
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "LockFeeEvent",
+            "WithdrawResourceEvent",
+            "RecallResourceEvent",
+            "DepositResourceEvent"
+        ]
+    );
+
+    let withdraw_events = decode(EventFilter::new().event_name("WithdrawResourceEvent"));
+    assert_eq!(withdraw_events.len(), 1);
+    let payload = &withdraw_events[0].1;
+    assert_eq!(payload["kind"], "Enum");
+    assert_eq!(payload["fields"][0]["kind"], "Decimal");
+    assert_eq!(payload["fields"][0]["value"], "1");
+
+    let vault_events = decode(EventFilter::new().emitter(vault_id));
+    assert_eq!(
+        vault_events
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["WithdrawResourceEvent", "RecallResourceEvent"]
+    );
+
+    let fungible_vault_events =
+        decode(EventFilter::new().blueprint(RESOURCE_PACKAGE, FUNGIBLE_VAULT_BLUEPRINT));
+    assert_eq!(fungible_vault_events.len(), 4);
+    assert!(decode(EventFilter::new().blueprint(ACCOUNT_PACKAGE, ACCOUNT_BLUEPRINT)).is_empty());
+}
+
+#[test]
+fn event_decoder_decodes_events_of_object_modules() {
+    // Arrange
+    let mut test_runner = TestRunner::builder().without_trace().build();
+    let resource_address = create_all_allowed_resource(&mut test_runner);
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee(test_runner.faucet_component(), 500u32.into())
+        .set_metadata(resource_address, "key", MetadataValue::I32(1))
+        .build();
+
+    // Act
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let events = receipt.expect_commit(true).clone().application_events;
+    let decoder = EventDecoder::new(test_runner.substate_db());
+    let metadata_events: Vec<_> = decoder
+        .decode_events(
+            &events,
+            &EventFilter::new().blueprint(METADATA_MODULE_PACKAGE, METADATA_BLUEPRINT),
+        )
+        .map(|event| event.unwrap())
+        .collect();
+    assert_eq!(metadata_events.len(), 1);
+    assert_eq!(metadata_events[0].event_name, "SetMetadataEvent");
+    assert_eq!(
+        metadata_events[0].event_type_identifier.0,
+        Emitter::Method(*resource_address.as_node_id(), ObjectModuleId::Metadata)
+    );
+}
+
 //=========
 // Helpers
 //=========
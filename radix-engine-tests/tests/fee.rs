@@ -1,10 +1,13 @@
 use radix_engine::blueprints::resource::WorktopError;
 use radix_engine::errors::{ApplicationError, CallFrameError, KernelError};
-use radix_engine::errors::{RejectionError, RuntimeError};
+use radix_engine::errors::{RejectionError, RuntimeError, SystemModuleError};
 use radix_engine::kernel::call_frame::OpenSubstateError;
 use radix_engine::kernel::heap::HeapOpenSubstateError;
+use radix_engine::system::system_modules::auth::AuthError;
 use radix_engine::track::interface::AcquireLockError;
-use radix_engine::transaction::{FeeLocks, TransactionReceipt};
+use radix_engine::transaction::{
+    FeeEstimationError, FeeEstimatorConfig, FeeLocks, TransactionReceipt,
+};
 use radix_engine::types::*;
 use radix_engine_interface::blueprints::resource::FromPublicKey;
 use scrypto_unit::*;
@@ -566,3 +569,74 @@ fn regular_and_contingent_fee_locks_are_correct_in_execution_trace() {
         }
     )
 }
+
+#[test]
+fn estimated_fee_is_sufficient_to_execute_transaction() {
+    // Arrange
+    let mut test_runner = TestRunner::builder().build();
+    let (public_key, _, account) = test_runner.new_account(false);
+    let (_, _, other_account) = test_runner.new_account(false);
+    let mut manifest_builder = ManifestBuilder::new();
+    manifest_builder
+        .withdraw_from_account(account, RADIX_TOKEN, 10.into())
+        .call_method(
+            other_account,
+            "try_deposit_batch_or_abort",
+            manifest_args!(ManifestExpression::EntireWorktop),
+        );
+    let estimate = test_runner
+        .estimate_fee(
+            manifest_builder.build(),
+            vec![public_key.into()],
+            0,
+            &FeeEstimatorConfig::default(),
+        )
+        .unwrap();
+
+    // Act
+    let manifest = manifest_builder
+        .prepend_lock_fee(account, estimate.recommended_lock_fee)
+        .build();
+    let receipt = test_runner.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+
+    // Assert
+    let commit = receipt.expect_commit_success();
+    assert!(estimate.execution_cost_units > 0);
+    assert!(!estimate.execution_cost_breakdown.is_empty());
+    assert_eq!(estimate.royalty_cost_xrd, Decimal::ZERO);
+    assert_eq!(estimate.tipping_cost_xrd, Decimal::ZERO);
+    assert!(estimate.recommended_lock_fee > estimate.total_cost_xrd());
+    assert!(
+        commit.fee_summary.total_execution_cost_xrd
+            + commit.fee_summary.total_state_expansion_cost_xrd
+            <= estimate.recommended_lock_fee
+    );
+}
+
+#[test]
+fn fee_estimation_of_failing_transaction_returns_error() {
+    // Arrange
+    let mut test_runner = TestRunner::builder().build();
+    let (_, _, account) = test_runner.new_account(false);
+    let manifest = ManifestBuilder::new()
+        .withdraw_from_account(account, RADIX_TOKEN, 10.into())
+        .try_deposit_batch_or_abort(account)
+        .build();
+
+    // Act
+    // (the withdrawal is not authorized, which fails well before the fee loan is repaid)
+    let result = test_runner.estimate_fee(manifest, vec![], 0, &FeeEstimatorConfig::default());
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(FeeEstimationError::TransactionRejected(
+            RejectionError::ErrorBeforeFeeLoanRepaid(RuntimeError::SystemModuleError(
+                SystemModuleError::AuthError(AuthError::Unauthorized(..))
+            ))
+        ))
+    ));
+}
@@ -0,0 +1,157 @@
+use radix_engine::types::*;
+use scrypto_unit::*;
+use transaction::builder::ManifestBuilder;
+
+fn create_leaderboard(test_runner: &mut TestRunner) -> ComponentAddress {
+    let package_address = test_runner.compile_and_publish("./tests/blueprints/index");
+    let manifest = ManifestBuilder::new()
+        .lock_fee(test_runner.faucet_component(), 500u32.into())
+        .call_function(package_address, "Leaderboard", "new", manifest_args!())
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    receipt.expect_commit_success().new_component_addresses()[0]
+}
+
+fn submit(test_runner: &mut TestRunner, leaderboard: ComponentAddress, player: &str, score: u16) {
+    let manifest = ManifestBuilder::new()
+        .lock_fee(test_runner.faucet_component(), 500u32.into())
+        .call_method(
+            leaderboard,
+            "submit",
+            manifest_args!(player.to_string(), score),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    receipt.expect_commit_success();
+}
+
+fn top(
+    test_runner: &mut TestRunner,
+    leaderboard: ComponentAddress,
+    count: u32,
+) -> Vec<(String, u16)> {
+    let manifest = ManifestBuilder::new()
+        .lock_fee(test_runner.faucet_component(), 500u32.into())
+        .call_method(leaderboard, "top", manifest_args!(count))
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    receipt.expect_commit_success().output(1)
+}
+
+#[test]
+fn sorted_index_scan_returns_entries_in_sort_order() {
+    // Arrange
+    let mut test_runner = TestRunner::builder().build();
+    let leaderboard = create_leaderboard(&mut test_runner);
+
+    // Act
+    submit(&mut test_runner, leaderboard, "alice", 10);
+    submit(&mut test_runner, leaderboard, "bob", 30);
+    submit(&mut test_runner, leaderboard, "carol", 20);
+    submit(&mut test_runner, leaderboard, "alice", 40);
+
+    // Assert
+    assert_eq!(
+        top(&mut test_runner, leaderboard, 2),
+        vec![("alice".to_string(), 40), ("bob".to_string(), 30)]
+    );
+    assert_eq!(
+        top(&mut test_runner, leaderboard, 10),
+        vec![
+            ("alice".to_string(), 40),
+            ("bob".to_string(), 30),
+            ("carol".to_string(), 20)
+        ]
+    );
+}
+
+#[test]
+fn index_remove_returns_removed_value() {
+    // Arrange
+    let mut test_runner = TestRunner::builder().build();
+    let leaderboard = create_leaderboard(&mut test_runner);
+    submit(&mut test_runner, leaderboard, "alice", 10);
+    submit(&mut test_runner, leaderboard, "bob", 30);
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee(test_runner.faucet_component(), 500u32.into())
+        .call_method(
+            leaderboard,
+            "remove_player",
+            manifest_args!("bob".to_string()),
+        )
+        .call_method(
+            leaderboard,
+            "remove_player",
+            manifest_args!("dave".to_string()),
+        )
+        .call_method(leaderboard, "player_count", manifest_args!())
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let commit = receipt.expect_commit_success();
+    assert_eq!(commit.output::<Option<u16>>(1), Some(30));
+    assert_eq!(commit.output::<Option<u16>>(2), None);
+    assert_eq!(commit.output::<u32>(3), 1);
+    assert_eq!(
+        top(&mut test_runner, leaderboard, 10),
+        vec![("alice".to_string(), 10)]
+    );
+}
+
+#[test]
+fn index_take_removes_entries() {
+    // Arrange
+    let mut test_runner = TestRunner::builder().build();
+    let leaderboard = create_leaderboard(&mut test_runner);
+    submit(&mut test_runner, leaderboard, "alice", 10);
+    submit(&mut test_runner, leaderboard, "bob", 30);
+    submit(&mut test_runner, leaderboard, "carol", 20);
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee(test_runner.faucet_component(), 500u32.into())
+        .call_method(leaderboard, "take_players", manifest_args!(2u32))
+        .call_method(leaderboard, "player_count", manifest_args!())
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let commit = receipt.expect_commit_success();
+    assert_eq!(commit.output::<Vec<u16>>(1).len(), 2);
+    assert_eq!(commit.output::<u32>(2), 1);
+}
+
+#[test]
+fn index_access_cost_grows_with_the_number_of_returned_entries() {
+    // Arrange
+    let mut test_runner = TestRunner::builder().build();
+    let leaderboard = create_leaderboard(&mut test_runner);
+    submit(&mut test_runner, leaderboard, "alice", 10);
+    submit(&mut test_runner, leaderboard, "bob", 30);
+    submit(&mut test_runner, leaderboard, "carol", 20);
+    let mut access_index_cost = |count: u32| {
+        let manifest = ManifestBuilder::new()
+            .lock_fee(test_runner.faucet_component(), 500u32.into())
+            .call_method(leaderboard, "top", manifest_args!(count))
+            .build();
+        let receipt = test_runner.execute_manifest(manifest, vec![]);
+        receipt
+            .expect_commit_success()
+            .fee_summary
+            .execution_cost_breakdown
+            .get("AccessIndex")
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    // Act
+    let cost_of_one_entry = access_index_cost(1);
+    let cost_of_three_entries = access_index_cost(3);
+
+    // Assert
+    assert!(cost_of_one_entry > 0);
+    assert!(cost_of_three_entries > cost_of_one_entry);
+}
@@ -44,6 +44,7 @@ pub fn test_open_substate_of_invisible_package_address() {
     let mut id_allocator = IdAllocator::new(executable.intent_hash().to_hash());
     let mut system = SystemConfig {
         blueprint_cache: NonIterMap::new(),
+        blueprint_version_cache: NonIterMap::new(),
         auth_cache: NonIterMap::new(),
         schema_cache: NonIterMap::new(),
         callback_obj: Vm {
@@ -1,8 +1,11 @@
 use radix_engine::errors::{CallFrameError, KernelError, RuntimeError};
 use radix_engine::kernel::call_frame::{
-    CloseSubstateError, CreateNodeError, OpenSubstateError, TakeNodeError,
+    CallFrameScanSubstateError, CloseSubstateError, CreateNodeError, OpenSubstateError,
+    TakeNodeError,
 };
 use radix_engine::types::*;
+use radix_engine_queries::query::{KeyValueStoreReader, KeyValueStoreReaderError};
+use radix_engine_store_interface::db_key_mapper::{MappedSubstateDatabase, SpreadPrefixKeyMapper};
 use scrypto_unit::*;
 use transaction::builder::ManifestBuilder;
 
@@ -523,3 +526,198 @@ fn nested_kv_stores_works() {
     // Assert
     receipt.expect_commit_success();
 }
+
+fn create_basic_kv_store_with_entries(
+    test_runner: &mut TestRunner,
+    entries: &[(&str, &str)],
+) -> ComponentAddress {
+    let package_address = test_runner.compile_and_publish("./tests/blueprints/kv_store");
+    let manifest = ManifestBuilder::new()
+        .lock_fee(test_runner.faucet_component(), 500u32.into())
+        .call_function(package_address, "Basic", "new", manifest_args!())
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+    let component = receipt.expect_commit_success().new_component_addresses()[0];
+
+    let mut builder = ManifestBuilder::new();
+    builder.lock_fee(test_runner.faucet_component(), 500u32.into());
+    for (key, value) in entries {
+        builder.call_method(
+            component,
+            "insert",
+            manifest_args!(key.to_string(), value.to_string()),
+        );
+    }
+    let receipt = test_runner.execute_manifest(builder.build(), vec![]);
+    receipt.expect_commit_success();
+    component
+}
+
+#[test]
+fn scan_of_stored_map_returns_remaining_entries() {
+    // Arrange
+    let mut test_runner = TestRunner::builder().build();
+    let component =
+        create_basic_kv_store_with_entries(&mut test_runner, &[("a", "1"), ("b", "2"), ("c", "3")]);
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee(test_runner.faucet_component(), 500u32.into())
+        .call_method(component, "remove", manifest_args!("b".to_string()))
+        .call_method(component, "scan", manifest_args!(None::<String>, 10u32))
+        .call_method(component, "scan", manifest_args!(None::<String>, 0u32))
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let result = receipt.expect_commit_success();
+    let (mut entries, next_key): (Vec<(String, String)>, Option<String>) = result.output(2);
+    entries.sort();
+    assert_eq!(
+        entries,
+        vec![
+            ("a".to_string(), "1".to_string()),
+            ("c".to_string(), "3".to_string())
+        ]
+    );
+    assert_eq!(next_key, None);
+    let (entries, next_key): (Vec<(String, String)>, Option<String>) = result.output(3);
+    assert_eq!(entries, vec![]);
+    assert!(next_key.is_some());
+}
+
+#[test]
+fn scan_of_stored_map_pages_through_all_entries_skipping_removed_ones() {
+    // Arrange
+    let mut test_runner = TestRunner::builder().build();
+    let component = create_basic_kv_store_with_entries(
+        &mut test_runner,
+        &[("a", "1"), ("b", "2"), ("c", "3"), ("d", "4"), ("e", "5")],
+    );
+    let manifest = ManifestBuilder::new()
+        .lock_fee(test_runner.faucet_component(), 500u32.into())
+        .call_method(component, "remove", manifest_args!("b".to_string()))
+        .call_method(component, "remove", manifest_args!("d".to_string()))
+        .build();
+    test_runner
+        .execute_manifest(manifest, vec![])
+        .expect_commit_success();
+
+    // Act
+    let mut entries = Vec::new();
+    let mut start_key: Option<String> = None;
+    loop {
+        let manifest = ManifestBuilder::new()
+            .lock_fee(test_runner.faucet_component(), 500u32.into())
+            .call_method(component, "scan", manifest_args!(start_key.clone(), 2u32))
+            .build();
+        let receipt = test_runner.execute_manifest(manifest, vec![]);
+        let (page, next_key): (Vec<(String, String)>, Option<String>) =
+            receipt.expect_commit_success().output(1);
+        assert!(page.len() <= 2);
+        assert!(next_key.is_none() || page.len() == 2);
+        entries.extend(page);
+        start_key = next_key;
+        if start_key.is_none() {
+            break;
+        }
+    }
+
+    // Assert
+    entries.sort();
+    assert_eq!(
+        entries,
+        vec![
+            ("a".to_string(), "1".to_string()),
+            ("c".to_string(), "3".to_string()),
+            ("e".to_string(), "5".to_string())
+        ]
+    );
+}
+
+#[test]
+fn scan_of_stored_map_with_an_entry_open_for_writing_fails() {
+    // Arrange
+    let mut test_runner = TestRunner::builder().build();
+    let component = create_basic_kv_store_with_entries(&mut test_runner, &[("a", "1"), ("b", "2")]);
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee(test_runner.faucet_component(), 500u32.into())
+        .call_method(
+            component,
+            "scan_while_writing",
+            manifest_args!("a".to_string(), 10u32),
+        )
+        .build();
+    let receipt = test_runner.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::KernelError(KernelError::CallFrameError(
+                CallFrameError::ScanSubstatesError(CallFrameScanSubstateError::StoreError(..))
+            ))
+        )
+    });
+}
+
+#[test]
+fn kv_store_reader_lists_stored_map_in_pages() {
+    // Arrange
+    let mut test_runner = TestRunner::builder().build();
+    let component =
+        create_basic_kv_store_with_entries(&mut test_runner, &[("a", "1"), ("b", "2"), ("c", "3")]);
+    let (kv_store,): (Own,) = test_runner
+        .substate_db()
+        .get_mapped::<SpreadPrefixKeyMapper, (Own,)>(
+            component.as_node_id(),
+            MAIN_BASE_PARTITION,
+            &ComponentField::State0.into(),
+        )
+        .unwrap();
+    let reader = KeyValueStoreReader::new(test_runner.substate_db());
+
+    // Act
+    let mut entries = Vec::new();
+    let mut next_key = None;
+    loop {
+        let contents = reader
+            .list_entries(kv_store.as_node_id(), next_key.as_ref(), 2)
+            .unwrap();
+        assert!(contents.entries.len() <= 2);
+        entries.extend(contents.entries);
+        next_key = contents.next_key;
+        if next_key.is_none() {
+            break;
+        }
+    }
+
+    // Assert
+    let mut entries: Vec<(String, String)> = entries
+        .into_iter()
+        .map(|entry| {
+            (
+                scrypto_decode(&scrypto_encode(&entry.key).unwrap()).unwrap(),
+                scrypto_decode(&scrypto_encode(&entry.value).unwrap()).unwrap(),
+            )
+        })
+        .collect();
+    entries.sort();
+    assert_eq!(
+        entries,
+        vec![
+            ("a".to_string(), "1".to_string()),
+            ("b".to_string(), "2".to_string()),
+            ("c".to_string(), "3".to_string())
+        ]
+    );
+    assert_eq!(reader.count_entries(kv_store.as_node_id()), Ok(3));
+    assert_eq!(
+        reader.list_entries(component.as_node_id(), None, 10),
+        Err(KeyValueStoreReaderError::NotAKeyValueStore(
+            *component.as_node_id()
+        ))
+    );
+}
@@ -0,0 +1,323 @@
+use radix_engine::blueprints::package::{BlueprintIncompatibility, PackageError};
+use radix_engine::errors::{ApplicationError, RuntimeError, SystemError, SystemModuleError};
+use radix_engine::system::system_modules::auth::AuthError;
+use radix_engine::transaction::TransactionReceipt;
+use radix_engine::types::*;
+use radix_engine_interface::blueprints::package::{BlueprintDefinitionInit, BlueprintVersion};
+use radix_engine_interface::blueprints::resource::FromPublicKey;
+use scrypto_unit::*;
+use transaction::builder::ManifestBuilder;
+
+const V2: BlueprintVersion = BlueprintVersion {
+    major: 2,
+    minor: 0,
+    patch: 0,
+};
+
+struct UpgradeTest {
+    test_runner: TestRunner,
+    public_key: Secp256k1PublicKey,
+    account: ComponentAddress,
+    owner_badge_resource: ResourceAddress,
+    package_address: PackageAddress,
+}
+
+impl UpgradeTest {
+    fn new() -> Self {
+        let mut test_runner = TestRunner::builder().build();
+        let (public_key, _, account) = test_runner.new_allocated_account();
+        let owner_badge_resource = test_runner.create_non_fungible_resource(account);
+        let owner_badge_addr =
+            NonFungibleGlobalId::new(owner_badge_resource, NonFungibleLocalId::integer(1));
+        let package_address = test_runner.compile_and_publish_with_owner(
+            "./tests/blueprints/package_upgrade_v1",
+            owner_badge_addr,
+        );
+
+        Self {
+            test_runner,
+            public_key,
+            account,
+            owner_badge_resource,
+            package_address,
+        }
+    }
+
+    fn instantiate(&mut self, function_name: &str) -> ComponentAddress {
+        let receipt = self.test_runner.execute_manifest(
+            ManifestBuilder::new()
+                .lock_fee(self.account, 500u32.into())
+                .call_function(
+                    self.package_address,
+                    "Counter",
+                    function_name,
+                    manifest_args!(),
+                )
+                .build(),
+            vec![],
+        );
+        receipt.expect_commit(true).new_component_addresses()[0]
+    }
+
+    fn call_method(
+        &mut self,
+        component_address: ComponentAddress,
+        method_name: &str,
+    ) -> TransactionReceipt {
+        self.test_runner.execute_manifest(
+            ManifestBuilder::new()
+                .lock_fee(self.account, 500u32.into())
+                .call_method(component_address, method_name, manifest_args!())
+                .build(),
+            vec![],
+        )
+    }
+
+    fn get(&mut self, component_address: ComponentAddress) -> u32 {
+        self.call_method(component_address, "get")
+            .expect_commit_success()
+            .output(1)
+    }
+
+    fn upgrade_blueprint(
+        &mut self,
+        version: BlueprintVersion,
+        code: Vec<u8>,
+        definition: BlueprintDefinitionInit,
+        migrations: BTreeMap<BlueprintVersion, String>,
+        with_owner_badge: bool,
+    ) -> TransactionReceipt {
+        let mut builder = ManifestBuilder::new();
+        builder.lock_fee(self.account, 500u32.into());
+        if with_owner_badge {
+            builder.create_proof_from_account_of_non_fungibles(
+                self.account,
+                self.owner_badge_resource,
+                &btreeset!(NonFungibleLocalId::integer(1)),
+            );
+        }
+        builder.upgrade_blueprint(
+            self.package_address,
+            "Counter",
+            version,
+            code,
+            definition,
+            migrations,
+        );
+        self.test_runner.execute_manifest(
+            builder.build(),
+            vec![NonFungibleGlobalId::from_public_key(&self.public_key)],
+        )
+    }
+
+    fn upgrade_component(&mut self, component_address: ComponentAddress) -> TransactionReceipt {
+        self.test_runner.execute_manifest(
+            ManifestBuilder::new()
+                .lock_fee(self.account, 500u32.into())
+                .upgrade_component(self.package_address, component_address)
+                .build(),
+            vec![],
+        )
+    }
+}
+
+fn compile_v2() -> (Vec<u8>, BlueprintDefinitionInit) {
+    let (code, mut definition) = Compile::compile("./tests/blueprints/package_upgrade_v2");
+    (code, definition.blueprints.remove("Counter").unwrap())
+}
+
+fn v2_migrations() -> BTreeMap<BlueprintVersion, String> {
+    btreemap!(BlueprintVersion::default() => "migrate".to_string())
+}
+
+#[test]
+fn components_keep_their_version_until_upgraded_and_migrated() {
+    // Arrange
+    let mut test = UpgradeTest::new();
+    let component_address = test.instantiate("instantiate");
+    test.call_method(component_address, "increment")
+        .expect_commit_success();
+    let (code, definition) = compile_v2();
+
+    // Act
+    let receipt = test.upgrade_blueprint(V2, code, definition, v2_migrations(), true);
+
+    // Assert
+    receipt.expect_commit_success();
+    test.call_method(component_address, "increment")
+        .expect_commit_success();
+    assert_eq!(test.get(component_address), 2);
+
+    // Act
+    let receipt = test.upgrade_component(component_address);
+
+    // Assert
+    let version: BlueprintVersion = receipt.expect_commit_success().output(1);
+    assert_eq!(version, V2);
+    test.call_method(component_address, "increment")
+        .expect_commit_success();
+    assert_eq!(test.get(component_address), 12);
+    // The key value store owned by the component is carried through the migration
+    let recorded_steps: u32 = test
+        .call_method(component_address, "get_recorded_steps")
+        .expect_commit_success()
+        .output(1);
+    assert_eq!(recorded_steps, 12);
+}
+
+#[test]
+fn new_components_are_instantiated_with_the_latest_version() {
+    // Arrange
+    let mut test = UpgradeTest::new();
+    let (code, definition) = compile_v2();
+    test.upgrade_blueprint(V2, code, definition, v2_migrations(), true)
+        .expect_commit_success();
+
+    // Act
+    let component_address = test.instantiate("instantiate");
+
+    // Assert
+    test.call_method(component_address, "increment")
+        .expect_commit_success();
+    assert_eq!(test.get(component_address), 1);
+    let version: BlueprintVersion = test
+        .upgrade_component(component_address)
+        .expect_commit_success()
+        .output(1);
+    assert_eq!(version, V2);
+}
+
+#[test]
+fn upgrading_blueprint_requires_package_owner() {
+    // Arrange
+    let mut test = UpgradeTest::new();
+    let (code, definition) = compile_v2();
+
+    // Act
+    let receipt = test.upgrade_blueprint(V2, code, definition, v2_migrations(), false);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::SystemModuleError(SystemModuleError::AuthError(AuthError::Unauthorized(
+                ..
+            )))
+        )
+    });
+}
+
+#[test]
+fn upgrading_blueprint_requires_a_greater_version() {
+    // Arrange
+    let mut test = UpgradeTest::new();
+    let (code, definition) = compile_v2();
+
+    // Act
+    let receipt = test.upgrade_blueprint(
+        BlueprintVersion::default(),
+        code,
+        definition,
+        v2_migrations(),
+        true,
+    );
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::BlueprintVersionNotGreater { .. }
+            ))
+        )
+    });
+}
+
+#[test]
+fn upgrading_blueprint_cannot_remove_functions() {
+    // Arrange
+    let mut test = UpgradeTest::new();
+    let (code, mut definition) = compile_v2();
+    definition.schema.functions.functions.remove("get");
+
+    // Act
+    let receipt = test.upgrade_blueprint(V2, code, definition, v2_migrations(), true);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::IncompatibleBlueprintUpgrade {
+                    incompatibility: BlueprintIncompatibility::FunctionRemoved(..),
+                    ..
+                }
+            ))
+        )
+    });
+}
+
+#[test]
+fn upgrading_blueprint_fields_requires_a_migration() {
+    // Arrange
+    let mut test = UpgradeTest::new();
+    let (code, definition) = compile_v2();
+
+    // Act
+    let receipt = test.upgrade_blueprint(V2, code, definition, BTreeMap::new(), true);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::IncompatibleBlueprintUpgrade {
+                    incompatibility: BlueprintIncompatibility::FieldsChangedWithoutMigration,
+                    ..
+                }
+            ))
+        )
+    });
+}
+
+#[test]
+fn upgrading_blueprint_with_an_invalid_migration_fails() {
+    // Arrange
+    let mut test = UpgradeTest::new();
+    let (code, definition) = compile_v2();
+    let migrations = btreemap!(BlueprintVersion::default() => "get".to_string());
+
+    // Act
+    let receipt = test.upgrade_blueprint(V2, code, definition, migrations, true);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::InvalidMigration { .. }
+            ))
+        )
+    });
+}
+
+#[test]
+fn upgrading_component_requires_component_owner() {
+    // Arrange
+    let mut test = UpgradeTest::new();
+    let component_address = test.instantiate("instantiate_without_owner");
+    let (code, definition) = compile_v2();
+    test.upgrade_blueprint(V2, code, definition, v2_migrations(), true)
+        .expect_commit_success();
+
+    // Act
+    let receipt = test.upgrade_component(component_address);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::SystemError(SystemError::AssertAccessRuleFailed)
+        )
+    });
+}
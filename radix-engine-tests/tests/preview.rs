@@ -1,5 +1,6 @@
 use radix_engine::transaction::ExecutionConfig;
 use radix_engine::transaction::FeeReserveConfig;
+use radix_engine::transaction::PreviewOptions;
 use radix_engine::types::*;
 use radix_engine_interface::rule;
 use scrypto_unit::*;
@@ -93,6 +94,157 @@ fn test_assume_all_signature_proofs_flag_method_authorization() {
     result.unwrap().expect_commit_success();
 }
 
+#[test]
+fn test_disable_auth_option_skips_method_authorization() {
+    // Arrange
+    let mut test_runner = TestRunner::builder().build();
+    let network = NetworkDefinition::simulator();
+    let (_, _, account) = test_runner.new_allocated_account();
+    let (_, _, other_account) = test_runner.new_allocated_account();
+    let manifest = ManifestBuilder::new()
+        .lock_fee(account, 500u32.into())
+        .withdraw_from_account(account, RADIX_TOKEN, 1.into())
+        .call_method(
+            other_account,
+            "try_deposit_batch_or_abort",
+            manifest_args!(ManifestExpression::EntireWorktop),
+        )
+        .build();
+    let (_, preview_intent) = prepare_matching_test_tx_and_preview_intent(
+        &mut test_runner,
+        &network,
+        manifest,
+        &PreviewFlags::default(),
+    );
+
+    // Act
+    let receipt_with_auth = test_runner
+        .preview_with_options(preview_intent.clone(), &network, PreviewOptions::default())
+        .unwrap();
+    let receipt_without_auth = test_runner
+        .preview_with_options(
+            preview_intent,
+            &network,
+            PreviewOptions {
+                disable_auth: true,
+                ..PreviewOptions::default()
+            },
+        )
+        .unwrap();
+
+    // Assert
+    receipt_with_auth.expect_rejection();
+    receipt_without_auth.expect_commit_success();
+}
+
+#[test]
+fn test_substate_overrides_option_is_applied_on_top_of_database() {
+    // Arrange
+    let mut test_runner = TestRunner::builder().build();
+    let network = NetworkDefinition::simulator();
+    let (public_key, _, account) = test_runner.new_allocated_account();
+    let (_, _, other_account) = test_runner.new_allocated_account();
+    let preview_flags = PreviewFlags {
+        use_free_credit: true,
+        assume_all_signature_proofs: false,
+        skip_epoch_check: false,
+    };
+
+    // Preview (without committing) a creation of a new token deposited to the account
+    let creation_manifest = ManifestBuilder::new()
+        .new_token_fixed(OwnerRole::None, metadata!(), 100.into())
+        .call_method(
+            account,
+            "try_deposit_batch_or_abort",
+            manifest_args!(ManifestExpression::EntireWorktop),
+        )
+        .build();
+    let (_, creation_preview_intent) = prepare_matching_test_tx_and_preview_intent(
+        &mut test_runner,
+        &network,
+        creation_manifest,
+        &preview_flags,
+    );
+    let creation_receipt = test_runner
+        .preview(creation_preview_intent, &network)
+        .unwrap();
+    let creation_result = creation_receipt.expect_commit_success();
+    let resource_address = creation_result.new_resource_addresses()[0];
+    let overrides = creation_result.state_updates.database_updates.clone();
+
+    // Prepare a transfer of the (not really existing) token
+    let transfer_manifest = ManifestBuilder::new()
+        .withdraw_from_account(account, resource_address, 30.into())
+        .call_method(
+            other_account,
+            "try_deposit_batch_or_abort",
+            manifest_args!(ManifestExpression::EntireWorktop),
+        )
+        .build();
+    let (_, transfer_preview_intent) = prepare_matching_test_tx_and_preview_intent(
+        &mut test_runner,
+        &network,
+        transfer_manifest,
+        &preview_flags,
+    );
+    let transfer_preview_intent = PreviewIntentV1 {
+        signer_public_keys: vec![public_key.into()],
+        ..transfer_preview_intent
+    };
+
+    // Act
+    let receipt_without_overrides = test_runner
+        .preview(transfer_preview_intent.clone(), &network)
+        .unwrap();
+    let receipt_with_overrides = test_runner
+        .preview_with_options(
+            transfer_preview_intent,
+            &network,
+            PreviewOptions {
+                substate_overrides: overrides,
+                ..PreviewOptions::default()
+            },
+        )
+        .unwrap();
+
+    // Assert
+    assert!(!receipt_without_overrides.is_commit_success());
+    receipt_with_overrides.expect_commit_success();
+    assert_eq!(test_runner.account_balance(account, resource_address), None);
+}
+
+#[test]
+fn test_cost_unit_limit_option_is_applied() {
+    // Arrange
+    let mut test_runner = TestRunner::builder().build();
+    let network = NetworkDefinition::simulator();
+    let manifest = ManifestBuilder::new()
+        .lock_fee(test_runner.faucet_component(), 500u32.into())
+        .clear_auth_zone()
+        .build();
+    let (_, preview_intent) = prepare_matching_test_tx_and_preview_intent(
+        &mut test_runner,
+        &network,
+        manifest,
+        &PreviewFlags::default(),
+    );
+
+    // Act
+    let receipt = test_runner
+        .preview_with_options(
+            preview_intent,
+            &network,
+            PreviewOptions {
+                cost_unit_limit: Some(1_000),
+                ..PreviewOptions::default()
+            },
+        )
+        .unwrap();
+
+    // Assert
+    receipt.expect_rejection();
+}
+
 fn prepare_matching_test_tx_and_preview_intent(
     test_runner: &mut TestRunner,
     network: &NetworkDefinition,
@@ -0,0 +1,247 @@
+use radix_engine::blueprints::consensus_manager::{
+    ProtocolUpdateReadinessThresholdReachedEvent, VALIDATOR_PROTOCOL_VERSION_NAME_LEN,
+};
+use radix_engine::system::bootstrap::*;
+use radix_engine::system::node_modules::royalty::RoyaltyNativePackage;
+use radix_engine::system::protocol_update::{
+    NativePackageRepublication, ProtocolUpdate, ProtocolUpdateError,
+};
+use radix_engine::transaction::CommitResult;
+use radix_engine::types::*;
+use radix_engine_interface::api::node_modules::metadata::MetadataValue;
+use radix_engine_interface::blueprints::consensus_manager::*;
+use radix_engine_interface::blueprints::package::ROYALTY_CODE_ID;
+use radix_engine_interface::blueprints::resource::FromPublicKey;
+use radix_engine_interface::metadata_init;
+use scrypto_unit::*;
+use transaction::builder::ManifestBuilder;
+use transaction::signing::secp256k1::Secp256k1PrivateKey;
+
+fn protocol_version_name() -> String {
+    format!(
+        "{:0>width$}",
+        "test-protocol-version",
+        width = VALIDATOR_PROTOCOL_VERSION_NAME_LEN
+    )
+}
+
+/// Sets up two validators, with the given stakes, and lets those listed in `signalling` signal
+/// their readiness for the protocol version, before changing the epoch.
+fn signal_and_change_epoch(
+    stakes: [Decimal; 2],
+    signalling: &[usize],
+) -> (TestRunner, CommitResult) {
+    let genesis_epoch = Epoch::of(5);
+    let keys: Vec<Secp256k1PublicKey> = (1..=2)
+        .map(|index| Secp256k1PrivateKey::from_u64(index).unwrap().public_key())
+        .collect();
+    let validators: Vec<GenesisValidator> = keys
+        .iter()
+        .map(|key| GenesisValidator::from(*key))
+        .collect();
+    let accounts = validators
+        .iter()
+        .map(|validator| validator.owner)
+        .collect::<Vec<_>>();
+    let allocations = keys
+        .iter()
+        .zip(stakes)
+        .enumerate()
+        .map(|(account_index, (key, xrd_amount))| {
+            (
+                *key,
+                vec![GenesisStakeAllocation {
+                    account_index: account_index as u32,
+                    xrd_amount,
+                }],
+            )
+        })
+        .collect();
+    let genesis = CustomGenesis {
+        genesis_data_chunks: vec![
+            GenesisDataChunk::Validators(validators),
+            GenesisDataChunk::Stakes {
+                accounts: accounts.clone(),
+                allocations,
+            },
+        ],
+        genesis_epoch,
+        initial_config: CustomGenesis::default_consensus_manager_config(),
+        initial_time_ms: 1,
+        initial_current_leader: Some(0),
+        faucet_supply: *DEFAULT_TESTING_FAUCET_SUPPLY,
+    };
+    let mut test_runner = TestRunner::builder().with_custom_genesis(genesis).build();
+
+    for index in signalling {
+        let validator_address = test_runner.get_active_validator_with_key(&keys[*index]);
+        let manifest = ManifestBuilder::new()
+            .lock_fee(test_runner.faucet_component(), 500u32.into())
+            .create_proof_from_account(accounts[*index], VALIDATOR_OWNER_BADGE)
+            .signal_protocol_update_readiness(validator_address, &protocol_version_name())
+            .build();
+        test_runner
+            .execute_manifest(
+                manifest,
+                vec![NonFungibleGlobalId::from_public_key(&keys[*index])],
+            )
+            .expect_commit_success();
+    }
+
+    let receipt = test_runner.advance_to_round(Round::of(1));
+    let commit_result = receipt.expect_commit_success().clone();
+    assert!(commit_result.next_epoch().is_some());
+    (test_runner, commit_result)
+}
+
+fn test_protocol_update(description: &str) -> ProtocolUpdate {
+    ProtocolUpdate {
+        protocol_version_name: protocol_version_name(),
+        native_package_republications: vec![NativePackageRepublication {
+            package_address: ROYALTY_MODULE_PACKAGE,
+            definition: RoyaltyNativePackage::definition(),
+            native_package_code_id: ROYALTY_CODE_ID,
+            metadata_init: metadata_init! {
+                "name" => "Royalty Package".to_owned(), locked;
+                "description" => description.to_owned(), locked;
+            },
+        }],
+        state_migrations: vec![ManifestBuilder::new()
+            .call_method(
+                CONSENSUS_MANAGER,
+                CONSENSUS_MANAGER_GET_CURRENT_EPOCH_IDENT,
+                manifest_args!(),
+            )
+            .build()],
+    }
+}
+
+#[test]
+fn readiness_threshold_is_reached_when_enough_stake_signals() {
+    // Arrange
+    let stakes = [dec!("3"), dec!("1")];
+
+    // Act
+    let (test_runner, commit_result) = signal_and_change_epoch(stakes, &[0]);
+
+    // Assert
+    let events =
+        commit_result.protocol_update_readiness_thresholds_reached(test_runner.substate_db());
+    assert_eq!(
+        events,
+        vec![ProtocolUpdateReadinessThresholdReachedEvent {
+            epoch: commit_result.next_epoch().unwrap().epoch,
+            protocol_version_name: protocol_version_name(),
+            ready_stake_xrd: dec!("3"),
+            total_stake_xrd: dec!("4"),
+        }]
+    );
+}
+
+#[test]
+fn readiness_threshold_is_not_reached_when_too_little_stake_signals() {
+    // Arrange
+    let stakes = [dec!("3"), dec!("1")];
+
+    // Act
+    let (test_runner, commit_result) = signal_and_change_epoch(stakes, &[1]);
+
+    // Assert
+    assert!(commit_result
+        .protocol_update_readiness_thresholds_reached(test_runner.substate_db())
+        .is_empty());
+}
+
+#[test]
+fn ready_protocol_update_republishes_native_packages_and_runs_state_migrations() {
+    // Arrange
+    let stakes = [dec!("1"), dec!("1")];
+    let (mut test_runner, commit_result) = signal_and_change_epoch(stakes, &[0, 1]);
+
+    // Act
+    let receipts = test_runner
+        .execute_ready_protocol_updates(vec![test_protocol_update("Republished")], &commit_result)
+        .unwrap();
+
+    // Assert
+    assert_eq!(receipts.len(), 1);
+    assert_eq!(
+        test_runner.get_metadata(ROYALTY_MODULE_PACKAGE.into(), "description"),
+        Some(MetadataValue::String("Republished".to_string()))
+    );
+    let epoch: Epoch = receipts[0].state_migration_receipts[0]
+        .expect_commit_success()
+        .output(0);
+    assert_eq!(epoch, commit_result.next_epoch().unwrap().epoch);
+}
+
+#[test]
+fn protocol_update_is_not_executed_before_readiness_threshold_is_reached() {
+    // Arrange
+    let stakes = [dec!("3"), dec!("1")];
+    let (mut test_runner, commit_result) = signal_and_change_epoch(stakes, &[1]);
+
+    // Act
+    let receipts = test_runner
+        .execute_ready_protocol_updates(vec![test_protocol_update("Republished")], &commit_result)
+        .unwrap();
+
+    // Assert
+    assert!(receipts.is_empty());
+    assert_ne!(
+        test_runner.get_metadata(ROYALTY_MODULE_PACKAGE.into(), "description"),
+        Some(MetadataValue::String("Republished".to_string()))
+    );
+}
+
+#[test]
+fn republication_deletes_substates_no_longer_produced_by_the_package() {
+    // Arrange
+    let stakes = [dec!("1"), dec!("1")];
+    let (mut test_runner, commit_result) = signal_and_change_epoch(stakes, &[0, 1]);
+    let mut protocol_update = test_protocol_update("Republished");
+    protocol_update.native_package_republications[0].metadata_init = metadata_init! {
+        "name" => "Royalty Package".to_owned(), locked;
+    };
+
+    // Act
+    test_runner
+        .execute_ready_protocol_updates(vec![protocol_update], &commit_result)
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        test_runner.get_metadata(ROYALTY_MODULE_PACKAGE.into(), "name"),
+        Some(MetadataValue::String("Royalty Package".to_string()))
+    );
+    assert_eq!(
+        test_runner.get_metadata(ROYALTY_MODULE_PACKAGE.into(), "description"),
+        None
+    );
+}
+
+#[test]
+fn failing_state_migration_leaves_the_state_untouched() {
+    // Arrange
+    let stakes = [dec!("1"), dec!("1")];
+    let (mut test_runner, commit_result) = signal_and_change_epoch(stakes, &[0, 1]);
+    let mut protocol_update = test_protocol_update("Republished");
+    protocol_update.state_migrations.push(
+        ManifestBuilder::new()
+            .call_method(CONSENSUS_MANAGER, "non_existent_method", manifest_args!())
+            .build(),
+    );
+
+    // Act
+    let result = test_runner.execute_ready_protocol_updates(vec![protocol_update], &commit_result);
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(ProtocolUpdateError::StateMigrationFailed { index: 1, .. })
+    ));
+    assert_ne!(
+        test_runner.get_metadata(ROYALTY_MODULE_PACKAGE.into(), "description"),
+        Some(MetadataValue::String("Republished".to_string()))
+    );
+}
@@ -0,0 +1,361 @@
+#![cfg(feature = "rocksdb")]
+
+use radix_engine::types::*;
+use radix_engine_store_interface::interface::*;
+use radix_engine_stores::rocks_db::RocksdbSubstateStore;
+use radix_engine_stores::rocks_db_with_merkle_tree::{
+    RocksDBWithMerkleTreeSubstateStore, StateHistoryConfig, StateHistoryError,
+};
+use std::path::PathBuf;
+
+/// A database directory which is removed when dropped.
+struct TempDatabaseDir(PathBuf);
+
+impl TempDatabaseDir {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!(
+            "radix-engine-tests-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&path);
+        Self(path)
+    }
+}
+
+impl Drop for TempDatabaseDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+fn partition_key(byte: u8) -> DbPartitionKey {
+    DbPartitionKey(vec![byte])
+}
+
+fn sort_key(bytes: &[u8]) -> DbSortKey {
+    DbSortKey(bytes.to_vec())
+}
+
+/// Entries of partition `1` are surrounded by other partitions, so that the iteration must stop at
+/// the partition's boundaries in both directions.
+fn entries() -> DatabaseUpdates {
+    indexmap!(
+        partition_key(0) => indexmap!(
+            sort_key(&[9]) => DatabaseUpdate::Set(vec![9]),
+        ),
+        partition_key(1) => indexmap!(
+            sort_key(&[1]) => DatabaseUpdate::Set(vec![11]),
+            sort_key(&[2, 1]) => DatabaseUpdate::Set(vec![21]),
+            sort_key(&[2, 2]) => DatabaseUpdate::Set(vec![22]),
+            sort_key(&[3]) => DatabaseUpdate::Set(vec![31]),
+        ),
+        partition_key(2) => indexmap!(
+            sort_key(&[0]) => DatabaseUpdate::Set(vec![0]),
+        ),
+    )
+}
+
+fn values(iter: Box<dyn Iterator<Item = PartitionEntry> + '_>) -> Vec<u8> {
+    iter.map(|(_, value)| value[0]).collect()
+}
+
+fn assert_range_scans<S: SubstateDatabase>(substate_db: &S) {
+    assert_eq!(
+        values(substate_db.list_entries_from(&partition_key(1), None)),
+        vec![11, 21, 22, 31]
+    );
+    assert_eq!(
+        values(substate_db.list_entries_from(&partition_key(1), Some(&sort_key(&[2, 2])))),
+        vec![22, 31]
+    );
+    assert_eq!(
+        values(substate_db.list_entries_from(&partition_key(1), Some(&sort_key(&[2])))),
+        vec![21, 22, 31]
+    );
+    assert_eq!(
+        values(substate_db.list_entries_from(&partition_key(1), Some(&sort_key(&[4])))),
+        Vec::<u8>::new()
+    );
+    assert_eq!(
+        values(substate_db.list_entries_rev_from(&partition_key(1), None)),
+        vec![31, 22, 21, 11]
+    );
+    assert_eq!(
+        values(substate_db.list_entries_rev_from(&partition_key(1), Some(&sort_key(&[2, 1])))),
+        vec![21, 11]
+    );
+    assert_eq!(
+        values(substate_db.list_entries_rev_from(&partition_key(1), Some(&sort_key(&[0])))),
+        Vec::<u8>::new()
+    );
+    assert_eq!(
+        values(substate_db.list_entries_rev_from(&partition_key(2), None)),
+        vec![0]
+    );
+    assert_eq!(
+        values(substate_db.list_entries_with_prefix(&partition_key(1), &[2])),
+        vec![21, 22]
+    );
+}
+
+#[test]
+fn rocks_db_lists_entries_forward_and_in_reverse_within_a_partition() {
+    // Arrange
+    let dir = TempDatabaseDir::new("rocks-db-range-scans");
+    let mut substate_db = RocksdbSubstateStore::standard(dir.0.clone());
+    substate_db.commit(&entries());
+
+    // Act & Assert
+    assert_range_scans(&substate_db);
+}
+
+#[test]
+fn rocks_db_with_merkle_tree_lists_entries_forward_and_in_reverse_within_a_partition() {
+    // Arrange
+    let dir = TempDatabaseDir::new("rocks-db-with-merkle-tree-range-scans");
+    let mut substate_db = RocksDBWithMerkleTreeSubstateStore::standard(dir.0.clone());
+    substate_db.commit(&entries());
+
+    // Act & Assert
+    assert_range_scans(&substate_db);
+}
+
+#[test]
+fn rocks_db_reverse_listing_of_the_last_partition_stops_at_the_partition_start() {
+    // Arrange
+    let dir = TempDatabaseDir::new("rocks-db-last-partition");
+    let mut substate_db = RocksdbSubstateStore::standard(dir.0.clone());
+    // the upper bound of a partition key made only of `0xFF` bytes is still encodable, since its
+    // length prefix can be incremented
+    substate_db.commit(&indexmap!(
+        partition_key(1) => indexmap!(
+            sort_key(&[1]) => DatabaseUpdate::Set(vec![1]),
+        ),
+        DbPartitionKey(vec![u8::MAX]) => indexmap!(
+            sort_key(&[1]) => DatabaseUpdate::Set(vec![2]),
+            sort_key(&[u8::MAX]) => DatabaseUpdate::Set(vec![3]),
+        ),
+    ));
+
+    // Act & Assert
+    assert_eq!(
+        values(substate_db.list_entries_rev_from(&DbPartitionKey(vec![u8::MAX]), None)),
+        vec![3, 2]
+    );
+    assert_eq!(
+        values(substate_db.list_entries_rev_from(&partition_key(1), None)),
+        vec![1]
+    );
+}
+
+/// Commits 3 versions of partition `1`:
+/// - version 1 sets `[1] = 11` and `[2] = 21`;
+/// - version 2 sets `[1] = 12` and deletes `[2]`;
+/// - version 3 sets `[3] = 33`.
+fn commit_versions(substate_db: &mut RocksDBWithMerkleTreeSubstateStore) {
+    substate_db.commit(&indexmap!(
+        partition_key(1) => indexmap!(
+            sort_key(&[1]) => DatabaseUpdate::Set(vec![11]),
+            sort_key(&[2]) => DatabaseUpdate::Set(vec![21]),
+        ),
+    ));
+    substate_db.commit(&indexmap!(
+        partition_key(1) => indexmap!(
+            sort_key(&[1]) => DatabaseUpdate::Set(vec![12]),
+            sort_key(&[2]) => DatabaseUpdate::Delete,
+        ),
+    ));
+    substate_db.commit(&indexmap!(
+        partition_key(1) => indexmap!(
+            sort_key(&[3]) => DatabaseUpdate::Set(vec![33]),
+        ),
+    ));
+}
+
+#[test]
+fn historical_view_reads_the_state_as_it_was_at_each_version() {
+    // Arrange
+    let dir = TempDatabaseDir::new("history-unpruned");
+    let mut substate_db = RocksDBWithMerkleTreeSubstateStore::with_history(
+        dir.0.clone(),
+        StateHistoryConfig::unpruned(),
+    )
+    .unwrap();
+
+    // Act
+    commit_versions(&mut substate_db);
+
+    // Assert
+    assert_eq!(substate_db.get_current_version(), 3);
+    assert_eq!(substate_db.get_earliest_historical_version(), Some(0));
+
+    let version_0 = substate_db.historical_view(0).unwrap();
+    assert_eq!(
+        values(version_0.list_entries(&partition_key(1))),
+        Vec::<u8>::new()
+    );
+
+    let version_1 = substate_db.historical_view(1).unwrap();
+    assert_eq!(
+        version_1.get_substate(&partition_key(1), &sort_key(&[2])),
+        Some(vec![21])
+    );
+    assert_eq!(
+        values(version_1.list_entries(&partition_key(1))),
+        vec![11, 21]
+    );
+
+    let version_2 = substate_db.historical_view(2).unwrap();
+    assert_eq!(
+        version_2.get_substate(&partition_key(1), &sort_key(&[2])),
+        None
+    );
+    assert_eq!(values(version_2.list_entries(&partition_key(1))), vec![12]);
+
+    let version_3 = substate_db.historical_view(3).unwrap();
+    assert_eq!(
+        values(version_3.list_entries(&partition_key(1))),
+        vec![12, 33]
+    );
+    assert_eq!(
+        values(version_3.list_entries_rev_from(&partition_key(1), None)),
+        vec![33, 12]
+    );
+
+    assert!(substate_db.historical_view(4).is_none());
+}
+
+#[test]
+fn historical_view_of_a_version_beyond_the_retention_horizon_is_not_available() {
+    // Arrange
+    let dir = TempDatabaseDir::new("history-pruned");
+    let mut substate_db = RocksDBWithMerkleTreeSubstateStore::with_history(
+        dir.0.clone(),
+        StateHistoryConfig::retaining_versions(1),
+    )
+    .unwrap();
+
+    // Act
+    commit_versions(&mut substate_db);
+
+    // Assert
+    assert_eq!(substate_db.get_earliest_historical_version(), Some(2));
+    assert!(substate_db.historical_view(1).is_none());
+    let version_2 = substate_db.historical_view(2).unwrap();
+    assert_eq!(
+        version_2.get_substate(&partition_key(1), &sort_key(&[1])),
+        Some(vec![12])
+    );
+    assert_eq!(values(version_2.list_entries(&partition_key(1))), vec![12]);
+}
+
+#[test]
+fn history_cannot_be_enabled_on_a_non_empty_database() {
+    // Arrange
+    let dir = TempDatabaseDir::new("history-late");
+    {
+        let mut substate_db = RocksDBWithMerkleTreeSubstateStore::standard(dir.0.clone());
+        substate_db.commit(&entries());
+    }
+
+    // Act
+    let result = RocksDBWithMerkleTreeSubstateStore::with_history(
+        dir.0.clone(),
+        StateHistoryConfig::unpruned(),
+    );
+
+    // Assert
+    assert_eq!(
+        result.err(),
+        Some(StateHistoryError::DatabaseNotEmpty {
+            current_state_version: 1
+        })
+    );
+}
+
+#[test]
+fn history_cannot_be_re_enabled_after_it_was_disabled() {
+    // Arrange
+    let dir = TempDatabaseDir::new("history-disabled");
+    {
+        let mut substate_db = RocksDBWithMerkleTreeSubstateStore::with_history(
+            dir.0.clone(),
+            StateHistoryConfig::unpruned(),
+        )
+        .unwrap();
+        substate_db.commit(&entries());
+    }
+    {
+        let substate_db = RocksDBWithMerkleTreeSubstateStore::standard(dir.0.clone());
+        assert_eq!(substate_db.get_earliest_historical_version(), None);
+    }
+
+    // Act
+    let result = RocksDBWithMerkleTreeSubstateStore::with_history(
+        dir.0.clone(),
+        StateHistoryConfig::unpruned(),
+    );
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(StateHistoryError::DatabaseNotEmpty { .. })
+    ));
+}
+
+#[test]
+fn pruning_stale_tree_nodes_keeps_the_versions_within_the_retention_horizon_provable() {
+    // Arrange
+    let dir = TempDatabaseDir::new("prune-stale-tree-nodes");
+    let mut substate_db = RocksDBWithMerkleTreeSubstateStore::standard(dir.0.clone());
+    commit_versions(&mut substate_db);
+    let root_hash_2 = substate_db.get_state_root_hash(2).unwrap();
+    let root_hash_3 = substate_db.get_state_root_hash(3).unwrap();
+
+    // Act
+    let processed_stale_versions = substate_db.prune_stale_tree_nodes(2, usize::MAX);
+
+    // Assert
+    assert!(processed_stale_versions > 0);
+    assert_eq!(substate_db.prune_stale_tree_nodes(2, usize::MAX), 0);
+
+    assert_eq!(substate_db.get_state_root_hash(1), None);
+    assert!(substate_db
+        .get_substate_proof(1, &partition_key(1), &sort_key(&[1]))
+        .is_none());
+
+    assert_eq!(substate_db.get_state_root_hash(2), Some(root_hash_2));
+    assert_eq!(substate_db.get_state_root_hash(3), Some(root_hash_3));
+    for (version, root_hash) in [(2, root_hash_2), (3, root_hash_3)] {
+        substate_db
+            .get_substate_proof(version, &partition_key(1), &sort_key(&[1]))
+            .unwrap()
+            .verify(
+                &root_hash,
+                &partition_key(1),
+                &sort_key(&[1]),
+                Some(&vec![12]),
+            )
+            .unwrap();
+        substate_db
+            .get_substate_proof(version, &partition_key(1), &sort_key(&[2]))
+            .unwrap()
+            .verify(&root_hash, &partition_key(1), &sort_key(&[2]), None)
+            .unwrap();
+    }
+    substate_db
+        .get_substate_proof(3, &partition_key(1), &sort_key(&[3]))
+        .unwrap()
+        .verify(
+            &root_hash_3,
+            &partition_key(1),
+            &sort_key(&[3]),
+            Some(&vec![33]),
+        )
+        .unwrap();
+    assert_eq!(
+        values(substate_db.list_entries(&partition_key(1))),
+        vec![12, 33]
+    );
+}
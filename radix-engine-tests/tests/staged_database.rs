@@ -0,0 +1,155 @@
+use radix_engine::system::bootstrap::Bootstrapper;
+use radix_engine::transaction::*;
+use radix_engine::types::*;
+use radix_engine::vm::wasm::DefaultWasmEngine;
+use radix_engine::vm::ScryptoVm;
+use radix_engine_store_interface::interface::*;
+use radix_engine_stores::memory_db::InMemorySubstateDatabase;
+use transaction::builder::{ManifestBuilder, TransactionManifestV1};
+use transaction::model::TestTransaction;
+use transaction::signing::secp256k1::Secp256k1PrivateKey;
+
+struct StagingTestEnv {
+    scrypto_vm: ScryptoVm<DefaultWasmEngine>,
+    public_key: Secp256k1PublicKey,
+    account: ComponentAddress,
+    other_account: ComponentAddress,
+}
+
+impl StagingTestEnv {
+    fn new(database: &mut InMemorySubstateDatabase) -> Self {
+        let scrypto_vm = ScryptoVm::<DefaultWasmEngine>::default();
+        Bootstrapper::new(database, &scrypto_vm, false);
+        let public_key = Secp256k1PrivateKey::from_u64(1).unwrap().public_key();
+        let other_public_key = Secp256k1PrivateKey::from_u64(2).unwrap().public_key();
+        Self {
+            scrypto_vm,
+            public_key,
+            account: ComponentAddress::virtual_account_from_public_key(&public_key),
+            other_account: ComponentAddress::virtual_account_from_public_key(&other_public_key),
+        }
+    }
+
+    fn execute<S: SubstateDatabase>(
+        &self,
+        staged_db: &mut StagedSubstateDatabase<S>,
+        manifest: TransactionManifestV1,
+        nonce: u32,
+    ) -> TransactionReceipt {
+        let transaction = TestTransaction::new_from_nonce(manifest, nonce)
+            .prepare()
+            .unwrap();
+        staged_db.execute_and_stage(
+            &self.scrypto_vm,
+            &FeeReserveConfig::default(),
+            &ExecutionConfig::for_test_transaction(),
+            &transaction.get_executable(btreeset![NonFungibleGlobalId::from_public_key(
+                &self.public_key
+            )]),
+        )
+    }
+
+    fn create_token_manifest(&self) -> TransactionManifestV1 {
+        ManifestBuilder::new()
+            .lock_fee(FAUCET, 500u32.into())
+            .new_token_fixed(OwnerRole::None, metadata!(), 100.into())
+            .call_method(
+                self.account,
+                "try_deposit_batch_or_abort",
+                manifest_args!(ManifestExpression::EntireWorktop),
+            )
+            .build()
+    }
+
+    fn transfer_token_manifest(&self, resource_address: ResourceAddress) -> TransactionManifestV1 {
+        ManifestBuilder::new()
+            .lock_fee(FAUCET, 500u32.into())
+            .withdraw_from_account(self.account, resource_address, 30.into())
+            .call_method(
+                self.other_account,
+                "try_deposit_batch_or_abort",
+                manifest_args!(ManifestExpression::EntireWorktop),
+            )
+            .build()
+    }
+}
+
+#[test]
+fn staged_transactions_see_each_others_state_updates() {
+    // Arrange
+    let mut database = InMemorySubstateDatabase::standard();
+    let env = StagingTestEnv::new(&mut database);
+    let mut staged_db = StagedSubstateDatabase::new(&database);
+
+    // Act
+    let creation_receipt = env.execute(&mut staged_db, env.create_token_manifest(), 1);
+    let resource_address = creation_receipt
+        .expect_commit_success()
+        .new_resource_addresses()[0];
+    let transfer_receipt = env.execute(
+        &mut staged_db,
+        env.transfer_token_manifest(resource_address),
+        2,
+    );
+
+    // Assert
+    transfer_receipt.expect_commit_success();
+    assert_eq!(staged_db.stage_count(), 2);
+}
+
+#[test]
+fn rollback_drops_later_stages() {
+    // Arrange
+    let mut database = InMemorySubstateDatabase::standard();
+    let env = StagingTestEnv::new(&mut database);
+    let mut staged_db = StagedSubstateDatabase::new(&database);
+    let creation_receipt = env.execute(&mut staged_db, env.create_token_manifest(), 1);
+    let resource_address = creation_receipt
+        .expect_commit_success()
+        .new_resource_addresses()[0];
+
+    // Act
+    staged_db.rollback_to(0);
+    let transfer_receipt = env.execute(
+        &mut staged_db,
+        env.transfer_token_manifest(resource_address),
+        2,
+    );
+
+    // Assert
+    transfer_receipt.expect_commit_failure();
+    assert_eq!(staged_db.stage_count(), 1);
+}
+
+#[test]
+fn merged_staged_updates_match_sequentially_committed_updates() {
+    // Arrange
+    let mut database = InMemorySubstateDatabase::standard();
+    let env = StagingTestEnv::new(&mut database);
+    let mut staged_db = StagedSubstateDatabase::new(&database);
+    let creation_receipt = env.execute(&mut staged_db, env.create_token_manifest(), 1);
+    let resource_address = creation_receipt
+        .expect_commit_success()
+        .new_resource_addresses()[0];
+    let transfer_receipt = env.execute(
+        &mut staged_db,
+        env.transfer_token_manifest(resource_address),
+        2,
+    );
+    let mut sequentially_committed_database = database.clone();
+    for receipt in [&creation_receipt, &transfer_receipt] {
+        sequentially_committed_database.commit(
+            &receipt
+                .expect_commit_success()
+                .state_updates
+                .database_updates,
+        );
+    }
+
+    // Act
+    let database_updates = staged_db.database_updates();
+    database.commit(&database_updates);
+
+    // Assert
+    assert_eq!(database, sequentially_committed_database);
+}
@@ -0,0 +1,429 @@
+use radix_engine::track::SubstateDatabaseOverlay;
+use radix_engine::types::*;
+use radix_engine_store_interface::interface::*;
+use radix_engine_stores::hash_tree::tree_store::TypedInMemoryTreeStore;
+use radix_engine_stores::hash_tree::{put_at_next_version, SubstateHashChange};
+use radix_engine_stores::memory_db::InMemorySubstateDatabase;
+use radix_engine_stores::snapshot::*;
+use scrypto_unit::*;
+use std::io::Cursor;
+
+fn partition_key(byte: u8) -> DbPartitionKey {
+    DbPartitionKey(vec![byte])
+}
+
+fn sort_key(bytes: &[u8]) -> DbSortKey {
+    DbSortKey(bytes.to_vec())
+}
+
+fn database_with_entries() -> InMemorySubstateDatabase {
+    let mut substate_db = InMemorySubstateDatabase::standard();
+    substate_db.commit(&indexmap!(
+        partition_key(1) => indexmap!(
+            sort_key(&[1]) => DatabaseUpdate::Set(vec![11]),
+            sort_key(&[2, 1]) => DatabaseUpdate::Set(vec![21]),
+            sort_key(&[2, 2]) => DatabaseUpdate::Set(vec![22]),
+            sort_key(&[3]) => DatabaseUpdate::Set(vec![31]),
+        ),
+        partition_key(2) => indexmap!(
+            sort_key(&[0]) => DatabaseUpdate::Set(vec![0]),
+        ),
+    ));
+    substate_db
+}
+
+fn values(iter: Box<dyn Iterator<Item = PartitionEntry> + '_>) -> Vec<u8> {
+    iter.map(|(_, value)| value[0]).collect()
+}
+
+#[test]
+fn list_entries_from_starts_at_inclusive_sort_key() {
+    // Arrange
+    let substate_db = database_with_entries();
+
+    // Act & Assert
+    assert_eq!(
+        values(substate_db.list_entries_from(&partition_key(1), None)),
+        vec![11, 21, 22, 31]
+    );
+    assert_eq!(
+        values(substate_db.list_entries_from(&partition_key(1), Some(&sort_key(&[2, 2])))),
+        vec![22, 31]
+    );
+    assert_eq!(
+        values(substate_db.list_entries_from(&partition_key(1), Some(&sort_key(&[2])))),
+        vec![21, 22, 31]
+    );
+    assert_eq!(
+        values(substate_db.list_entries_from(&partition_key(1), Some(&sort_key(&[4])))),
+        Vec::<u8>::new()
+    );
+}
+
+#[test]
+fn list_entries_rev_from_iterates_in_descending_order() {
+    // Arrange
+    let substate_db = database_with_entries();
+
+    // Act & Assert
+    assert_eq!(
+        values(substate_db.list_entries_rev_from(&partition_key(1), None)),
+        vec![31, 22, 21, 11]
+    );
+    assert_eq!(
+        values(substate_db.list_entries_rev_from(&partition_key(1), Some(&sort_key(&[2, 1])))),
+        vec![21, 11]
+    );
+    assert_eq!(
+        values(substate_db.list_entries_rev_from(&partition_key(1), Some(&sort_key(&[0])))),
+        Vec::<u8>::new()
+    );
+}
+
+#[test]
+fn list_entries_with_prefix_returns_only_matching_entries() {
+    // Arrange
+    let substate_db = database_with_entries();
+
+    // Act & Assert
+    assert_eq!(
+        values(substate_db.list_entries_with_prefix(&partition_key(1), &[2])),
+        vec![21, 22]
+    );
+    assert_eq!(
+        values(Box::new(
+            substate_db
+                .list_entries_with_prefix(&partition_key(1), &[2])
+                .take(1)
+        )),
+        vec![21]
+    );
+    assert_eq!(
+        values(substate_db.list_entries_with_prefix(&partition_key(3), &[])),
+        Vec::<u8>::new()
+    );
+}
+
+#[test]
+fn overlay_applies_changes_on_top_of_underlying_database() {
+    // Arrange
+    let substate_db = database_with_entries();
+    let overlay = SubstateDatabaseOverlay::with_updates(
+        &substate_db,
+        &indexmap!(
+            partition_key(1) => indexmap!(
+                sort_key(&[0]) => DatabaseUpdate::Set(vec![1]),
+                sort_key(&[2, 1]) => DatabaseUpdate::Delete,
+                sort_key(&[3]) => DatabaseUpdate::Set(vec![33]),
+            ),
+        ),
+    );
+
+    // Act & Assert
+    assert_eq!(
+        overlay.get_substate(&partition_key(1), &sort_key(&[3])),
+        Some(vec![33])
+    );
+    assert_eq!(
+        overlay.get_substate(&partition_key(1), &sort_key(&[2, 1])),
+        None
+    );
+    assert_eq!(
+        values(overlay.list_entries(&partition_key(1))),
+        vec![1, 11, 22, 33]
+    );
+    assert_eq!(
+        values(overlay.list_entries_rev_from(&partition_key(1), Some(&sort_key(&[2, 2])))),
+        vec![22, 11, 1]
+    );
+    assert_eq!(values(overlay.list_entries(&partition_key(2))), vec![0]);
+    assert_eq!(
+        substate_db.get_substate(&partition_key(1), &sort_key(&[3])),
+        Some(vec![31])
+    );
+}
+
+#[test]
+fn snapshot_of_bootstrapped_ledger_can_be_imported_into_empty_database() {
+    // Arrange
+    let test_runner = TestRunner::builder().build();
+    let mut snapshot_bytes = Vec::new();
+    let export_outcome =
+        export_snapshot(test_runner.substate_db(), None, &mut snapshot_bytes).unwrap();
+
+    // Act
+    let mut imported_db = InMemorySubstateDatabase::standard();
+    let import_outcome =
+        import_snapshot(&mut imported_db, &mut Cursor::new(snapshot_bytes)).unwrap();
+
+    // Assert
+    assert_eq!(import_outcome, export_outcome);
+    assert_eq!(&imported_db, test_runner.substate_db());
+}
+
+#[test]
+fn snapshot_with_corrupted_entry_is_rejected() {
+    // Arrange
+    let substate_db = database_with_entries();
+    let mut snapshot_bytes = Vec::new();
+    export_snapshot(&substate_db, None, &mut snapshot_bytes).unwrap();
+    // the last byte of the last entry's value (i.e. just before the end record)
+    let end_record_length = scrypto_encode(&SnapshotRecordV1::End(SnapshotSummary {
+        partition_count: 0,
+        entry_count: 0,
+        content_hash: Hash([0; Hash::LENGTH]),
+    }))
+    .unwrap()
+    .len();
+    let corrupted_index = snapshot_bytes.len() - end_record_length - 4 - 1;
+    snapshot_bytes[corrupted_index] ^= 0xFF;
+
+    // Act
+    let mut imported_db = InMemorySubstateDatabase::standard();
+    let result = import_snapshot(&mut imported_db, &mut Cursor::new(snapshot_bytes));
+
+    // Assert
+    assert!(matches!(result, Err(SnapshotError::SummaryMismatch { .. })));
+    assert!(imported_db.list_partition_keys().next().is_none());
+}
+
+#[test]
+fn snapshot_with_tampered_header_is_rejected() {
+    // Arrange
+    let substate_db = database_with_entries();
+    let mut snapshot_bytes = Vec::new();
+    export_snapshot(
+        &substate_db,
+        Some(SnapshotStateTree {
+            state_version: 1,
+            root_hash: Hash([1; Hash::LENGTH]),
+        }),
+        &mut snapshot_bytes,
+    )
+    .unwrap();
+    // the last byte of the header's root hash (i.e. of the whole header)
+    let header_length = u32::from_be_bytes(snapshot_bytes[5..9].try_into().unwrap()) as usize;
+    snapshot_bytes[9 + header_length - 1] ^= 0xFF;
+
+    // Act
+    let result = import_snapshot(
+        &mut InMemorySubstateDatabase::standard(),
+        &mut Cursor::new(snapshot_bytes),
+    );
+
+    // Assert
+    assert!(matches!(result, Err(SnapshotError::SummaryMismatch { .. })));
+}
+
+#[test]
+fn snapshot_with_oversized_record_length_is_rejected() {
+    // Arrange
+    let substate_db = database_with_entries();
+    let mut snapshot_bytes = Vec::new();
+    export_snapshot(&substate_db, None, &mut snapshot_bytes).unwrap();
+    // the length prefix of the header
+    snapshot_bytes[5..9].copy_from_slice(&u32::MAX.to_be_bytes());
+
+    // Act
+    let result = import_snapshot(
+        &mut InMemorySubstateDatabase::standard(),
+        &mut Cursor::new(snapshot_bytes),
+    );
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(SnapshotError::RecordTooLarge(length)) if length == u32::MAX as usize
+    ));
+}
+
+#[test]
+fn truncated_snapshot_leaves_the_database_untouched() {
+    // Arrange
+    let substate_db = database_with_entries();
+    let mut snapshot_bytes = Vec::new();
+    export_snapshot(&substate_db, None, &mut snapshot_bytes).unwrap();
+    snapshot_bytes.truncate(snapshot_bytes.len() - 1);
+
+    // Act
+    let mut imported_db = InMemorySubstateDatabase::standard();
+    let result = import_snapshot(&mut imported_db, &mut Cursor::new(snapshot_bytes));
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(SnapshotError::UnexpectedEndOfSnapshot)
+    ));
+    assert!(imported_db.list_partition_keys().next().is_none());
+}
+
+#[test]
+fn snapshot_cannot_be_imported_into_non_empty_database() {
+    // Arrange
+    let mut snapshot_bytes = Vec::new();
+    export_snapshot(&database_with_entries(), None, &mut snapshot_bytes).unwrap();
+    let mut imported_db = InMemorySubstateDatabase::standard();
+    imported_db.commit(&indexmap!(
+        partition_key(3) => indexmap!(
+            sort_key(&[3]) => DatabaseUpdate::Set(vec![33]),
+        ),
+    ));
+
+    // Act
+    let result = import_snapshot(&mut imported_db, &mut Cursor::new(snapshot_bytes));
+
+    // Assert
+    assert!(matches!(result, Err(SnapshotError::TargetDatabaseNotEmpty)));
+    assert_eq!(
+        imported_db.list_partition_keys().collect::<Vec<_>>(),
+        vec![partition_key(3)]
+    );
+}
+
+#[test]
+fn snapshot_with_duplicate_partition_is_rejected() {
+    // Arrange
+    let snapshot_bytes = encode_snapshot(
+        SnapshotHeaderV1 { state_tree: None },
+        vec![
+            SnapshotRecordV1::Partition(partition_key(1)),
+            SnapshotRecordV1::Entry(sort_key(&[1]), vec![11]),
+            SnapshotRecordV1::Partition(partition_key(2)),
+            SnapshotRecordV1::Entry(sort_key(&[0]), vec![0]),
+            SnapshotRecordV1::Partition(partition_key(1)),
+            SnapshotRecordV1::Entry(sort_key(&[2]), vec![21]),
+        ],
+    );
+
+    // Act
+    let mut imported_db = InMemorySubstateDatabase::standard();
+    let result = import_snapshot(&mut imported_db, &mut Cursor::new(snapshot_bytes));
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(SnapshotError::DuplicatePartition(key)) if key == partition_key(1)
+    ));
+    assert!(imported_db.list_partition_keys().next().is_none());
+}
+
+#[test]
+fn snapshot_with_entries_out_of_order_is_rejected() {
+    for out_of_order_sort_key in [sort_key(&[1]), sort_key(&[2, 1])] {
+        // Arrange
+        let snapshot_bytes = encode_snapshot(
+            SnapshotHeaderV1 { state_tree: None },
+            vec![
+                SnapshotRecordV1::Partition(partition_key(1)),
+                SnapshotRecordV1::Entry(sort_key(&[1]), vec![11]),
+                SnapshotRecordV1::Entry(sort_key(&[2, 1]), vec![21]),
+                SnapshotRecordV1::Entry(out_of_order_sort_key.clone(), vec![0]),
+            ],
+        );
+
+        // Act
+        let mut imported_db = InMemorySubstateDatabase::standard();
+        let result = import_snapshot(&mut imported_db, &mut Cursor::new(snapshot_bytes));
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(SnapshotError::EntryOutOfOrder { partition_key: key, sort_key })
+                if key == partition_key(1) && sort_key == out_of_order_sort_key
+        ));
+        assert!(imported_db.list_partition_keys().next().is_none());
+    }
+}
+
+#[test]
+fn snapshot_with_matching_state_root_can_be_imported() {
+    // Arrange
+    let substate_db = database_with_entries();
+    let mut snapshot_bytes = Vec::new();
+    export_snapshot(
+        &substate_db,
+        Some(SnapshotStateTree {
+            state_version: 1,
+            root_hash: state_root_hash(&substate_db),
+        }),
+        &mut snapshot_bytes,
+    )
+    .unwrap();
+
+    // Act
+    let mut imported_db = InMemorySubstateDatabase::standard();
+    let result = import_snapshot(&mut imported_db, &mut Cursor::new(snapshot_bytes));
+
+    // Assert
+    assert!(result.is_ok());
+    assert_eq!(imported_db, substate_db);
+}
+
+#[test]
+fn snapshot_with_mismatched_state_root_is_rejected() {
+    // Arrange
+    let substate_db = database_with_entries();
+    let mut snapshot_bytes = Vec::new();
+    export_snapshot(
+        &substate_db,
+        Some(SnapshotStateTree {
+            state_version: 1,
+            root_hash: Hash([1; Hash::LENGTH]),
+        }),
+        &mut snapshot_bytes,
+    )
+    .unwrap();
+
+    // Act
+    let mut imported_db = InMemorySubstateDatabase::standard();
+    let result = import_snapshot(&mut imported_db, &mut Cursor::new(snapshot_bytes));
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(SnapshotError::StateRootMismatch { expected, actual })
+            if expected == Hash([1; Hash::LENGTH]) && actual == state_root_hash(&substate_db)
+    ));
+    assert!(imported_db.list_partition_keys().next().is_none());
+}
+
+/// Encodes a snapshot of the given records, terminated by their correct summary.
+fn encode_snapshot(header: SnapshotHeaderV1, records: Vec<SnapshotRecordV1>) -> Vec<u8> {
+    fn write_record(bytes: &mut Vec<u8>, record_bytes: &[u8]) {
+        bytes.extend_from_slice(&(record_bytes.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(record_bytes);
+    }
+
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&SNAPSHOT_MAGIC_BYTES);
+    bytes.push(SNAPSHOT_FORMAT_VERSION);
+    let header_bytes = scrypto_encode(&header).unwrap();
+    write_record(&mut bytes, &header_bytes);
+    let mut hasher = SnapshotHasher::new(&header_bytes);
+    for record in records {
+        let record_bytes = scrypto_encode(&record).unwrap();
+        match record {
+            SnapshotRecordV1::Partition(_) => hasher.partition(&record_bytes),
+            SnapshotRecordV1::Entry(..) => hasher.entry(&record_bytes),
+            SnapshotRecordV1::End(_) => panic!("The summary is appended automatically"),
+        }
+        write_record(&mut bytes, &record_bytes);
+    }
+    let end_record = SnapshotRecordV1::End(hasher.into_summary());
+    write_record(&mut bytes, &scrypto_encode(&end_record).unwrap());
+    bytes
+}
+
+/// Computes the root hash of a state tree holding all the entries of the given database.
+fn state_root_hash(substate_db: &InMemorySubstateDatabase) -> Hash {
+    let mut hash_changes = Vec::new();
+    for partition_key in substate_db.list_partition_keys() {
+        for (sort_key, value) in substate_db.list_entries(&partition_key) {
+            hash_changes.push(SubstateHashChange::new(
+                (partition_key.clone(), sort_key),
+                Some(hash(value)),
+            ));
+        }
+    }
+    put_at_next_version(&mut TypedInMemoryTreeStore::new(), None, hash_changes)
+}
@@ -0,0 +1,391 @@
+use radix_engine::blueprints::resource::VestingVaultError;
+use radix_engine::errors::{ApplicationError, RuntimeError, SystemModuleError};
+use radix_engine::system::system_modules::auth::AuthError;
+use radix_engine::transaction::TransactionReceipt;
+use radix_engine::types::*;
+use radix_engine_interface::blueprints::resource::*;
+use radix_engine_interface::time::Instant;
+use radix_engine_queries::query::{Accounting, ResourceAccounter, StateTreeTraverser};
+use scrypto_unit::{CustomGenesis, TestRunner};
+use transaction::builder::ManifestBuilder;
+
+#[test]
+fn nothing_can_be_withdrawn_before_the_cliff() {
+    // Arrange
+    let mut test_runner = VestingVaultTestRunner::new();
+    let vesting_vault = test_runner
+        .create_vesting_vault(epoch_schedule(10, 15, 20))
+        .expect_commit_success()
+        .new_component_addresses()[0];
+    test_runner.set_current_epoch(14);
+
+    // Act
+    let receipt = test_runner.withdraw(vesting_vault, 1.into(), true);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::VestingVaultError(
+                VestingVaultError::InsufficientVestedAmount { .. }
+            ))
+        )
+    });
+    assert_eq!(test_runner.get_locked_amount(vesting_vault), 1000.into());
+}
+
+#[test]
+fn resources_are_released_linearly_between_the_cliff_and_the_end_of_an_epoch_schedule() {
+    // Arrange
+    let mut test_runner = VestingVaultTestRunner::new();
+    let vesting_vault = test_runner
+        .create_vesting_vault(epoch_schedule(10, 15, 20))
+        .expect_commit_success()
+        .new_component_addresses()[0];
+    test_runner.set_current_epoch(16);
+
+    // Act
+    let receipt = test_runner.withdraw(vesting_vault, 600.into(), true);
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(test_runner.owner_balance(), 600.into());
+    assert_eq!(test_runner.get_locked_amount(vesting_vault), 400.into());
+    assert_eq!(test_runner.get_amount(vesting_vault), 400.into());
+    test_runner
+        .withdraw(vesting_vault, Decimal::ONE, true)
+        .expect_specific_failure(|e| {
+            matches!(
+                e,
+                RuntimeError::ApplicationError(ApplicationError::VestingVaultError(
+                    VestingVaultError::InsufficientVestedAmount { .. }
+                ))
+            )
+        });
+}
+
+#[test]
+fn everything_can_be_withdrawn_after_the_end_of_an_epoch_schedule() {
+    // Arrange
+    let mut test_runner = VestingVaultTestRunner::new();
+    let vesting_vault = test_runner
+        .create_vesting_vault(epoch_schedule(10, 15, 20))
+        .expect_commit_success()
+        .new_component_addresses()[0];
+    test_runner.set_current_epoch(20);
+
+    // Act
+    let receipt = test_runner.withdraw(vesting_vault, 1000.into(), true);
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(test_runner.owner_balance(), 1000.into());
+    assert_eq!(test_runner.get_locked_amount(vesting_vault), Decimal::ZERO);
+    assert_eq!(test_runner.get_amount(vesting_vault), Decimal::ZERO);
+}
+
+#[test]
+fn resources_are_released_linearly_between_the_cliff_and_the_end_of_a_time_schedule() {
+    // Arrange
+    let mut test_runner = VestingVaultTestRunner::new();
+    let vesting_vault = test_runner
+        .create_vesting_vault(time_schedule(0, 10, 100))
+        .expect_commit_success()
+        .new_component_addresses()[0];
+
+    // Act & Assert
+    test_runner.set_current_minute(9);
+    assert_eq!(test_runner.get_locked_amount(vesting_vault), 1000.into());
+
+    test_runner.set_current_minute(50);
+    assert_eq!(test_runner.get_locked_amount(vesting_vault), 500.into());
+    test_runner
+        .withdraw(vesting_vault, 500.into(), true)
+        .expect_commit_success();
+
+    test_runner.set_current_minute(100);
+    assert_eq!(test_runner.get_locked_amount(vesting_vault), Decimal::ZERO);
+    test_runner
+        .withdraw(vesting_vault, 500.into(), true)
+        .expect_commit_success();
+    assert_eq!(test_runner.owner_balance(), 1000.into());
+}
+
+#[test]
+fn withdraw_requires_the_owner_signature() {
+    // Arrange
+    let mut test_runner = VestingVaultTestRunner::new();
+    let vesting_vault = test_runner
+        .create_vesting_vault(epoch_schedule(0, 0, 1))
+        .expect_commit_success()
+        .new_component_addresses()[0];
+    test_runner.set_current_epoch(1);
+
+    // Act
+    let receipt = test_runner.withdraw(vesting_vault, 1.into(), false);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::SystemModuleError(SystemModuleError::AuthError(AuthError::Unauthorized(
+                ..
+            )))
+        )
+    });
+}
+
+#[test]
+fn creating_a_vesting_vault_with_an_invalid_schedule_fails() {
+    // Arrange
+    let mut test_runner = VestingVaultTestRunner::new();
+
+    // Act
+    let receipt = test_runner.create_vesting_vault(epoch_schedule(10, 20, 15));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::VestingVaultError(
+                VestingVaultError::InvalidSchedule(..)
+            ))
+        )
+    });
+}
+
+#[test]
+fn creating_a_vesting_vault_with_non_fungibles_fails() {
+    // Arrange
+    let mut test_runner = VestingVaultTestRunner::new();
+    let non_fungible_resource = test_runner
+        .test_runner
+        .create_non_fungible_resource(test_runner.account);
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .withdraw_from_account(test_runner.account, non_fungible_resource, 1.into())
+        .take_all_from_worktop(non_fungible_resource, |builder, bucket| {
+            builder.create_vesting_vault(OwnerRole::None, bucket, epoch_schedule(0, 0, 1))
+        })
+        .build();
+    let receipt = test_runner.test_runner.execute_manifest_ignoring_fee(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(
+            &test_runner.public_key,
+        )],
+    );
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::VestingVaultError(
+                VestingVaultError::NonFungibleResourcesAreNotAccepted { .. }
+            ))
+        )
+    });
+}
+
+#[test]
+fn resource_accounter_reports_the_locked_balance_of_a_vesting_vault() {
+    // Arrange
+    let mut test_runner = VestingVaultTestRunner::new();
+    let vesting_vault = test_runner
+        .create_vesting_vault(epoch_schedule(10, 15, 20))
+        .expect_commit_success()
+        .new_component_addresses()[0];
+    test_runner.set_current_epoch(16);
+
+    // Act
+    let mut accounter = ResourceAccounter::new(test_runner.test_runner.substate_db());
+    accounter.traverse(vesting_vault.as_node_id().clone());
+    let accounting = accounter.close();
+
+    // Assert
+    assert_eq!(
+        accounting.balances.get(&test_runner.resource_address),
+        Some(&Decimal::from(1000))
+    );
+    assert_eq!(
+        accounting
+            .locked_balances
+            .get(&test_runner.resource_address),
+        Some(&Decimal::from(400))
+    );
+}
+
+#[test]
+fn accounting_without_time_reports_the_locked_balance_of_a_vesting_vault_as_unknown() {
+    // Arrange
+    let mut test_runner = VestingVaultTestRunner::new();
+    let vesting_vault = test_runner
+        .create_vesting_vault(epoch_schedule(10, 15, 20))
+        .expect_commit_success()
+        .new_component_addresses()[0];
+
+    // Act
+    let mut accounting = Accounting::new();
+    StateTreeTraverser::new(test_runner.test_runner.substate_db(), &mut accounting, 100)
+        .traverse_all_descendents(None, vesting_vault.as_node_id().clone());
+
+    // Assert
+    assert_eq!(
+        accounting.balances.get(&test_runner.resource_address),
+        Some(&Decimal::from(1000))
+    );
+    assert!(accounting.locked_balances.is_empty());
+    assert_eq!(
+        accounting.unknown_locked_balances,
+        hashset!(test_runner.resource_address)
+    );
+}
+
+#[test]
+fn locked_amount_of_extreme_schedules_and_amounts_does_not_overflow() {
+    for (schedule, start, middle, end) in [
+        (
+            epoch_schedule(0, 0, u64::MAX),
+            (Instant::new(0), Epoch::of(0)),
+            (Instant::new(0), Epoch::of(u64::MAX / 2)),
+            (Instant::new(0), Epoch::of(u64::MAX)),
+        ),
+        (
+            VestingSchedule::Time {
+                start: Instant::new(i64::MIN),
+                cliff: Instant::new(i64::MIN),
+                end: Instant::new(i64::MAX),
+            },
+            (Instant::new(i64::MIN), Epoch::of(0)),
+            (Instant::new(0), Epoch::of(0)),
+            (Instant::new(i64::MAX), Epoch::of(0)),
+        ),
+    ] {
+        // Act
+        let locked_at_start = schedule.locked_amount(Decimal::MAX, start.0, start.1);
+        let locked_in_the_middle = schedule.locked_amount(Decimal::MAX, middle.0, middle.1);
+        let locked_at_end = schedule.locked_amount(Decimal::MAX, end.0, end.1);
+
+        // Assert
+        assert_eq!(locked_at_start, Decimal::MAX);
+        assert!(locked_in_the_middle > Decimal::ZERO && locked_in_the_middle < Decimal::MAX);
+        assert_eq!(locked_at_end, Decimal::ZERO);
+    }
+}
+
+fn epoch_schedule(start: u64, cliff: u64, end: u64) -> VestingSchedule {
+    VestingSchedule::Epoch {
+        start: Epoch::of(start),
+        cliff: Epoch::of(cliff),
+        end: Epoch::of(end),
+    }
+}
+
+fn time_schedule(start_minute: i64, cliff_minute: i64, end_minute: i64) -> VestingSchedule {
+    VestingSchedule::Time {
+        start: Instant::new(start_minute * 60),
+        cliff: Instant::new(cliff_minute * 60),
+        end: Instant::new(end_minute * 60),
+    }
+}
+
+struct VestingVaultTestRunner {
+    test_runner: TestRunner,
+    public_key: Secp256k1PublicKey,
+    account: ComponentAddress,
+    resource_address: ResourceAddress,
+}
+
+impl VestingVaultTestRunner {
+    pub fn new() -> Self {
+        let mut test_runner = TestRunner::builder()
+            .without_trace()
+            .with_custom_genesis(CustomGenesis::default(
+                Epoch::of(1),
+                CustomGenesis::default_consensus_manager_config(),
+            ))
+            .build();
+        let (public_key, _, account) = test_runner.new_allocated_account();
+        let resource_address = test_runner.create_fungible_resource(1000.into(), 18, account);
+
+        Self {
+            test_runner,
+            public_key,
+            account,
+            resource_address,
+        }
+    }
+
+    pub fn create_vesting_vault(&mut self, schedule: VestingSchedule) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(self.account, self.resource_address, 1000.into())
+            .take_all_from_worktop(self.resource_address, |builder, bucket| {
+                builder.create_vesting_vault(
+                    OwnerRole::Fixed(rule!(require(NonFungibleGlobalId::from_public_key(
+                        &self.public_key
+                    )))),
+                    bucket,
+                    schedule,
+                )
+            })
+            .build();
+        self.test_runner.execute_manifest_ignoring_fee(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&self.public_key)],
+        )
+    }
+
+    pub fn withdraw(
+        &mut self,
+        vesting_vault: ComponentAddress,
+        amount: Decimal,
+        sign: bool,
+    ) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_vesting_vault(vesting_vault, amount)
+            .try_deposit_batch_or_abort(self.account)
+            .build();
+        let initial_proofs = if sign {
+            vec![NonFungibleGlobalId::from_public_key(&self.public_key)]
+        } else {
+            vec![]
+        };
+        self.test_runner
+            .execute_manifest_ignoring_fee(manifest, initial_proofs)
+    }
+
+    pub fn get_amount(&mut self, vesting_vault: ComponentAddress) -> Decimal {
+        self.call_getter(vesting_vault, VESTING_VAULT_GET_AMOUNT_IDENT)
+    }
+
+    pub fn get_locked_amount(&mut self, vesting_vault: ComponentAddress) -> Decimal {
+        self.call_getter(vesting_vault, VESTING_VAULT_GET_LOCKED_AMOUNT_IDENT)
+    }
+
+    pub fn owner_balance(&mut self) -> Decimal {
+        self.test_runner
+            .account_balance(self.account, self.resource_address)
+            .unwrap_or_default()
+    }
+
+    pub fn set_current_epoch(&mut self, epoch: u64) {
+        self.test_runner.set_current_epoch(Epoch::of(epoch));
+    }
+
+    fn set_current_minute(&mut self, minutes: i64) {
+        // we use a single-round epochs, so the only possible round advance is to round 1
+        self.test_runner
+            .advance_to_round_at_timestamp(Round::of(1), minutes * 60 * 1000)
+            .expect_commit_success();
+    }
+
+    fn call_getter(&mut self, vesting_vault: ComponentAddress, method_name: &str) -> Decimal {
+        let manifest = ManifestBuilder::new()
+            .call_method(vesting_vault, method_name, manifest_args!())
+            .build();
+        let receipt = self
+            .test_runner
+            .execute_manifest_ignoring_fee(manifest, vec![]);
+        receipt.expect_commit_success().output(1)
+    }
+}
@@ -1,3 +1,7 @@
+use super::{
+    AddAuthorizedDepositorEvent, ChangeDefaultDepositRuleEvent, ConfigureResourceDepositRuleEvent,
+    RemoveAuthorizedDepositorEvent,
+};
 use crate::blueprints::util::{PresecurifiedAccessRules, SecurifiedAccessRules};
 use crate::errors::ApplicationError;
 use crate::errors::RuntimeError;
@@ -9,6 +13,8 @@ use native_sdk::resource::NativeBucket;
 use native_sdk::resource::NativeFungibleVault;
 use native_sdk::resource::NativeNonFungibleVault;
 use native_sdk::resource::NativeVault;
+use native_sdk::resource::{NativeNonFungibleProof, NativeProof};
+use native_sdk::runtime::Runtime;
 use radix_engine_interface::api::field_lock_api::LockFlags;
 use radix_engine_interface::api::node_modules::metadata::*;
 use radix_engine_interface::api::object_api::ObjectModuleId;
@@ -66,6 +72,9 @@ pub type AccountVaultIndexEntry = Option<Own>;
 pub const ACCOUNT_RESOURCE_DEPOSIT_CONFIGURATION_INDEX: CollectionIndex = 1u8;
 pub type AccountResourceDepositRuleEntry = Option<ResourceDepositRule>;
 
+pub const ACCOUNT_AUTHORIZED_DEPOSITORS_INDEX: CollectionIndex = 2u8;
+pub type AccountAuthorizedDepositorEntry = Option<()>;
+
 pub struct AccountBlueprint;
 
 impl AccountBlueprint {
@@ -313,9 +322,11 @@ impl AccountBlueprint {
         Ok(())
     }
 
-    /// Method is public to all - if the resource can't be deposited it is returned.
+    /// Method is public to all - if the resource can't be deposited it is returned. A proof of
+    /// an authorized depositor badge allows deposits which the default deposit rule rejects.
     pub fn try_deposit_or_refund<Y>(
         bucket: Bucket,
+        authorized_depositor_badge: Option<Proof>,
         api: &mut Y,
     ) -> Result<Option<Bucket>, RuntimeError>
     where
@@ -323,7 +334,12 @@ impl AccountBlueprint {
     {
         let resource_address = bucket.resource_address(api)?;
 
-        let is_deposit_allowed = Self::is_deposit_allowed(&resource_address, api)?;
+        let is_authorized_depositor = match authorized_depositor_badge {
+            Some(proof) => Self::is_authorized_depositor(proof, api)?,
+            None => false,
+        };
+        let is_deposit_allowed =
+            Self::is_deposit_allowed(&resource_address, is_authorized_depositor, api)?;
         if is_deposit_allowed {
             Self::get_vault(
                 resource_address,
@@ -345,29 +361,35 @@ impl AccountBlueprint {
     where
         Y: ClientApi<RuntimeError>,
     {
-        let can_all_be_deposited = buckets
-            .iter()
-            .map(|bucket| {
-                bucket
-                    .resource_address(api)
-                    .and_then(|resource_address| Self::is_deposit_allowed(&resource_address, api))
-            })
-            .all(|item| item == Ok(true));
+        Self::try_deposit_batch_or_refund_internal(buckets, false, api)
+    }
 
-        if can_all_be_deposited {
-            Self::deposit_batch(buckets, api)?;
-            Ok(vec![])
-        } else {
-            Ok(buckets)
-        }
+    /// Method is public to all - as [`try_deposit_batch_or_refund`](Self::try_deposit_batch_or_refund),
+    /// with a proof of an authorized depositor badge which applies to all the resources.
+    pub fn try_deposit_batch_or_refund_with_badge<Y>(
+        buckets: Vec<Bucket>,
+        authorized_depositor_badge: Proof,
+        api: &mut Y,
+    ) -> Result<Vec<Bucket>, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let is_authorized_depositor =
+            Self::is_authorized_depositor(authorized_depositor_badge, api)?;
+        Self::try_deposit_batch_or_refund_internal(buckets, is_authorized_depositor, api)
     }
 
     /// Method is public to all - if the resources can't be deposited then the execution panics.
-    pub fn try_deposit_or_abort<Y>(bucket: Bucket, api: &mut Y) -> Result<(), RuntimeError>
+    pub fn try_deposit_or_abort<Y>(
+        bucket: Bucket,
+        authorized_depositor_badge: Option<Proof>,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
     where
         Y: ClientApi<RuntimeError>,
     {
-        if let Some(bucket) = Self::try_deposit_or_refund(bucket, api)? {
+        if let Some(bucket) = Self::try_deposit_or_refund(bucket, authorized_depositor_badge, api)?
+        {
             let resource_address = bucket.resource_address(api)?;
             Err(AccountError::DepositIsDisallowed { resource_address }.into())
         } else {
@@ -385,11 +407,22 @@ impl AccountBlueprint {
         Y: ClientApi<RuntimeError>,
     {
         let buckets = Self::try_deposit_batch_or_refund(buckets, api)?;
-        if buckets.len() != 0 {
-            Err(AccountError::NotAllBucketsCouldBeDeposited.into())
-        } else {
-            Ok(())
-        }
+        Self::assert_all_deposited(buckets)
+    }
+
+    /// Method is public to all - as [`try_deposit_batch_or_abort`](Self::try_deposit_batch_or_abort),
+    /// with a proof of an authorized depositor badge which applies to all the resources.
+    pub fn try_deposit_batch_or_abort_with_badge<Y>(
+        buckets: Vec<Bucket>,
+        authorized_depositor_badge: Proof,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let buckets =
+            Self::try_deposit_batch_or_refund_with_badge(buckets, authorized_depositor_badge, api)?;
+        Self::assert_all_deposited(buckets)
     }
 
     pub fn withdraw<Y>(
@@ -566,11 +599,18 @@ impl AccountBlueprint {
         let handle = api.actor_open_field(OBJECT_HANDLE_SELF, substate_key, LockFlags::MUTABLE)?;
         let mut account = api.field_lock_read_typed::<AccountSubstate>(handle)?;
 
-        account.default_deposit_rule = default_deposit_rule;
+        account.default_deposit_rule = default_deposit_rule.clone();
 
         api.field_lock_write_typed(handle, account)?;
         api.field_lock_release(handle)?;
 
+        Runtime::emit_event(
+            api,
+            ChangeDefaultDepositRuleEvent {
+                default_deposit_rule,
+            },
+        )?;
+
         Ok(())
     }
 
@@ -608,6 +648,58 @@ impl AccountBlueprint {
                 )?;
             }
         };
+
+        Runtime::emit_event(
+            api,
+            ConfigureResourceDepositRuleEvent {
+                resource_address,
+                resource_deposit_rule: resource_deposit_configuration,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    pub fn add_authorized_depositor<Y>(
+        badge: ResourceOrNonFungible,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let encoded_key = scrypto_encode(&badge).expect("Impossible Case!");
+
+        let kv_store_entry_lock_handle = api.actor_open_key_value_entry(
+            OBJECT_HANDLE_SELF,
+            ACCOUNT_AUTHORIZED_DEPOSITORS_INDEX,
+            &encoded_key,
+            LockFlags::MUTABLE,
+        )?;
+        api.key_value_entry_set_typed(kv_store_entry_lock_handle, &())?;
+        api.key_value_entry_release(kv_store_entry_lock_handle)?;
+
+        Runtime::emit_event(api, AddAuthorizedDepositorEvent { badge })?;
+
+        Ok(())
+    }
+
+    pub fn remove_authorized_depositor<Y>(
+        badge: ResourceOrNonFungible,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let encoded_key = scrypto_encode(&badge).expect("Impossible Case!");
+
+        api.actor_remove_key_value_entry(
+            OBJECT_HANDLE_SELF,
+            ACCOUNT_AUTHORIZED_DEPOSITORS_INDEX,
+            &encoded_key,
+        )?;
+
+        Runtime::emit_event(api, RemoveAuthorizedDepositorEvent { badge })?;
+
         Ok(())
     }
 
@@ -685,8 +777,10 @@ impl AccountBlueprint {
         }
     }
 
+    /// Authorized depositors bypass the default deposit rule, but not the deny list.
     fn is_deposit_allowed<Y>(
         resource_address: &ResourceAddress,
+        is_authorized_depositor: bool,
         api: &mut Y,
     ) -> Result<bool, RuntimeError>
     where
@@ -698,6 +792,7 @@ impl AccountBlueprint {
         let is_deposit_allowed = match resource_deposit_rule {
             ResourceDepositRule::Allowed => true,
             ResourceDepositRule::Disallowed => false,
+            ResourceDepositRule::Neither if is_authorized_depositor => true,
             ResourceDepositRule::Neither => {
                 let default_deposit_rule = Self::get_account_default_deposit_rule(api)?;
                 match default_deposit_rule {
@@ -714,6 +809,97 @@ impl AccountBlueprint {
         Ok(is_deposit_allowed)
     }
 
+    /// Checks whether the proof is of one of the authorized depositor badges, i.e. of an
+    /// authorized resource or of an authorized non-fungible. Consumes the proof.
+    fn try_deposit_batch_or_refund_internal<Y>(
+        buckets: Vec<Bucket>,
+        is_authorized_depositor: bool,
+        api: &mut Y,
+    ) -> Result<Vec<Bucket>, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let can_all_be_deposited = buckets
+            .iter()
+            .map(|bucket| {
+                bucket.resource_address(api).and_then(|resource_address| {
+                    Self::is_deposit_allowed(&resource_address, is_authorized_depositor, api)
+                })
+            })
+            .all(|item| item == Ok(true));
+
+        if can_all_be_deposited {
+            Self::deposit_batch(buckets, api)?;
+            Ok(vec![])
+        } else {
+            Ok(buckets)
+        }
+    }
+
+    fn assert_all_deposited(refunded_buckets: Vec<Bucket>) -> Result<(), RuntimeError> {
+        if refunded_buckets.len() != 0 {
+            Err(AccountError::NotAllBucketsCouldBeDeposited.into())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn is_authorized_depositor<Y>(proof: Proof, api: &mut Y) -> Result<bool, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let resource_address = proof.resource_address(api)?;
+
+        let mut is_authorized_depositor = Self::is_authorized_depositor_badge(
+            &ResourceOrNonFungible::Resource(resource_address),
+            api,
+        )?;
+        if !is_authorized_depositor
+            && resource_address
+                .as_node_id()
+                .is_global_non_fungible_resource_manager()
+        {
+            for local_id in proof.non_fungible_local_ids(api)? {
+                let badge = ResourceOrNonFungible::NonFungible(NonFungibleGlobalId::new(
+                    resource_address,
+                    local_id,
+                ));
+                if Self::is_authorized_depositor_badge(&badge, api)? {
+                    is_authorized_depositor = true;
+                    break;
+                }
+            }
+        }
+
+        proof.drop(api)?;
+
+        Ok(is_authorized_depositor)
+    }
+
+    fn is_authorized_depositor_badge<Y>(
+        badge: &ResourceOrNonFungible,
+        api: &mut Y,
+    ) -> Result<bool, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let encoded_key = scrypto_encode(badge).expect("Impossible Case!");
+
+        let kv_store_entry_lock_handle = api.actor_open_key_value_entry(
+            OBJECT_HANDLE_SELF,
+            ACCOUNT_AUTHORIZED_DEPOSITORS_INDEX,
+            &encoded_key,
+            LockFlags::read_only(),
+        )?;
+
+        let entry: AccountAuthorizedDepositorEntry =
+            api.key_value_entry_get_typed(kv_store_entry_lock_handle)?;
+
+        api.key_value_entry_release(kv_store_entry_lock_handle)?;
+
+        Ok(entry.is_some())
+    }
+
     fn does_vault_exist<Y>(
         resource_address: &ResourceAddress,
         api: &mut Y,
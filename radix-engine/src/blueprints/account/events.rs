@@ -0,0 +1,23 @@
+use crate::types::*;
+use radix_engine_interface::blueprints::account::*;
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, Clone, PartialEq, Eq)]
+pub struct ChangeDefaultDepositRuleEvent {
+    pub default_deposit_rule: AccountDefaultDepositRule,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, Clone, PartialEq, Eq)]
+pub struct ConfigureResourceDepositRuleEvent {
+    pub resource_address: ResourceAddress,
+    pub resource_deposit_rule: ResourceDepositRule,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, Clone, PartialEq, Eq)]
+pub struct AddAuthorizedDepositorEvent {
+    pub badge: ResourceOrNonFungible,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, Clone, PartialEq, Eq)]
+pub struct RemoveAuthorizedDepositorEvent {
+    pub badge: ResourceOrNonFungible,
+}
@@ -0,0 +1,556 @@
+use super::{ClaimEvent, RecoverEvent, StoreEvent};
+use crate::errors::ApplicationError;
+use crate::errors::RuntimeError;
+use crate::types::*;
+use native_sdk::modules::access_rules::AccessRules;
+use native_sdk::modules::metadata::Metadata;
+use native_sdk::modules::royalty::ComponentRoyalty;
+use native_sdk::resource::NativeBucket;
+use native_sdk::resource::NativeNonFungibleBucket;
+use native_sdk::resource::NativeNonFungibleVault;
+use native_sdk::resource::NativeVault;
+use native_sdk::runtime::Runtime;
+use radix_engine_interface::api::field_lock_api::LockFlags;
+use radix_engine_interface::api::node_modules::auth::{
+    AccessRulesGetOwnerRoleInput, AccessRulesGetOwnerRoleOutput, ACCESS_RULES_GET_OWNER_ROLE_IDENT,
+};
+use radix_engine_interface::api::object_api::ObjectModuleId;
+use radix_engine_interface::api::CollectionIndex;
+use radix_engine_interface::api::{ClientApi, OBJECT_HANDLE_SELF};
+use radix_engine_interface::blueprints::account::*;
+use radix_engine_interface::blueprints::resource::Bucket;
+
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum AccountLockerError {
+    ClaimantIsNotAnAccount {
+        claimant: ComponentAddress,
+    },
+    NothingStoredForClaimant {
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress,
+    },
+    NotStoredForClaimant {
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress,
+        resources: AccountLockerResources,
+    },
+    MismatchedResources {
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress,
+    },
+    MissingVault {
+        resource_address: ResourceAddress,
+    },
+}
+
+impl From<AccountLockerError> for RuntimeError {
+    fn from(value: AccountLockerError) -> Self {
+        Self::ApplicationError(ApplicationError::AccountLockerError(value))
+    }
+}
+
+/// The vaults of the locker, one per resource, shared by all the claimants.
+pub const ACCOUNT_LOCKER_VAULT_INDEX: CollectionIndex = 0u8;
+pub type AccountLockerVaultIndexKey = ResourceAddress;
+pub type AccountLockerVaultIndexEntry = Option<Own>;
+
+/// The resources which each claimant can still claim, keyed by the claimant and the resource.
+/// An entry is removed as soon as its claimant has nothing left to claim.
+pub const ACCOUNT_LOCKER_CLAIM_INDEX: CollectionIndex = 1u8;
+pub type AccountLockerClaimIndexKey = (ComponentAddress, ResourceAddress);
+pub type AccountLockerClaimIndexEntry = Option<AccountLockerResources>;
+
+pub struct AccountLockerBlueprint;
+
+impl AccountLockerBlueprint {
+    pub fn instantiate<Y>(
+        owner_role: OwnerRole,
+        api: &mut Y,
+    ) -> Result<ComponentAddress, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let locker_id = api.new_simple_object(ACCOUNT_LOCKER_BLUEPRINT, vec![])?;
+
+        let access_rules = AccessRules::create(owner_role, btreemap!(), api)?.0;
+        let metadata = Metadata::create(api)?;
+        let royalty = ComponentRoyalty::create(ComponentRoyaltyConfig::default(), api)?;
+
+        let address = api.globalize(
+            btreemap!(
+                ObjectModuleId::Main => locker_id,
+                ObjectModuleId::AccessRules => access_rules.0,
+                ObjectModuleId::Metadata => metadata.0,
+                ObjectModuleId::Royalty => royalty.0,
+            ),
+            None,
+        )?;
+
+        Ok(ComponentAddress::new_or_panic(address.as_node_id().0))
+    }
+
+    /// Method requires auth - stores the bucket for the claimant, or, if instructed to, deposits it
+    /// into the claimant account directly and only stores what the account refuses.
+    pub fn store<Y>(
+        claimant: ComponentAddress,
+        bucket: Bucket,
+        try_direct_send: bool,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::assert_is_account(claimant)?;
+
+        let bucket = if try_direct_send {
+            Self::try_deposit_or_refund(claimant, bucket, api)?
+        } else {
+            Some(bucket)
+        };
+
+        if let Some(bucket) = bucket {
+            let resource_address = bucket.resource_address(api)?;
+            let resources = Self::resources_of(&resource_address, &bucket, api)?;
+
+            Self::update_claimable(
+                claimant,
+                resource_address,
+                |claimable| {
+                    Self::merge(claimable, resources.clone()).map(Some).ok_or(
+                        AccountLockerError::MismatchedResources {
+                            claimant,
+                            resource_address,
+                        },
+                    )
+                },
+                api,
+            )?;
+            Self::get_vault(
+                resource_address,
+                |vault, api| vault.put(bucket, api),
+                true,
+                api,
+            )?;
+
+            Runtime::emit_event(
+                api,
+                StoreEvent {
+                    claimant,
+                    resource_address,
+                    resources,
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Method requires auth - stores the given resources from the bucket for each of the claimants,
+    /// as [`store`](Self::store) does, and returns what remains of the bucket.
+    pub fn airdrop<Y>(
+        claimants: BTreeMap<ComponentAddress, AccountLockerResources>,
+        bucket: Bucket,
+        try_direct_send: bool,
+        api: &mut Y,
+    ) -> Result<Option<Bucket>, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        for (claimant, resources) in claimants {
+            let claimant_bucket = match resources {
+                AccountLockerResources::Fungible(amount) => bucket.take(amount, api)?,
+                AccountLockerResources::NonFungible(ids) => bucket.take_non_fungibles(ids, api)?,
+            };
+            Self::store(claimant, claimant_bucket, try_direct_send, api)?;
+        }
+
+        if bucket.is_empty(api)? {
+            bucket.drop_empty(api)?;
+            Ok(None)
+        } else {
+            Ok(Some(bucket))
+        }
+    }
+
+    /// Method requires auth - takes back resources which the claimant has not claimed yet.
+    pub fn recover<Y>(
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress,
+        resources: AccountLockerResources,
+        api: &mut Y,
+    ) -> Result<Bucket, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let bucket = Self::take(claimant, resource_address, resources.clone(), api)?;
+
+        Runtime::emit_event(
+            api,
+            RecoverEvent {
+                claimant,
+                resource_address,
+                resources,
+            },
+        )?;
+
+        Ok(bucket)
+    }
+
+    /// Method is public to all - the caller must satisfy the owner role of the claimant account.
+    pub fn claim<Y>(
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress,
+        resources: AccountLockerResources,
+        api: &mut Y,
+    ) -> Result<Bucket, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        Self::assert_is_account(claimant)?;
+        Self::assert_claimant_owner(claimant, api)?;
+
+        let bucket = Self::take(claimant, resource_address, resources.clone(), api)?;
+
+        Runtime::emit_event(
+            api,
+            ClaimEvent {
+                claimant,
+                resource_address,
+                resources,
+            },
+        )?;
+
+        Ok(bucket)
+    }
+
+    pub fn get_amount<Y>(
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress,
+        api: &mut Y,
+    ) -> Result<Decimal, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let key: AccountLockerClaimIndexKey = (claimant, resource_address);
+        let encoded_key = scrypto_encode(&key).expect("Impossible Case!");
+
+        let kv_store_entry_lock_handle = api.actor_open_key_value_entry(
+            OBJECT_HANDLE_SELF,
+            ACCOUNT_LOCKER_CLAIM_INDEX,
+            &encoded_key,
+            LockFlags::read_only(),
+        )?;
+
+        let entry: AccountLockerClaimIndexEntry =
+            api.key_value_entry_get_typed(kv_store_entry_lock_handle)?;
+        let amount = match entry {
+            Some(AccountLockerResources::Fungible(amount)) => amount,
+            Some(AccountLockerResources::NonFungible(ids)) => Decimal::from(ids.len()),
+            None => Decimal::ZERO,
+        };
+
+        api.key_value_entry_release(kv_store_entry_lock_handle)?;
+
+        Ok(amount)
+    }
+
+    /// Deducts the given resources from those claimable by the claimant, and takes them from the
+    /// locker's vault.
+    fn take<Y>(
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress,
+        resources: AccountLockerResources,
+        api: &mut Y,
+    ) -> Result<Bucket, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let mut taken = None;
+        Self::update_claimable(
+            claimant,
+            resource_address,
+            |claimable| {
+                let claimable = claimable.ok_or(AccountLockerError::NothingStoredForClaimant {
+                    claimant,
+                    resource_address,
+                })?;
+                let (resources, remainder) =
+                    Self::deduct(claimable, &resources).ok_or_else(|| {
+                        AccountLockerError::NotStoredForClaimant {
+                            claimant,
+                            resource_address,
+                            resources: resources.clone(),
+                        }
+                    })?;
+                taken = Some(resources);
+                Ok(Some(remainder))
+            },
+            api,
+        )?;
+
+        // Since the vault is shared by all the claimants, the non-fungibles must always be taken
+        // by their IDs, so that only those of the claimant are taken.
+        Self::get_vault(
+            resource_address,
+            |vault, api| match taken.expect("Resources taken once deducted") {
+                AccountLockerResources::Fungible(amount) => vault.take(amount, api),
+                AccountLockerResources::NonFungible(ids) => vault.take_non_fungibles(ids, api),
+            },
+            false,
+            api,
+        )
+    }
+
+    /// Adds the given resources to the claimable ones, or returns `None` if they are not of the same
+    /// kind (which cannot happen, as the kind of the stored resources only depends on the resource
+    /// type).
+    fn merge(
+        claimable: Option<AccountLockerResources>,
+        resources: AccountLockerResources,
+    ) -> Option<AccountLockerResources> {
+        match (claimable, resources) {
+            (None, resources) => Some(resources),
+            (
+                Some(AccountLockerResources::Fungible(claimable)),
+                AccountLockerResources::Fungible(amount),
+            ) => Some(AccountLockerResources::Fungible(claimable + amount)),
+            (
+                Some(AccountLockerResources::NonFungible(mut claimable)),
+                AccountLockerResources::NonFungible(ids),
+            ) => {
+                claimable.extend(ids);
+                Some(AccountLockerResources::NonFungible(claimable))
+            }
+            _ => None,
+        }
+    }
+
+    /// Resolves the requested resources against the claimable ones (i.e. an amount of
+    /// non-fungibles into their IDs), and returns them along with the remainder, or `None` if the
+    /// request exceeds what is claimable.
+    fn deduct(
+        claimable: AccountLockerResources,
+        requested: &AccountLockerResources,
+    ) -> Option<(AccountLockerResources, AccountLockerResources)> {
+        match (claimable, requested) {
+            (
+                AccountLockerResources::Fungible(claimable),
+                AccountLockerResources::Fungible(amount),
+            ) => {
+                if amount.is_negative() || *amount > claimable {
+                    return None;
+                }
+                Some((
+                    AccountLockerResources::Fungible(*amount),
+                    AccountLockerResources::Fungible(claimable - *amount),
+                ))
+            }
+            (
+                AccountLockerResources::NonFungible(mut claimable),
+                AccountLockerResources::NonFungible(ids),
+            ) => {
+                if !ids.is_subset(&claimable) {
+                    return None;
+                }
+                claimable.retain(|id| !ids.contains(id));
+                Some((
+                    AccountLockerResources::NonFungible(ids.clone()),
+                    AccountLockerResources::NonFungible(claimable),
+                ))
+            }
+            (
+                AccountLockerResources::NonFungible(mut claimable),
+                AccountLockerResources::Fungible(amount),
+            ) => {
+                if amount.is_negative()
+                    || *amount != amount.floor()
+                    || *amount > Decimal::from(claimable.len())
+                {
+                    return None;
+                }
+                let taken: BTreeSet<NonFungibleLocalId> = claimable
+                    .iter()
+                    .enumerate()
+                    .take_while(|(index, _)| Decimal::from(*index) < *amount)
+                    .map(|(_, id)| id.clone())
+                    .collect();
+                claimable.retain(|id| !taken.contains(id));
+                Some((
+                    AccountLockerResources::NonFungible(taken),
+                    AccountLockerResources::NonFungible(claimable),
+                ))
+            }
+            (AccountLockerResources::Fungible(_), AccountLockerResources::NonFungible(_)) => None,
+        }
+    }
+
+    fn is_empty(resources: &AccountLockerResources) -> bool {
+        match resources {
+            AccountLockerResources::Fungible(amount) => amount.is_zero(),
+            AccountLockerResources::NonFungible(ids) => ids.is_empty(),
+        }
+    }
+
+    fn try_deposit_or_refund<Y>(
+        claimant: ComponentAddress,
+        bucket: Bucket,
+        api: &mut Y,
+    ) -> Result<Option<Bucket>, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let rtn = api.call_method(
+            claimant.as_node_id(),
+            ACCOUNT_TRY_DEPOSIT_OR_REFUND_IDENT,
+            scrypto_encode(&AccountTryDepositOrRefundInput {
+                bucket,
+                authorized_depositor_badge: None,
+            })
+            .unwrap(),
+        )?;
+        let refund: AccountTryDepositOrRefundOutput = scrypto_decode(&rtn).unwrap();
+
+        Ok(refund)
+    }
+
+    fn resources_of<Y>(
+        resource_address: &ResourceAddress,
+        bucket: &Bucket,
+        api: &mut Y,
+    ) -> Result<AccountLockerResources, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let resources = if resource_address
+            .as_node_id()
+            .is_global_non_fungible_resource_manager()
+        {
+            AccountLockerResources::NonFungible(bucket.non_fungible_local_ids(api)?)
+        } else {
+            AccountLockerResources::Fungible(bucket.amount(api)?)
+        };
+
+        Ok(resources)
+    }
+
+    fn assert_is_account(claimant: ComponentAddress) -> Result<(), RuntimeError> {
+        if claimant.as_node_id().is_global_account() {
+            Ok(())
+        } else {
+            Err(AccountLockerError::ClaimantIsNotAnAccount { claimant }.into())
+        }
+    }
+
+    /// Asserts that the caller's auth zone satisfies the owner role of the claimant account, which
+    /// proves that the caller controls the account.
+    fn assert_claimant_owner<Y>(claimant: ComponentAddress, api: &mut Y) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let rtn = api.call_method_advanced(
+            claimant.as_node_id(),
+            false,
+            ObjectModuleId::AccessRules,
+            ACCESS_RULES_GET_OWNER_ROLE_IDENT,
+            scrypto_encode(&AccessRulesGetOwnerRoleInput {}).unwrap(),
+        )?;
+        let owner_role: AccessRulesGetOwnerRoleOutput = scrypto_decode(&rtn).unwrap();
+
+        Runtime::assert_access_rule(owner_role, api)
+    }
+
+    /// Applies the given update to the resources claimable by the claimant, and removes the entry
+    /// once nothing is left to claim (so that claimed and recovered entries do not linger).
+    fn update_claimable<F, Y>(
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress,
+        update_fn: F,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+        F: FnOnce(
+            Option<AccountLockerResources>,
+        ) -> Result<Option<AccountLockerResources>, AccountLockerError>,
+    {
+        let key: AccountLockerClaimIndexKey = (claimant, resource_address);
+        let encoded_key = scrypto_encode(&key).expect("Impossible Case!");
+
+        let kv_store_entry_lock_handle = api.actor_open_key_value_entry(
+            OBJECT_HANDLE_SELF,
+            ACCOUNT_LOCKER_CLAIM_INDEX,
+            &encoded_key,
+            LockFlags::MUTABLE,
+        )?;
+
+        let entry: AccountLockerClaimIndexEntry =
+            api.key_value_entry_get_typed(kv_store_entry_lock_handle)?;
+        let had_entry = entry.is_some();
+        let updated = match update_fn(entry) {
+            Ok(updated) => updated,
+            Err(error) => {
+                api.key_value_entry_release(kv_store_entry_lock_handle)?;
+                return Err(error.into());
+            }
+        };
+        match updated.filter(|resources| !Self::is_empty(resources)) {
+            Some(resources) => {
+                api.key_value_entry_set_typed(kv_store_entry_lock_handle, &resources)?;
+            }
+            None => {
+                if had_entry {
+                    api.key_value_entry_remove(kv_store_entry_lock_handle)?;
+                }
+            }
+        }
+        api.key_value_entry_release(kv_store_entry_lock_handle)?;
+
+        Ok(())
+    }
+
+    fn get_vault<F, Y, R>(
+        resource_address: ResourceAddress,
+        vault_fn: F,
+        create: bool,
+        api: &mut Y,
+    ) -> Result<R, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+        F: FnOnce(&mut Vault, &mut Y) -> Result<R, RuntimeError>,
+    {
+        let key: AccountLockerVaultIndexKey = resource_address;
+        let encoded_key = scrypto_encode(&key).expect("Impossible Case!");
+
+        let kv_store_entry_lock_handle = api.actor_open_key_value_entry(
+            OBJECT_HANDLE_SELF,
+            ACCOUNT_LOCKER_VAULT_INDEX,
+            &encoded_key,
+            if create {
+                LockFlags::MUTABLE
+            } else {
+                LockFlags::read_only()
+            },
+        )?;
+
+        let entry: AccountLockerVaultIndexEntry =
+            api.key_value_entry_get_typed(kv_store_entry_lock_handle)?;
+        let mut vault = match entry {
+            Some(own) => Vault(own),
+            None if create => {
+                let vault = Vault::create(resource_address, api)?;
+                api.key_value_entry_set_typed(kv_store_entry_lock_handle, &vault.0)?;
+                vault
+            }
+            // Unreachable, as long as the claimable resources are backed by the vaults
+            None => {
+                api.key_value_entry_release(kv_store_entry_lock_handle)?;
+                return Err(AccountLockerError::MissingVault { resource_address }.into());
+            }
+        };
+
+        let rtn = vault_fn(&mut vault, api)?;
+        api.key_value_entry_release(kv_store_entry_lock_handle)?;
+
+        Ok(rtn)
+    }
+}
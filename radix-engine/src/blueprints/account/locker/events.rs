@@ -0,0 +1,23 @@
+use crate::types::*;
+use radix_engine_interface::blueprints::account::*;
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, Clone, PartialEq, Eq)]
+pub struct StoreEvent {
+    pub claimant: ComponentAddress,
+    pub resource_address: ResourceAddress,
+    pub resources: AccountLockerResources,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, Clone, PartialEq, Eq)]
+pub struct ClaimEvent {
+    pub claimant: ComponentAddress,
+    pub resource_address: ResourceAddress,
+    pub resources: AccountLockerResources,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Debug, Clone, PartialEq, Eq)]
+pub struct RecoverEvent {
+    pub claimant: ComponentAddress,
+    pub resource_address: ResourceAddress,
+    pub resources: AccountLockerResources,
+}
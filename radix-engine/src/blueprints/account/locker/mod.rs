@@ -0,0 +1,5 @@
+mod blueprint;
+mod events;
+
+pub use blueprint::*;
+pub use events::*;
@@ -1,9 +1,13 @@
-use super::AccountSubstate;
+use super::{
+    AccountLockerBlueprint, AccountLockerClaimIndexKey, AccountLockerVaultIndexKey,
+    AccountSubstate, AddAuthorizedDepositorEvent, ChangeDefaultDepositRuleEvent, ClaimEvent,
+    ConfigureResourceDepositRuleEvent, RecoverEvent, RemoveAuthorizedDepositorEvent, StoreEvent,
+};
 use crate::blueprints::account::{AccountBlueprint, SECURIFY_ROLE};
 use crate::errors::ApplicationError;
 use crate::errors::RuntimeError;
-use crate::roles_template;
 use crate::types::*;
+use crate::{event_schema, roles_template};
 use native_sdk::runtime::Runtime;
 use radix_engine_interface::api::system_modules::virtualization::VirtualLazyLoadInput;
 use radix_engine_interface::api::ClientApi;
@@ -13,9 +17,9 @@ use radix_engine_interface::blueprints::package::{
     PackageDefinition,
 };
 use radix_engine_interface::schema::{
-    BlueprintCollectionSchema, BlueprintEventSchemaInit, BlueprintFunctionsSchemaInit,
-    BlueprintKeyValueStoreSchema, BlueprintSchemaInit, BlueprintStateSchemaInit, FieldSchema,
-    FunctionSchemaInit, ReceiverInfo, TypeRef,
+    BlueprintCollectionSchema, BlueprintFunctionsSchemaInit, BlueprintKeyValueStoreSchema,
+    BlueprintSchemaInit, BlueprintStateSchemaInit, FieldSchema, FunctionSchemaInit, ReceiverInfo,
+    TypeRef,
 };
 
 const ACCOUNT_CREATE_VIRTUAL_SECP256K1_EXPORT_NAME: &str = "create_virtual_secp256k1";
@@ -53,6 +57,15 @@ impl AccountNativePackage {
                 can_own: false,
             },
         ));
+        collections.push(BlueprintCollectionSchema::KeyValueStore(
+            BlueprintKeyValueStoreSchema {
+                key: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<ResourceOrNonFungible>(),
+                ),
+                value: TypeRef::Static(aggregator.add_child_type_and_descendents::<()>()),
+                can_own: false,
+            },
+        ));
 
         let mut functions = BTreeMap::new();
 
@@ -310,6 +323,36 @@ impl AccountNativePackage {
             },
         );
 
+        functions.insert(
+            ACCOUNT_ADD_AUTHORIZED_DEPOSITOR_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref_mut()),
+                input: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<AccountAddAuthorizedDepositorInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<AccountAddAuthorizedDepositorOutput>(),
+                ),
+                export: ACCOUNT_ADD_AUTHORIZED_DEPOSITOR_IDENT.to_string(),
+            },
+        );
+        functions.insert(
+            ACCOUNT_REMOVE_AUTHORIZED_DEPOSITOR_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref_mut()),
+                input: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<AccountRemoveAuthorizedDepositorInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<AccountRemoveAuthorizedDepositorOutput>(),
+                ),
+                export: ACCOUNT_REMOVE_AUTHORIZED_DEPOSITOR_IDENT.to_string(),
+            },
+        );
         functions.insert(
             ACCOUNT_TRY_DEPOSIT_OR_REFUND_IDENT.to_string(),
             FunctionSchemaInit {
@@ -340,6 +383,22 @@ impl AccountNativePackage {
             },
         );
 
+        functions.insert(
+            ACCOUNT_TRY_DEPOSIT_BATCH_OR_REFUND_WITH_BADGE_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref_mut()),
+                input: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<AccountTryDepositBatchOrRefundWithBadgeInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<AccountTryDepositBatchOrRefundWithBadgeOutput>(),
+                ),
+                export: ACCOUNT_TRY_DEPOSIT_BATCH_OR_REFUND_WITH_BADGE_IDENT.to_string(),
+            },
+        );
+
         functions.insert(
             ACCOUNT_TRY_DEPOSIT_OR_ABORT_IDENT.to_string(),
             FunctionSchemaInit {
@@ -370,11 +429,37 @@ impl AccountNativePackage {
             },
         );
 
+        functions.insert(
+            ACCOUNT_TRY_DEPOSIT_BATCH_OR_ABORT_WITH_BADGE_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref_mut()),
+                input: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<AccountTryDepositBatchOrAbortWithBadgeInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<AccountTryDepositBatchOrAbortWithBadgeOutput>(),
+                ),
+                export: ACCOUNT_TRY_DEPOSIT_BATCH_OR_ABORT_WITH_BADGE_IDENT.to_string(),
+            },
+        );
+
         let virtual_lazy_load_functions = btreemap!(
             ACCOUNT_CREATE_VIRTUAL_SECP256K1_ID => ACCOUNT_CREATE_VIRTUAL_SECP256K1_EXPORT_NAME.to_string(),
             ACCOUNT_CREATE_VIRTUAL_ED25519_ID => ACCOUNT_CREATE_VIRTUAL_ED25519_EXPORT_NAME.to_string(),
         );
 
+        let events = event_schema! {
+            aggregator,
+            [
+                ChangeDefaultDepositRuleEvent,
+                ConfigureResourceDepositRuleEvent,
+                AddAuthorizedDepositorEvent,
+                RemoveAuthorizedDepositorEvent
+            ]
+        };
+
         let schema = generate_full_schema(aggregator);
         let blueprints = btreemap!(
             ACCOUNT_BLUEPRINT.to_string() => BlueprintDefinitionInit {
@@ -394,7 +479,7 @@ impl AccountNativePackage {
                         fields,
                         collections,
                     },
-                    events: BlueprintEventSchemaInit::default(),
+                    events,
                     functions: BlueprintFunctionsSchemaInit {
                         virtual_lazy_load_functions,
                         functions,
@@ -413,6 +498,8 @@ impl AccountNativePackage {
 
                             ACCOUNT_CHANGE_DEFAULT_DEPOSIT_RULE_IDENT => [OWNER_ROLE];
                             ACCOUNT_CONFIGURE_RESOURCE_DEPOSIT_RULE_IDENT => [OWNER_ROLE];
+                            ACCOUNT_ADD_AUTHORIZED_DEPOSITOR_IDENT => [OWNER_ROLE];
+                            ACCOUNT_REMOVE_AUTHORIZED_DEPOSITOR_IDENT => [OWNER_ROLE];
                             ACCOUNT_WITHDRAW_IDENT => [OWNER_ROLE];
                             ACCOUNT_WITHDRAW_NON_FUNGIBLES_IDENT => [OWNER_ROLE];
                             ACCOUNT_LOCK_FEE_IDENT => [OWNER_ROLE];
@@ -429,17 +516,214 @@ impl AccountNativePackage {
 
                             ACCOUNT_TRY_DEPOSIT_OR_REFUND_IDENT => MethodAccessibility::Public;
                             ACCOUNT_TRY_DEPOSIT_BATCH_OR_REFUND_IDENT => MethodAccessibility::Public;
+                            ACCOUNT_TRY_DEPOSIT_BATCH_OR_REFUND_WITH_BADGE_IDENT => MethodAccessibility::Public;
                             ACCOUNT_TRY_DEPOSIT_OR_ABORT_IDENT => MethodAccessibility::Public;
                             ACCOUNT_TRY_DEPOSIT_BATCH_OR_ABORT_IDENT => MethodAccessibility::Public;
+                            ACCOUNT_TRY_DEPOSIT_BATCH_OR_ABORT_WITH_BADGE_IDENT => MethodAccessibility::Public;
                         }
                     )),
                 },
-            }
+            },
+            ACCOUNT_LOCKER_BLUEPRINT.to_string() => Self::account_locker_blueprint_definition()
         );
 
         PackageDefinition { blueprints }
     }
 
+    fn account_locker_blueprint_definition() -> BlueprintDefinitionInit {
+        let mut aggregator = TypeAggregator::<ScryptoCustomTypeKind>::new();
+
+        let fields = Vec::new();
+
+        let mut collections = Vec::new();
+        collections.push(BlueprintCollectionSchema::KeyValueStore(
+            BlueprintKeyValueStoreSchema {
+                key: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<AccountLockerVaultIndexKey>(),
+                ),
+                value: TypeRef::Static(aggregator.add_child_type_and_descendents::<Own>()),
+                can_own: true,
+            },
+        ));
+        collections.push(BlueprintCollectionSchema::KeyValueStore(
+            BlueprintKeyValueStoreSchema {
+                key: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<AccountLockerClaimIndexKey>(),
+                ),
+                value: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<AccountLockerResources>(),
+                ),
+                can_own: false,
+            },
+        ));
+
+        let mut functions = BTreeMap::new();
+
+        functions.insert(
+            ACCOUNT_LOCKER_INSTANTIATE_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: None,
+                input: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<AccountLockerInstantiateInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<AccountLockerInstantiateOutput>(),
+                ),
+                export: ACCOUNT_LOCKER_INSTANTIATE_EXPORT_NAME.to_string(),
+            },
+        );
+
+        functions.insert(
+            ACCOUNT_LOCKER_STORE_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref_mut()),
+                input: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<AccountLockerStoreInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<AccountLockerStoreOutput>(),
+                ),
+                export: ACCOUNT_LOCKER_STORE_EXPORT_NAME.to_string(),
+            },
+        );
+
+        functions.insert(
+            ACCOUNT_LOCKER_AIRDROP_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref_mut()),
+                input: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<AccountLockerAirdropInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<AccountLockerAirdropOutput>(),
+                ),
+                export: ACCOUNT_LOCKER_AIRDROP_EXPORT_NAME.to_string(),
+            },
+        );
+
+        functions.insert(
+            ACCOUNT_LOCKER_RECOVER_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref_mut()),
+                input: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<AccountLockerRecoverInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<AccountLockerRecoverOutput>(),
+                ),
+                export: ACCOUNT_LOCKER_RECOVER_EXPORT_NAME.to_string(),
+            },
+        );
+
+        functions.insert(
+            ACCOUNT_LOCKER_RECOVER_NON_FUNGIBLES_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref_mut()),
+                input: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<AccountLockerRecoverNonFungiblesInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<AccountLockerRecoverNonFungiblesOutput>(),
+                ),
+                export: ACCOUNT_LOCKER_RECOVER_NON_FUNGIBLES_EXPORT_NAME.to_string(),
+            },
+        );
+
+        functions.insert(
+            ACCOUNT_LOCKER_CLAIM_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref_mut()),
+                input: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<AccountLockerClaimInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<AccountLockerClaimOutput>(),
+                ),
+                export: ACCOUNT_LOCKER_CLAIM_EXPORT_NAME.to_string(),
+            },
+        );
+
+        functions.insert(
+            ACCOUNT_LOCKER_CLAIM_NON_FUNGIBLES_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref_mut()),
+                input: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<AccountLockerClaimNonFungiblesInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator
+                        .add_child_type_and_descendents::<AccountLockerClaimNonFungiblesOutput>(),
+                ),
+                export: ACCOUNT_LOCKER_CLAIM_NON_FUNGIBLES_EXPORT_NAME.to_string(),
+            },
+        );
+
+        functions.insert(
+            ACCOUNT_LOCKER_GET_AMOUNT_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref()),
+                input: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<AccountLockerGetAmountInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<AccountLockerGetAmountOutput>(),
+                ),
+                export: ACCOUNT_LOCKER_GET_AMOUNT_EXPORT_NAME.to_string(),
+            },
+        );
+
+        let events = event_schema! {
+            aggregator,
+            [
+                StoreEvent,
+                ClaimEvent,
+                RecoverEvent
+            ]
+        };
+
+        let schema = generate_full_schema(aggregator);
+
+        BlueprintDefinitionInit {
+            blueprint_type: BlueprintType::default(),
+            feature_set: btreeset!(),
+            dependencies: btreeset!(),
+
+            schema: BlueprintSchemaInit {
+                generics: vec![],
+                schema,
+                state: BlueprintStateSchemaInit {
+                    fields,
+                    collections,
+                },
+                events,
+                functions: BlueprintFunctionsSchemaInit {
+                    virtual_lazy_load_functions: btreemap!(),
+                    functions,
+                },
+            },
+
+            royalty_config: PackageRoyaltyConfig::default(),
+            auth_config: AuthConfig {
+                function_auth: FunctionAuth::AllowAll,
+                method_auth: MethodAuthTemplate::StaticRoles(roles_template!(
+                    methods {
+                        ACCOUNT_LOCKER_STORE_IDENT => [OWNER_ROLE];
+                        ACCOUNT_LOCKER_AIRDROP_IDENT => [OWNER_ROLE];
+                        ACCOUNT_LOCKER_RECOVER_IDENT => [OWNER_ROLE];
+                        ACCOUNT_LOCKER_RECOVER_NON_FUNGIBLES_IDENT => [OWNER_ROLE];
+
+                        ACCOUNT_LOCKER_CLAIM_IDENT => MethodAccessibility::Public;
+                        ACCOUNT_LOCKER_CLAIM_NON_FUNGIBLES_IDENT => MethodAccessibility::Public;
+                        ACCOUNT_LOCKER_GET_AMOUNT_IDENT => MethodAccessibility::Public;
+                    }
+                )),
+            },
+        }
+    }
+
     pub fn invoke_export<Y>(
         export_name: &str,
         input: &IndexedScryptoValue,
@@ -529,7 +813,11 @@ impl AccountNativePackage {
                     RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
                 })?;
 
-                let rtn = AccountBlueprint::try_deposit_or_refund(input.bucket, api)?;
+                let rtn = AccountBlueprint::try_deposit_or_refund(
+                    input.bucket,
+                    input.authorized_depositor_badge,
+                    api,
+                )?;
                 Ok(IndexedScryptoValue::from_typed(&rtn))
             }
             ACCOUNT_TRY_DEPOSIT_BATCH_OR_REFUND_IDENT => {
@@ -540,12 +828,29 @@ impl AccountNativePackage {
                 let rtn = AccountBlueprint::try_deposit_batch_or_refund(input.buckets, api)?;
                 Ok(IndexedScryptoValue::from_typed(&rtn))
             }
+            ACCOUNT_TRY_DEPOSIT_BATCH_OR_REFUND_WITH_BADGE_IDENT => {
+                let input: AccountTryDepositBatchOrRefundWithBadgeInput =
+                    input.as_typed().map_err(|e| {
+                        RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                    })?;
+
+                let rtn = AccountBlueprint::try_deposit_batch_or_refund_with_badge(
+                    input.buckets,
+                    input.authorized_depositor_badge,
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
             ACCOUNT_TRY_DEPOSIT_OR_ABORT_IDENT => {
                 let input: AccountTryDepositOrAbortInput = input.as_typed().map_err(|e| {
                     RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
                 })?;
 
-                let rtn = AccountBlueprint::try_deposit_or_abort(input.bucket, api)?;
+                let rtn = AccountBlueprint::try_deposit_or_abort(
+                    input.bucket,
+                    input.authorized_depositor_badge,
+                    api,
+                )?;
                 Ok(IndexedScryptoValue::from_typed(&rtn))
             }
             ACCOUNT_TRY_DEPOSIT_BATCH_OR_ABORT_IDENT => {
@@ -556,6 +861,19 @@ impl AccountNativePackage {
                 let rtn = AccountBlueprint::try_deposit_batch_or_abort(input.buckets, api)?;
                 Ok(IndexedScryptoValue::from_typed(&rtn))
             }
+            ACCOUNT_TRY_DEPOSIT_BATCH_OR_ABORT_WITH_BADGE_IDENT => {
+                let input: AccountTryDepositBatchOrAbortWithBadgeInput =
+                    input.as_typed().map_err(|e| {
+                        RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                    })?;
+
+                let rtn = AccountBlueprint::try_deposit_batch_or_abort_with_badge(
+                    input.buckets,
+                    input.authorized_depositor_badge,
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
             ACCOUNT_WITHDRAW_IDENT => {
                 let input: AccountWithdrawInput = input.as_typed().map_err(|e| {
                     RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
@@ -672,6 +990,126 @@ impl AccountNativePackage {
                 )?;
                 Ok(IndexedScryptoValue::from_typed(&rtn))
             }
+            ACCOUNT_ADD_AUTHORIZED_DEPOSITOR_IDENT => {
+                let AccountAddAuthorizedDepositorInput { badge } =
+                    input.as_typed().map_err(|e| {
+                        RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                    })?;
+                let rtn = AccountBlueprint::add_authorized_depositor(badge, api)?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            ACCOUNT_REMOVE_AUTHORIZED_DEPOSITOR_IDENT => {
+                let AccountRemoveAuthorizedDepositorInput { badge } =
+                    input.as_typed().map_err(|e| {
+                        RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                    })?;
+                let rtn = AccountBlueprint::remove_authorized_depositor(badge, api)?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            ACCOUNT_LOCKER_INSTANTIATE_EXPORT_NAME => {
+                let AccountLockerInstantiateInput { owner_role } =
+                    input.as_typed().map_err(|e| {
+                        RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                    })?;
+                let rtn = AccountLockerBlueprint::instantiate(owner_role, api)?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            ACCOUNT_LOCKER_STORE_EXPORT_NAME => {
+                let AccountLockerStoreInput {
+                    claimant,
+                    bucket,
+                    try_direct_send,
+                } = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = AccountLockerBlueprint::store(claimant, bucket, try_direct_send, api)?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            ACCOUNT_LOCKER_AIRDROP_EXPORT_NAME => {
+                let AccountLockerAirdropInput {
+                    claimants,
+                    bucket,
+                    try_direct_send,
+                } = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = AccountLockerBlueprint::airdrop(claimants, bucket, try_direct_send, api)?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            ACCOUNT_LOCKER_RECOVER_EXPORT_NAME => {
+                let AccountLockerRecoverInput {
+                    claimant,
+                    resource_address,
+                    amount,
+                } = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = AccountLockerBlueprint::recover(
+                    claimant,
+                    resource_address,
+                    AccountLockerResources::Fungible(amount),
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            ACCOUNT_LOCKER_RECOVER_NON_FUNGIBLES_EXPORT_NAME => {
+                let AccountLockerRecoverNonFungiblesInput {
+                    claimant,
+                    resource_address,
+                    ids,
+                } = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = AccountLockerBlueprint::recover(
+                    claimant,
+                    resource_address,
+                    AccountLockerResources::NonFungible(ids),
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            ACCOUNT_LOCKER_CLAIM_EXPORT_NAME => {
+                let AccountLockerClaimInput {
+                    claimant,
+                    resource_address,
+                    amount,
+                } = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = AccountLockerBlueprint::claim(
+                    claimant,
+                    resource_address,
+                    AccountLockerResources::Fungible(amount),
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            ACCOUNT_LOCKER_CLAIM_NON_FUNGIBLES_EXPORT_NAME => {
+                let AccountLockerClaimNonFungiblesInput {
+                    claimant,
+                    resource_address,
+                    ids,
+                } = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = AccountLockerBlueprint::claim(
+                    claimant,
+                    resource_address,
+                    AccountLockerResources::NonFungible(ids),
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            ACCOUNT_LOCKER_GET_AMOUNT_EXPORT_NAME => {
+                let AccountLockerGetAmountInput {
+                    claimant,
+                    resource_address,
+                } = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = AccountLockerBlueprint::get_amount(claimant, resource_address, api)?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
             _ => Err(RuntimeError::ApplicationError(
                 ApplicationError::ExportDoesNotExist(export_name.to_string()),
             )),
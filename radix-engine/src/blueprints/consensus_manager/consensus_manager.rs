@@ -1,8 +1,12 @@
-use super::{EpochChangeEvent, RoundChangeEvent, ValidatorCreator, ValidatorOwnerBadgeData};
+use super::{
+    EpochChangeEvent, ProtocolUpdateReadinessThresholdReachedEvent, RoundChangeEvent,
+    ValidatorCreator, ValidatorOwnerBadgeData, ValidatorProtocolUpdateReadinessSignalSubstate,
+};
 use crate::blueprints::consensus_manager::VALIDATOR_ROLE;
 use crate::errors::ApplicationError;
 use crate::errors::RuntimeError;
-use crate::kernel::kernel_api::KernelNodeApi;
+use crate::kernel::kernel_api::{KernelNodeApi, KernelSubstateApi};
+use crate::system::system_callback::SystemLockData;
 use crate::types::*;
 use native_sdk::modules::access_rules::AccessRules;
 use native_sdk::modules::metadata::Metadata;
@@ -351,7 +355,7 @@ impl ConsensusManagerBlueprint {
 
     pub(crate) fn start<Y>(api: &mut Y) -> Result<(), RuntimeError>
     where
-        Y: ClientApi<RuntimeError>,
+        Y: ClientApi<RuntimeError> + KernelSubstateApi<SystemLockData>,
     {
         let config_substate = {
             let config_handle = api.actor_open_field(
@@ -465,7 +469,7 @@ impl ConsensusManagerBlueprint {
         api: &mut Y,
     ) -> Result<(), RuntimeError>
     where
-        Y: ClientApi<RuntimeError>,
+        Y: ClientApi<RuntimeError> + KernelSubstateApi<SystemLockData>,
     {
         Self::check_non_decreasing_and_update_timestamps(proposer_timestamp_milli, api)?;
 
@@ -697,7 +701,7 @@ impl ConsensusManagerBlueprint {
         api: &mut Y,
     ) -> Result<(), RuntimeError>
     where
-        Y: ClientApi<RuntimeError>,
+        Y: ClientApi<RuntimeError> + KernelSubstateApi<SystemLockData>,
     {
         // Read previous validator set
         let validator_set_handle = api.actor_open_field(
@@ -728,6 +732,9 @@ impl ConsensusManagerBlueprint {
         let mut rewards_substate: ValidatorRewardsSubstate =
             api.field_lock_read_typed(rewards_handle)?;
 
+        // Tally protocol update readiness
+        Self::tally_protocol_update_readiness(&previous_validator_set, next_epoch, config, api)?;
+
         // Apply emissions
         Self::apply_validator_emissions_and_rewards(
             previous_validator_set,
@@ -803,6 +810,61 @@ impl ConsensusManagerBlueprint {
         Ok(())
     }
 
+    /// Tallies the stake of the given validator set by the protocol version its validators signalled
+    /// readiness for, and emits a [`ProtocolUpdateReadinessThresholdReachedEvent`] for each protocol
+    /// version reaching the configured threshold.
+    fn tally_protocol_update_readiness<Y>(
+        validator_set: &ActiveValidatorSet,
+        next_epoch: Epoch,
+        config: &ConsensusManagerConfig,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError> + KernelSubstateApi<SystemLockData>,
+    {
+        let total_stake_xrd = validator_set.total_active_stake_xrd();
+        if total_stake_xrd.is_zero() {
+            return Ok(());
+        }
+
+        let mut ready_stake_by_protocol_version: BTreeMap<String, Decimal> = BTreeMap::new();
+        // The signals are read directly from the validators' state, rather than by calling each
+        // validator, since this runs at every epoch change
+        for (address, validator) in &validator_set.validators_by_stake_desc {
+            let handle = api.kernel_open_substate(
+                address.as_node_id(),
+                MAIN_BASE_PARTITION,
+                &ValidatorField::ProtocolUpdateReadinessSignal.into(),
+                LockFlags::read_only(),
+                SystemLockData::default(),
+            )?;
+            let signal: ValidatorProtocolUpdateReadinessSignalSubstate =
+                api.kernel_read_substate(handle)?.as_typed().unwrap();
+            api.kernel_close_substate(handle)?;
+            if let Some(protocol_version_name) = signal.protocol_version_name {
+                *ready_stake_by_protocol_version
+                    .entry(protocol_version_name)
+                    .or_default() += validator.stake;
+            }
+        }
+
+        for (protocol_version_name, ready_stake_xrd) in ready_stake_by_protocol_version {
+            if ready_stake_xrd / total_stake_xrd >= config.protocol_update_readiness_threshold {
+                Runtime::emit_event(
+                    api,
+                    ProtocolUpdateReadinessThresholdReachedEvent {
+                        epoch: next_epoch,
+                        protocol_version_name,
+                        ready_stake_xrd,
+                        total_stake_xrd,
+                    },
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Emits a configured XRD amount ([`ConsensusManagerConfigSubstate.total_emission_xrd_per_epoch`])
     /// and distributes it across the given validator set, according to their stake.
     fn apply_validator_emissions_and_rewards<Y>(
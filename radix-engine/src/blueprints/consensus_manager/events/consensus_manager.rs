@@ -13,3 +13,17 @@ pub struct EpochChangeEvent {
     /// The *new* epoch's validator set.
     pub validator_set: ActiveValidatorSet,
 }
+
+/// Emitted on each epoch change at which the validators which signalled readiness for a protocol
+/// version hold at least [`ConsensusManagerConfig.protocol_update_readiness_threshold`] of the
+/// stake of the *concluded* epoch's validator set.
+#[derive(Debug, Clone, ScryptoSbor, ScryptoEvent, PartialEq, Eq)]
+pub struct ProtocolUpdateReadinessThresholdReachedEvent {
+    /// The *new* epoch's number.
+    pub epoch: Epoch,
+    pub protocol_version_name: String,
+    /// The stake of the validators which signalled readiness for the protocol version.
+    pub ready_stake_xrd: Decimal,
+    /// The stake of the concluded epoch's validator set.
+    pub total_stake_xrd: Decimal,
+}
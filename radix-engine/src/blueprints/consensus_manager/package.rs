@@ -1,6 +1,7 @@
 use crate::blueprints::consensus_manager::{ConsensusManagerBlueprint, ValidatorBlueprint};
 use crate::errors::{ApplicationError, RuntimeError};
-use crate::kernel::kernel_api::KernelNodeApi;
+use crate::kernel::kernel_api::{KernelNodeApi, KernelSubstateApi};
+use crate::system::system_callback::SystemLockData;
 use crate::{event_schema, roles_template, types::*};
 use radix_engine_interface::api::node_modules::auth::AuthAddresses;
 use radix_engine_interface::api::ClientApi;
@@ -155,7 +156,8 @@ impl ConsensusManagerNativePackage {
                 aggregator,
                 [
                     RoundChangeEvent,
-                    EpochChangeEvent
+                    EpochChangeEvent,
+                    ProtocolUpdateReadinessThresholdReachedEvent
                 ]
             };
 
@@ -503,7 +505,7 @@ impl ConsensusManagerNativePackage {
         api: &mut Y,
     ) -> Result<IndexedScryptoValue, RuntimeError>
     where
-        Y: KernelNodeApi + ClientApi<RuntimeError>,
+        Y: KernelNodeApi + KernelSubstateApi<SystemLockData> + ClientApi<RuntimeError>,
     {
         match export_name {
             CONSENSUS_MANAGER_CREATE_IDENT => {
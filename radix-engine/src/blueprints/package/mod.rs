@@ -1,3 +1,5 @@
 mod package;
+mod upgrade;
 
 pub use package::*;
+pub use upgrade::*;
@@ -1,6 +1,6 @@
 use crate::blueprints::util::SecurifiedAccessRules;
 use crate::errors::*;
-use crate::kernel::kernel_api::{KernelApi, KernelSubstateApi};
+use crate::kernel::kernel_api::{KernelApi, KernelNodeApi, KernelSubstateApi};
 use crate::system::node_init::type_info_partition;
 use crate::system::node_modules::metadata::MetadataEntrySubstate;
 use crate::system::node_modules::type_info::TypeInfoSubstate;
@@ -15,7 +15,8 @@ use native_sdk::resource::NativeVault;
 use native_sdk::resource::ResourceManager;
 use radix_engine_interface::api::node_modules::metadata::MetadataInit;
 use radix_engine_interface::api::{
-    ClientApi, ClientObjectApi, KVEntry, LockFlags, ObjectModuleId, OBJECT_HANDLE_SELF,
+    ClientApi, ClientObjectApi, CollectionIndex, KVEntry, LockFlags, ObjectModuleId,
+    OBJECT_HANDLE_SELF,
 };
 pub use radix_engine_interface::blueprints::package::*;
 use radix_engine_interface::blueprints::resource::{require, Bucket};
@@ -27,8 +28,13 @@ use radix_engine_interface::schema::{
 use sbor::LocalTypeIndex;
 
 // Import and re-export substate types
+use super::{
+    check_blueprint_upgrade, fields_equivalent, interface_schema_hashes, is_valid_migration,
+    migrated_fields, replace_owned_nodes, BlueprintIncompatibility, TypeComparison,
+};
 use crate::roles_template;
 use crate::system::node_modules::access_rules::AccessRulesNativePackage;
+use crate::system::node_modules::access_rules::OwnerRoleSubstate;
 use crate::system::node_modules::royalty::RoyaltyUtil;
 use crate::system::system::{KeyValueEntrySubstate, SubstateMutability, SystemService};
 use crate::system::system_callback::{SystemConfig, SystemLockData};
@@ -102,6 +108,34 @@ pub enum PackageError {
     InvalidMetadataKey(String),
 
     RoyaltiesNotEnabled,
+
+    BlueprintNotFound(String),
+    BlueprintVersionNotGreater {
+        blueprint: String,
+        latest_version: BlueprintVersion,
+        version: BlueprintVersion,
+    },
+    IncompatibleBlueprintUpgrade {
+        blueprint: String,
+        incompatibility: BlueprintIncompatibility,
+    },
+    InvalidMigration {
+        blueprint: String,
+        from: BlueprintVersion,
+        migration: String,
+    },
+    MissingMigration {
+        blueprint: String,
+        from: BlueprintVersion,
+    },
+    NotAComponentOfPackage(ComponentAddress),
+    OwnedObjectNotKeptByMigration {
+        field_index: u8,
+    },
+    InvalidMigrationOutput {
+        blueprint: String,
+        migration: String,
+    },
 }
 
 fn validate_package_schema<'a, I: Iterator<Item = &'a BlueprintSchemaInit>>(
@@ -864,6 +898,15 @@ impl PackageNativePackage {
                 can_own: false,
             },
         ));
+        collections.push(BlueprintCollectionSchema::KeyValueStore(
+            BlueprintKeyValueStoreSchema {
+                key: TypeRef::Static(aggregator.add_child_type_and_descendents::<String>()),
+                value: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<BlueprintUpgrades>(),
+                ),
+                can_own: false,
+            },
+        ));
 
         let mut functions = BTreeMap::new();
         functions.insert(
@@ -918,6 +961,32 @@ impl PackageNativePackage {
                 export: PACKAGE_CLAIM_ROYALTIES_IDENT.to_string(),
             },
         );
+        functions.insert(
+            PACKAGE_UPGRADE_BLUEPRINT_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref_mut()),
+                input: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<PackageUpgradeBlueprintInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<PackageUpgradeBlueprintOutput>(),
+                ),
+                export: PACKAGE_UPGRADE_BLUEPRINT_IDENT.to_string(),
+            },
+        );
+        functions.insert(
+            PACKAGE_UPGRADE_COMPONENT_IDENT.to_string(),
+            FunctionSchemaInit {
+                receiver: Some(ReceiverInfo::normal_ref()),
+                input: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<PackageUpgradeComponentInput>(),
+                ),
+                output: TypeRef::Static(
+                    aggregator.add_child_type_and_descendents::<PackageUpgradeComponentOutput>(),
+                ),
+                export: PACKAGE_UPGRADE_COMPONENT_IDENT.to_string(),
+            },
+        );
 
         let schema = generate_full_schema(aggregator);
         let blueprints = btreemap!(
@@ -961,6 +1030,9 @@ impl PackageNativePackage {
                             },
                             methods {
                                 PACKAGE_CLAIM_ROYALTIES_IDENT => [SECURIFY_OWNER_ROLE];
+                                PACKAGE_UPGRADE_BLUEPRINT_IDENT => [SECURIFY_OWNER_ROLE];
+                                // Checks the owner role of the component
+                                PACKAGE_UPGRADE_COMPONENT_IDENT => MethodAccessibility::Public;
                             }
                         },
                     ),
@@ -977,7 +1049,7 @@ impl PackageNativePackage {
         api: &mut Y,
     ) -> Result<IndexedScryptoValue, RuntimeError>
     where
-        Y: ClientApi<RuntimeError>,
+        Y: ClientApi<RuntimeError> + KernelNodeApi + KernelSubstateApi<SystemLockData>,
     {
         match export_name {
             PACKAGE_PUBLISH_NATIVE_IDENT => {
@@ -1027,6 +1099,27 @@ impl PackageNativePackage {
                 let rtn = PackageRoyaltyNativeBlueprint::claim_royalties(api)?;
                 Ok(IndexedScryptoValue::from_typed(&rtn))
             }
+            PACKAGE_UPGRADE_BLUEPRINT_IDENT => {
+                let input: PackageUpgradeBlueprintInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = PackageUpgradeNativeBlueprint::upgrade_blueprint(
+                    input.blueprint,
+                    input.version,
+                    input.code,
+                    input.definition,
+                    input.migrations,
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            PACKAGE_UPGRADE_COMPONENT_IDENT => {
+                let input: PackageUpgradeComponentInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+                let rtn = PackageUpgradeNativeBlueprint::upgrade_component(input.component, api)?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
             _ => Err(RuntimeError::ApplicationError(
                 ApplicationError::ExportDoesNotExist(export_name.to_string()),
             )),
@@ -1375,6 +1468,508 @@ impl PackageRoyaltyNativeBlueprint {
     }
 }
 
+const PACKAGE_BLUEPRINTS_COLLECTION_INDEX: CollectionIndex = 0u8;
+const PACKAGE_BLUEPRINT_DEPENDENCIES_COLLECTION_INDEX: CollectionIndex = 1u8;
+const PACKAGE_SCHEMAS_COLLECTION_INDEX: CollectionIndex = 2u8;
+const PACKAGE_ROYALTY_COLLECTION_INDEX: CollectionIndex = 3u8;
+const PACKAGE_AUTH_TEMPLATE_COLLECTION_INDEX: CollectionIndex = 4u8;
+const PACKAGE_VM_TYPE_COLLECTION_INDEX: CollectionIndex = 5u8;
+const PACKAGE_ORIGINAL_CODE_COLLECTION_INDEX: CollectionIndex = 6u8;
+const PACKAGE_INSTRUMENTED_CODE_COLLECTION_INDEX: CollectionIndex = 7u8;
+const PACKAGE_BLUEPRINT_UPGRADES_COLLECTION_INDEX: CollectionIndex = 8u8;
+
+pub struct PackageUpgradeNativeBlueprint;
+
+impl PackageUpgradeNativeBlueprint {
+    /// Adds a new version of a blueprint, which must be compatible with the latest version.
+    /// Components are only upgraded to it through `upgrade_component`.
+    pub(crate) fn upgrade_blueprint<Y>(
+        blueprint: String,
+        version: BlueprintVersion,
+        code: Vec<u8>,
+        definition_init: BlueprintDefinitionInit,
+        migrations: BTreeMap<BlueprintVersion, String>,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let mut upgrades = Self::get_upgrades(&blueprint, api)?;
+        let latest_version = upgrades.latest_version();
+        if version <= latest_version {
+            return Err(RuntimeError::ApplicationError(
+                ApplicationError::PackageError(PackageError::BlueprintVersionNotGreater {
+                    blueprint,
+                    latest_version,
+                    version,
+                }),
+            ));
+        }
+
+        let old_definition = Self::get_definition(&blueprint, latest_version, api)?;
+        let old_schemas = Self::get_schemas(&old_definition.interface, api)?;
+
+        let definition = PackageDefinition {
+            blueprints: btreemap!(blueprint.clone() => definition_init),
+        };
+        validate_royalties(&definition, api)?;
+        let package_structure = PackageNativePackage::validate_and_build_package_structure(
+            definition,
+            VmType::ScryptoV1,
+            code,
+        )?;
+        let new_definition = package_structure.definitions.get(&blueprint).unwrap();
+
+        check_blueprint_upgrade(
+            &old_definition.interface,
+            &old_schemas,
+            &new_definition.interface,
+            &package_structure.schemas,
+            migrations.contains_key(&latest_version),
+        )
+        .map_err(|incompatibility| {
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::IncompatibleBlueprintUpgrade {
+                    blueprint: blueprint.clone(),
+                    incompatibility,
+                },
+            ))
+        })?;
+
+        for (from, migration) in &migrations {
+            let valid = upgrades.contains_version(from) && {
+                let from_definition = Self::get_definition(&blueprint, *from, api)?;
+                let from_schemas = Self::get_schemas(&from_definition.interface, api)?;
+                match (
+                    new_definition.interface.functions.get(migration),
+                    &from_definition.interface.state.fields,
+                    &new_definition.interface.state.fields,
+                ) {
+                    (Some(function), Some((_, from_fields)), Some((_, to_fields))) => {
+                        is_valid_migration(
+                            function,
+                            from_fields,
+                            &from_schemas,
+                            to_fields,
+                            &package_structure.schemas,
+                        )
+                    }
+                    _ => false,
+                }
+            };
+            if !valid {
+                return Err(RuntimeError::ApplicationError(
+                    ApplicationError::PackageError(PackageError::InvalidMigration {
+                        blueprint,
+                        from: *from,
+                        migration: migration.clone(),
+                    }),
+                ));
+            }
+        }
+
+        let key = scrypto_encode(&BlueprintVersionKey::new(&blueprint, version)).unwrap();
+        for (blueprint, definition) in &package_structure.definitions {
+            Self::insert_entry(PACKAGE_BLUEPRINTS_COLLECTION_INDEX, &key, definition, api)?;
+            Self::insert_entry(
+                PACKAGE_BLUEPRINT_DEPENDENCIES_COLLECTION_INDEX,
+                &key,
+                package_structure.dependencies.get(blueprint).unwrap(),
+                api,
+            )?;
+            Self::insert_entry(
+                PACKAGE_ROYALTY_COLLECTION_INDEX,
+                &key,
+                package_structure.package_royalties.get(blueprint).unwrap(),
+                api,
+            )?;
+            Self::insert_entry(
+                PACKAGE_AUTH_TEMPLATE_COLLECTION_INDEX,
+                &key,
+                package_structure.auth_configs.get(blueprint).unwrap(),
+                api,
+            )?;
+        }
+        // Schemas and code are keyed by hash, and may be shared with previous versions
+        for (hash, schema) in &package_structure.schemas {
+            Self::insert_entry_if_absent(PACKAGE_SCHEMAS_COLLECTION_INDEX, hash, schema, api)?;
+        }
+        for (hash, vm_type) in &package_structure.vm_type {
+            Self::insert_entry_if_absent(PACKAGE_VM_TYPE_COLLECTION_INDEX, hash, vm_type, api)?;
+        }
+        for (hash, code) in &package_structure.original_code {
+            Self::insert_entry_if_absent(PACKAGE_ORIGINAL_CODE_COLLECTION_INDEX, hash, code, api)?;
+        }
+        for (hash, code) in &package_structure.instrumented_code {
+            Self::insert_entry_if_absent(
+                PACKAGE_INSTRUMENTED_CODE_COLLECTION_INDEX,
+                hash,
+                code,
+                api,
+            )?;
+        }
+
+        upgrades.upgrades.push(BlueprintUpgrade {
+            version,
+            migrations,
+        });
+        let handle = api.actor_open_key_value_entry(
+            OBJECT_HANDLE_SELF,
+            PACKAGE_BLUEPRINT_UPGRADES_COLLECTION_INDEX,
+            &scrypto_encode(&blueprint).unwrap(),
+            LockFlags::MUTABLE,
+        )?;
+        api.key_value_entry_set_typed(handle, &upgrades)?;
+        api.key_value_entry_release(handle)?;
+
+        Ok(())
+    }
+
+    /// Upgrades a component of this package to the latest version of its blueprint, migrating its
+    /// fields if their types changed. Requires the owner role of the component.
+    ///
+    /// The objects owned by the fields can't leave the component, so the migration is given
+    /// placeholders of them (see [`Self::create_placeholder`]), which it must return in the same
+    /// fields; they are then replaced back with the objects.
+    pub(crate) fn upgrade_component<Y>(
+        component: ComponentAddress,
+        api: &mut Y,
+    ) -> Result<BlueprintVersion, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError> + KernelNodeApi + KernelSubstateApi<SystemLockData>,
+    {
+        let package_address =
+            PackageAddress::new_or_panic(api.actor_get_global_address()?.into_node_id().0);
+        let node_id = component.as_node_id();
+        let mut object_info = api.get_object_info(node_id)?;
+        if !object_info.global || object_info.blueprint_id.package_address != package_address {
+            return Err(RuntimeError::ApplicationError(
+                ApplicationError::PackageError(PackageError::NotAComponentOfPackage(component)),
+            ));
+        }
+
+        let handle = api.kernel_open_substate(
+            node_id,
+            ACCESS_RULES_BASE_PARTITION
+                .at_offset(ACCESS_RULES_FIELDS_PARTITION_OFFSET)
+                .unwrap(),
+            &SubstateKey::Field(0u8),
+            LockFlags::read_only(),
+            SystemLockData::default(),
+        )?;
+        let owner_role: OwnerRoleSubstate = api.kernel_read_substate(handle)?.as_typed().unwrap();
+        api.kernel_close_substate(handle)?;
+        api.assert_access_rule(owner_role.owner_role_entry.rule)?;
+
+        let blueprint = object_info.blueprint_id.blueprint_name.clone();
+        let upgrades = Self::get_upgrades(&blueprint, api)?;
+        let latest_version = upgrades.latest_version();
+        if object_info.version == latest_version {
+            return Ok(latest_version);
+        }
+
+        let old_definition = Self::get_definition(&blueprint, object_info.version, api)?;
+        let new_definition = Self::get_definition(&blueprint, latest_version, api)?;
+        let migration = upgrades
+            .get(&latest_version)
+            .and_then(|upgrade| upgrade.migrations.get(&object_info.version))
+            .cloned();
+
+        match migration {
+            Some(migration) => {
+                let num_fields = old_definition
+                    .interface
+                    .state
+                    .fields
+                    .as_ref()
+                    .map_or(0, |(_, fields)| fields.len());
+                let mut fields = Vec::new();
+                // The placeholders of the owned objects, and the fields owning them
+                let mut placeholders = index_map_new();
+                let mut owning_fields = index_map_new();
+                for index in 0..num_fields {
+                    let handle = api.kernel_open_substate(
+                        node_id,
+                        MAIN_BASE_PARTITION,
+                        &SubstateKey::Field(index as u8),
+                        LockFlags::read_only(),
+                        SystemLockData::default(),
+                    )?;
+                    let field = api.kernel_read_substate(handle)?.clone();
+                    // The owned objects are only visible while the field is open
+                    for owned_node in field.owned_nodes() {
+                        let placeholder = Self::create_placeholder(owned_node, api)?;
+                        placeholders.insert(*owned_node, placeholder);
+                        owning_fields.insert(placeholder, index);
+                    }
+                    api.kernel_close_substate(handle)?;
+                    let mut field = field.to_scrypto_value();
+                    replace_owned_nodes(&mut field, &placeholders);
+                    fields.push(field);
+                }
+
+                let rtn = api.call_function(
+                    package_address,
+                    &blueprint,
+                    &migration,
+                    scrypto_encode(&ScryptoValue::Tuple { fields }).unwrap(),
+                )?;
+                let rtn = IndexedScryptoValue::from_vec(rtn).unwrap();
+                let num_migrated_fields = new_definition
+                    .interface
+                    .state
+                    .fields
+                    .as_ref()
+                    .map_or(0, |(_, fields)| fields.len());
+                let mut fields = migrated_fields(rtn.to_scrypto_value(), num_migrated_fields)
+                    .ok_or_else(|| {
+                        RuntimeError::ApplicationError(ApplicationError::PackageError(
+                            PackageError::InvalidMigrationOutput {
+                                blueprint: blueprint.clone(),
+                                migration: migration.clone(),
+                            },
+                        ))
+                    })?;
+
+                let objects = placeholders
+                    .iter()
+                    .map(|(owned_node, placeholder)| (*placeholder, *owned_node))
+                    .collect();
+                let mut kept_placeholders = index_map_new();
+                for (index, field) in fields.iter_mut().enumerate() {
+                    for owned_node in
+                        IndexedScryptoValue::from_scrypto_value(field.clone()).owned_nodes()
+                    {
+                        if owning_fields.contains_key(owned_node) {
+                            kept_placeholders.insert(*owned_node, index);
+                        }
+                    }
+                    replace_owned_nodes(field, &objects);
+                }
+                // The objects are in the store, so they can't be dropped nor moved to another field
+                for (placeholder, index) in &owning_fields {
+                    if kept_placeholders.get(placeholder) != Some(index) {
+                        return Err(RuntimeError::ApplicationError(
+                            ApplicationError::PackageError(
+                                PackageError::OwnedObjectNotKeptByMigration {
+                                    field_index: *index as u8,
+                                },
+                            ),
+                        ));
+                    }
+                }
+                for placeholder in placeholders.values() {
+                    api.kernel_drop_node(placeholder)?;
+                }
+
+                for (index, field) in fields.into_iter().enumerate() {
+                    let handle = api.kernel_open_substate_with_default(
+                        node_id,
+                        MAIN_BASE_PARTITION,
+                        &SubstateKey::Field(index as u8),
+                        LockFlags::MUTABLE,
+                        Some(IndexedScryptoValue::unit),
+                        SystemLockData::default(),
+                    )?;
+                    api.kernel_write_substate(
+                        handle,
+                        IndexedScryptoValue::from_scrypto_value(field),
+                    )?;
+                    api.kernel_close_substate(handle)?;
+                }
+            }
+            None => {
+                let old_schemas = Self::get_schemas(&old_definition.interface, api)?;
+                let new_schemas = Self::get_schemas(&new_definition.interface, api)?;
+                let equivalent = match (
+                    &old_definition.interface.state.fields,
+                    &new_definition.interface.state.fields,
+                ) {
+                    (Some((_, old_fields)), Some((_, new_fields))) => fields_equivalent(
+                        &TypeComparison::new(&old_schemas, &new_schemas),
+                        old_fields,
+                        new_fields,
+                    )
+                    .unwrap_or(false),
+                    (None, None) => true,
+                    _ => false,
+                };
+                if !equivalent {
+                    return Err(RuntimeError::ApplicationError(
+                        ApplicationError::PackageError(PackageError::MissingMigration {
+                            blueprint,
+                            from: object_info.version,
+                        }),
+                    ));
+                }
+            }
+        }
+
+        object_info.version = latest_version;
+        let handle = api.kernel_open_substate(
+            node_id,
+            TYPE_INFO_FIELD_PARTITION,
+            &TypeInfoField::TypeInfo.into(),
+            LockFlags::MUTABLE,
+            SystemLockData::default(),
+        )?;
+        api.kernel_write_substate(
+            handle,
+            IndexedScryptoValue::from_typed(&TypeInfoSubstate::Object(object_info)),
+        )?;
+        api.kernel_close_substate(handle)?;
+
+        Ok(latest_version)
+    }
+
+    /// Creates an empty object standing in for an object owned by a component during its
+    /// migration. It has the entity type and type info of the object, so that it passes the
+    /// validation of the migration's input and output, but none of its state.
+    fn create_placeholder<Y>(node_id: &NodeId, api: &mut Y) -> Result<NodeId, RuntimeError>
+    where
+        Y: KernelNodeApi + KernelSubstateApi<SystemLockData>,
+    {
+        let handle = api.kernel_open_substate(
+            node_id,
+            TYPE_INFO_FIELD_PARTITION,
+            &TypeInfoField::TypeInfo.into(),
+            LockFlags::read_only(),
+            SystemLockData::default(),
+        )?;
+        let type_info = api.kernel_read_substate(handle)?.clone();
+        api.kernel_close_substate(handle)?;
+
+        let placeholder = api.kernel_allocate_node_id(
+            node_id
+                .entity_type()
+                .expect("Owned objects have an entity type"),
+        )?;
+        api.kernel_create_node(
+            placeholder,
+            btreemap!(
+                TYPE_INFO_FIELD_PARTITION => btreemap!(
+                    TypeInfoField::TypeInfo.into() => type_info
+                )
+            ),
+        )?;
+        Ok(placeholder)
+    }
+
+    fn get_upgrades<Y>(blueprint: &str, api: &mut Y) -> Result<BlueprintUpgrades, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let handle = api.actor_open_key_value_entry(
+            OBJECT_HANDLE_SELF,
+            PACKAGE_BLUEPRINT_UPGRADES_COLLECTION_INDEX,
+            &scrypto_encode(blueprint).unwrap(),
+            LockFlags::read_only(),
+        )?;
+        let upgrades: Option<BlueprintUpgrades> = api.key_value_entry_get_typed(handle)?;
+        api.key_value_entry_release(handle)?;
+
+        Ok(upgrades.unwrap_or_default())
+    }
+
+    fn get_definition<Y>(
+        blueprint: &str,
+        version: BlueprintVersion,
+        api: &mut Y,
+    ) -> Result<BlueprintDefinition, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let handle = api.actor_open_key_value_entry(
+            OBJECT_HANDLE_SELF,
+            PACKAGE_BLUEPRINTS_COLLECTION_INDEX,
+            &scrypto_encode(&BlueprintVersionKey::new(blueprint, version)).unwrap(),
+            LockFlags::read_only(),
+        )?;
+        let definition: Option<BlueprintDefinition> = api.key_value_entry_get_typed(handle)?;
+        api.key_value_entry_release(handle)?;
+
+        definition.ok_or_else(|| {
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::BlueprintNotFound(blueprint.to_string()),
+            ))
+        })
+    }
+
+    fn get_schemas<Y>(
+        interface: &BlueprintInterface,
+        api: &mut Y,
+    ) -> Result<BTreeMap<Hash, ScryptoSchema>, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let mut schemas = BTreeMap::new();
+        for hash in interface_schema_hashes(interface) {
+            let handle = api.actor_open_key_value_entry(
+                OBJECT_HANDLE_SELF,
+                PACKAGE_SCHEMAS_COLLECTION_INDEX,
+                &scrypto_encode(&hash).unwrap(),
+                LockFlags::read_only(),
+            )?;
+            let schema: Option<ScryptoSchema> = api.key_value_entry_get_typed(handle)?;
+            api.key_value_entry_release(handle)?;
+            if let Some(schema) = schema {
+                schemas.insert(hash, schema);
+            }
+        }
+
+        Ok(schemas)
+    }
+
+    fn insert_entry<Y, V: ScryptoEncode>(
+        collection_index: CollectionIndex,
+        key: &Vec<u8>,
+        value: &V,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let handle = api.actor_open_key_value_entry(
+            OBJECT_HANDLE_SELF,
+            collection_index,
+            key,
+            LockFlags::MUTABLE,
+        )?;
+        api.key_value_entry_set_typed(handle, value)?;
+        api.key_value_entry_freeze(handle)?;
+        api.key_value_entry_release(handle)?;
+
+        Ok(())
+    }
+
+    fn insert_entry_if_absent<Y, V: ScryptoEncode>(
+        collection_index: CollectionIndex,
+        hash: &Hash,
+        value: &V,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let key = scrypto_encode(hash).unwrap();
+        // Existing entries are frozen, so can't be opened for writing
+        let handle = api.actor_open_key_value_entry(
+            OBJECT_HANDLE_SELF,
+            collection_index,
+            &key,
+            LockFlags::read_only(),
+        )?;
+        let existing: Option<ScryptoValue> = api.key_value_entry_get_typed(handle)?;
+        api.key_value_entry_release(handle)?;
+
+        if existing.is_none() {
+            Self::insert_entry(collection_index, &key, value, api)?;
+        }
+
+        Ok(())
+    }
+}
+
 pub struct PackageAuthNativeBlueprint;
 
 impl PackageAuthNativeBlueprint {
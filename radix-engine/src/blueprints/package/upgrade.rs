@@ -0,0 +1,429 @@
+use crate::types::*;
+use radix_engine_interface::blueprints::package::*;
+use radix_engine_interface::schema::{BlueprintCollectionSchema, Condition, FieldSchema};
+
+/// Why a new version of a blueprint can't replace the previous one.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum BlueprintIncompatibility {
+    BlueprintTypeChanged,
+    GenericsChanged,
+    FeatureRemoved(String),
+    FunctionRemoved(String),
+    FunctionSignatureChanged(String),
+    EventRemoved(String),
+    EventTypeChanged(String),
+    FieldsAddedOrRemoved,
+    FieldRemoved(u8),
+    FieldConditionChanged(u8),
+    /// The types of the fields changed, but no migration from the previous version is declared.
+    FieldsChangedWithoutMigration,
+    CollectionRemoved(u8),
+    CollectionChanged(u8),
+}
+
+/// Returns the hashes of the schemas the types of a blueprint interface are defined in.
+pub fn interface_schema_hashes(interface: &BlueprintInterface) -> BTreeSet<Hash> {
+    let mut pointers = Vec::new();
+    if let Some((_, fields)) = &interface.state.fields {
+        pointers.extend(fields.iter().map(|field| &field.field));
+    }
+    for (_, collection) in &interface.state.collections {
+        if let BlueprintCollectionSchema::KeyValueStore(kv_store) = collection {
+            pointers.push(&kv_store.key);
+            pointers.push(&kv_store.value);
+        }
+    }
+    for function in interface.functions.values() {
+        pointers.push(&function.input);
+        pointers.push(&function.output);
+    }
+    pointers.extend(interface.events.values());
+
+    pointers
+        .into_iter()
+        .filter_map(|pointer| match pointer {
+            TypePointer::Package(schema_hash, _) => Some(*schema_hash),
+            TypePointer::Instance(..) => None,
+        })
+        .collect()
+}
+
+/// Compares types across two sets of schemas. Types are equivalent if their values have the same
+/// SBOR encoding and validation; the names of types, fields and variants are ignored.
+pub struct TypeComparison<'s> {
+    old_schemas: &'s BTreeMap<Hash, ScryptoSchema>,
+    new_schemas: &'s BTreeMap<Hash, ScryptoSchema>,
+}
+
+impl<'s> TypeComparison<'s> {
+    pub fn new(
+        old_schemas: &'s BTreeMap<Hash, ScryptoSchema>,
+        new_schemas: &'s BTreeMap<Hash, ScryptoSchema>,
+    ) -> Self {
+        Self {
+            old_schemas,
+            new_schemas,
+        }
+    }
+
+    pub fn pointers_equivalent(&self, old: &TypePointer, new: &TypePointer) -> bool {
+        match (old, new) {
+            (
+                TypePointer::Package(old_hash, old_index),
+                TypePointer::Package(new_hash, new_index),
+            ) => match (
+                self.old_schemas.get(old_hash),
+                self.new_schemas.get(new_hash),
+            ) {
+                (Some(old_schema), Some(new_schema)) => {
+                    types_equivalent(old_schema, *old_index, new_schema, *new_index)
+                }
+                _ => false,
+            },
+            (TypePointer::Instance(old_index), TypePointer::Instance(new_index)) => {
+                old_index == new_index
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the type of `new` is a tuple of the types of `old`.
+    pub fn is_tuple_of(&self, old: &[FieldSchema<TypePointer>], new: &TypePointer) -> bool {
+        let (new_schema, new_index) = match new {
+            TypePointer::Package(new_hash, new_index) => match self.new_schemas.get(new_hash) {
+                Some(new_schema) => (new_schema, *new_index),
+                None => return false,
+            },
+            TypePointer::Instance(..) => return false,
+        };
+        let field_types = match new_schema.resolve_type_kind(new_index) {
+            Some(TypeKind::Tuple { field_types }) => field_types,
+            _ => return false,
+        };
+
+        field_types.len() == old.len()
+            && old
+                .iter()
+                .zip(field_types)
+                .all(|(old_field, new_index)| match &old_field.field {
+                    TypePointer::Package(old_hash, old_index) => {
+                        match self.old_schemas.get(old_hash) {
+                            Some(old_schema) => {
+                                types_equivalent(old_schema, *old_index, new_schema, *new_index)
+                            }
+                            None => false,
+                        }
+                    }
+                    TypePointer::Instance(..) => false,
+                })
+    }
+}
+
+/// Whether two types have the same SBOR encoding and validation, ignoring their names.
+pub fn types_equivalent(
+    old_schema: &ScryptoSchema,
+    old_index: LocalTypeIndex,
+    new_schema: &ScryptoSchema,
+    new_index: LocalTypeIndex,
+) -> bool {
+    let mut visited = index_set_new();
+    types_equivalent_internal(old_schema, old_index, new_schema, new_index, &mut visited)
+}
+
+fn types_equivalent_internal(
+    old_schema: &ScryptoSchema,
+    old_index: LocalTypeIndex,
+    new_schema: &ScryptoSchema,
+    new_index: LocalTypeIndex,
+    visited: &mut IndexSet<(LocalTypeIndex, LocalTypeIndex)>,
+) -> bool {
+    // Recursive types are equivalent if they are equivalent wherever they don't recurse
+    if !visited.insert((old_index, new_index)) {
+        return true;
+    }
+
+    if old_schema.resolve_type_validation(old_index)
+        != new_schema.resolve_type_validation(new_index)
+    {
+        return false;
+    }
+
+    let (old_kind, new_kind) = match (
+        old_schema.resolve_type_kind(old_index),
+        new_schema.resolve_type_kind(new_index),
+    ) {
+        (Some(old_kind), Some(new_kind)) => (old_kind, new_kind),
+        _ => return false,
+    };
+
+    let mut equivalent = |old_index: &LocalTypeIndex, new_index: &LocalTypeIndex| {
+        types_equivalent_internal(old_schema, *old_index, new_schema, *new_index, visited)
+    };
+
+    match (old_kind, new_kind) {
+        (
+            TypeKind::Array {
+                element_type: old_element,
+            },
+            TypeKind::Array {
+                element_type: new_element,
+            },
+        ) => equivalent(old_element, new_element),
+        (
+            TypeKind::Tuple {
+                field_types: old_fields,
+            },
+            TypeKind::Tuple {
+                field_types: new_fields,
+            },
+        ) => {
+            old_fields.len() == new_fields.len()
+                && old_fields
+                    .iter()
+                    .zip(new_fields)
+                    .all(|(old, new)| equivalent(old, new))
+        }
+        (
+            TypeKind::Enum {
+                variants: old_variants,
+            },
+            TypeKind::Enum {
+                variants: new_variants,
+            },
+        ) => {
+            old_variants.len() == new_variants.len()
+                && old_variants.iter().zip(new_variants).all(
+                    |((old_discriminator, old_fields), (new_discriminator, new_fields))| {
+                        old_discriminator == new_discriminator
+                            && old_fields.len() == new_fields.len()
+                            && old_fields
+                                .iter()
+                                .zip(new_fields)
+                                .all(|(old, new)| equivalent(old, new))
+                    },
+                )
+        }
+        (
+            TypeKind::Map {
+                key_type: old_key,
+                value_type: old_value,
+            },
+            TypeKind::Map {
+                key_type: new_key,
+                value_type: new_value,
+            },
+        ) => equivalent(old_key, new_key) && equivalent(old_value, new_value),
+        // Types without children
+        (old_kind, new_kind) => old_kind == new_kind,
+    }
+}
+
+/// Checks that the new version of a blueprint can replace the previous one, i.e. that it keeps the
+/// functions and events of the previous version, and that the state of its components is still
+/// valid (or that a migration is declared, if the types of their fields changed).
+pub fn check_blueprint_upgrade(
+    old: &BlueprintInterface,
+    old_schemas: &BTreeMap<Hash, ScryptoSchema>,
+    new: &BlueprintInterface,
+    new_schemas: &BTreeMap<Hash, ScryptoSchema>,
+    has_migration: bool,
+) -> Result<(), BlueprintIncompatibility> {
+    let comparison = TypeComparison::new(old_schemas, new_schemas);
+
+    if old.blueprint_type != new.blueprint_type {
+        return Err(BlueprintIncompatibility::BlueprintTypeChanged);
+    }
+    if old.generics != new.generics {
+        return Err(BlueprintIncompatibility::GenericsChanged);
+    }
+    for feature in &old.feature_set {
+        if !new.feature_set.contains(feature) {
+            return Err(BlueprintIncompatibility::FeatureRemoved(feature.clone()));
+        }
+    }
+
+    for (ident, old_function) in &old.functions {
+        let new_function = new
+            .functions
+            .get(ident)
+            .ok_or_else(|| BlueprintIncompatibility::FunctionRemoved(ident.clone()))?;
+        if old_function.receiver != new_function.receiver
+            || !comparison.pointers_equivalent(&old_function.input, &new_function.input)
+            || !comparison.pointers_equivalent(&old_function.output, &new_function.output)
+        {
+            return Err(BlueprintIncompatibility::FunctionSignatureChanged(
+                ident.clone(),
+            ));
+        }
+    }
+
+    for (event_name, old_event) in &old.events {
+        let new_event = new
+            .events
+            .get(event_name)
+            .ok_or_else(|| BlueprintIncompatibility::EventRemoved(event_name.clone()))?;
+        if !comparison.pointers_equivalent(old_event, new_event) {
+            return Err(BlueprintIncompatibility::EventTypeChanged(
+                event_name.clone(),
+            ));
+        }
+    }
+
+    match (&old.state.fields, &new.state.fields) {
+        (Some((_, old_fields)), Some((_, new_fields))) => {
+            if has_migration {
+                if new_fields.len() < old_fields.len() {
+                    return Err(BlueprintIncompatibility::FieldRemoved(
+                        new_fields.len() as u8
+                    ));
+                }
+            } else if !fields_equivalent(&comparison, old_fields, new_fields)? {
+                return Err(BlueprintIncompatibility::FieldsChangedWithoutMigration);
+            }
+        }
+        (None, None) => {}
+        // Adding or removing all the fields moves the partitions of the collections
+        _ => return Err(BlueprintIncompatibility::FieldsAddedOrRemoved),
+    }
+
+    if new.state.collections.len() < old.state.collections.len() {
+        return Err(BlueprintIncompatibility::CollectionRemoved(
+            new.state.collections.len() as u8,
+        ));
+    }
+    for (index, ((old_offset, old_collection), (new_offset, new_collection))) in old
+        .state
+        .collections
+        .iter()
+        .zip(&new.state.collections)
+        .enumerate()
+    {
+        let unchanged = old_offset == new_offset
+            && match (old_collection, new_collection) {
+                (
+                    BlueprintCollectionSchema::KeyValueStore(old_kv_store),
+                    BlueprintCollectionSchema::KeyValueStore(new_kv_store),
+                ) => {
+                    old_kv_store.can_own == new_kv_store.can_own
+                        && comparison.pointers_equivalent(&old_kv_store.key, &new_kv_store.key)
+                        && comparison.pointers_equivalent(&old_kv_store.value, &new_kv_store.value)
+                }
+                (old_collection, new_collection) => old_collection == new_collection,
+            };
+        if !unchanged {
+            return Err(BlueprintIncompatibility::CollectionChanged(index as u8));
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether the fields of the components of a version are valid fields of another version.
+pub fn fields_equivalent(
+    comparison: &TypeComparison,
+    old_fields: &[FieldSchema<TypePointer>],
+    new_fields: &[FieldSchema<TypePointer>],
+) -> Result<bool, BlueprintIncompatibility> {
+    if old_fields.len() != new_fields.len() {
+        return Ok(false);
+    }
+    for (index, (old_field, new_field)) in old_fields.iter().zip(new_fields).enumerate() {
+        if old_field.condition != new_field.condition {
+            return Err(BlueprintIncompatibility::FieldConditionChanged(index as u8));
+        }
+        if !comparison.pointers_equivalent(&old_field.field, &new_field.field) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Whether a function migrates the fields of the components of a version to those of another
+/// version, i.e. whether it takes the fields of the former as arguments and returns a tuple of
+/// the fields of the latter. Conditional fields can't be migrated.
+pub fn is_valid_migration(
+    function: &FunctionSchema,
+    from_fields: &[FieldSchema<TypePointer>],
+    from_schemas: &BTreeMap<Hash, ScryptoSchema>,
+    to_fields: &[FieldSchema<TypePointer>],
+    to_schemas: &BTreeMap<Hash, ScryptoSchema>,
+) -> bool {
+    let unconditional = from_fields
+        .iter()
+        .chain(to_fields)
+        .all(|field| matches!(field.condition, Condition::Always));
+
+    unconditional
+        && function.receiver.is_none()
+        && TypeComparison::new(from_schemas, to_schemas).is_tuple_of(from_fields, &function.input)
+        && TypeComparison::new(to_schemas, to_schemas).is_tuple_of(to_fields, &function.output)
+}
+
+/// Returns the fields of a migrated component from the output of its migration, or `None` if the
+/// output is not a tuple of the given number of fields.
+pub fn migrated_fields(output: ScryptoValue, num_fields: usize) -> Option<Vec<ScryptoValue>> {
+    match output {
+        Value::Tuple { fields } if fields.len() == num_fields => Some(fields),
+        _ => None,
+    }
+}
+
+/// Replaces the given owned nodes of a value, e.g. to carry the objects owned by the fields of a
+/// component through a migration.
+pub fn replace_owned_nodes(value: &mut ScryptoValue, replacements: &IndexMap<NodeId, NodeId>) {
+    match value {
+        Value::Enum { fields, .. } | Value::Tuple { fields } => {
+            for field in fields {
+                replace_owned_nodes(field, replacements);
+            }
+        }
+        Value::Array { elements, .. } => {
+            for element in elements {
+                replace_owned_nodes(element, replacements);
+            }
+        }
+        Value::Map { entries, .. } => {
+            for (key, value) in entries {
+                replace_owned_nodes(key, replacements);
+                replace_owned_nodes(value, replacements);
+            }
+        }
+        Value::Custom {
+            value: ScryptoCustomValue::Own(own),
+        } => {
+            if let Some(replacement) = replacements.get(&own.0) {
+                own.0 = *replacement;
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_of_migration_must_be_a_tuple_of_the_fields() {
+        let field = ScryptoValue::U32 { value: 1 };
+        assert_eq!(
+            migrated_fields(
+                ScryptoValue::Tuple {
+                    fields: vec![field.clone()]
+                },
+                1
+            ),
+            Some(vec![field.clone()])
+        );
+        assert_eq!(
+            migrated_fields(
+                ScryptoValue::Tuple {
+                    fields: vec![field.clone(), field.clone()]
+                },
+                1
+            ),
+            None
+        );
+        assert_eq!(migrated_fields(field, 1), None);
+    }
+}
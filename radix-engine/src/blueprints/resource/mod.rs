@@ -9,6 +9,7 @@ mod package;
 mod proof_common;
 mod resource_manager_common;
 mod vault_common;
+mod vesting_vault;
 mod worktop;
 
 pub use auth_zone::*;
@@ -22,4 +23,5 @@ pub use package::*;
 pub use proof_common::*;
 pub use resource_manager_common::*;
 pub use vault_common::*;
+pub use vesting_vault::*;
 pub use worktop::*;
@@ -134,6 +134,11 @@ const NON_FUNGIBLE_PROOF_GET_RESOURCE_ADDRESS_EXPORT_NAME: &str =
     "get_resource_address_NonFungibleProof";
 const NON_FUNGIBLE_PROOF_DROP_EXPORT_NAME: &str = "drop_NonFungibleProof";
 
+const VESTING_VAULT_CREATE_EXPORT_NAME: &str = "create_VestingVault";
+const VESTING_VAULT_WITHDRAW_EXPORT_NAME: &str = "withdraw_VestingVault";
+const VESTING_VAULT_GET_AMOUNT_EXPORT_NAME: &str = "get_amount_VestingVault";
+const VESTING_VAULT_GET_LOCKED_AMOUNT_EXPORT_NAME: &str = "get_locked_amount_VestingVault";
+
 pub struct ResourceNativePackage;
 
 impl ResourceNativePackage {
@@ -2164,6 +2169,107 @@ impl ResourceNativePackage {
             }
         };
 
+        //====================================================================================
+
+        let vesting_vault_blueprint = {
+            let mut aggregator = TypeAggregator::<ScryptoCustomTypeKind>::new();
+
+            let mut fields = Vec::new();
+            fields.push(FieldSchema::static_field(
+                aggregator.add_child_type_and_descendents::<VestingVaultSubstate>(),
+            ));
+
+            let mut functions = BTreeMap::new();
+            functions.insert(
+                VESTING_VAULT_CREATE_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: None,
+                    input: TypeRef::Static(
+                        aggregator.add_child_type_and_descendents::<VestingVaultCreateInput>(),
+                    ),
+                    output: TypeRef::Static(
+                        aggregator.add_child_type_and_descendents::<VestingVaultCreateOutput>(),
+                    ),
+                    export: VESTING_VAULT_CREATE_EXPORT_NAME.to_string(),
+                },
+            );
+            functions.insert(
+                VESTING_VAULT_WITHDRAW_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref_mut()),
+                    input: TypeRef::Static(
+                        aggregator.add_child_type_and_descendents::<VestingVaultWithdrawInput>(),
+                    ),
+                    output: TypeRef::Static(
+                        aggregator.add_child_type_and_descendents::<VestingVaultWithdrawOutput>(),
+                    ),
+                    export: VESTING_VAULT_WITHDRAW_EXPORT_NAME.to_string(),
+                },
+            );
+            functions.insert(
+                VESTING_VAULT_GET_AMOUNT_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref()),
+                    input: TypeRef::Static(
+                        aggregator.add_child_type_and_descendents::<VestingVaultGetAmountInput>(),
+                    ),
+                    output: TypeRef::Static(
+                        aggregator.add_child_type_and_descendents::<VestingVaultGetAmountOutput>(),
+                    ),
+                    export: VESTING_VAULT_GET_AMOUNT_EXPORT_NAME.to_string(),
+                },
+            );
+            functions.insert(
+                VESTING_VAULT_GET_LOCKED_AMOUNT_IDENT.to_string(),
+                FunctionSchemaInit {
+                    receiver: Some(ReceiverInfo::normal_ref()),
+                    input: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<VestingVaultGetLockedAmountInput>(),
+                    ),
+                    output: TypeRef::Static(
+                        aggregator
+                            .add_child_type_and_descendents::<VestingVaultGetLockedAmountOutput>(),
+                    ),
+                    export: VESTING_VAULT_GET_LOCKED_AMOUNT_EXPORT_NAME.to_string(),
+                },
+            );
+
+            let schema = generate_full_schema(aggregator);
+
+            BlueprintDefinitionInit {
+                blueprint_type: BlueprintType::default(),
+                dependencies: btreeset!(),
+                feature_set: btreeset!(),
+
+                schema: BlueprintSchemaInit {
+                    generics: vec![],
+                    schema,
+                    state: BlueprintStateSchemaInit {
+                        fields,
+                        collections: vec![],
+                    },
+                    events: BlueprintEventSchemaInit::default(),
+                    functions: BlueprintFunctionsSchemaInit {
+                        functions,
+                        virtual_lazy_load_functions: btreemap!(),
+                    },
+                },
+
+                royalty_config: PackageRoyaltyConfig::default(),
+                auth_config: AuthConfig {
+                    function_auth: FunctionAuth::AllowAll,
+                    method_auth: MethodAuthTemplate::StaticRoles(roles_template!(
+                        methods {
+                            VESTING_VAULT_WITHDRAW_IDENT => [OWNER_ROLE];
+                            VESTING_VAULT_GET_AMOUNT_IDENT => MethodAccessibility::Public;
+                            VESTING_VAULT_GET_LOCKED_AMOUNT_IDENT => MethodAccessibility::Public;
+                        }
+                    )),
+                },
+            }
+        };
+
         let blueprints = btreemap!(
             FUNGIBLE_RESOURCE_MANAGER_BLUEPRINT.to_string() => fungible_resource_manager_blueprint,
             NON_FUNGIBLE_RESOURCE_MANAGER_BLUEPRINT.to_string() => non_fungible_resource_manager_blueprint,
@@ -2175,6 +2281,7 @@ impl ResourceNativePackage {
             NON_FUNGIBLE_PROOF_BLUEPRINT.to_string() => non_fungible_proof_blueprint,
             WORKTOP_BLUEPRINT.to_string() => worktop_blueprint,
             AUTH_ZONE_BLUEPRINT.to_string() => auth_zone_blueprint,
+            VESTING_VAULT_BLUEPRINT.to_string() => vesting_vault_blueprint,
         );
 
         PackageDefinition { blueprints }
@@ -2967,6 +3074,47 @@ impl ResourceNativePackage {
                 Ok(IndexedScryptoValue::from_typed(&proofs))
             }
             AUTH_ZONE_DROP_EXPORT_NAME => AuthZoneBlueprint::drop(input, api),
+            VESTING_VAULT_CREATE_EXPORT_NAME => {
+                let input: VestingVaultCreateInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+
+                let rtn = VestingVaultBlueprint::create(
+                    input.owner_role,
+                    input.bucket,
+                    input.schedule,
+                    api,
+                )?;
+
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            VESTING_VAULT_WITHDRAW_EXPORT_NAME => {
+                let input: VestingVaultWithdrawInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+
+                let rtn = VestingVaultBlueprint::withdraw(input.amount, api)?;
+
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            VESTING_VAULT_GET_AMOUNT_EXPORT_NAME => {
+                let _input: VestingVaultGetAmountInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+
+                let rtn = VestingVaultBlueprint::get_amount(api)?;
+
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            VESTING_VAULT_GET_LOCKED_AMOUNT_EXPORT_NAME => {
+                let _input: VestingVaultGetLockedAmountInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+
+                let rtn = VestingVaultBlueprint::get_locked_amount(api)?;
+
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
             _ => Err(RuntimeError::ApplicationError(
                 ApplicationError::ExportDoesNotExist(export_name.to_string()),
             )),
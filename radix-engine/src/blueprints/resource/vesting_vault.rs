@@ -0,0 +1,200 @@
+use crate::errors::ApplicationError;
+use crate::errors::RuntimeError;
+use crate::types::*;
+use native_sdk::modules::access_rules::AccessRules;
+use native_sdk::modules::metadata::Metadata;
+use native_sdk::modules::royalty::ComponentRoyalty;
+use native_sdk::resource::NativeBucket;
+use native_sdk::resource::NativeVault;
+use native_sdk::runtime::Runtime;
+use radix_engine_interface::api::field_lock_api::LockFlags;
+use radix_engine_interface::api::object_api::ObjectModuleId;
+use radix_engine_interface::api::{ClientApi, OBJECT_HANDLE_SELF};
+use radix_engine_interface::blueprints::consensus_manager::TimePrecision;
+use radix_engine_interface::blueprints::resource::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum VestingVaultError {
+    InvalidSchedule(VestingSchedule),
+    NonFungibleResourcesAreNotAccepted {
+        resource_address: ResourceAddress,
+    },
+    InsufficientVestedAmount {
+        requested: Decimal,
+        available: Decimal,
+    },
+}
+
+impl From<VestingVaultError> for RuntimeError {
+    fn from(value: VestingVaultError) -> Self {
+        Self::ApplicationError(ApplicationError::VestingVaultError(value))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, ScryptoSbor)]
+pub struct VestingVaultSubstate {
+    /// The vault holding the resources which are yet to be withdrawn.
+    pub vault: Vault,
+
+    /// The resource held in the vault.
+    pub resource_address: ResourceAddress,
+
+    /// The amount the vault was created with, which the schedule releases over time.
+    pub total_amount: Decimal,
+
+    pub schedule: VestingSchedule,
+}
+
+impl VestingVaultSubstate {
+    /// Returns the amount which is still locked at the given time or epoch. Whatever is in the
+    /// vault in excess of this amount may be withdrawn.
+    pub fn locked_amount(&self, current_time: Instant, current_epoch: Epoch) -> Decimal {
+        self.schedule
+            .locked_amount(self.total_amount, current_time, current_epoch)
+    }
+}
+
+impl Clone for VestingVaultSubstate {
+    fn clone(&self) -> Self {
+        Self {
+            vault: Vault(self.vault.0.clone()),
+            resource_address: self.resource_address,
+            total_amount: self.total_amount,
+            schedule: self.schedule.clone(),
+        }
+    }
+}
+
+pub const VESTING_VAULT_FIELD: u8 = 0u8;
+
+pub struct VestingVaultBlueprint;
+
+impl VestingVaultBlueprint {
+    pub fn create<Y>(
+        owner_role: OwnerRole,
+        bucket: Bucket,
+        schedule: VestingSchedule,
+        api: &mut Y,
+    ) -> Result<ComponentAddress, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        if !schedule.is_valid() {
+            return Err(VestingVaultError::InvalidSchedule(schedule).into());
+        }
+
+        // Only fungible resources vest - there is no meaningful way to release a fraction of a
+        // set of non fungibles.
+        let resource_address = bucket.resource_address(api)?;
+        if !resource_address
+            .as_node_id()
+            .is_global_fungible_resource_manager()
+        {
+            return Err(
+                VestingVaultError::NonFungibleResourcesAreNotAccepted { resource_address }.into(),
+            );
+        }
+
+        let total_amount = bucket.amount(api)?;
+        let mut vault = Vault::create(resource_address, api)?;
+        vault.put(bucket, api)?;
+
+        let substate = VestingVaultSubstate {
+            vault,
+            resource_address,
+            total_amount,
+            schedule,
+        };
+        let object_id = api.new_simple_object(
+            VESTING_VAULT_BLUEPRINT,
+            vec![scrypto_encode(&substate).unwrap()],
+        )?;
+
+        let access_rules = AccessRules::create(owner_role, btreemap!(), api)?.0;
+        let metadata = Metadata::create(api)?;
+        let royalty = ComponentRoyalty::create(ComponentRoyaltyConfig::default(), api)?;
+
+        let address = api.globalize(
+            btreemap!(
+                ObjectModuleId::Main => object_id,
+                ObjectModuleId::AccessRules => access_rules.0,
+                ObjectModuleId::Metadata => metadata.0,
+                ObjectModuleId::Royalty => royalty.0,
+            ),
+            None,
+        )?;
+
+        Ok(ComponentAddress::new_or_panic(address.as_node_id().0))
+    }
+
+    /// Method requires auth - withdraws resources which have already been released by the schedule.
+    pub fn withdraw<Y>(amount: Decimal, api: &mut Y) -> Result<Bucket, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let (mut substate, handle) = Self::lock_and_read(api)?;
+
+        let available = substate.vault.amount(api)? - Self::locked_amount(&substate, api)?;
+        if amount > available {
+            return Err(VestingVaultError::InsufficientVestedAmount {
+                requested: amount,
+                available,
+            }
+            .into());
+        }
+
+        let bucket = substate.vault.take(amount, api)?;
+        api.field_lock_release(handle)?;
+
+        Ok(bucket)
+    }
+
+    pub fn get_amount<Y>(api: &mut Y) -> Result<Decimal, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let (substate, handle) = Self::lock_and_read(api)?;
+        let amount = substate.vault.amount(api)?;
+        api.field_lock_release(handle)?;
+
+        Ok(amount)
+    }
+
+    pub fn get_locked_amount<Y>(api: &mut Y) -> Result<Decimal, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let (substate, handle) = Self::lock_and_read(api)?;
+        let locked_amount = Self::locked_amount(&substate, api)?;
+        api.field_lock_release(handle)?;
+
+        Ok(locked_amount)
+    }
+
+    fn locked_amount<Y>(
+        substate: &VestingVaultSubstate,
+        api: &mut Y,
+    ) -> Result<Decimal, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let current_time = Runtime::current_time(api, TimePrecision::Minute)?;
+        let current_epoch = Runtime::current_epoch(api)?;
+
+        Ok(substate.locked_amount(current_time, current_epoch))
+    }
+
+    fn lock_and_read<Y>(api: &mut Y) -> Result<(VestingVaultSubstate, LockHandle), RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        let handle = api.actor_open_field(
+            OBJECT_HANDLE_SELF,
+            VESTING_VAULT_FIELD,
+            LockFlags::read_only(),
+        )?;
+        let substate = api.field_lock_read_typed::<VestingVaultSubstate>(handle)?;
+
+        Ok((substate, handle))
+    }
+}
@@ -1,5 +1,5 @@
 use crate::blueprints::access_controller::AccessControllerError;
-use crate::blueprints::account::AccountError;
+use crate::blueprints::account::{AccountError, AccountLockerError};
 use crate::blueprints::consensus_manager::{ConsensusManagerError, ValidatorError};
 use crate::blueprints::package::PackageError;
 use crate::blueprints::pool::multi_resource_pool::MultiResourcePoolError;
@@ -8,7 +8,7 @@ use crate::blueprints::pool::two_resource_pool::TwoResourcePoolError;
 use crate::blueprints::resource::{AuthZoneError, NonFungibleVaultError};
 use crate::blueprints::resource::{
     BucketError, FungibleResourceManagerError, NonFungibleResourceManagerError, ProofError,
-    VaultError, WorktopError,
+    VaultError, VestingVaultError, WorktopError,
 };
 use crate::blueprints::transaction_processor::TransactionProcessorError;
 use crate::kernel::call_frame::{
@@ -454,8 +454,12 @@ pub enum ApplicationError {
 
     AuthZoneError(AuthZoneError),
 
+    VestingVaultError(VestingVaultError),
+
     AccountError(AccountError),
 
+    AccountLockerError(AccountLockerError),
+
     AccessControllerError(AccessControllerError),
 
     OneResourcePoolError(OneResourcePoolError),
@@ -37,6 +37,7 @@ pub enum Actor {
     Method(MethodActor),
     Function {
         blueprint_id: BlueprintId,
+        version: BlueprintVersion,
         ident: String,
     },
     VirtualLazyLoad {
@@ -55,6 +56,7 @@ impl Actor {
             Actor::Function {
                 blueprint_id: blueprint,
                 ident,
+                ..
             } => {
                 blueprint.package_address.as_ref().len()
                     + blueprint.blueprint_name.len()
@@ -107,6 +109,7 @@ impl Actor {
             Actor::Function {
                 blueprint_id: blueprint,
                 ident,
+                ..
             } => FnIdentifier {
                 blueprint_id: blueprint.clone(),
                 ident: FnIdent::Application(ident.to_string()),
@@ -235,6 +238,25 @@ impl Actor {
         &blueprint.package_address
     }
 
+    /// The version of the blueprint the actor runs: that of the object for methods, and the
+    /// latest one (at the time of the call) for functions.
+    pub fn blueprint_version_key(&self) -> BlueprintVersionKey {
+        match self {
+            Actor::Method(MethodActor {
+                module_object_info, ..
+            }) => module_object_info.blueprint_version_key(),
+            Actor::Function {
+                blueprint_id,
+                version,
+                ..
+            } => BlueprintVersionKey::new(&blueprint_id.blueprint_name, *version),
+            Actor::VirtualLazyLoad { blueprint_id, .. } => {
+                BlueprintVersionKey::new_default(&blueprint_id.blueprint_name)
+            }
+            Actor::Root => panic!("Unexpected call"), // FIXME: have the right interface
+        }
+    }
+
     pub fn blueprint_name(&self) -> &str {
         match &self {
             Actor::Method(MethodActor {
@@ -275,9 +297,10 @@ impl Actor {
         })
     }
 
-    pub fn function(blueprint: BlueprintId, ident: String) -> Self {
+    pub fn function(blueprint: BlueprintId, version: BlueprintVersion, ident: String) -> Self {
         Self::Function {
             blueprint_id: blueprint,
+            version,
             ident,
         }
     }
@@ -1,7 +1,7 @@
 use crate::system::node_modules::type_info::TypeInfoSubstate;
 use crate::track::interface::{
-    AcquireLockError, NodeSubstates, SetSubstateError, StoreAccess, StoreAccessInfo, SubstateStore,
-    TakeSubstateError,
+    AcquireLockError, NodeSubstates, ScanSubstatesError, SetSubstateError, StoreAccess,
+    StoreAccessInfo, SubstateStore, TakeSubstateError,
 };
 use crate::types::*;
 use radix_engine_interface::api::field_lock_api::LockFlags;
@@ -247,6 +247,7 @@ pub enum CallFrameRemoveSubstateError {
 #[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
 pub enum CallFrameScanSubstateError {
     NodeNotVisible(NodeId),
+    StoreError(ScanSubstatesError),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
@@ -967,7 +968,10 @@ impl<L: Clone> CallFrame<L> {
         count: u32,
         heap: &'f mut Heap,
         store: &'f mut S,
-    ) -> Result<(Vec<IndexedScryptoValue>, StoreAccessInfo), CallFrameScanSubstateError> {
+    ) -> Result<
+        (Vec<(SubstateKey, IndexedScryptoValue)>, StoreAccessInfo),
+        CallFrameScanSubstateError,
+    > {
         // Check node visibility
         if !self.get_node_visibility(node_id).can_be_read_or_write() {
             return Err(CallFrameScanSubstateError::NodeNotVisible(node_id.clone()));
@@ -979,10 +983,58 @@ impl<L: Clone> CallFrame<L> {
                 StoreAccessInfo::new(),
             )
         } else {
-            store.scan_substates(node_id, partition_num, count)
+            store
+                .scan_substates(node_id, partition_num, count)
+                .map_err(|e| CallFrameScanSubstateError::StoreError(e))?
         };
 
-        for substate in &substates {
+        for (_key, substate) in &substates {
+            for reference in substate.references() {
+                if reference.is_global() {
+                    self.stable_references
+                        .insert(reference.clone(), StableReferenceType::Global);
+                } else {
+                    // FIXME: check if non-global reference is needed
+                }
+            }
+        }
+
+        Ok((substates, store_access))
+    }
+
+    pub fn scan_substates_from<'f, S: SubstateStore>(
+        &mut self,
+        node_id: &NodeId,
+        partition_num: PartitionNumber,
+        from_key: Option<&SubstateKey>,
+        count: u32,
+        heap: &'f mut Heap,
+        store: &'f mut S,
+    ) -> Result<
+        (Vec<(SubstateKey, IndexedScryptoValue)>, StoreAccessInfo),
+        CallFrameScanSubstateError,
+    > {
+        // Check node visibility
+        if !self.get_node_visibility(node_id).can_be_read_or_write() {
+            return Err(CallFrameScanSubstateError::NodeNotVisible(node_id.clone()));
+        }
+
+        // Note that the heap and the store order the substates differently (by substate key and by
+        // database sort key, respectively) - each order is stable, but a node moved from the heap
+        // to the store in between two scans cannot be continued from the same key.
+        // FIXME: heap substates are not lock-checked, same as everywhere else
+        let (substates, store_access) = if heap.contains_node(node_id) {
+            (
+                heap.scan_substates_from(node_id, partition_num, from_key, count),
+                StoreAccessInfo::new(),
+            )
+        } else {
+            store
+                .scan_substates_from(node_id, partition_num, from_key, count)
+                .map_err(|e| CallFrameScanSubstateError::StoreError(e))?
+        };
+
+        for (_key, substate) in &substates {
             for reference in substate.references() {
                 if reference.is_global() {
                     self.stable_references
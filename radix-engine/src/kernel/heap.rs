@@ -6,6 +6,7 @@ use radix_engine_interface::blueprints::resource::{
     LockedNonFungibleResource,
 };
 use sbor::rust::collections::btree_map::Entry;
+use sbor::rust::ops::Bound;
 
 #[derive(Debug, Default)]
 pub struct HeapNode {
@@ -142,15 +143,15 @@ impl Heap {
         node_id: &NodeId,
         partition_num: PartitionNumber,
         count: u32,
-    ) -> Vec<IndexedScryptoValue> {
+    ) -> Vec<(SubstateKey, IndexedScryptoValue)> {
         let node_substates = self
             .nodes
             .get_mut(node_id)
             .and_then(|n| n.substates.get_mut(&partition_num));
         if let Some(substates) = node_substates {
-            let substates: Vec<IndexedScryptoValue> = substates
+            let substates: Vec<(SubstateKey, IndexedScryptoValue)> = substates
                 .iter()
-                .map(|(_key, v)| v.clone())
+                .map(|(key, v)| (key.clone(), v.clone()))
                 .take(count.try_into().unwrap())
                 .collect();
 
@@ -160,6 +161,33 @@ impl Heap {
         }
     }
 
+    /// Returns the substates in the order of their keys, starting from the given key (inclusive).
+    pub fn scan_substates_from(
+        &mut self,
+        node_id: &NodeId,
+        partition_num: PartitionNumber,
+        from_key: Option<&SubstateKey>,
+        count: u32,
+    ) -> Vec<(SubstateKey, IndexedScryptoValue)> {
+        let node_substates = self
+            .nodes
+            .get(node_id)
+            .and_then(|n| n.substates.get(&partition_num));
+        if let Some(substates) = node_substates {
+            let from_bound = match from_key {
+                Some(from_key) => Bound::Included(from_key),
+                None => Bound::Unbounded,
+            };
+            substates
+                .range::<SubstateKey, _>((from_bound, Bound::Unbounded))
+                .map(|(key, v)| (key.clone(), v.clone()))
+                .take(count.try_into().unwrap())
+                .collect()
+        } else {
+            vec![]
+        }
+    }
+
     pub fn take_substates(
         &mut self,
         node_id: &NodeId,
@@ -804,7 +804,7 @@ where
         node_id: &NodeId,
         partition_num: PartitionNumber,
         count: u32,
-    ) -> Result<Vec<IndexedScryptoValue>, RuntimeError> {
+    ) -> Result<Vec<(SubstateKey, IndexedScryptoValue)>, RuntimeError> {
         let (substeates, store_access) = self
             .current_frame
             .scan_substates(node_id, partition_num, count, &mut self.heap, self.store)
@@ -817,6 +817,33 @@ where
         Ok(substeates)
     }
 
+    #[trace_resources]
+    fn kernel_scan_substates_from(
+        &mut self,
+        node_id: &NodeId,
+        partition_num: PartitionNumber,
+        from_key: Option<&SubstateKey>,
+        count: u32,
+    ) -> Result<Vec<(SubstateKey, IndexedScryptoValue)>, RuntimeError> {
+        let (substates, store_access) = self
+            .current_frame
+            .scan_substates_from(
+                node_id,
+                partition_num,
+                from_key,
+                count,
+                &mut self.heap,
+                self.store,
+            )
+            .map_err(CallFrameError::ScanSubstatesError)
+            .map_err(KernelError::CallFrameError)
+            .map_err(RuntimeError::KernelError)?;
+
+        M::on_scan_substates(&store_access, self)?;
+
+        Ok(substates)
+    }
+
     #[trace_resources]
     fn kernel_take_substates(
         &mut self,
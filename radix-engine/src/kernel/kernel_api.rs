@@ -145,7 +145,20 @@ pub trait KernelSubstateApi<L> {
         node_id: &NodeId,
         partition_num: PartitionNumber,
         count: u32,
-    ) -> Result<Vec<IndexedScryptoValue>, RuntimeError>;
+    ) -> Result<Vec<(SubstateKey, IndexedScryptoValue)>, RuntimeError>;
+
+    /// Scans the substates in a stable order, starting from the given substate key (inclusive),
+    /// so that a partition can be paged through using the key following the last returned one.
+    /// Fails if any of the substates is write locked.
+    ///
+    /// Clients must ensure the partition is keyed by [`SubstateKey::Map`].
+    fn kernel_scan_substates_from(
+        &mut self,
+        node_id: &NodeId,
+        partition_num: PartitionNumber,
+        from_key: Option<&SubstateKey>,
+        count: u32,
+    ) -> Result<Vec<(SubstateKey, IndexedScryptoValue)>, RuntimeError>;
 
     fn kernel_take_substates(
         &mut self,
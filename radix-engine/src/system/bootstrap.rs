@@ -51,6 +51,7 @@ use transaction::validation::ManifestIdAllocator;
 lazy_static! {
     pub static ref DEFAULT_TESTING_FAUCET_SUPPLY: Decimal = dec!("100000000000000000");
     pub static ref DEFAULT_VALIDATOR_XRD_COST: Decimal = dec!("1000");
+    pub static ref DEFAULT_PROTOCOL_UPDATE_READINESS_THRESHOLD: Decimal = dec!("0.75");
 }
 
 //==========================================================================================
@@ -266,6 +267,7 @@ where
                 num_owner_stake_units_unlock_epochs: 2,
                 num_fee_increase_delay_epochs: 1,
                 validator_creation_xrd_cost: *DEFAULT_VALIDATOR_XRD_COST,
+                protocol_update_readiness_threshold: *DEFAULT_PROTOCOL_UPDATE_READINESS_THRESHOLD,
             },
             1,
             Some(0),
@@ -4,6 +4,7 @@ pub mod module;
 pub mod node_init;
 pub mod node_modules;
 pub mod payload_validation;
+pub mod protocol_update;
 pub mod system;
 pub mod system_callback;
 pub mod system_callback_api;
@@ -0,0 +1,261 @@
+use crate::blueprints::package::{create_bootstrap_package_partitions, PackageNativePackage};
+use crate::errors::RuntimeError;
+use crate::system::bootstrap::FlashReceipt;
+use crate::track::SystemUpdates;
+use crate::transaction::{
+    CommitResult, ExecutionConfig, FeeReserveConfig, StagedSubstateDatabase, StateUpdateSummary,
+    TransactionReceipt,
+};
+use crate::types::*;
+use crate::vm::wasm::WasmEngine;
+use crate::vm::ScryptoVm;
+use radix_engine_interface::api::node_modules::auth::AuthAddresses;
+use radix_engine_interface::api::node_modules::metadata::MetadataInit;
+use radix_engine_interface::blueprints::package::*;
+use radix_engine_store_interface::db_key_mapper::{DatabaseKeyMapper, SpreadPrefixKeyMapper};
+use radix_engine_store_interface::interface::{
+    CommittableSubstateDatabase, DatabaseUpdate, SubstateDatabase,
+};
+use transaction::builder::TransactionManifestV1;
+use transaction::model::{SystemTransactionV1, TransactionPayload};
+
+/// A native package to be re-published at its existing address, e.g. with new blueprints or
+/// functions implemented by a newer version of the engine.
+#[derive(Debug, Clone)]
+pub struct NativePackageRepublication {
+    pub package_address: PackageAddress,
+    pub definition: PackageDefinition,
+    pub native_package_code_id: u64,
+    pub metadata_init: MetadataInit,
+}
+
+/// The changes enacted by a protocol update, once the readiness threshold is reached for its
+/// protocol version.
+#[derive(Debug, Clone)]
+pub struct ProtocolUpdate {
+    /// The name of the protocol version, as signalled by the validators.
+    pub protocol_version_name: String,
+    /// Flashed, before the state migrations are executed.
+    pub native_package_republications: Vec<NativePackageRepublication>,
+    /// Executed in order, each as a system transaction.
+    pub state_migrations: Vec<TransactionManifestV1>,
+}
+
+#[derive(Debug, Clone, ScryptoSbor)]
+pub struct ProtocolUpdateReceipt {
+    pub protocol_version_name: String,
+    pub native_package_flash_receipt: FlashReceipt,
+    pub state_migration_receipts: Vec<TransactionReceipt>,
+}
+
+#[derive(Debug, Clone)]
+pub enum ProtocolUpdateError {
+    InvalidNativePackageRepublication {
+        protocol_version_name: String,
+        error: RuntimeError,
+    },
+    /// The state migration at the given index did not commit successfully.
+    StateMigrationFailed {
+        protocol_version_name: String,
+        index: usize,
+        receipt: TransactionReceipt,
+    },
+}
+
+/// Executes the registered protocol updates, similarly to how the [`Bootstrapper`](crate::system::bootstrap::Bootstrapper)
+/// executes the genesis.
+pub struct ProtocolUpdateExecutor<'s, 'i, S, W>
+where
+    S: SubstateDatabase + CommittableSubstateDatabase,
+    W: WasmEngine,
+{
+    substate_db: &'s mut S,
+    scrypto_vm: &'i ScryptoVm<W>,
+    trace: bool,
+    protocol_updates: BTreeMap<String, ProtocolUpdate>,
+}
+
+impl<'s, 'i, S, W> ProtocolUpdateExecutor<'s, 'i, S, W>
+where
+    S: SubstateDatabase + CommittableSubstateDatabase,
+    W: WasmEngine,
+{
+    pub fn new(
+        substate_db: &'s mut S,
+        scrypto_vm: &'i ScryptoVm<W>,
+        trace: bool,
+    ) -> ProtocolUpdateExecutor<'s, 'i, S, W> {
+        ProtocolUpdateExecutor {
+            substate_db,
+            scrypto_vm,
+            trace,
+            protocol_updates: BTreeMap::new(),
+        }
+    }
+
+    /// Registers a protocol update, replacing any previously registered for the same protocol
+    /// version.
+    pub fn register(&mut self, protocol_update: ProtocolUpdate) {
+        self.protocol_updates.insert(
+            protocol_update.protocol_version_name.clone(),
+            protocol_update,
+        );
+    }
+
+    /// Executes the registered protocol updates whose readiness threshold was reached by the
+    /// given (epoch changing) transaction, and unregisters them, so that each is executed once.
+    /// Stops at the first protocol update which fails, leaving the database as it was after the
+    /// preceding ones.
+    pub fn execute_ready_protocol_updates(
+        &mut self,
+        commit_result: &CommitResult,
+    ) -> Result<Vec<ProtocolUpdateReceipt>, ProtocolUpdateError> {
+        let mut receipts = Vec::new();
+        let events = commit_result.protocol_update_readiness_thresholds_reached(&*self.substate_db);
+        for event in events {
+            if let Some(protocol_update) =
+                self.protocol_updates.remove(&event.protocol_version_name)
+            {
+                receipts.push(self.execute(protocol_update)?);
+            }
+        }
+        Ok(receipts)
+    }
+
+    /// Executes the given protocol update against a staged copy of the state, and commits its
+    /// flash and all its state migrations at once - or nothing, if any of them fails.
+    pub fn execute(
+        &mut self,
+        protocol_update: ProtocolUpdate,
+    ) -> Result<ProtocolUpdateReceipt, ProtocolUpdateError> {
+        let protocol_version_name = protocol_update.protocol_version_name;
+        let native_package_flash_receipt = create_native_package_republication_flash(
+            &*self.substate_db,
+            protocol_update.native_package_republications,
+        )
+        .map_err(
+            |error| ProtocolUpdateError::InvalidNativePackageRepublication {
+                protocol_version_name: protocol_version_name.clone(),
+                error,
+            },
+        )?;
+
+        let mut staged_db = StagedSubstateDatabase::new(&*self.substate_db);
+        staged_db.stage(native_package_flash_receipt.database_updates.clone());
+
+        let mut state_migration_receipts = Vec::new();
+        for (index, manifest) in protocol_update.state_migrations.into_iter().enumerate() {
+            let receipt = Self::execute_state_migration(
+                &mut staged_db,
+                self.scrypto_vm,
+                self.trace,
+                &protocol_version_name,
+                index,
+                manifest,
+            );
+            if !receipt.is_commit_success() {
+                return Err(ProtocolUpdateError::StateMigrationFailed {
+                    protocol_version_name,
+                    index,
+                    receipt,
+                });
+            }
+            state_migration_receipts.push(receipt);
+        }
+
+        let database_updates = staged_db.database_updates();
+        self.substate_db.commit(&database_updates);
+
+        Ok(ProtocolUpdateReceipt {
+            protocol_version_name,
+            native_package_flash_receipt,
+            state_migration_receipts,
+        })
+    }
+
+    fn execute_state_migration(
+        staged_db: &mut StagedSubstateDatabase<'_, S>,
+        scrypto_vm: &ScryptoVm<W>,
+        trace: bool,
+        protocol_version_name: &str,
+        index: usize,
+        manifest: TransactionManifestV1,
+    ) -> TransactionReceipt {
+        let (instructions, blobs) = manifest.for_intent();
+        let transaction = SystemTransactionV1 {
+            instructions,
+            blobs,
+            pre_allocated_addresses: vec![],
+            hash_for_execution: hash(format!(
+                "Protocol update {}: state migration {}",
+                protocol_version_name, index
+            )),
+        };
+
+        staged_db.execute_and_stage(
+            scrypto_vm,
+            &FeeReserveConfig::default(),
+            &ExecutionConfig::for_system_transaction().with_kernel_trace(trace),
+            &transaction
+                .prepare()
+                .expect("Expected state migration transaction to be preparable")
+                .get_executable(btreeset![AuthAddresses::system_role()]),
+        )
+    }
+}
+
+/// Overwrites the substates of the re-published packages, and deletes those of their existing
+/// substates which the new definitions no longer produce.
+pub fn create_native_package_republication_flash<S: SubstateDatabase>(
+    substate_db: &S,
+    republications: Vec<NativePackageRepublication>,
+) -> Result<FlashReceipt, RuntimeError> {
+    let mut database_updates = index_map_new();
+    let mut system_updates = SystemUpdates::default();
+
+    for republication in republications {
+        let package_structure = PackageNativePackage::validate_and_build_package_structure(
+            republication.definition,
+            VmType::Native,
+            republication.native_package_code_id.to_be_bytes().to_vec(),
+        )?;
+        let partitions =
+            create_bootstrap_package_partitions(package_structure, republication.metadata_init);
+
+        let node_id = republication.package_address.into_node_id();
+        for (partition_num, partition_substates) in partitions {
+            let partition_key = SpreadPrefixKeyMapper::to_db_partition_key(&node_id, partition_num);
+            let mut partition_updates = index_map_new();
+            let mut substate_updates = index_map_new();
+            for (substate_key, value) in partition_substates {
+                let key = SpreadPrefixKeyMapper::to_db_sort_key(&substate_key);
+                let update = DatabaseUpdate::Set(value.into());
+                partition_updates.insert(key, update.clone());
+                substate_updates.insert(substate_key, update);
+            }
+
+            for (key, _) in substate_db.list_entries(&partition_key) {
+                if partition_updates.contains_key(&key) {
+                    continue;
+                }
+                // Apart from the type info, all the package's partitions are key-value ones
+                let substate_key = if partition_num == TYPE_INFO_FIELD_PARTITION {
+                    SpreadPrefixKeyMapper::from_db_sort_key::<FieldKey>(&key)
+                } else {
+                    SpreadPrefixKeyMapper::from_db_sort_key::<MapKey>(&key)
+                };
+                partition_updates.insert(key, DatabaseUpdate::Delete);
+                substate_updates.insert(substate_key, DatabaseUpdate::Delete);
+            }
+
+            database_updates.insert(partition_key, partition_updates);
+            system_updates.insert((node_id, partition_num), substate_updates);
+        }
+    }
+
+    Ok(FlashReceipt {
+        database_updates,
+        system_updates,
+        state_update_summary: StateUpdateSummary::default(),
+    })
+}
@@ -38,6 +38,7 @@ use radix_engine_interface::schema::{
 use resources_tracker_macro::trace_resources;
 use sbor::rust::string::ToString;
 use sbor::rust::vec::Vec;
+use transaction::validation::{recover_secp256k1, verify_ed25519, verify_secp256k1};
 
 #[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
 pub enum SubstateMutability {
@@ -549,6 +550,64 @@ where
         Ok(definition)
     }
 
+    /// Returns the latest version of a blueprint, i.e. the version of the new objects of and the
+    /// function calls to the blueprint.
+    pub fn get_blueprint_latest_version(
+        &mut self,
+        blueprint_id: &BlueprintId,
+    ) -> Result<BlueprintVersion, RuntimeError> {
+        // Native blueprints are never upgraded, and the invocation of blueprints of packages which
+        // aren't visible fails anyway
+        if blueprint_id.package_address.is_native_address()
+            || !self
+                .api
+                .kernel_get_node_visibility(blueprint_id.package_address.as_node_id())
+                .can_be_invoked(false)
+        {
+            return Ok(BlueprintVersion::default());
+        }
+
+        if let Some(version) = self
+            .api
+            .kernel_get_system_state()
+            .system
+            .blueprint_version_cache
+            .get(blueprint_id)
+        {
+            return Ok(*version);
+        }
+
+        let handle = self.api.kernel_open_substate_with_default(
+            blueprint_id.package_address.as_node_id(),
+            MAIN_BASE_PARTITION
+                .at_offset(PACKAGE_BLUEPRINT_UPGRADES_PARTITION_OFFSET)
+                .unwrap(),
+            &SubstateKey::Map(scrypto_encode(&blueprint_id.blueprint_name).unwrap()),
+            LockFlags::read_only(),
+            Some(|| {
+                let kv_entry = KeyValueEntrySubstate::<()>::default();
+                IndexedScryptoValue::from_typed(&kv_entry)
+            }),
+            SystemLockData::default(),
+        )?;
+
+        let substate: KeyValueEntrySubstate<BlueprintUpgrades> =
+            self.api.kernel_read_substate(handle)?.as_typed().unwrap();
+        self.api.kernel_close_substate(handle)?;
+
+        let version = substate
+            .value
+            .map(|upgrades| upgrades.latest_version())
+            .unwrap_or_default();
+        self.api
+            .kernel_get_system_state()
+            .system
+            .blueprint_version_cache
+            .insert(blueprint_id.clone(), version);
+
+        Ok(version)
+    }
+
     pub fn prepare_global_address(
         &mut self,
         blueprint_id: BlueprintId,
@@ -649,16 +708,19 @@ where
     fn new_object_internal(
         &mut self,
         blueprint_id: &BlueprintId,
+        version: BlueprintVersion,
         features: Vec<&str>,
         instance_context: Option<InstanceContext>,
         instance_schema: Option<InstanceSchema>,
         fields: Vec<Vec<u8>>,
         kv_entries: BTreeMap<u8, BTreeMap<Vec<u8>, KVEntry>>,
     ) -> Result<NodeId, RuntimeError> {
-        let blueprint_interface = self.get_blueprint_default_interface(
-            blueprint_id.package_address,
-            blueprint_id.blueprint_name.as_str(),
-        )?;
+        let blueprint_interface = self
+            .get_blueprint_definition(
+                blueprint_id.package_address,
+                &BlueprintVersionKey::new(blueprint_id.blueprint_name.as_str(), version),
+            )?
+            .interface;
         let expected_outer_blueprint = blueprint_interface.blueprint_type.clone();
 
         let (blueprint_info, object_features, outer_object_features) =
@@ -722,7 +784,7 @@ where
                     global:false,
 
                     blueprint_id: blueprint_id.clone(),
-                    version: BlueprintVersion::default(),
+                    version,
 
                     blueprint_info,
                     features: object_features,
@@ -778,10 +840,12 @@ where
                 let address = method.module_object_info.get_outer_object();
                 let info = self.get_object_info(address.as_node_id())?;
 
-                let blueprint_interface = self.get_blueprint_default_interface(
-                    info.blueprint_id.package_address,
-                    info.blueprint_id.blueprint_name.as_str(),
-                )?;
+                let blueprint_interface = self
+                    .get_blueprint_definition(
+                        info.blueprint_id.package_address,
+                        &info.blueprint_version_key(),
+                    )?
+                    .interface;
 
                 Ok((
                     address.into_node_id(),
@@ -794,10 +858,12 @@ where
                 let node_id = method.node_id;
                 let info = method.module_object_info.clone();
                 let object_module_id = method.module_id;
-                let blueprint_interface = self.get_blueprint_default_interface(
-                    info.blueprint_id.package_address,
-                    info.blueprint_id.blueprint_name.as_str(),
-                )?;
+                let blueprint_interface = self
+                    .get_blueprint_definition(
+                        info.blueprint_id.package_address,
+                        &info.blueprint_version_key(),
+                    )?
+                    .interface;
                 Ok((
                     node_id,
                     object_module_id.base_partition_num(),
@@ -1059,10 +1125,11 @@ where
             .unwrap();
         self.api.kernel_close_substate(lock_handle)?;
 
-        let blueprint_id = match &mut type_info {
+        let (blueprint_id, version) = match &mut type_info {
             TypeInfoSubstate::Object(ObjectInfo {
                 global,
                 blueprint_id: blueprint,
+                version,
                 ..
             }) => {
                 if *global {
@@ -1079,7 +1146,7 @@ where
                     *global = true;
                 }
 
-                blueprint
+                (blueprint, *version)
             }
             _ => {
                 return Err(RuntimeError::SystemError(SystemError::CannotGlobalize(
@@ -1088,10 +1155,12 @@ where
             }
         };
 
-        let interface = self.get_blueprint_default_interface(
-            blueprint_id.package_address,
-            blueprint_id.blueprint_name.as_str(),
-        )?;
+        let interface = self
+            .get_blueprint_definition(
+                blueprint_id.package_address,
+                &BlueprintVersionKey::new(blueprint_id.blueprint_name.as_str(), version),
+            )?
+            .interface;
 
         let num_main_partitions = interface.state.num_partitions();
 
@@ -1179,6 +1248,11 @@ where
             .map(|a| (a.node_id, a.is_direct_access))
     }
 
+    pub fn actor_get_blueprint_version_key(&mut self) -> BlueprintVersionKey {
+        let actor = self.api.kernel_get_system_state().current;
+        actor.blueprint_version_key()
+    }
+
     pub fn actor_get_fn_identifier(&mut self) -> Result<FnIdentifier, RuntimeError> {
         let actor = self.api.kernel_get_system_state().current;
         Ok(actor.fn_identifier())
@@ -1284,9 +1358,22 @@ where
         let package_address = actor.package_address().clone();
         let instance_context = actor.instance_context();
         let blueprint = BlueprintId::new(&package_address, blueprint_ident);
+        // Objects created by the code of their own blueprint are of its version, others are of
+        // the latest version
+        let actor_version = match actor {
+            Actor::Method(..) | Actor::Function { .. } if actor.blueprint_id().eq(&blueprint) => {
+                Some(actor.blueprint_version_key().version)
+            }
+            _ => None,
+        };
+        let version = match actor_version {
+            Some(version) => version,
+            None => self.get_blueprint_latest_version(&blueprint)?,
+        };
 
         self.new_object_internal(
             &blueprint,
+            version,
             features,
             instance_context,
             schema,
@@ -1371,9 +1458,11 @@ where
         let global_address = self.globalize_with_address_internal(modules, address_reservation)?;
 
         let blueprint = BlueprintId::new(&actor_blueprint.package_address, inner_object_blueprint);
+        let version = self.get_blueprint_latest_version(&blueprint)?;
 
         let inner_object = self.new_object_internal(
             &blueprint,
+            version,
             vec![],
             Some(InstanceContext {
                 outer_object: global_address,
@@ -1645,7 +1734,13 @@ where
         handle: KeyValueEntryHandle,
         buffer: Vec<u8>,
     ) -> Result<(), RuntimeError> {
-        let LockInfo { data, .. } = self.api.kernel_get_lock_info(handle)?;
+        let LockInfo {
+            node_id,
+            partition_num,
+            substate_key,
+            data,
+            ..
+        } = self.api.kernel_get_lock_info(handle)?;
 
         let can_own = match data {
             SystemLockData::KeyValueEntry(KeyValueEntryLockData::BlueprintWrite {
@@ -1702,6 +1797,26 @@ where
 
         self.api.kernel_write_substate(handle, indexed)?;
 
+        // Upgrading a blueprint changes its latest version
+        if node_id.is_global_package()
+            && partition_num
+                == MAIN_BASE_PARTITION
+                    .at_offset(PACKAGE_BLUEPRINT_UPGRADES_PARTITION_OFFSET)
+                    .unwrap()
+        {
+            if let SubstateKey::Map(key) = substate_key {
+                let blueprint_name: String = scrypto_decode(&key).unwrap();
+                self.api
+                    .kernel_get_system_state()
+                    .system
+                    .blueprint_version_cache
+                    .remove(&BlueprintId::new(
+                        &PackageAddress::new_or_panic(node_id.0),
+                        blueprint_name,
+                    ));
+            }
+        }
+
         Ok(())
     }
 
@@ -1840,6 +1955,64 @@ where
         let handle = self.key_value_store_open_entry(node_id, key, LockFlags::MUTABLE)?;
         self.key_value_entry_remove_and_close_substate(handle)
     }
+
+    // Costing through kernel
+    #[trace_resources]
+    fn key_value_store_scan(
+        &mut self,
+        node_id: &NodeId,
+        start_key: Option<Vec<u8>>,
+        limit: u32,
+    ) -> Result<(Vec<(Vec<u8>, Vec<u8>)>, Option<Vec<u8>>), RuntimeError> {
+        let type_info = TypeInfoBlueprint::get_type(&node_id, self.api)?;
+        if !matches!(type_info, TypeInfoSubstate::KeyValueStore(..)) {
+            return Err(RuntimeError::SystemError(SystemError::NotAKeyValueStore));
+        }
+
+        let limit: usize = limit.try_into().unwrap();
+        let mut entries = Vec::new();
+        let mut from_key = start_key.map(SubstateKey::Map);
+        let mut is_continuation = false;
+        loop {
+            // One more entry than needed is scanned, so that the key to continue from is known
+            // (and yet one more when continuing, since the entry at `from_key` was already seen).
+            // Removed entries are kept as empty substates, so they are skipped rather than counted.
+            let count = u32::try_from(limit - entries.len() + 1 + usize::from(is_continuation))
+                .unwrap_or(u32::MAX);
+            let substates = self.api.kernel_scan_substates_from(
+                &node_id,
+                MAIN_BASE_PARTITION,
+                from_key.as_ref(),
+                count,
+            )?;
+            let is_end = substates.len() < count as usize;
+
+            let mut last_key = None;
+            for (index, (key, value)) in substates.into_iter().enumerate() {
+                if is_continuation && index == 0 {
+                    continue;
+                }
+                let key = match key {
+                    SubstateKey::Map(key) => key,
+                    _ => continue,
+                };
+                if entries.len() == limit {
+                    return Ok((entries, Some(key)));
+                }
+                let kv_entry: KeyValueEntrySubstate<ScryptoValue> = value.as_typed().unwrap();
+                if let Some(value) = kv_entry.value {
+                    entries.push((key.clone(), scrypto_encode(&value).unwrap()));
+                }
+                last_key = Some(key);
+            }
+
+            if is_end {
+                return Ok((entries, None));
+            }
+            from_key = last_key.map(SubstateKey::Map);
+            is_continuation = true;
+        }
+    }
 }
 
 impl<'a, Y, V> ClientActorIndexApi<RuntimeError> for SystemService<'a, Y, V>
@@ -1906,7 +2079,7 @@ where
             .api
             .kernel_scan_substates(&node_id, partition_num, count)?
             .into_iter()
-            .map(|value| value.into())
+            .map(|(_key, value)| value.into())
             .collect();
 
         Ok(substates)
@@ -2036,9 +2209,10 @@ where
             BlueprintId::new(&package_address, blueprint_name),
             function_name.to_string(),
         );
+        let version = self.get_blueprint_latest_version(&identifier.0)?;
 
         let invocation = KernelInvocation {
-            actor: Actor::function(identifier.0, identifier.1),
+            actor: Actor::function(identifier.0, version, identifier.1),
             args: IndexedScryptoValue::from_vec(args).map_err(|e| {
                 RuntimeError::SystemUpstreamError(SystemUpstreamError::InputDecodeError(e))
             })?,
@@ -2090,6 +2264,9 @@ where
                 ClientCostingEntry::PrepareWasmCode { size } => {
                     CostingEntry::PrepareWasmCode { size }
                 }
+                ClientCostingEntry::AccessIndex { entry_count, size } => {
+                    CostingEntry::AccessIndex { entry_count, size }
+                }
             })
     }
 
@@ -2529,11 +2706,11 @@ where
                     )))
                 }
             };
+            let bp_version_key = actor.blueprint_version_key();
 
-            let blueprint_interface = self.get_blueprint_default_interface(
-                blueprint_id.package_address,
-                blueprint_id.blueprint_name.as_str(),
-            )?;
+            let blueprint_interface = self
+                .get_blueprint_definition(blueprint_id.package_address, &bp_version_key)?
+                .interface;
 
             let type_pointer = blueprint_interface
                 .get_event_type_pointer(event_name.as_str())
@@ -2654,6 +2831,86 @@ where
     }
 }
 
+impl<'a, Y, V> ClientCryptoUtilsApi<RuntimeError> for SystemService<'a, Y, V>
+where
+    Y: KernelApi<SystemConfig<V>>,
+    V: SystemCallbackObject,
+{
+    #[trace_resources]
+    fn blake2b_256_hash(&mut self, data: Vec<u8>) -> Result<Hash, RuntimeError> {
+        self.api
+            .kernel_get_system()
+            .modules
+            .apply_execution_cost(CostingEntry::Blake2b256Hash { size: data.len() })?;
+
+        Ok(blake2b_256_hash(data))
+    }
+
+    #[trace_resources]
+    fn keccak256_hash(&mut self, data: Vec<u8>) -> Result<Hash, RuntimeError> {
+        self.api
+            .kernel_get_system()
+            .modules
+            .apply_execution_cost(CostingEntry::Keccak256Hash { size: data.len() })?;
+
+        Ok(keccak256_hash(data))
+    }
+
+    #[trace_resources]
+    fn sha256_hash(&mut self, data: Vec<u8>) -> Result<Hash, RuntimeError> {
+        self.api
+            .kernel_get_system()
+            .modules
+            .apply_execution_cost(CostingEntry::Sha256Hash { size: data.len() })?;
+
+        Ok(sha256_hash(data))
+    }
+
+    #[trace_resources]
+    fn secp256k1_ecdsa_verify(
+        &mut self,
+        message_hash: Hash,
+        public_key: Secp256k1PublicKey,
+        signature: Secp256k1Signature,
+    ) -> Result<bool, RuntimeError> {
+        self.api
+            .kernel_get_system()
+            .modules
+            .apply_execution_cost(CostingEntry::Secp256k1EcdsaVerify)?;
+
+        Ok(verify_secp256k1(&message_hash, &public_key, &signature))
+    }
+
+    #[trace_resources]
+    fn secp256k1_ecdsa_recover(
+        &mut self,
+        message_hash: Hash,
+        signature: Secp256k1Signature,
+    ) -> Result<Option<Secp256k1PublicKey>, RuntimeError> {
+        self.api
+            .kernel_get_system()
+            .modules
+            .apply_execution_cost(CostingEntry::Secp256k1EcdsaRecover)?;
+
+        Ok(recover_secp256k1(&message_hash, &signature))
+    }
+
+    #[trace_resources]
+    fn ed25519_verify(
+        &mut self,
+        message_hash: Hash,
+        public_key: Ed25519PublicKey,
+        signature: Ed25519Signature,
+    ) -> Result<bool, RuntimeError> {
+        self.api
+            .kernel_get_system()
+            .modules
+            .apply_execution_cost(CostingEntry::Ed25519Verify)?;
+
+        Ok(verify_ed25519(&message_hash, &public_key, &signature))
+    }
+}
+
 impl<'a, Y, V> ClientApi<RuntimeError> for SystemService<'a, Y, V>
 where
     Y: KernelApi<SystemConfig<V>>,
@@ -2784,11 +3041,22 @@ where
         node_id: &NodeId,
         partition_num: PartitionNumber,
         count: u32,
-    ) -> Result<Vec<IndexedScryptoValue>, RuntimeError> {
+    ) -> Result<Vec<(SubstateKey, IndexedScryptoValue)>, RuntimeError> {
         self.api
             .kernel_scan_substates(node_id, partition_num, count)
     }
 
+    fn kernel_scan_substates_from(
+        &mut self,
+        node_id: &NodeId,
+        partition_num: PartitionNumber,
+        from_key: Option<&SubstateKey>,
+        count: u32,
+    ) -> Result<Vec<(SubstateKey, IndexedScryptoValue)>, RuntimeError> {
+        self.api
+            .kernel_scan_substates_from(node_id, partition_num, from_key, count)
+    }
+
     fn kernel_take_substates(
         &mut self,
         node_id: &NodeId,
@@ -78,6 +78,8 @@ impl SystemLockData {
 pub struct SystemConfig<C: SystemCallbackObject> {
     pub callback_obj: C,
     pub blueprint_cache: NonIterMap<CanonicalBlueprintId, BlueprintDefinition>,
+    /// The latest versions of the blueprints, which are invalidated by their upgrades.
+    pub blueprint_version_cache: NonIterMap<BlueprintId, BlueprintVersion>,
     pub schema_cache: NonIterMap<Hash, ScryptoSchema>,
     pub auth_cache: NonIterMap<CanonicalBlueprintId, AuthConfig>,
     pub modules: SystemModuleMixer,
@@ -306,14 +308,10 @@ impl<C: SystemCallbackObject> KernelCallbackObject for SystemConfig<C> {
             blueprint_id,
             ident,
         } = system.actor_get_fn_identifier()?;
+        let key = system.actor_get_blueprint_version_key();
 
         let output = {
             // Make dependent resources/components visible
-            let key = BlueprintVersionKey {
-                blueprint: blueprint_id.blueprint_name.clone(),
-                version: BlueprintVersion::default(),
-            };
-
             let handle = system.kernel_open_substate_with_default(
                 blueprint_id.package_address.as_node_id(),
                 MAIN_BASE_PARTITION
@@ -331,10 +329,7 @@ impl<C: SystemCallbackObject> KernelCallbackObject for SystemConfig<C> {
             system.kernel_close_substate(handle)?;
 
             //  Validate input
-            let definition = system.get_blueprint_definition(
-                blueprint_id.package_address,
-                &BlueprintVersionKey::new_default(blueprint_id.blueprint_name.as_str()),
-            )?;
+            let definition = system.get_blueprint_definition(blueprint_id.package_address, &key)?;
 
             let export = match &ident {
                 FnIdent::Application(ident) => {
@@ -107,12 +107,16 @@ impl AuthModule {
                 }
                 Actor::Function {
                     blueprint_id,
+                    version,
                     ident,
                 } => {
                     let resolved_permission =
                         PackageAuthNativeBlueprint::resolve_function_permission(
                             blueprint_id.package_address.as_node_id(),
-                            &BlueprintVersionKey::new_default(blueprint_id.blueprint_name.as_str()),
+                            &BlueprintVersionKey::new(
+                                blueprint_id.blueprint_name.as_str(),
+                                *version,
+                            ),
                             ident.as_str(),
                             system.api,
                         )?;
@@ -219,13 +223,7 @@ impl AuthModule {
                 .blueprint_id
                 .package_address
                 .as_node_id(),
-            &BlueprintVersionKey::new_default(
-                callee
-                    .module_object_info
-                    .blueprint_id
-                    .blueprint_name
-                    .as_str(),
-            ),
+            &callee.module_object_info.blueprint_version_key(),
             api.api,
         )?
         .method_auth;
@@ -86,6 +86,10 @@ pub enum CostingEntry<'a> {
     TakeSubstate {
         store_access: &'a StoreAccessInfo,
     },
+    AccessIndex {
+        entry_count: usize,
+        size: usize,
+    },
 
     /* commit */
     Commit {
@@ -110,6 +114,20 @@ pub enum CostingEntry<'a> {
         size: usize,
     },
 
+    /* crypto utils */
+    Blake2b256Hash {
+        size: usize,
+    },
+    Keccak256Hash {
+        size: usize,
+    },
+    Sha256Hash {
+        size: usize,
+    },
+    Secp256k1EcdsaVerify,
+    Secp256k1EcdsaRecover,
+    Ed25519Verify,
+
     /* system modules */
     RoyaltyModule {
         direct_charge: u32,
@@ -176,6 +194,9 @@ impl<'a> CostingEntry<'a> {
             }
             CostingEntry::ScanSubstates { store_access } => ft.scan_substates_cost(store_access),
             CostingEntry::TakeSubstate { store_access } => ft.take_substates_cost(store_access),
+            CostingEntry::AccessIndex { entry_count, size } => {
+                ft.access_index_cost(*entry_count, *size)
+            }
             CostingEntry::Commit { store_commit } => ft.store_commit_cost(store_commit),
             CostingEntry::LockFee => ft.lock_fee_cost(),
             CostingEntry::QueryFeeReserve => ft.query_fee_reserve_cost(),
@@ -187,6 +208,12 @@ impl<'a> CostingEntry<'a> {
             CostingEntry::EmitEvent { size } => ft.emit_event_cost(*size),
             CostingEntry::EmitLog { size } => ft.emit_log_cost(*size),
             CostingEntry::Panic { size } => ft.panic_cost(*size),
+            CostingEntry::Blake2b256Hash { size } => ft.blake2b_256_hash_cost(*size),
+            CostingEntry::Keccak256Hash { size } => ft.keccak256_hash_cost(*size),
+            CostingEntry::Sha256Hash { size } => ft.sha256_hash_cost(*size),
+            CostingEntry::Secp256k1EcdsaVerify => ft.secp256k1_ecdsa_verify_cost(),
+            CostingEntry::Secp256k1EcdsaRecover => ft.secp256k1_ecdsa_recover_cost(),
+            CostingEntry::Ed25519Verify => ft.ed25519_verify_cost(),
             CostingEntry::RoyaltyModule { direct_charge } => *direct_charge,
             CostingEntry::AuthModule { direct_charge } => *direct_charge,
         }
@@ -226,7 +226,7 @@ impl<V: SystemCallbackObject> SystemModule<SystemConfig<V>> for CostingModule {
         //===========================
         // Apply package royalty
         //===========================
-        let bp_version_key = BlueprintVersionKey::new_default(blueprint.blueprint_name.as_str());
+        let bp_version_key = callee.blueprint_version_key();
         PackageRoyaltyNativeBlueprint::charge_package_royalty(
             blueprint.package_address.as_node_id(),
             &bp_version_key,
@@ -37,6 +37,14 @@ lazy_static! {
             .entry(PACKAGE_PACKAGE)
             .or_default()
             .insert(PACKAGE_PUBLISH_WASM_ADVANCED_IDENT, (1 /*1055*/, 14305886));
+        // Upgrading a blueprint validates and instruments the new code through the same
+        // `validate_and_build_package_structure` as `publish_wasm_advanced`, which dominates both
+        // costs; the reads of the previous definition and the writes of the new one are charged
+        // separately, as substate accesses.
+        costs
+            .entry(PACKAGE_PACKAGE)
+            .or_default()
+            .insert(PACKAGE_UPGRADE_BLUEPRINT_IDENT, (1 /*1055*/, 14305886));
         costs
     };
 }
@@ -305,6 +313,17 @@ impl FeeTable {
         add(500, Self::store_access_cost(store_access))
     }
 
+    #[inline]
+    pub fn access_index_cost(&self, entry_count: usize, size: usize) -> u32 {
+        // The scan/take costs above only depend on the database reads, so the entries already
+        // loaded into the track would be free. Each entry is therefore charged as if it was
+        // accessed on its own, plus the processing of the bytes copied to/from the WASM memory.
+        add(
+            mul(cast(entry_count), 500),
+            Self::data_processing_cost(size),
+        )
+    }
+
     //======================
     // System costs
     //======================
@@ -359,6 +378,56 @@ impl FeeTable {
         500 + Self::data_processing_cost(size) + Self::transient_data_cost(size)
     }
 
+    //======================
+    // Crypto utils costs
+    //======================
+    // The crypto benchmarks were run alongside `bench_validate_secp256k1` and scaled to its
+    // 67.522 µs reference, at 100 units/µs. The base cost of 500 covers the host call itself.
+
+    #[inline]
+    pub fn blake2b_256_hash_cost(&self, size: usize) -> u32 {
+        // Based on benchmark `bench_blake2b_256_hash`
+        // Hashing 1 MiB takes 1,200 µs, i.e. 0.114 units per byte, charged as 1 unit per 8 bytes
+        add(500, cast(size / 8))
+    }
+
+    #[inline]
+    pub fn keccak256_hash_cost(&self, size: usize) -> u32 {
+        // Based on benchmark `bench_keccak256_hash`
+        // Hashing 1 MiB takes 2,875 µs, i.e. 0.274 units per byte, charged as 1 unit per 3 bytes
+        add(500, cast(size / 3))
+    }
+
+    #[inline]
+    pub fn sha256_hash_cost(&self, size: usize) -> u32 {
+        // Based on benchmark `bench_sha256_hash`, without hardware acceleration (which validators
+        // cannot be assumed to have)
+        // Hashing 1 MiB takes 3,750 µs, i.e. 0.358 units per byte, charged as 1 unit per 2 bytes
+        add(500, cast(size / 2))
+    }
+
+    #[inline]
+    pub fn secp256k1_ecdsa_verify_cost(&self) -> u32 {
+        // Based on benchmark `bench_verify_secp256k1`
+        // Unlike a transaction signature validation, no public key is recovered:
+        // 31.0 µs * 100 units/µs = 3,100 cost units
+        3_100
+    }
+
+    #[inline]
+    pub fn secp256k1_ecdsa_recover_cost(&self) -> u32 {
+        // Based on benchmark `bench_recover_secp256k1`, including the public key serialization
+        // 35.1 µs * 100 units/µs = 3,510 cost units, rounded up to 3,600
+        3_600
+    }
+
+    #[inline]
+    pub fn ed25519_verify_cost(&self) -> u32 {
+        // Based on benchmark `bench_verify_ed25519`
+        // 45.4 µs * 100 units/µs = 4,540 cost units, rounded up to 4,600
+        4_600
+    }
+
     //======================
     // System module costs
     //======================
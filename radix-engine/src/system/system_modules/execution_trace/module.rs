@@ -479,6 +479,7 @@ impl ExecutionTraceModule {
                 Actor::Function {
                     blueprint_id: blueprint,
                     ident,
+                    ..
                 } => Origin::ScryptoFunction(ApplicationFnIdentifier {
                     package_address: blueprint.package_address.clone(),
                     blueprint_name: blueprint.blueprint_name.clone(),
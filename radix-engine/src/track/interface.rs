@@ -21,6 +21,11 @@ pub enum TakeSubstateError {
     SubstateLocked(NodeId, PartitionNumber, SubstateKey),
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum ScanSubstatesError {
+    SubstateLocked(NodeId, PartitionNumber, SubstateKey),
+}
+
 pub type NodeSubstates = BTreeMap<PartitionNumber, BTreeMap<SubstateKey, IndexedScryptoValue>>;
 
 /// Represents the interface between Radix Engine and Track.
@@ -63,13 +68,32 @@ pub trait SubstateStore {
         substate_key: &SubstateKey,
     ) -> Result<(Option<IndexedScryptoValue>, StoreAccessInfo), TakeSubstateError>;
 
-    /// Returns tuple of substate vector and boolean which is true for the first database access.
+    /// Returns tuple of (substate key, substate) vector and boolean which is true for the first
+    /// database access.
+    ///
+    /// Clients must ensure this is only called on a partition keyed by [`SubstateKey::Map`].
     fn scan_substates(
         &mut self,
         node_id: &NodeId,
         partition_num: PartitionNumber,
         count: u32,
-    ) -> (Vec<IndexedScryptoValue>, StoreAccessInfo);
+    ) -> Result<(Vec<(SubstateKey, IndexedScryptoValue)>, StoreAccessInfo), ScanSubstatesError>;
+
+    /// Returns tuple of (substate key, substate) vector and boolean which is true for the first
+    /// database access.
+    ///
+    /// Unlike [`SubstateStore::scan_substates`], the substates are returned in the order of their
+    /// database sort keys, starting from the given substate key (inclusive), or from the first
+    /// substate if [`Option::None`]. This allows a partition to be paged through.
+    ///
+    /// Clients must ensure this is only called on a partition keyed by [`SubstateKey::Map`].
+    fn scan_substates_from(
+        &mut self,
+        node_id: &NodeId,
+        partition_num: PartitionNumber,
+        from_key: Option<&SubstateKey>,
+        count: u32,
+    ) -> Result<(Vec<(SubstateKey, IndexedScryptoValue)>, StoreAccessInfo), ScanSubstatesError>;
 
     /// Returns tuple of substate vector and boolean which is true for the first database access.
     fn take_substates(
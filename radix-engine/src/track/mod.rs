@@ -1,8 +1,10 @@
 pub mod interface;
+pub mod overlay;
 pub mod track;
 pub mod utils;
 
 #[cfg(test)]
 mod test;
 
+pub use overlay::*;
 pub use track::*;
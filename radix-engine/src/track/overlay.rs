@@ -0,0 +1,131 @@
+use crate::track::utils::OverlayingIterator;
+use crate::types::*;
+use radix_engine_store_interface::interface::*;
+use sbor::rust::cmp::Reverse;
+
+/// A [`SubstateDatabase`] which applies a set of in-memory substate changes on top of an
+/// underlying (read-only) database.
+/// Committing to this database only updates the in-memory changes - the underlying database is
+/// never written to.
+pub struct SubstateDatabaseOverlay<'s, S: SubstateDatabase> {
+    root: &'s S,
+    /// The overlaid changes, sorted within each partition (needed for range iteration), where
+    /// [`None`] represents a deleted substate.
+    overlaid: IndexMap<DbPartitionKey, BTreeMap<DbSortKey, Option<DbSubstateValue>>>,
+}
+
+impl<'s, S: SubstateDatabase> SubstateDatabaseOverlay<'s, S> {
+    pub fn new(root: &'s S) -> Self {
+        Self {
+            root,
+            overlaid: index_map_new(),
+        }
+    }
+
+    pub fn with_updates(root: &'s S, database_updates: &DatabaseUpdates) -> Self {
+        let mut overlay = Self::new(root);
+        overlay.commit(database_updates);
+        overlay
+    }
+
+    pub fn root(&self) -> &'s S {
+        self.root
+    }
+
+    /// Returns the overlaid changes, as [`DatabaseUpdates`] which could be committed to the
+    /// underlying database.
+    pub fn database_updates(&self) -> DatabaseUpdates {
+        self.overlaid
+            .iter()
+            .map(|(partition_key, partition_changes)| {
+                let partition_updates = partition_changes
+                    .iter()
+                    .map(|(sort_key, change)| {
+                        let update = match change {
+                            Some(value) => DatabaseUpdate::Set(value.clone()),
+                            None => DatabaseUpdate::Delete,
+                        };
+                        (sort_key.clone(), update)
+                    })
+                    .collect();
+                (partition_key.clone(), partition_updates)
+            })
+            .collect()
+    }
+}
+
+impl<'s, S: SubstateDatabase> SubstateDatabase for SubstateDatabaseOverlay<'s, S> {
+    fn get_substate(
+        &self,
+        partition_key: &DbPartitionKey,
+        sort_key: &DbSortKey,
+    ) -> Option<DbSubstateValue> {
+        match self
+            .overlaid
+            .get(partition_key)
+            .and_then(|partition_changes| partition_changes.get(sort_key))
+        {
+            Some(change) => change.clone(),
+            None => self.root.get_substate(partition_key, sort_key),
+        }
+    }
+
+    fn list_entries_from(
+        &self,
+        partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
+    ) -> Box<dyn Iterator<Item = PartitionEntry> + '_> {
+        let underlying = self.root.list_entries_from(partition_key, from_sort_key);
+        let Some(partition_changes) = self.overlaid.get(partition_key) else {
+            return underlying;
+        };
+        let overlaid = match from_sort_key {
+            Some(from_sort_key) => partition_changes.range(from_sort_key.clone()..),
+            None => partition_changes.range(..),
+        }
+        .map(|(sort_key, change)| (sort_key.clone(), change.clone()));
+        Box::new(OverlayingIterator::new(underlying, overlaid))
+    }
+
+    fn list_entries_rev_from(
+        &self,
+        partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
+    ) -> Box<dyn Iterator<Item = PartitionEntry> + '_> {
+        let underlying = self
+            .root
+            .list_entries_rev_from(partition_key, from_sort_key);
+        let Some(partition_changes) = self.overlaid.get(partition_key) else {
+            return underlying;
+        };
+        let overlaid = match from_sort_key {
+            Some(from_sort_key) => partition_changes.range(..=from_sort_key.clone()).rev(),
+            None => partition_changes.range(..).rev(),
+        }
+        .map(|(sort_key, change)| (Reverse(sort_key.clone()), change.clone()));
+        // the `OverlayingIterator` expects an ascending order, hence the `Reverse` keys
+        let underlying = underlying.map(|(sort_key, value)| (Reverse(sort_key), value));
+        Box::new(
+            OverlayingIterator::new(underlying, overlaid)
+                .map(|(Reverse(sort_key), value)| (sort_key, value)),
+        )
+    }
+}
+
+impl<'s, S: SubstateDatabase> CommittableSubstateDatabase for SubstateDatabaseOverlay<'s, S> {
+    fn commit(&mut self, database_updates: &DatabaseUpdates) {
+        for (partition_key, partition_updates) in database_updates {
+            let partition_changes = self
+                .overlaid
+                .entry(partition_key.clone())
+                .or_insert_with(|| BTreeMap::new());
+            for (sort_key, update) in partition_updates {
+                let change = match update {
+                    DatabaseUpdate::Set(value) => Some(value.clone()),
+                    DatabaseUpdate::Delete => None,
+                };
+                partition_changes.insert(sort_key.clone(), change);
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::track::interface::{
-    AcquireLockError, NodeSubstates, SetSubstateError, StoreAccess, StoreAccessInfo, SubstateStore,
-    TakeSubstateError,
+    AcquireLockError, NodeSubstates, ScanSubstatesError, SetSubstateError, StoreAccess,
+    StoreAccessInfo, SubstateStore, TakeSubstateError,
 };
 use crate::track::utils::OverlayingIterator;
 use crate::types::*;
@@ -14,6 +14,7 @@ use radix_engine_store_interface::{
 use sbor::rust::collections::btree_map::Entry;
 use sbor::rust::iter::empty;
 use sbor::rust::mem;
+use sbor::rust::ops::Bound;
 
 use super::interface::{StoreCommit, StoreCommitInfo};
 
@@ -159,6 +160,22 @@ impl TrackedSubstateValue {
         }
     }
 
+    fn is_write_locked(&self) -> bool {
+        match self {
+            TrackedSubstateValue::New(substate)
+            | TrackedSubstateValue::WriteOnly(Write::Update(substate))
+            | TrackedSubstateValue::ReadOnly(ReadOnly::Existent(substate))
+            | TrackedSubstateValue::ReadExistAndWrite(_, Write::Update(substate))
+            | TrackedSubstateValue::ReadNonExistAndWrite(substate) => {
+                matches!(substate.lock_state, SubstateLockState::Write)
+            }
+            TrackedSubstateValue::WriteOnly(Write::Delete)
+            | TrackedSubstateValue::ReadExistAndWrite(_, Write::Delete)
+            | TrackedSubstateValue::ReadOnly(ReadOnly::NonExistent)
+            | TrackedSubstateValue::Garbage => false,
+        }
+    }
+
     pub fn set(&mut self, value: IndexedScryptoValue) {
         match self {
             TrackedSubstateValue::Garbage => {
@@ -427,6 +444,7 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> Track<'s, S, M> {
     fn list_entries_from_db<'x>(
         substate_db: &'x S,
         partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
         store_access: &'x mut StoreAccessInfo,
     ) -> Box<dyn Iterator<Item = (DbSortKey, IndexedScryptoValue)> + 'x> {
         struct TracedIterator<'a, 'b> {
@@ -453,7 +471,7 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> Track<'s, S, M> {
         }
 
         Box::new(TracedIterator {
-            iterator: substate_db.list_entries(partition_key),
+            iterator: substate_db.list_entries_from(partition_key, from_sort_key),
             store_access,
         })
     }
@@ -760,7 +778,8 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> SubstateStore for Track<'s,
         node_id: &NodeId,
         partition_num: PartitionNumber,
         count: u32,
-    ) -> (Vec<IndexedScryptoValue>, StoreAccessInfo) {
+    ) -> Result<(Vec<(SubstateKey, IndexedScryptoValue)>, StoreAccessInfo), ScanSubstatesError>
+    {
         let mut store_access = Vec::new();
 
         let count: usize = count.try_into().unwrap();
@@ -775,25 +794,33 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> SubstateStore for Track<'s,
         if let Some(tracked_partition) = tracked_partition {
             for tracked in tracked_partition.substates.values() {
                 if items.len() == count {
-                    return (items, store_access);
+                    return Ok((items, store_access));
                 }
 
-                // TODO: Check that substate is not write locked, before use outside of native blueprints
                 if let Some(substate) = tracked.substate_value.get() {
-                    items.push(substate.clone());
+                    // A write locked substate may be halfway through an update, so it must not be observed
+                    if tracked.substate_value.is_write_locked() {
+                        return Err(ScanSubstatesError::SubstateLocked(
+                            *node_id,
+                            partition_num,
+                            tracked.substate_key.clone(),
+                        ));
+                    }
+                    items.push((tracked.substate_key.clone(), substate.clone()));
                 }
             }
         }
 
         // Optimization, no need to go into database if the node is just created
         if is_new {
-            return (items, store_access);
+            return Ok((items, store_access));
         }
 
         let db_partition_key = M::to_db_partition_key(node_id, partition_num);
         let mut tracked_iter = TrackedIter::new(Self::list_entries_from_db(
             self.substate_db,
             &db_partition_key,
+            None,
             &mut store_access,
         ));
         for (db_sort_key, value) in &mut tracked_iter {
@@ -808,7 +835,10 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> SubstateStore for Track<'s,
                 continue;
             }
 
-            items.push(value);
+            items.push((
+                SubstateKey::Map(M::map_from_db_sort_key(&db_sort_key)),
+                value,
+            ));
         }
 
         // Update track
@@ -817,7 +847,89 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> SubstateStore for Track<'s,
         tracked_partition.range_read = u32::max(tracked_partition.range_read, num_iterations);
 
         drop(tracked_iter);
-        (items, store_access)
+        Ok((items, store_access))
+    }
+
+    fn scan_substates_from(
+        &mut self,
+        node_id: &NodeId,
+        partition_num: PartitionNumber,
+        from_key: Option<&SubstateKey>,
+        count: u32,
+    ) -> Result<(Vec<(SubstateKey, IndexedScryptoValue)>, StoreAccessInfo), ScanSubstatesError>
+    {
+        let mut store_access = Vec::new();
+
+        let count: usize = count.try_into().unwrap();
+        let from_sort_key = from_key.map(|from_key| M::to_db_sort_key(from_key));
+
+        let tracked_node = self
+            .tracked_nodes
+            .entry(node_id.clone())
+            .or_insert(TrackedNode::new(false));
+        let tracked_partition = tracked_node
+            .tracked_partitions
+            .entry(partition_num)
+            .or_insert(TrackedPartition::new());
+
+        // the database is seeked directly to the requested sort key, rather than iterated from the
+        // beginning of the partition
+        let mut db_values_count = 0u32;
+        let raw_db_entries: Box<dyn Iterator<Item = (DbSortKey, IndexedScryptoValue)>> =
+            if tracked_node.is_new {
+                Box::new(empty()) // optimization: avoid touching the database altogether
+            } else {
+                let partition_key = M::to_db_partition_key(node_id, partition_num);
+                Box::new(Self::list_entries_from_db(
+                    self.substate_db,
+                    &partition_key,
+                    from_sort_key.as_ref(),
+                    &mut store_access,
+                ))
+            };
+        let db_read_entries = raw_db_entries
+            .inspect(|(_key, _value)| {
+                db_values_count += 1;
+            })
+            .map(|(key, value)| (key, (value, false)));
+
+        let from_bound = match from_sort_key {
+            Some(from_sort_key) => Bound::Included(from_sort_key),
+            None => Bound::Unbounded,
+        };
+        let tracked_entry_changes = tracked_partition
+            .substates
+            .range((from_bound, Bound::Unbounded))
+            .map(|(key, tracked_substate)| {
+                let substate_value = &tracked_substate.substate_value;
+                let change = substate_value
+                    .get()
+                    .map(|value| (value.clone(), substate_value.is_write_locked()));
+                (key.clone(), change)
+            });
+
+        let items: Vec<(DbSortKey, (IndexedScryptoValue, bool))> =
+            OverlayingIterator::new(db_read_entries, tracked_entry_changes)
+                .take(count)
+                .collect();
+
+        tracked_partition.range_read = u32::max(tracked_partition.range_read, db_values_count);
+
+        let mut substates = Vec::new();
+        for (db_sort_key, (value, is_write_locked)) in items {
+            let substate_key = SubstateKey::Map(M::map_from_db_sort_key(&db_sort_key));
+            // A write locked substate may be halfway through an update, so it must not be observed
+            if is_write_locked {
+                return Err(ScanSubstatesError::SubstateLocked(
+                    *node_id,
+                    partition_num,
+                    substate_key,
+                ));
+            }
+            substates.push((substate_key, value));
+        }
+
+        Ok((substates, store_access))
     }
 
     fn take_substates(
@@ -863,6 +975,7 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> SubstateStore for Track<'s,
         let mut tracked_iter = TrackedIter::new(Self::list_entries_from_db(
             self.substate_db,
             &db_partition_key,
+            None,
             &mut store_access,
         ));
         let new_updates = {
@@ -947,6 +1060,7 @@ impl<'s, S: SubstateDatabase, M: DatabaseKeyMapper> SubstateStore for Track<'s,
                 Box::new(Self::list_entries_from_db(
                     self.substate_db,
                     &partition_key,
+                    None,
                     &mut store_access,
                 ))
             };
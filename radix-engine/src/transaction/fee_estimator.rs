@@ -0,0 +1,119 @@
+use crate::errors::{RejectionError, RuntimeError};
+use crate::transaction::*;
+use crate::types::*;
+use crate::vm::wasm::WasmEngine;
+use crate::vm::ScryptoVm;
+use radix_engine_interface::network::NetworkDefinition;
+use radix_engine_store_interface::interface::*;
+use transaction::model::PreviewIntentV1;
+
+/// The default safety margin added on top of the estimated fee (see [`FeeEstimate`]).
+pub const DEFAULT_FEE_SAFETY_MARGIN_PERCENTAGE: u16 = 20;
+
+#[derive(Debug, Clone)]
+pub struct FeeEstimatorConfig {
+    /// A percentage of the estimated total fee added to the recommended `lock_fee` amount, to
+    /// account for the cost of the `lock_fee` call itself and for state changes happening between
+    /// the estimation and the actual execution.
+    pub safety_margin_percentage: u16,
+    /// The options of the underlying preview.
+    pub preview_options: PreviewOptions,
+}
+
+impl Default for FeeEstimatorConfig {
+    fn default() -> Self {
+        Self {
+            safety_margin_percentage: DEFAULT_FEE_SAFETY_MARGIN_PERCENTAGE,
+            preview_options: PreviewOptions::default(),
+        }
+    }
+}
+
+/// An estimate of fees to be paid by a transaction, broken down by their kind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeEstimate {
+    /// The number of cost units consumed by the execution.
+    pub execution_cost_units: u32,
+    pub execution_cost_xrd: Decimal,
+    pub state_expansion_cost_xrd: Decimal,
+    pub royalty_cost_xrd: Decimal,
+    pub tipping_cost_xrd: Decimal,
+    /// The per-`CostingEntry` breakdown of the execution cost units.
+    pub execution_cost_breakdown: BTreeMap<String, u32>,
+    /// The amount which should be passed to `lock_fee` (i.e. the total fee increased by the safety
+    /// margin).
+    pub recommended_lock_fee: Decimal,
+}
+
+impl FeeEstimate {
+    pub fn total_cost_xrd(&self) -> Decimal {
+        self.execution_cost_xrd
+            + self.state_expansion_cost_xrd
+            + self.royalty_cost_xrd
+            + self.tipping_cost_xrd
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeeEstimationError {
+    PreviewError(PreviewError),
+    TransactionRejected(RejectionError),
+    TransactionFailed(RuntimeError),
+    TransactionAborted(AbortReason),
+}
+
+impl From<PreviewError> for FeeEstimationError {
+    fn from(value: PreviewError) -> Self {
+        Self::PreviewError(value)
+    }
+}
+
+/// Previews the given intent and estimates the fee it will have to lock in order to be executed.
+/// The intent is previewed with a free credit (i.e. it is expected not to lock any fee itself),
+/// and a cost breakdown enabled.
+pub fn estimate_fee<S: SubstateDatabase, W: WasmEngine>(
+    substate_db: &S,
+    scrypto_interpreter: &ScryptoVm<W>,
+    network: &NetworkDefinition,
+    mut preview_intent: PreviewIntentV1,
+    config: &FeeEstimatorConfig,
+) -> Result<FeeEstimate, FeeEstimationError> {
+    preview_intent.flags.use_free_credit = true;
+    let receipt = execute_preview_with_options(
+        substate_db,
+        scrypto_interpreter,
+        network,
+        preview_intent,
+        &PreviewOptions {
+            enable_cost_breakdown: true,
+            ..config.preview_options.clone()
+        },
+    )?;
+
+    let commit = match receipt.transaction_result {
+        TransactionResult::Commit(commit) => commit,
+        TransactionResult::Reject(reject) => {
+            return Err(FeeEstimationError::TransactionRejected(reject.error))
+        }
+        TransactionResult::Abort(abort) => {
+            return Err(FeeEstimationError::TransactionAborted(abort.reason))
+        }
+    };
+    if let TransactionOutcome::Failure(error) = commit.outcome {
+        return Err(FeeEstimationError::TransactionFailed(error));
+    }
+
+    let fee_summary = commit.fee_summary;
+    let mut estimate = FeeEstimate {
+        execution_cost_units: fee_summary.execution_cost_sum,
+        execution_cost_xrd: fee_summary.total_execution_cost_xrd,
+        state_expansion_cost_xrd: fee_summary.total_state_expansion_cost_xrd,
+        royalty_cost_xrd: fee_summary.total_royalty_cost_xrd,
+        tipping_cost_xrd: fee_summary.total_tipping_cost_xrd,
+        execution_cost_breakdown: fee_summary.execution_cost_breakdown,
+        recommended_lock_fee: Decimal::ZERO,
+    };
+    estimate.recommended_lock_fee =
+        estimate.total_cost_xrd() * (100 + u32::from(config.safety_margin_percentage)) / 100;
+    Ok(estimate)
+}
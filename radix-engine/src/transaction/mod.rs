@@ -1,9 +1,13 @@
+mod fee_estimator;
 mod preview_executor;
+mod staged_database;
 mod state_update_summary;
 mod transaction_executor;
 mod transaction_receipt;
 
+pub use fee_estimator::*;
 pub use preview_executor::*;
+pub use staged_database::*;
 pub use state_update_summary::*;
 pub use transaction_executor::*;
 pub use transaction_receipt::*;
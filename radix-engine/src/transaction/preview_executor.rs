@@ -1,5 +1,8 @@
+use crate::system::system_modules::EnabledModules;
+use crate::track::SubstateDatabaseOverlay;
 use crate::transaction::TransactionReceipt;
 use crate::transaction::*;
+use crate::types::*;
 use crate::vm::wasm::WasmEngine;
 use crate::vm::ScryptoVm;
 use radix_engine_interface::network::NetworkDefinition;
@@ -14,12 +17,86 @@ pub enum PreviewError {
     TransactionValidationError(TransactionValidationError),
 }
 
+/// Engine-side options of a preview, complementing the `PreviewFlags` carried by the intent
+/// itself (which control the free credit, the signature proofs and the epoch check).
+#[derive(Debug, Clone)]
+pub struct PreviewOptions {
+    /// A cost unit price to use instead of the default one.
+    pub cost_unit_price: Option<Decimal>,
+    /// A cost unit limit to use instead of the default one.
+    pub cost_unit_limit: Option<u32>,
+    /// Whether to skip all the authorization checks (i.e. as if every access rule was satisfied).
+    pub disable_auth: bool,
+    pub with_kernel_trace: bool,
+    /// Whether to collect the execution cost breakdown (see `FeeSummary`).
+    pub enable_cost_breakdown: bool,
+    /// Substate changes applied on top of the database (in memory only) before the preview.
+    pub substate_overrides: DatabaseUpdates,
+}
+
+impl Default for PreviewOptions {
+    fn default() -> Self {
+        Self {
+            cost_unit_price: None,
+            cost_unit_limit: None,
+            disable_auth: false,
+            with_kernel_trace: false,
+            enable_cost_breakdown: false,
+            substate_overrides: index_map_new(),
+        }
+    }
+}
+
+impl PreviewOptions {
+    pub fn fee_reserve_config(&self) -> FeeReserveConfig {
+        let mut fee_reserve_config = FeeReserveConfig::default();
+        if let Some(cost_unit_price) = self.cost_unit_price {
+            fee_reserve_config.cost_unit_price = cost_unit_price;
+        }
+        fee_reserve_config
+    }
+
+    pub fn execution_config(&self) -> ExecutionConfig {
+        let mut execution_config =
+            ExecutionConfig::for_preview().with_kernel_trace(self.with_kernel_trace);
+        if let Some(cost_unit_limit) = self.cost_unit_limit {
+            execution_config = execution_config.with_cost_unit_limit(cost_unit_limit);
+        }
+        execution_config.enable_cost_breakdown = self.enable_cost_breakdown;
+        if self.disable_auth {
+            execution_config
+                .enabled_modules
+                .remove(EnabledModules::AUTH);
+        }
+        execution_config
+    }
+}
+
 pub fn execute_preview<S: SubstateDatabase, W: WasmEngine>(
     substate_db: &S,
     scrypto_interpreter: &ScryptoVm<W>,
     network: &NetworkDefinition,
     preview_intent: PreviewIntentV1,
     with_kernel_trace: bool,
+) -> Result<TransactionReceipt, PreviewError> {
+    execute_preview_with_options(
+        substate_db,
+        scrypto_interpreter,
+        network,
+        preview_intent,
+        &PreviewOptions {
+            with_kernel_trace,
+            ..PreviewOptions::default()
+        },
+    )
+}
+
+pub fn execute_preview_with_options<S: SubstateDatabase, W: WasmEngine>(
+    substate_db: &S,
+    scrypto_interpreter: &ScryptoVm<W>,
+    network: &NetworkDefinition,
+    preview_intent: PreviewIntentV1,
+    options: &PreviewOptions,
 ) -> Result<TransactionReceipt, PreviewError> {
     let validation_config = ValidationConfig::default(network.id);
 
@@ -29,11 +106,26 @@ pub fn execute_preview<S: SubstateDatabase, W: WasmEngine>(
         .validate_preview_intent_v1(preview_intent)
         .map_err(PreviewError::TransactionValidationError)?;
 
-    Ok(execute_transaction(
-        substate_db,
-        scrypto_interpreter,
-        &FeeReserveConfig::default(),
-        &ExecutionConfig::for_preview().with_kernel_trace(with_kernel_trace),
-        &validated.get_executable(),
-    ))
+    let fee_reserve_config = options.fee_reserve_config();
+    let execution_config = options.execution_config();
+    let executable = validated.get_executable();
+
+    let receipt = if options.substate_overrides.is_empty() {
+        execute_transaction(
+            substate_db,
+            scrypto_interpreter,
+            &fee_reserve_config,
+            &execution_config,
+            &executable,
+        )
+    } else {
+        execute_transaction(
+            &SubstateDatabaseOverlay::with_updates(substate_db, &options.substate_overrides),
+            scrypto_interpreter,
+            &fee_reserve_config,
+            &execution_config,
+            &executable,
+        )
+    };
+    Ok(receipt)
 }
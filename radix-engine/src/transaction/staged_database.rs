@@ -0,0 +1,117 @@
+use crate::track::SubstateDatabaseOverlay;
+use crate::transaction::*;
+use crate::types::*;
+use crate::vm::wasm::WasmEngine;
+use crate::vm::ScryptoVm;
+use radix_engine_store_interface::interface::*;
+use transaction::model::Executable;
+
+/// A [`SubstateDatabase`] which stages the state updates of successively executed transactions in
+/// memory, on top of an underlying (read-only) database.
+/// Each staged set of updates is a separate "stage", which allows to roll back to any earlier
+/// point of the batch (e.g. to drop a transaction which turned out to be unwanted).
+/// The underlying database is never written to - the caller commits the merged
+/// [`StagedSubstateDatabase::database_updates()`] once the whole batch is accepted.
+pub struct StagedSubstateDatabase<'s, S: SubstateDatabase> {
+    overlay: SubstateDatabaseOverlay<'s, S>,
+    stages: Vec<DatabaseUpdates>,
+}
+
+impl<'s, S: SubstateDatabase> StagedSubstateDatabase<'s, S> {
+    pub fn new(root: &'s S) -> Self {
+        Self {
+            overlay: SubstateDatabaseOverlay::new(root),
+            stages: Vec::new(),
+        }
+    }
+
+    /// Returns the number of stages applied on top of the underlying database.
+    pub fn stage_count(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Stages the given updates and returns the resulting stage count (which can later be used as
+    /// an argument of [`Self::rollback_to()`], to roll back everything staged after this call).
+    pub fn stage(&mut self, database_updates: DatabaseUpdates) -> usize {
+        self.overlay.commit(&database_updates);
+        self.stages.push(database_updates);
+        self.stages.len()
+    }
+
+    /// Stages the state updates of the given receipt, if it is a commit (including a failed one,
+    /// which still updates the fee-related state).
+    /// Returns the resulting stage count, or [`None`] if there was nothing to stage.
+    pub fn stage_receipt(&mut self, receipt: &TransactionReceipt) -> Option<usize> {
+        match &receipt.transaction_result {
+            TransactionResult::Commit(commit) => {
+                Some(self.stage(commit.state_updates.database_updates.clone()))
+            }
+            TransactionResult::Reject(_) | TransactionResult::Abort(_) => None,
+        }
+    }
+
+    /// Executes the given transaction against the current staged state and stages its result.
+    pub fn execute_and_stage<W: WasmEngine>(
+        &mut self,
+        scrypto_interpreter: &ScryptoVm<W>,
+        fee_reserve_config: &FeeReserveConfig,
+        execution_config: &ExecutionConfig,
+        transaction: &Executable,
+    ) -> TransactionReceipt {
+        let receipt = execute_transaction(
+            &self.overlay,
+            scrypto_interpreter,
+            fee_reserve_config,
+            execution_config,
+            transaction,
+        );
+        self.stage_receipt(&receipt);
+        receipt
+    }
+
+    /// Drops all the stages above the given stage count (i.e. `rollback_to(0)` restores the state
+    /// of the underlying database).
+    pub fn rollback_to(&mut self, stage_count: usize) {
+        if stage_count >= self.stages.len() {
+            return;
+        }
+        self.stages.truncate(stage_count);
+        self.overlay = SubstateDatabaseOverlay::new(self.overlay.root());
+        for database_updates in &self.stages {
+            self.overlay.commit(database_updates);
+        }
+    }
+
+    /// Returns all the staged updates, merged into a single [`DatabaseUpdates`] which can be
+    /// committed to the underlying database.
+    pub fn database_updates(&self) -> DatabaseUpdates {
+        self.overlay.database_updates()
+    }
+}
+
+impl<'s, S: SubstateDatabase> SubstateDatabase for StagedSubstateDatabase<'s, S> {
+    fn get_substate(
+        &self,
+        partition_key: &DbPartitionKey,
+        sort_key: &DbSortKey,
+    ) -> Option<DbSubstateValue> {
+        self.overlay.get_substate(partition_key, sort_key)
+    }
+
+    fn list_entries_from(
+        &self,
+        partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
+    ) -> Box<dyn Iterator<Item = PartitionEntry> + '_> {
+        self.overlay.list_entries_from(partition_key, from_sort_key)
+    }
+
+    fn list_entries_rev_from(
+        &self,
+        partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
+    ) -> Box<dyn Iterator<Item = PartitionEntry> + '_> {
+        self.overlay
+            .list_entries_rev_from(partition_key, from_sort_key)
+    }
+}
@@ -475,6 +475,7 @@ where
         let mut id_allocator = IdAllocator::new(executable.intent_hash().to_hash());
         let mut system = SystemConfig {
             blueprint_cache: NonIterMap::new(),
+            blueprint_version_cache: NonIterMap::new(),
             auth_cache: NonIterMap::new(),
             schema_cache: NonIterMap::new(),
             callback_obj: Vm {
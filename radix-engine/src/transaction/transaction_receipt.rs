@@ -1,5 +1,7 @@
 use super::{BalanceChange, StateUpdateSummary};
-use crate::blueprints::consensus_manager::EpochChangeEvent;
+use crate::blueprints::consensus_manager::{
+    EpochChangeEvent, ProtocolUpdateReadinessThresholdReachedEvent,
+};
 use crate::errors::*;
 use crate::system::system_modules::costing::FeeSummary;
 use crate::system::system_modules::execution_trace::{
@@ -10,9 +12,12 @@ use crate::types::*;
 use colored::*;
 use radix_engine_interface::address::AddressDisplayContext;
 use radix_engine_interface::api::ObjectModuleId;
+use radix_engine_interface::blueprints::package::{TypePointer, PACKAGE_SCHEMAS_PARTITION_OFFSET};
 use radix_engine_interface::blueprints::transaction_processor::InstructionOutput;
 use radix_engine_interface::data::scrypto::ScryptoDecode;
 use radix_engine_interface::types::*;
+use radix_engine_store_interface::db_key_mapper::{MappedSubstateDatabase, SpreadPrefixKeyMapper};
+use radix_engine_store_interface::interface::SubstateDatabase;
 use sbor::representations::*;
 use utils::ContextualDisplay;
 
@@ -111,6 +116,48 @@ impl CommitResult {
         None
     }
 
+    /// The protocol versions for which the readiness threshold was reached, if this transaction
+    /// changed the epoch.
+    /// The consensus manager's events are told apart by their type's name, resolved from the
+    /// schema stored by its package in the given database.
+    pub fn protocol_update_readiness_thresholds_reached<S: SubstateDatabase>(
+        &self,
+        substate_db: &S,
+    ) -> Vec<ProtocolUpdateReadinessThresholdReachedEvent> {
+        self.application_events
+            .iter()
+            .filter(|(event_type_id, _)| match event_type_id {
+                EventTypeIdentifier(
+                    Emitter::Method(node_id, ObjectModuleId::Main),
+                    TypePointer::Package(schema_hash, type_index),
+                ) if node_id.entity_type() == Some(EntityType::GlobalConsensusManager) => {
+                    substate_db
+                        .get_mapped::<SpreadPrefixKeyMapper, KeyValueEntrySubstate<ScryptoSchema>>(
+                            CONSENSUS_MANAGER_PACKAGE.as_node_id(),
+                            MAIN_BASE_PARTITION
+                                .at_offset(PACKAGE_SCHEMAS_PARTITION_OFFSET)
+                                .unwrap(),
+                            &SubstateKey::Map(scrypto_encode(schema_hash).unwrap()),
+                        )
+                        .and_then(|entry| entry.value)
+                        .and_then(|schema| {
+                            schema
+                                .resolve_type_metadata(*type_index)
+                                .and_then(|metadata| metadata.get_name_string())
+                        })
+                        .map_or(false, |name| {
+                            name == ProtocolUpdateReadinessThresholdReachedEvent::event_name()
+                        })
+                }
+                _ => false,
+            })
+            .map(|(_, event_data)| {
+                scrypto_decode(event_data)
+                    .expect("Expected a valid ProtocolUpdateReadinessThresholdReachedEvent")
+            })
+            .collect()
+    }
+
     pub fn new_package_addresses(&self) -> &Vec<PackageAddress> {
         &self.state_update_summary.new_packages
     }
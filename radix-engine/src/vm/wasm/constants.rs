@@ -30,6 +30,7 @@ pub const KEY_VALUE_STORE_NEW_FUNCTION_NAME: &str = "kv_store_new";
 pub const KEY_VALUE_STORE_GET_INFO_FUNCTION_NAME: &str = "kv_store_get_info";
 pub const KEY_VALUE_STORE_OPEN_ENTRY_FUNCTION_NAME: &str = "kv_store_open_entry";
 pub const KEY_VALUE_STORE_REMOVE_ENTRY_FUNCTION_NAME: &str = "kv_store_remove_entry";
+pub const KEY_VALUE_STORE_SCAN_FUNCTION_NAME: &str = "kv_store_scan";
 
 //=================
 // KV Entry Handle
@@ -45,6 +46,21 @@ pub const FIELD_LOCK_READ_FUNCTION_NAME: &str = "field_lock_read";
 pub const FIELD_LOCK_WRITE_FUNCTION_NAME: &str = "field_lock_write";
 pub const FIELD_LOCK_RELEASE_FUNCTION_NAME: &str = "field_lock_release";
 
+//=================
+// Actor Index
+//=================
+pub const ACTOR_INDEX_INSERT_FUNCTION_NAME: &str = "actor_index_insert";
+pub const ACTOR_INDEX_REMOVE_FUNCTION_NAME: &str = "actor_index_remove";
+pub const ACTOR_INDEX_SCAN_FUNCTION_NAME: &str = "actor_index_scan";
+pub const ACTOR_INDEX_TAKE_FUNCTION_NAME: &str = "actor_index_take";
+
+//=================
+// Actor Sorted Index
+//=================
+pub const ACTOR_SORTED_INDEX_INSERT_FUNCTION_NAME: &str = "actor_sorted_index_insert";
+pub const ACTOR_SORTED_INDEX_REMOVE_FUNCTION_NAME: &str = "actor_sorted_index_remove";
+pub const ACTOR_SORTED_INDEX_SCAN_FUNCTION_NAME: &str = "actor_sorted_index_scan";
+
 //=================
 // Actor
 //=================
@@ -65,6 +81,16 @@ pub const GET_TRANSACTION_HASH_FUNCTION_NAME: &str = "get_transaction_hash";
 pub const GENERATE_RUID_FUNCTION_NAME: &str = "generate_ruid";
 pub const PANIC_FUNCTION_NAME: &str = "panic";
 
+//=================
+// Crypto Utils
+//=================
+pub const BLAKE2B_256_HASH_FUNCTION_NAME: &str = "crypto_utils_blake2b_256_hash";
+pub const KECCAK256_HASH_FUNCTION_NAME: &str = "crypto_utils_keccak256_hash";
+pub const SHA256_HASH_FUNCTION_NAME: &str = "crypto_utils_sha256_hash";
+pub const SECP256K1_ECDSA_VERIFY_FUNCTION_NAME: &str = "crypto_utils_secp256k1_ecdsa_verify";
+pub const SECP256K1_ECDSA_RECOVER_FUNCTION_NAME: &str = "crypto_utils_secp256k1_ecdsa_recover";
+pub const ED25519_VERIFY_FUNCTION_NAME: &str = "crypto_utils_ed25519_verify";
+
 pub const MODULE_ENV_NAME: &str = "env";
 pub const EXPORT_MEMORY: &str = "memory";
 
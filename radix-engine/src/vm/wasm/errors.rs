@@ -130,12 +130,36 @@ pub enum WasmRuntimeError {
 
     InvalidKeyValueStoreSchema(DecodeError),
 
+    /// Invalid key to start a key value store scan from
+    InvalidKeyValueStoreScanStartKey(DecodeError),
+
     /// Invalid component address
     InvalidLockFlags,
 
+    /// Invalid collection index
+    InvalidCollectionIndex(u32),
+
+    /// Invalid sort prefix of a sorted index key
+    InvalidSortPrefix(u32),
+
     /// Invalid log level
     InvalidLogLevel(DecodeError),
 
+    /// Invalid hash
+    InvalidHash(DecodeError),
+
+    /// Invalid Secp256k1 public key
+    InvalidSecp256k1PublicKey(DecodeError),
+
+    /// Invalid Secp256k1 signature
+    InvalidSecp256k1Signature(DecodeError),
+
+    /// Invalid Ed25519 public key
+    InvalidEd25519PublicKey(DecodeError),
+
+    /// Invalid Ed25519 signature
+    InvalidEd25519Signature(DecodeError),
+
     /// Costing error (no-op runtime only!)
     FeeReserveError(FeeReserveError),
 }
@@ -406,6 +406,27 @@ impl WasmerModule {
             Ok(buffer.0)
         }
 
+        pub fn key_value_store_scan(
+            env: &WasmerInstanceEnv,
+            node_id_ptr: u32,
+            node_id_len: u32,
+            start_key_ptr: u32,
+            start_key_len: u32,
+            limit: u32,
+        ) -> Result<u64, RuntimeError> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let buffer = runtime
+                .key_value_store_scan(
+                    read_memory(&instance, node_id_ptr, node_id_len)?,
+                    read_memory(&instance, start_key_ptr, start_key_len)?,
+                    limit,
+                )
+                .map_err(|e| RuntimeError::user(Box::new(e)))?;
+
+            Ok(buffer.0)
+        }
+
         pub fn key_value_entry_get(
             env: &WasmerInstanceEnv,
             handle: u32,
@@ -520,6 +541,131 @@ impl WasmerModule {
             Ok(())
         }
 
+        pub fn actor_index_insert(
+            env: &WasmerInstanceEnv,
+            object_handle: u32,
+            collection_index: u32,
+            key_ptr: u32,
+            key_len: u32,
+            value_ptr: u32,
+            value_len: u32,
+        ) -> Result<(), RuntimeError> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let key = read_memory(&instance, key_ptr, key_len)?;
+            let value = read_memory(&instance, value_ptr, value_len)?;
+
+            runtime
+                .actor_index_insert(object_handle, collection_index, key, value)
+                .map_err(|e| RuntimeError::user(Box::new(e)))?;
+
+            Ok(())
+        }
+
+        pub fn actor_index_remove(
+            env: &WasmerInstanceEnv,
+            object_handle: u32,
+            collection_index: u32,
+            key_ptr: u32,
+            key_len: u32,
+        ) -> Result<u64, RuntimeError> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let key = read_memory(&instance, key_ptr, key_len)?;
+
+            let buffer = runtime
+                .actor_index_remove(object_handle, collection_index, key)
+                .map_err(|e| RuntimeError::user(Box::new(e)))?;
+
+            Ok(buffer.0)
+        }
+
+        pub fn actor_index_scan(
+            env: &WasmerInstanceEnv,
+            object_handle: u32,
+            collection_index: u32,
+            count: u32,
+        ) -> Result<u64, RuntimeError> {
+            let (_instance, runtime) = grab_runtime!(env);
+
+            let buffer = runtime
+                .actor_index_scan(object_handle, collection_index, count)
+                .map_err(|e| RuntimeError::user(Box::new(e)))?;
+
+            Ok(buffer.0)
+        }
+
+        pub fn actor_index_take(
+            env: &WasmerInstanceEnv,
+            object_handle: u32,
+            collection_index: u32,
+            count: u32,
+        ) -> Result<u64, RuntimeError> {
+            let (_instance, runtime) = grab_runtime!(env);
+
+            let buffer = runtime
+                .actor_index_take(object_handle, collection_index, count)
+                .map_err(|e| RuntimeError::user(Box::new(e)))?;
+
+            Ok(buffer.0)
+        }
+
+        pub fn actor_sorted_index_insert(
+            env: &WasmerInstanceEnv,
+            object_handle: u32,
+            collection_index: u32,
+            sort_prefix: u32,
+            key_ptr: u32,
+            key_len: u32,
+            value_ptr: u32,
+            value_len: u32,
+        ) -> Result<(), RuntimeError> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let key = read_memory(&instance, key_ptr, key_len)?;
+            let value = read_memory(&instance, value_ptr, value_len)?;
+
+            runtime
+                .actor_sorted_index_insert(object_handle, collection_index, sort_prefix, key, value)
+                .map_err(|e| RuntimeError::user(Box::new(e)))?;
+
+            Ok(())
+        }
+
+        pub fn actor_sorted_index_remove(
+            env: &WasmerInstanceEnv,
+            object_handle: u32,
+            collection_index: u32,
+            sort_prefix: u32,
+            key_ptr: u32,
+            key_len: u32,
+        ) -> Result<u64, RuntimeError> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let key = read_memory(&instance, key_ptr, key_len)?;
+
+            let buffer = runtime
+                .actor_sorted_index_remove(object_handle, collection_index, sort_prefix, key)
+                .map_err(|e| RuntimeError::user(Box::new(e)))?;
+
+            Ok(buffer.0)
+        }
+
+        pub fn actor_sorted_index_scan(
+            env: &WasmerInstanceEnv,
+            object_handle: u32,
+            collection_index: u32,
+            count: u32,
+        ) -> Result<u64, RuntimeError> {
+            let (_instance, runtime) = grab_runtime!(env);
+
+            let buffer = runtime
+                .actor_sorted_index_scan(object_handle, collection_index, count)
+                .map_err(|e| RuntimeError::user(Box::new(e)))?;
+
+            Ok(buffer.0)
+        }
+
         pub fn get_node_id(env: &WasmerInstanceEnv) -> Result<u64, RuntimeError> {
             let (_instance, runtime) = grab_runtime!(env);
 
@@ -646,6 +792,107 @@ impl WasmerModule {
             Ok(buffer.0)
         }
 
+        pub fn blake2b_256_hash(
+            env: &WasmerInstanceEnv,
+            data_ptr: u32,
+            data_len: u32,
+        ) -> Result<u64, RuntimeError> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let buffer = runtime
+                .blake2b_256_hash(read_memory(&instance, data_ptr, data_len)?)
+                .map_err(|e| RuntimeError::user(Box::new(e)))?;
+
+            Ok(buffer.0)
+        }
+
+        pub fn keccak256_hash(
+            env: &WasmerInstanceEnv,
+            data_ptr: u32,
+            data_len: u32,
+        ) -> Result<u64, RuntimeError> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let buffer = runtime
+                .keccak256_hash(read_memory(&instance, data_ptr, data_len)?)
+                .map_err(|e| RuntimeError::user(Box::new(e)))?;
+
+            Ok(buffer.0)
+        }
+
+        pub fn sha256_hash(
+            env: &WasmerInstanceEnv,
+            data_ptr: u32,
+            data_len: u32,
+        ) -> Result<u64, RuntimeError> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let buffer = runtime
+                .sha256_hash(read_memory(&instance, data_ptr, data_len)?)
+                .map_err(|e| RuntimeError::user(Box::new(e)))?;
+
+            Ok(buffer.0)
+        }
+
+        pub fn secp256k1_ecdsa_verify(
+            env: &WasmerInstanceEnv,
+            message_hash_ptr: u32,
+            message_hash_len: u32,
+            public_key_ptr: u32,
+            public_key_len: u32,
+            signature_ptr: u32,
+            signature_len: u32,
+        ) -> Result<u32, RuntimeError> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            runtime
+                .secp256k1_ecdsa_verify(
+                    read_memory(&instance, message_hash_ptr, message_hash_len)?,
+                    read_memory(&instance, public_key_ptr, public_key_len)?,
+                    read_memory(&instance, signature_ptr, signature_len)?,
+                )
+                .map_err(|e| RuntimeError::user(Box::new(e)))
+        }
+
+        pub fn secp256k1_ecdsa_recover(
+            env: &WasmerInstanceEnv,
+            message_hash_ptr: u32,
+            message_hash_len: u32,
+            signature_ptr: u32,
+            signature_len: u32,
+        ) -> Result<u64, RuntimeError> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let buffer = runtime
+                .secp256k1_ecdsa_recover(
+                    read_memory(&instance, message_hash_ptr, message_hash_len)?,
+                    read_memory(&instance, signature_ptr, signature_len)?,
+                )
+                .map_err(|e| RuntimeError::user(Box::new(e)))?;
+
+            Ok(buffer.0)
+        }
+
+        pub fn ed25519_verify(
+            env: &WasmerInstanceEnv,
+            message_hash_ptr: u32,
+            message_hash_len: u32,
+            public_key_ptr: u32,
+            public_key_len: u32,
+            signature_ptr: u32,
+            signature_len: u32,
+        ) -> Result<u32, RuntimeError> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            runtime
+                .ed25519_verify(
+                    read_memory(&instance, message_hash_ptr, message_hash_len)?,
+                    read_memory(&instance, public_key_ptr, public_key_len)?,
+                    read_memory(&instance, signature_ptr, signature_len)?,
+                )
+                .map_err(|e| RuntimeError::user(Box::new(e)))
+        }
+
         // native functions ends
 
         // env
@@ -674,12 +921,20 @@ impl WasmerModule {
                 KEY_VALUE_STORE_NEW_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), key_value_store_new),
                 KEY_VALUE_STORE_OPEN_ENTRY_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), key_value_store_open_entry),
                 KEY_VALUE_STORE_REMOVE_ENTRY_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), key_value_store_remove_entry),
+                KEY_VALUE_STORE_SCAN_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), key_value_store_scan),
                 KEY_VALUE_ENTRY_GET_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), key_value_entry_get),
                 KEY_VALUE_ENTRY_SET_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), key_value_entry_set),
                 KEY_VALUE_ENTRY_RELEASE_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), key_value_entry_release),
                 FIELD_LOCK_READ_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), field_lock_read),
                 FIELD_LOCK_WRITE_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), field_lock_write),
                 FIELD_LOCK_RELEASE_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), field_lock_release),
+                ACTOR_INDEX_INSERT_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_index_insert),
+                ACTOR_INDEX_REMOVE_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_index_remove),
+                ACTOR_INDEX_SCAN_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_index_scan),
+                ACTOR_INDEX_TAKE_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_index_take),
+                ACTOR_SORTED_INDEX_INSERT_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_sorted_index_insert),
+                ACTOR_SORTED_INDEX_REMOVE_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_sorted_index_remove),
+                ACTOR_SORTED_INDEX_SCAN_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_sorted_index_scan),
                 GET_NODE_ID_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), get_node_id),
                 GET_GLOBAL_ADDRESS_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), get_global_address),
                 GET_BLUEPRINT_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), get_blueprint),
@@ -691,6 +946,12 @@ impl WasmerModule {
                 PANIC_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), panic),
                 GET_TRANSACTION_HASH_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), get_transaction_hash),
                 GENERATE_RUID_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), generate_ruid),
+                BLAKE2B_256_HASH_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), blake2b_256_hash),
+                KECCAK256_HASH_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), keccak256_hash),
+                SHA256_HASH_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), sha256_hash),
+                SECP256K1_ECDSA_VERIFY_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), secp256k1_ecdsa_verify),
+                SECP256K1_ECDSA_RECOVER_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), secp256k1_ecdsa_recover),
+                ED25519_VERIFY_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), ed25519_verify),
             }
         };
 
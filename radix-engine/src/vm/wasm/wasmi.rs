@@ -372,6 +372,28 @@ fn key_value_entry_remove(
         .map(|buffer| buffer.0)
 }
 
+fn key_value_store_scan(
+    mut caller: Caller<'_, HostState>,
+    node_id_ptr: u32,
+    node_id_len: u32,
+    start_key_ptr: u32,
+    start_key_len: u32,
+    limit: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+    let node_id = read_memory(caller.as_context_mut(), memory, node_id_ptr, node_id_len)?;
+    let start_key = read_memory(
+        caller.as_context_mut(),
+        memory,
+        start_key_ptr,
+        start_key_len,
+    )?;
+
+    runtime
+        .key_value_store_scan(node_id, start_key, limit)
+        .map(|buffer| buffer.0)
+}
+
 fn lock_field(
     caller: Caller<'_, HostState>,
     object_handle: u32,
@@ -413,6 +435,109 @@ fn field_lock_release(
     runtime.field_lock_release(handle)
 }
 
+fn actor_index_insert(
+    mut caller: Caller<'_, HostState>,
+    object_handle: u32,
+    collection_index: u32,
+    key_ptr: u32,
+    key_len: u32,
+    value_ptr: u32,
+    value_len: u32,
+) -> Result<(), InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+    let key = read_memory(caller.as_context_mut(), memory, key_ptr, key_len)?;
+    let value = read_memory(caller.as_context_mut(), memory, value_ptr, value_len)?;
+
+    runtime.actor_index_insert(object_handle, collection_index, key, value)
+}
+
+fn actor_index_remove(
+    mut caller: Caller<'_, HostState>,
+    object_handle: u32,
+    collection_index: u32,
+    key_ptr: u32,
+    key_len: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+    let key = read_memory(caller.as_context_mut(), memory, key_ptr, key_len)?;
+
+    runtime
+        .actor_index_remove(object_handle, collection_index, key)
+        .map(|buffer| buffer.0)
+}
+
+fn actor_index_scan(
+    caller: Caller<'_, HostState>,
+    object_handle: u32,
+    collection_index: u32,
+    count: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (_memory, runtime) = grab_runtime!(caller);
+
+    runtime
+        .actor_index_scan(object_handle, collection_index, count)
+        .map(|buffer| buffer.0)
+}
+
+fn actor_index_take(
+    caller: Caller<'_, HostState>,
+    object_handle: u32,
+    collection_index: u32,
+    count: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (_memory, runtime) = grab_runtime!(caller);
+
+    runtime
+        .actor_index_take(object_handle, collection_index, count)
+        .map(|buffer| buffer.0)
+}
+
+fn actor_sorted_index_insert(
+    mut caller: Caller<'_, HostState>,
+    object_handle: u32,
+    collection_index: u32,
+    sort_prefix: u32,
+    key_ptr: u32,
+    key_len: u32,
+    value_ptr: u32,
+    value_len: u32,
+) -> Result<(), InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+    let key = read_memory(caller.as_context_mut(), memory, key_ptr, key_len)?;
+    let value = read_memory(caller.as_context_mut(), memory, value_ptr, value_len)?;
+
+    runtime.actor_sorted_index_insert(object_handle, collection_index, sort_prefix, key, value)
+}
+
+fn actor_sorted_index_remove(
+    mut caller: Caller<'_, HostState>,
+    object_handle: u32,
+    collection_index: u32,
+    sort_prefix: u32,
+    key_ptr: u32,
+    key_len: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+    let key = read_memory(caller.as_context_mut(), memory, key_ptr, key_len)?;
+
+    runtime
+        .actor_sorted_index_remove(object_handle, collection_index, sort_prefix, key)
+        .map(|buffer| buffer.0)
+}
+
+fn actor_sorted_index_scan(
+    caller: Caller<'_, HostState>,
+    object_handle: u32,
+    collection_index: u32,
+    count: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (_memory, runtime) = grab_runtime!(caller);
+
+    runtime
+        .actor_sorted_index_scan(object_handle, collection_index, count)
+        .map(|buffer| buffer.0)
+}
+
 fn get_node_id(caller: Caller<'_, HostState>) -> Result<u64, InvokeError<WasmRuntimeError>> {
     let (_memory, runtime) = grab_runtime!(caller);
 
@@ -522,6 +647,135 @@ fn panic(
 
     runtime.panic(message)
 }
+
+fn blake2b_256_hash(
+    mut caller: Caller<'_, HostState>,
+    data_ptr: u32,
+    data_len: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+
+    let data = read_memory(caller.as_context_mut(), memory, data_ptr, data_len)?;
+
+    runtime.blake2b_256_hash(data).map(|buffer| buffer.0)
+}
+
+fn keccak256_hash(
+    mut caller: Caller<'_, HostState>,
+    data_ptr: u32,
+    data_len: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+
+    let data = read_memory(caller.as_context_mut(), memory, data_ptr, data_len)?;
+
+    runtime.keccak256_hash(data).map(|buffer| buffer.0)
+}
+
+fn sha256_hash(
+    mut caller: Caller<'_, HostState>,
+    data_ptr: u32,
+    data_len: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+
+    let data = read_memory(caller.as_context_mut(), memory, data_ptr, data_len)?;
+
+    runtime.sha256_hash(data).map(|buffer| buffer.0)
+}
+
+fn secp256k1_ecdsa_verify(
+    mut caller: Caller<'_, HostState>,
+    message_hash_ptr: u32,
+    message_hash_len: u32,
+    public_key_ptr: u32,
+    public_key_len: u32,
+    signature_ptr: u32,
+    signature_len: u32,
+) -> Result<u32, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+
+    let message_hash = read_memory(
+        caller.as_context_mut(),
+        memory,
+        message_hash_ptr,
+        message_hash_len,
+    )?;
+    let public_key = read_memory(
+        caller.as_context_mut(),
+        memory,
+        public_key_ptr,
+        public_key_len,
+    )?;
+    let signature = read_memory(
+        caller.as_context_mut(),
+        memory,
+        signature_ptr,
+        signature_len,
+    )?;
+
+    runtime.secp256k1_ecdsa_verify(message_hash, public_key, signature)
+}
+
+fn secp256k1_ecdsa_recover(
+    mut caller: Caller<'_, HostState>,
+    message_hash_ptr: u32,
+    message_hash_len: u32,
+    signature_ptr: u32,
+    signature_len: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+
+    let message_hash = read_memory(
+        caller.as_context_mut(),
+        memory,
+        message_hash_ptr,
+        message_hash_len,
+    )?;
+    let signature = read_memory(
+        caller.as_context_mut(),
+        memory,
+        signature_ptr,
+        signature_len,
+    )?;
+
+    runtime
+        .secp256k1_ecdsa_recover(message_hash, signature)
+        .map(|buffer| buffer.0)
+}
+
+fn ed25519_verify(
+    mut caller: Caller<'_, HostState>,
+    message_hash_ptr: u32,
+    message_hash_len: u32,
+    public_key_ptr: u32,
+    public_key_len: u32,
+    signature_ptr: u32,
+    signature_len: u32,
+) -> Result<u32, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+
+    let message_hash = read_memory(
+        caller.as_context_mut(),
+        memory,
+        message_hash_ptr,
+        message_hash_len,
+    )?;
+    let public_key = read_memory(
+        caller.as_context_mut(),
+        memory,
+        public_key_ptr,
+        public_key_len,
+    )?;
+    let signature = read_memory(
+        caller.as_context_mut(),
+        memory,
+        signature_ptr,
+        signature_len,
+    )?;
+
+    runtime.ed25519_verify(message_hash, public_key, signature)
+}
 // native functions ends
 
 macro_rules! linker_define {
@@ -810,6 +1064,27 @@ impl WasmiModule {
             },
         );
 
+        let host_key_value_store_scan = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             node_id_ptr: u32,
+             node_id_len: u32,
+             start_key_ptr: u32,
+             start_key_len: u32,
+             limit: u32|
+             -> Result<u64, Trap> {
+                key_value_store_scan(
+                    caller,
+                    node_id_ptr,
+                    node_id_len,
+                    start_key_ptr,
+                    start_key_len,
+                    limit,
+                )
+                .map_err(|e| e.into())
+            },
+        );
+
         let host_lock_field = Func::wrap(
             store.as_context_mut(),
             |caller: Caller<'_, HostState>,
@@ -846,6 +1121,124 @@ impl WasmiModule {
             },
         );
 
+        let host_actor_index_insert = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             object_handle: u32,
+             collection_index: u32,
+             key_ptr: u32,
+             key_len: u32,
+             value_ptr: u32,
+             value_len: u32|
+             -> Result<(), Trap> {
+                actor_index_insert(
+                    caller,
+                    object_handle,
+                    collection_index,
+                    key_ptr,
+                    key_len,
+                    value_ptr,
+                    value_len,
+                )
+                .map_err(|e| e.into())
+            },
+        );
+
+        let host_actor_index_remove = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             object_handle: u32,
+             collection_index: u32,
+             key_ptr: u32,
+             key_len: u32|
+             -> Result<u64, Trap> {
+                actor_index_remove(caller, object_handle, collection_index, key_ptr, key_len)
+                    .map_err(|e| e.into())
+            },
+        );
+
+        let host_actor_index_scan = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             object_handle: u32,
+             collection_index: u32,
+             count: u32|
+             -> Result<u64, Trap> {
+                actor_index_scan(caller, object_handle, collection_index, count)
+                    .map_err(|e| e.into())
+            },
+        );
+
+        let host_actor_index_take = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             object_handle: u32,
+             collection_index: u32,
+             count: u32|
+             -> Result<u64, Trap> {
+                actor_index_take(caller, object_handle, collection_index, count)
+                    .map_err(|e| e.into())
+            },
+        );
+
+        let host_actor_sorted_index_insert = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             object_handle: u32,
+             collection_index: u32,
+             sort_prefix: u32,
+             key_ptr: u32,
+             key_len: u32,
+             value_ptr: u32,
+             value_len: u32|
+             -> Result<(), Trap> {
+                actor_sorted_index_insert(
+                    caller,
+                    object_handle,
+                    collection_index,
+                    sort_prefix,
+                    key_ptr,
+                    key_len,
+                    value_ptr,
+                    value_len,
+                )
+                .map_err(|e| e.into())
+            },
+        );
+
+        let host_actor_sorted_index_remove = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             object_handle: u32,
+             collection_index: u32,
+             sort_prefix: u32,
+             key_ptr: u32,
+             key_len: u32|
+             -> Result<u64, Trap> {
+                actor_sorted_index_remove(
+                    caller,
+                    object_handle,
+                    collection_index,
+                    sort_prefix,
+                    key_ptr,
+                    key_len,
+                )
+                .map_err(|e| e.into())
+            },
+        );
+
+        let host_actor_sorted_index_scan = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             object_handle: u32,
+             collection_index: u32,
+             count: u32|
+             -> Result<u64, Trap> {
+                actor_sorted_index_scan(caller, object_handle, collection_index, count)
+                    .map_err(|e| e.into())
+            },
+        );
+
         let host_get_node_id = Func::wrap(
             store.as_context_mut(),
             |caller: Caller<'_, HostState>| -> Result<u64, Trap> {
@@ -944,6 +1337,92 @@ impl WasmiModule {
             },
         );
 
+        let host_blake2b_256_hash = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>, data_ptr: u32, data_len: u32| -> Result<u64, Trap> {
+                blake2b_256_hash(caller, data_ptr, data_len).map_err(|e| e.into())
+            },
+        );
+
+        let host_keccak256_hash = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>, data_ptr: u32, data_len: u32| -> Result<u64, Trap> {
+                keccak256_hash(caller, data_ptr, data_len).map_err(|e| e.into())
+            },
+        );
+
+        let host_sha256_hash = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>, data_ptr: u32, data_len: u32| -> Result<u64, Trap> {
+                sha256_hash(caller, data_ptr, data_len).map_err(|e| e.into())
+            },
+        );
+
+        let host_secp256k1_ecdsa_verify = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             message_hash_ptr: u32,
+             message_hash_len: u32,
+             public_key_ptr: u32,
+             public_key_len: u32,
+             signature_ptr: u32,
+             signature_len: u32|
+             -> Result<u32, Trap> {
+                secp256k1_ecdsa_verify(
+                    caller,
+                    message_hash_ptr,
+                    message_hash_len,
+                    public_key_ptr,
+                    public_key_len,
+                    signature_ptr,
+                    signature_len,
+                )
+                .map_err(|e| e.into())
+            },
+        );
+
+        let host_secp256k1_ecdsa_recover = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             message_hash_ptr: u32,
+             message_hash_len: u32,
+             signature_ptr: u32,
+             signature_len: u32|
+             -> Result<u64, Trap> {
+                secp256k1_ecdsa_recover(
+                    caller,
+                    message_hash_ptr,
+                    message_hash_len,
+                    signature_ptr,
+                    signature_len,
+                )
+                .map_err(|e| e.into())
+            },
+        );
+
+        let host_ed25519_verify = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             message_hash_ptr: u32,
+             message_hash_len: u32,
+             public_key_ptr: u32,
+             public_key_len: u32,
+             signature_ptr: u32,
+             signature_len: u32|
+             -> Result<u32, Trap> {
+                ed25519_verify(
+                    caller,
+                    message_hash_ptr,
+                    message_hash_len,
+                    public_key_ptr,
+                    public_key_len,
+                    signature_ptr,
+                    signature_len,
+                )
+                .map_err(|e| e.into())
+            },
+        );
+
         let mut linker = <Linker<HostState>>::new();
 
         linker_define!(linker, CONSUME_BUFFER_FUNCTION_NAME, host_consume_buffer);
@@ -1000,6 +1479,11 @@ impl WasmiModule {
             KEY_VALUE_STORE_REMOVE_ENTRY_FUNCTION_NAME,
             host_key_value_entry_remove
         );
+        linker_define!(
+            linker,
+            KEY_VALUE_STORE_SCAN_FUNCTION_NAME,
+            host_key_value_store_scan
+        );
 
         linker_define!(linker, FIELD_LOCK_READ_FUNCTION_NAME, host_field_lock_read);
         linker_define!(
@@ -1012,6 +1496,41 @@ impl WasmiModule {
             FIELD_LOCK_RELEASE_FUNCTION_NAME,
             host_field_lock_release
         );
+        linker_define!(
+            linker,
+            ACTOR_INDEX_INSERT_FUNCTION_NAME,
+            host_actor_index_insert
+        );
+        linker_define!(
+            linker,
+            ACTOR_INDEX_REMOVE_FUNCTION_NAME,
+            host_actor_index_remove
+        );
+        linker_define!(
+            linker,
+            ACTOR_INDEX_SCAN_FUNCTION_NAME,
+            host_actor_index_scan
+        );
+        linker_define!(
+            linker,
+            ACTOR_INDEX_TAKE_FUNCTION_NAME,
+            host_actor_index_take
+        );
+        linker_define!(
+            linker,
+            ACTOR_SORTED_INDEX_INSERT_FUNCTION_NAME,
+            host_actor_sorted_index_insert
+        );
+        linker_define!(
+            linker,
+            ACTOR_SORTED_INDEX_REMOVE_FUNCTION_NAME,
+            host_actor_sorted_index_remove
+        );
+        linker_define!(
+            linker,
+            ACTOR_SORTED_INDEX_SCAN_FUNCTION_NAME,
+            host_actor_sorted_index_scan
+        );
         linker_define!(linker, GET_NODE_ID_FUNCTION_NAME, host_get_node_id);
         linker_define!(
             linker,
@@ -1039,6 +1558,24 @@ impl WasmiModule {
             host_get_transaction_hash
         );
         linker_define!(linker, GENERATE_RUID_FUNCTION_NAME, host_generate_ruid);
+        linker_define!(
+            linker,
+            BLAKE2B_256_HASH_FUNCTION_NAME,
+            host_blake2b_256_hash
+        );
+        linker_define!(linker, KECCAK256_HASH_FUNCTION_NAME, host_keccak256_hash);
+        linker_define!(linker, SHA256_HASH_FUNCTION_NAME, host_sha256_hash);
+        linker_define!(
+            linker,
+            SECP256K1_ECDSA_VERIFY_FUNCTION_NAME,
+            host_secp256k1_ecdsa_verify
+        );
+        linker_define!(
+            linker,
+            SECP256K1_ECDSA_RECOVER_FUNCTION_NAME,
+            host_secp256k1_ecdsa_recover
+        );
+        linker_define!(linker, ED25519_VERIFY_FUNCTION_NAME, host_ed25519_verify);
 
         let global_value = Global::new(store.as_context_mut(), Value::I32(-1), Mutability::Var);
         linker_define!(linker, "test_global_mutable_value", global_value);
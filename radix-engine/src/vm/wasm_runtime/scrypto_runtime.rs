@@ -2,6 +2,7 @@ use crate::errors::InvokeError;
 use crate::errors::RuntimeError;
 use crate::types::*;
 use crate::vm::wasm::*;
+use radix_engine_interface::api::actor_sorted_index_api::SortedKey;
 use radix_engine_interface::api::field_lock_api::LockFlags;
 use radix_engine_interface::api::object_api::ObjectModuleId;
 use radix_engine_interface::api::ClientApi;
@@ -38,6 +39,16 @@ where
             wasm_execution_units_buffer: 0,
         }
     }
+
+    fn consume_index_access_cost(
+        &mut self,
+        entry_count: usize,
+        size: usize,
+    ) -> Result<(), InvokeError<WasmRuntimeError>> {
+        self.api
+            .consume_cost_units(ClientCostingEntry::AccessIndex { entry_count, size })
+            .map_err(InvokeError::downstream)
+    }
 }
 
 impl<'y, Y> WasmRuntime for ScryptoRuntime<'y, Y>
@@ -281,6 +292,23 @@ where
         self.allocate_buffer(rtn)
     }
 
+    fn key_value_store_scan(
+        &mut self,
+        node_id: Vec<u8>,
+        start_key: Vec<u8>,
+        limit: u32,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let node_id = NodeId(
+            TryInto::<[u8; NodeId::LENGTH]>::try_into(node_id.as_ref())
+                .map_err(|_| WasmRuntimeError::InvalidNodeId)?,
+        );
+        let start_key = scrypto_decode::<Option<Vec<u8>>>(&start_key)
+            .map_err(WasmRuntimeError::InvalidKeyValueStoreScanStartKey)?;
+        let scan_result = self.api.key_value_store_scan(&node_id, start_key, limit)?;
+
+        self.allocate_buffer(scrypto_encode(&scan_result).expect("Failed to encode entries"))
+    }
+
     fn actor_open_field(
         &mut self,
         object_handle: u32,
@@ -321,6 +349,130 @@ where
         Ok(())
     }
 
+    fn actor_index_insert(
+        &mut self,
+        object_handle: u32,
+        collection_index: u32,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> Result<(), InvokeError<WasmRuntimeError>> {
+        let collection_index = u8::try_from(collection_index)
+            .map_err(|_| WasmRuntimeError::InvalidCollectionIndex(collection_index))?;
+        self.consume_index_access_cost(1, key.len() + value.len())?;
+        self.api
+            .actor_index_insert(object_handle, collection_index, key, value)?;
+
+        Ok(())
+    }
+
+    fn actor_index_remove(
+        &mut self,
+        object_handle: u32,
+        collection_index: u32,
+        key: Vec<u8>,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let collection_index = u8::try_from(collection_index)
+            .map_err(|_| WasmRuntimeError::InvalidCollectionIndex(collection_index))?;
+        let value = self
+            .api
+            .actor_index_remove(object_handle, collection_index, key)?;
+        self.consume_index_access_cost(value.iter().count(), value.iter().map(Vec::len).sum())?;
+
+        self.allocate_buffer(scrypto_encode(&value).expect("Failed to encode index value"))
+    }
+
+    fn actor_index_scan(
+        &mut self,
+        object_handle: u32,
+        collection_index: u32,
+        count: u32,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let collection_index = u8::try_from(collection_index)
+            .map_err(|_| WasmRuntimeError::InvalidCollectionIndex(collection_index))?;
+        let values = self
+            .api
+            .actor_index_scan(object_handle, collection_index, count)?;
+        self.consume_index_access_cost(values.len(), values.iter().map(Vec::len).sum())?;
+
+        self.allocate_buffer(scrypto_encode(&values).expect("Failed to encode index values"))
+    }
+
+    fn actor_index_take(
+        &mut self,
+        object_handle: u32,
+        collection_index: u32,
+        count: u32,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let collection_index = u8::try_from(collection_index)
+            .map_err(|_| WasmRuntimeError::InvalidCollectionIndex(collection_index))?;
+        let values = self
+            .api
+            .actor_index_take(object_handle, collection_index, count)?;
+        self.consume_index_access_cost(values.len(), values.iter().map(Vec::len).sum())?;
+
+        self.allocate_buffer(scrypto_encode(&values).expect("Failed to encode index values"))
+    }
+
+    fn actor_sorted_index_insert(
+        &mut self,
+        object_handle: u32,
+        collection_index: u32,
+        sort_prefix: u32,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> Result<(), InvokeError<WasmRuntimeError>> {
+        let collection_index = u8::try_from(collection_index)
+            .map_err(|_| WasmRuntimeError::InvalidCollectionIndex(collection_index))?;
+        let sort_prefix = u16::try_from(sort_prefix)
+            .map_err(|_| WasmRuntimeError::InvalidSortPrefix(sort_prefix))?;
+        self.consume_index_access_cost(1, key.len() + value.len())?;
+        self.api.actor_sorted_index_insert(
+            object_handle,
+            collection_index,
+            SortedKey(sort_prefix, key),
+            value,
+        )?;
+
+        Ok(())
+    }
+
+    fn actor_sorted_index_remove(
+        &mut self,
+        object_handle: u32,
+        collection_index: u32,
+        sort_prefix: u32,
+        key: Vec<u8>,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let collection_index = u8::try_from(collection_index)
+            .map_err(|_| WasmRuntimeError::InvalidCollectionIndex(collection_index))?;
+        let sort_prefix = u16::try_from(sort_prefix)
+            .map_err(|_| WasmRuntimeError::InvalidSortPrefix(sort_prefix))?;
+        let value = self.api.actor_sorted_index_remove(
+            object_handle,
+            collection_index,
+            &SortedKey(sort_prefix, key),
+        )?;
+        self.consume_index_access_cost(value.iter().count(), value.iter().map(Vec::len).sum())?;
+
+        self.allocate_buffer(scrypto_encode(&value).expect("Failed to encode sorted index value"))
+    }
+
+    fn actor_sorted_index_scan(
+        &mut self,
+        object_handle: u32,
+        collection_index: u32,
+        count: u32,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let collection_index = u8::try_from(collection_index)
+            .map_err(|_| WasmRuntimeError::InvalidCollectionIndex(collection_index))?;
+        let values = self
+            .api
+            .actor_sorted_index_scan(object_handle, collection_index, count)?;
+        self.consume_index_access_cost(values.len(), values.iter().map(Vec::len).sum())?;
+
+        self.allocate_buffer(scrypto_encode(&values).expect("Failed to encode sorted index values"))
+    }
+
     fn get_node_id(&mut self) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
         let node_id = self.api.actor_get_node_id()?;
 
@@ -463,4 +615,70 @@ where
 
         self.allocate_buffer(scrypto_encode(&fee_balance).expect("Failed to encode fee_balance"))
     }
+
+    fn blake2b_256_hash(&mut self, data: Vec<u8>) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let hash = self.api.blake2b_256_hash(data)?;
+
+        self.allocate_buffer(scrypto_encode(&hash).expect("Failed to encode hash"))
+    }
+
+    fn keccak256_hash(&mut self, data: Vec<u8>) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let hash = self.api.keccak256_hash(data)?;
+
+        self.allocate_buffer(scrypto_encode(&hash).expect("Failed to encode hash"))
+    }
+
+    fn sha256_hash(&mut self, data: Vec<u8>) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let hash = self.api.sha256_hash(data)?;
+
+        self.allocate_buffer(scrypto_encode(&hash).expect("Failed to encode hash"))
+    }
+
+    fn secp256k1_ecdsa_verify(
+        &mut self,
+        message_hash: Vec<u8>,
+        public_key: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Result<u32, InvokeError<WasmRuntimeError>> {
+        let verified = self.api.secp256k1_ecdsa_verify(
+            scrypto_decode::<Hash>(&message_hash).map_err(WasmRuntimeError::InvalidHash)?,
+            scrypto_decode::<Secp256k1PublicKey>(&public_key)
+                .map_err(WasmRuntimeError::InvalidSecp256k1PublicKey)?,
+            scrypto_decode::<Secp256k1Signature>(&signature)
+                .map_err(WasmRuntimeError::InvalidSecp256k1Signature)?,
+        )?;
+
+        Ok(verified.into())
+    }
+
+    fn secp256k1_ecdsa_recover(
+        &mut self,
+        message_hash: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let public_key = self.api.secp256k1_ecdsa_recover(
+            scrypto_decode::<Hash>(&message_hash).map_err(WasmRuntimeError::InvalidHash)?,
+            scrypto_decode::<Secp256k1Signature>(&signature)
+                .map_err(WasmRuntimeError::InvalidSecp256k1Signature)?,
+        )?;
+
+        self.allocate_buffer(scrypto_encode(&public_key).expect("Failed to encode public key"))
+    }
+
+    fn ed25519_verify(
+        &mut self,
+        message_hash: Vec<u8>,
+        public_key: Vec<u8>,
+        signature: Vec<u8>,
+    ) -> Result<u32, InvokeError<WasmRuntimeError>> {
+        let verified = self.api.ed25519_verify(
+            scrypto_decode::<Hash>(&message_hash).map_err(WasmRuntimeError::InvalidHash)?,
+            scrypto_decode::<Ed25519PublicKey>(&public_key)
+                .map_err(WasmRuntimeError::InvalidEd25519PublicKey)?,
+            scrypto_decode::<Ed25519Signature>(&signature)
+                .map_err(WasmRuntimeError::InvalidEd25519Signature)?,
+        )?;
+
+        Ok(verified.into())
+    }
 }
@@ -3,7 +3,7 @@ use syn::punctuated::Punctuated;
 use syn::token::{Brace, Paren};
 use syn::{
     braced, parenthesized, Attribute, Ident, ItemConst, ItemImpl, ItemMacro, ItemStruct, ItemUse,
-    Path, Result, Token, Visibility,
+    Path, Result, Token, Type, Visibility,
 };
 
 /// Represents a blueprint which is a module with an optional set of attributes
@@ -103,3 +103,35 @@ impl Parse for EventsInner {
         })
     }
 }
+
+pub struct CollectionsInner {
+    pub paren_token: Paren,
+    pub collections: Punctuated<Collection, Token![,]>,
+}
+
+impl Parse for CollectionsInner {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        Ok(Self {
+            paren_token: parenthesized!(content in input),
+            collections: content.parse_terminated(Collection::parse)?,
+        })
+    }
+}
+
+/// Represents a collection declared as `name: Type`, e.g. `orders: Index<u64, Order>`
+pub struct Collection {
+    pub ident: Ident,
+    pub colon_token: Token![:],
+    pub ty: Type,
+}
+
+impl Parse for Collection {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            ident: input.parse()?,
+            colon_token: input.parse()?,
+            ty: input.parse()?,
+        })
+    }
+}
@@ -449,6 +449,59 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
         import_statements
     };
 
+    // Getting the index and sorted index collections if the collections attribute is defined
+    let (collection_schemas, collection_accessors) = {
+        let mut names = std::collections::BTreeSet::<String>::new();
+        let mut collection_schemas = Vec::new();
+        let mut collection_accessors = Vec::new();
+        for attribute in blueprint.attributes.iter() {
+            if attribute.path.is_ident("collections") {
+                let collections_inner = parse2::<ast::CollectionsInner>(attribute.tokens.clone())?;
+                for collection in collections_inner.collections.iter() {
+                    let ident = &collection.ident;
+                    let ty = &collection.ty;
+                    if !names.insert(ident.to_string()) {
+                        return Err(Error::new(
+                            ident.span(),
+                            "A collection with an identical name has already been registered",
+                        ));
+                    }
+                    let collection_index = u8::try_from(collection_schemas.len())
+                        .map_err(|_| Error::new(ident.span(), "Too many collections"))?;
+                    let collection_type = match ty {
+                        Type::Path(type_path) => type_path
+                            .path
+                            .segments
+                            .last()
+                            .map(|segment| segment.ident.to_string()),
+                        _ => None,
+                    };
+                    let collection_schema = match collection_type.as_deref() {
+                        Some("Index") => quote! {
+                            BlueprintCollectionSchema::Index(BlueprintIndexSchema {})
+                        },
+                        Some("SortedIndex") => quote! {
+                            BlueprintCollectionSchema::SortedIndex(BlueprintSortedIndexSchema {})
+                        },
+                        _ => {
+                            return Err(Error::new(
+                                ty.span(),
+                                "Only `Index<K, V>` and `SortedIndex<V>` collections are supported",
+                            ))
+                        }
+                    };
+                    collection_schemas.push(collection_schema);
+                    collection_accessors.push(quote! {
+                        fn #ident() -> #ty {
+                            <#ty>::new(#collection_index)
+                        }
+                    });
+                }
+            }
+        }
+        (collection_schemas, collection_accessors)
+    };
+
     #[cfg(feature = "no-schema")]
     let output_schema = quote! {};
     #[cfg(not(feature = "no-schema"))]
@@ -497,6 +550,12 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
             }
         };
 
+        let collections_expr = if collection_schemas.is_empty() {
+            quote! { Vec::new() }
+        } else {
+            quote! { vec![#(#collection_schemas),*] }
+        };
+
         let schema_ident = format_ident!("{}_schema", bp_ident);
         let fn_names = generated_schema_info.fn_names;
         let fn_schemas = generated_schema_info.fn_schemas;
@@ -552,7 +611,7 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
 
                     let state = BlueprintStateSchemaInit {
                         fields,
-                        collections: Vec::new(),
+                        collections: #collections_expr,
                     };
 
                     // Aggregate functions
@@ -619,6 +678,16 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
         }
     };
 
+    let output_collection_accessors = if collection_accessors.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            impl #bp_ident {
+                #(#collection_accessors)*
+            }
+        }
+    };
+
     let output_original_code = quote! {
         #[derive(::scrypto::prelude::ScryptoSbor)]
         pub struct #bp_ident #bp_fields #bp_semi_token
@@ -627,6 +696,8 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
             #(#bp_items)*
         }
 
+        #output_collection_accessors
+
         impl ::scrypto::component::ComponentState for #bp_ident {
             const BLUEPRINT_NAME: &'static str = #bp_name;
         }
@@ -98,7 +98,7 @@ impl BasicRocksdbTestRunner {
                 builder.call_method(
                     account_address,
                     ACCOUNT_TRY_DEPOSIT_OR_ABORT_IDENT,
-                    manifest_args!(bucket),
+                    manifest_args!(bucket, Option::<ManifestProof>::None),
                 )
             })
             .build();
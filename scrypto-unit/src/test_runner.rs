@@ -7,11 +7,12 @@ use std::process::Command;
 use radix_engine::blueprints::consensus_manager::*;
 use radix_engine::errors::*;
 use radix_engine::system::bootstrap::*;
-use radix_engine::system::node_modules::type_info::TypeInfoSubstate;
+use radix_engine::system::protocol_update::*;
 use radix_engine::system::system::KeyValueEntrySubstate;
 use radix_engine::transaction::{
-    execute_preview, execute_transaction, CommitResult, ExecutionConfig, FeeReserveConfig,
-    PreviewError, TransactionReceipt, TransactionResult,
+    estimate_fee, execute_preview, execute_preview_with_options, execute_transaction, CommitResult,
+    ExecutionConfig, FeeEstimate, FeeEstimationError, FeeEstimatorConfig, FeeReserveConfig,
+    PreviewError, PreviewOptions, TransactionReceipt, TransactionResult,
 };
 use radix_engine::types::*;
 use radix_engine::utils::*;
@@ -20,7 +21,6 @@ use radix_engine::vm::ScryptoVm;
 use radix_engine_interface::api::node_modules::auth::*;
 use radix_engine_interface::api::node_modules::metadata::*;
 use radix_engine_interface::api::node_modules::royalty::ComponentRoyaltySubstate;
-use radix_engine_interface::api::ObjectModuleId;
 use radix_engine_interface::blueprints::account::*;
 use radix_engine_interface::blueprints::consensus_manager::{
     ConsensusManagerConfig, ConsensusManagerGetCurrentEpochInput,
@@ -30,8 +30,8 @@ use radix_engine_interface::blueprints::consensus_manager::{
 };
 use radix_engine_interface::blueprints::package::{
     BlueprintDefinitionInit, PackageDefinition, PackagePublishWasmAdvancedManifestInput,
-    PackageRoyaltyAccumulatorSubstate, TypePointer, PACKAGE_BLUEPRINT,
-    PACKAGE_PUBLISH_WASM_ADVANCED_IDENT, PACKAGE_SCHEMAS_PARTITION_OFFSET,
+    PackageRoyaltyAccumulatorSubstate, PACKAGE_BLUEPRINT, PACKAGE_PUBLISH_WASM_ADVANCED_IDENT,
+    PACKAGE_SCHEMAS_PARTITION_OFFSET,
 };
 use radix_engine_interface::constants::CONSENSUS_MANAGER;
 use radix_engine_interface::data::manifest::model::ManifestExpression;
@@ -39,7 +39,9 @@ use radix_engine_interface::math::Decimal;
 use radix_engine_interface::network::NetworkDefinition;
 use radix_engine_interface::time::Instant;
 use radix_engine_interface::{dec, rule};
-use radix_engine_queries::query::{ResourceAccounter, StateTreeTraverser, VaultFinder};
+use radix_engine_queries::query::{
+    EventDecoder, ResourceAccounter, StateTreeTraverser, VaultFinder,
+};
 use radix_engine_queries::typed_substate_layout::{
     BlueprintDefinition, BlueprintVersionKey, PACKAGE_BLUEPRINTS_PARTITION_OFFSET,
 };
@@ -167,6 +169,7 @@ impl CustomGenesis {
             num_owner_stake_units_unlock_epochs: 2,
             num_fee_increase_delay_epochs: 4,
             validator_creation_xrd_cost: *DEFAULT_VALIDATOR_XRD_COST,
+            protocol_update_readiness_threshold: *DEFAULT_PROTOCOL_UPDATE_READINESS_THRESHOLD,
         }
     }
 
@@ -654,7 +657,7 @@ impl TestRunner {
                 builder.call_method(
                     account_address,
                     ACCOUNT_TRY_DEPOSIT_OR_ABORT_IDENT,
-                    manifest_args!(bucket),
+                    manifest_args!(bucket, Option::<ManifestProof>::None),
                 )
             })
             .build();
@@ -1034,6 +1037,24 @@ impl TestRunner {
         )
     }
 
+    pub fn preview_with_options(
+        &mut self,
+        preview_intent: PreviewIntentV1,
+        network: &NetworkDefinition,
+        options: PreviewOptions,
+    ) -> Result<TransactionReceipt, PreviewError> {
+        execute_preview_with_options(
+            &self.substate_db,
+            &self.scrypto_interpreter,
+            network,
+            preview_intent,
+            &PreviewOptions {
+                with_kernel_trace: options.with_kernel_trace || self.trace,
+                ..options
+            },
+        )
+    }
+
     pub fn preview_manifest(
         &mut self,
         manifest: TransactionManifestV1,
@@ -1041,36 +1062,70 @@ impl TestRunner {
         tip_percentage: u16,
         flags: PreviewFlags,
     ) -> TransactionReceipt {
-        let epoch = self.get_current_epoch();
+        let preview_intent =
+            self.preview_intent_for_manifest(manifest, signer_public_keys, tip_percentage, flags);
         execute_preview(
             &mut self.substate_db,
             &self.scrypto_interpreter,
             &NetworkDefinition::simulator(),
-            PreviewIntentV1 {
-                intent: IntentV1 {
-                    header: TransactionHeaderV1 {
-                        network_id: NetworkDefinition::simulator().id,
-                        start_epoch_inclusive: epoch,
-                        end_epoch_exclusive: epoch.after(10),
-                        nonce: 0,
-                        notary_public_key: PublicKey::Secp256k1(Secp256k1PublicKey([0u8; 33])),
-                        notary_is_signatory: false,
-                        tip_percentage,
-                    },
-                    instructions: InstructionsV1(manifest.instructions),
-                    blobs: BlobsV1 {
-                        blobs: manifest.blobs.values().map(|x| BlobV1(x.clone())).collect(),
-                    },
-                    message: MessageV1::default(),
-                },
-                signer_public_keys,
-                flags,
-            },
+            preview_intent,
             self.trace,
         )
         .unwrap()
     }
 
+    pub fn estimate_fee(
+        &mut self,
+        manifest: TransactionManifestV1,
+        signer_public_keys: Vec<PublicKey>,
+        tip_percentage: u16,
+        config: &FeeEstimatorConfig,
+    ) -> Result<FeeEstimate, FeeEstimationError> {
+        let preview_intent = self.preview_intent_for_manifest(
+            manifest,
+            signer_public_keys,
+            tip_percentage,
+            PreviewFlags::default(),
+        );
+        estimate_fee(
+            &self.substate_db,
+            &self.scrypto_interpreter,
+            &NetworkDefinition::simulator(),
+            preview_intent,
+            config,
+        )
+    }
+
+    fn preview_intent_for_manifest(
+        &mut self,
+        manifest: TransactionManifestV1,
+        signer_public_keys: Vec<PublicKey>,
+        tip_percentage: u16,
+        flags: PreviewFlags,
+    ) -> PreviewIntentV1 {
+        let epoch = self.get_current_epoch();
+        PreviewIntentV1 {
+            intent: IntentV1 {
+                header: TransactionHeaderV1 {
+                    network_id: NetworkDefinition::simulator().id,
+                    start_epoch_inclusive: epoch,
+                    end_epoch_exclusive: epoch.after(10),
+                    nonce: 0,
+                    notary_public_key: PublicKey::Secp256k1(Secp256k1PublicKey([0u8; 33])),
+                    notary_is_signatory: false,
+                    tip_percentage,
+                },
+                instructions: InstructionsV1(manifest.instructions),
+                blobs: BlobsV1 {
+                    blobs: manifest.blobs.values().map(|x| BlobV1(x.clone())).collect(),
+                },
+                message: MessageV1::default(),
+            },
+            signer_public_keys,
+            flags,
+        }
+    }
+
     /// Calls a package blueprint function with the given arguments, paying the fee from the faucet.
     ///
     /// Notes:
@@ -1610,6 +1665,40 @@ impl TestRunner {
         )
     }
 
+    /// Executes those of the given protocol updates whose readiness threshold was reached by the
+    /// given (epoch changing) transaction.
+    pub fn execute_ready_protocol_updates(
+        &mut self,
+        protocol_updates: Vec<ProtocolUpdate>,
+        commit_result: &CommitResult,
+    ) -> Result<Vec<ProtocolUpdateReceipt>, ProtocolUpdateError> {
+        let mut executor = ProtocolUpdateExecutor::new(
+            &mut self.substate_db,
+            &self.scrypto_interpreter,
+            self.trace,
+        );
+        for protocol_update in protocol_updates {
+            executor.register(protocol_update);
+        }
+        let receipts = executor.execute_ready_protocol_updates(commit_result)?;
+
+        if let Some(state_hash_support) = &mut self.state_hash_support {
+            for receipt in &receipts {
+                state_hash_support
+                    .update_with(&receipt.native_package_flash_receipt.database_updates);
+                for state_migration_receipt in &receipt.state_migration_receipts {
+                    state_hash_support.update_with(
+                        &state_migration_receipt
+                            .expect_commit_success()
+                            .state_updates
+                            .database_updates,
+                    );
+                }
+            }
+        }
+        Ok(receipts)
+    }
+
     /// Executes a "start round number `round` at timestamp `timestamp_ms`" system transaction, as
     /// if it was proposed by the first validator from the validator set, after `round - 1` missed
     /// rounds by that validator.
@@ -1686,63 +1775,9 @@ impl TestRunner {
         &self,
         event_type_identifier: &EventTypeIdentifier,
     ) -> (LocalTypeIndex, ScryptoSchema) {
-        let (package_address, schema_pointer) = match event_type_identifier {
-            EventTypeIdentifier(Emitter::Method(node_id, node_module), schema_pointer) => {
-                match node_module {
-                    ObjectModuleId::AccessRules => {
-                        (ACCESS_RULES_MODULE_PACKAGE, schema_pointer.clone())
-                    }
-                    ObjectModuleId::Royalty => (ROYALTY_MODULE_PACKAGE, schema_pointer.clone()),
-                    ObjectModuleId::Metadata => (METADATA_MODULE_PACKAGE, schema_pointer.clone()),
-                    ObjectModuleId::Main => {
-                        let type_info = self
-                            .substate_db()
-                            .get_mapped::<SpreadPrefixKeyMapper, TypeInfoSubstate>(
-                                node_id,
-                                TYPE_INFO_FIELD_PARTITION,
-                                &TypeInfoField::TypeInfo.into(),
-                            )
-                            .unwrap();
-
-                        match type_info {
-                            TypeInfoSubstate::Object(ObjectInfo {
-                                blueprint_id: blueprint,
-                                ..
-                            }) => (blueprint.package_address, *schema_pointer),
-                            _ => {
-                                panic!("No event schema.")
-                            }
-                        }
-                    }
-                }
-            }
-            EventTypeIdentifier(Emitter::Function(node_id, ..), schema_pointer) => (
-                PackageAddress::new_or_panic(node_id.0),
-                schema_pointer.clone(),
-            ),
-        };
-
-        match schema_pointer {
-            TypePointer::Package(schema_hash, index) => {
-                let schema = self
-                    .substate_db()
-                    .get_mapped::<SpreadPrefixKeyMapper, KeyValueEntrySubstate<ScryptoSchema>>(
-                        package_address.as_node_id(),
-                        MAIN_BASE_PARTITION
-                            .at_offset(PACKAGE_SCHEMAS_PARTITION_OFFSET)
-                            .unwrap(),
-                        &SubstateKey::Map(scrypto_encode(&schema_hash).unwrap()),
-                    )
-                    .unwrap()
-                    .value
-                    .unwrap();
-
-                (index, schema)
-            }
-            TypePointer::Instance(_instance_index) => {
-                todo!()
-            }
-        }
+        EventDecoder::new(self.substate_db())
+            .event_schema(event_type_identifier)
+            .expect("No event schema.")
     }
 
     pub fn event_name(&self, event_type_identifier: &EventTypeIdentifier) -> String {
@@ -0,0 +1,116 @@
+use radix_engine_interface::api::actor_index_api::ClientActorIndexApi;
+use radix_engine_interface::api::actor_sorted_index_api::SortedKey;
+use radix_engine_interface::api::{ClientActorSortedIndexApi, CollectionIndex, OBJECT_HANDLE_SELF};
+use radix_engine_interface::data::scrypto::*;
+use sbor::rust::marker::PhantomData;
+use sbor::rust::prelude::*;
+
+use crate::engine::scrypto_env::ScryptoEnv;
+
+/// An iterable collection of the component's state, in which entries are not ordered.
+///
+/// An index is declared with the `#[collections(...)]` attribute of a blueprint, and is accessed
+/// from within the blueprint's methods through the generated accessor, e.g. `Self::orders()`.
+pub struct Index<K: ScryptoEncode, V: ScryptoEncode + ScryptoDecode> {
+    pub collection_index: CollectionIndex,
+    pub key: PhantomData<K>,
+    pub value: PhantomData<V>,
+}
+
+impl<K: ScryptoEncode, V: ScryptoEncode + ScryptoDecode> Index<K, V> {
+    /// Returns a handle to the collection at the given position of the blueprint's state.
+    pub fn new(collection_index: CollectionIndex) -> Self {
+        Self {
+            collection_index,
+            key: PhantomData,
+            value: PhantomData,
+        }
+    }
+
+    /// Inserts an entry, replacing the value of any existing entry with the same key.
+    pub fn insert(&self, key: K, value: V) {
+        let mut env = ScryptoEnv;
+        env.actor_index_insert_typed(
+            OBJECT_HANDLE_SELF,
+            self.collection_index,
+            scrypto_encode(&key).unwrap(),
+            value,
+        )
+        .unwrap();
+    }
+
+    /// Removes an entry and returns its value if it exists.
+    pub fn remove(&self, key: &K) -> Option<V> {
+        let mut env = ScryptoEnv;
+        env.actor_index_remove_typed(
+            OBJECT_HANDLE_SELF,
+            self.collection_index,
+            scrypto_encode(key).unwrap(),
+        )
+        .unwrap()
+    }
+
+    /// Returns the values of at most `count` arbitrary entries.
+    pub fn scan(&self, count: u32) -> Vec<V> {
+        let mut env = ScryptoEnv;
+        env.actor_index_scan_typed(OBJECT_HANDLE_SELF, self.collection_index, count)
+            .unwrap()
+    }
+
+    /// Removes at most `count` arbitrary entries and returns their values.
+    pub fn take(&self, count: u32) -> Vec<V> {
+        let mut env = ScryptoEnv;
+        env.actor_index_take_typed(OBJECT_HANDLE_SELF, self.collection_index, count)
+            .unwrap()
+    }
+}
+
+/// An iterable collection of the component's state, in which entries are ordered by their
+/// [`SortedKey`] - i.e. by the `u16` sort prefix first, and then by the key bytes.
+///
+/// Scans return entries in ascending order, so e.g. a leaderboard keeping the highest scores
+/// first would use `u16::MAX - score` as the sort prefix, and an order book would use the price
+/// level as the sort prefix and the order id as the key.
+///
+/// A sorted index is declared with the `#[collections(...)]` attribute of a blueprint, and is
+/// accessed from within the blueprint's methods through the generated accessor.
+pub struct SortedIndex<V: ScryptoEncode + ScryptoDecode> {
+    pub collection_index: CollectionIndex,
+    pub value: PhantomData<V>,
+}
+
+impl<V: ScryptoEncode + ScryptoDecode> SortedIndex<V> {
+    /// Returns a handle to the collection at the given position of the blueprint's state.
+    pub fn new(collection_index: CollectionIndex) -> Self {
+        Self {
+            collection_index,
+            value: PhantomData,
+        }
+    }
+
+    /// Inserts an entry, replacing the value of any existing entry with the same key.
+    pub fn insert(&self, sorted_key: SortedKey, value: V) {
+        let mut env = ScryptoEnv;
+        env.actor_sorted_index_insert_typed(
+            OBJECT_HANDLE_SELF,
+            self.collection_index,
+            sorted_key,
+            value,
+        )
+        .unwrap();
+    }
+
+    /// Removes an entry and returns its value if it exists.
+    pub fn remove(&self, sorted_key: &SortedKey) -> Option<V> {
+        let mut env = ScryptoEnv;
+        env.actor_sorted_index_remove_typed(OBJECT_HANDLE_SELF, self.collection_index, sorted_key)
+            .unwrap()
+    }
+
+    /// Returns the values of the first (i.e. lowest) `count` entries.
+    pub fn scan(&self, count: u32) -> Vec<V> {
+        let mut env = ScryptoEnv;
+        env.actor_sorted_index_scan_typed(OBJECT_HANDLE_SELF, self.collection_index, count)
+            .unwrap()
+    }
+}
@@ -120,6 +120,31 @@ impl<
 
         scrypto_decode(&rtn).unwrap()
     }
+
+    /// Returns at most `limit` entries of the map, in an unspecified (but stable) order, starting
+    /// from the given key, together with the key to continue the scan from (or `None` if there are
+    /// no more entries).
+    ///
+    /// Every scanned entry is costed, so the limit should be kept small. Removed entries are
+    /// skipped and do not count towards the limit.
+    pub fn scan(&self, start_key: Option<&K>, limit: u32) -> (Vec<(K, V)>, Option<K>) {
+        let mut env = ScryptoEnv;
+        let start_key = start_key.map(|key| scrypto_encode(key).unwrap());
+        let (entries, next_key) = env
+            .key_value_store_scan(self.id.as_node_id(), start_key, limit)
+            .unwrap();
+        let entries = entries
+            .into_iter()
+            .map(|(key, value)| {
+                (
+                    scrypto_decode(&key).unwrap(),
+                    scrypto_decode(&value).unwrap(),
+                )
+            })
+            .collect();
+
+        (entries, next_key.map(|key| scrypto_decode(&key).unwrap()))
+    }
 }
 
 //========
@@ -1,10 +1,12 @@
 mod component;
+mod index;
 mod kv_store;
 mod object;
 mod package;
 mod stubs;
 
 pub use component::*;
+pub use index::*;
 pub use kv_store::*;
 pub use object::*;
 pub use package::*;
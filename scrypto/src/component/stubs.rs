@@ -138,9 +138,11 @@ extern_blueprint_internal! {
         fn lock_fee_and_withdraw_non_fungibles(&mut self, amount_to_lock: Decimal, resource_address: ResourceAddress, ids: Vec<NonFungibleLocalId>) -> Bucket;
         fn securify(&mut self) -> Bucket;
         fn try_deposit_batch_or_abort(&mut self, buckets: Vec<Bucket>);
+        fn try_deposit_batch_or_abort_with_badge(&mut self, buckets: Vec<Bucket>, authorized_depositor_badge: Proof);
         fn try_deposit_batch_or_refund(&mut self, buckets: Vec<Bucket>) -> Vec<Bucket>;
-        fn try_deposit_or_abort(&mut self, bucket: Bucket);
-        fn try_deposit_or_refund(&mut self, bucket: Bucket) -> Option<Bucket>;
+        fn try_deposit_batch_or_refund_with_badge(&mut self, buckets: Vec<Bucket>, authorized_depositor_badge: Proof) -> Vec<Bucket>;
+        fn try_deposit_or_abort(&mut self, bucket: Bucket, authorized_depositor_badge: Option<Proof>);
+        fn try_deposit_or_refund(&mut self, bucket: Bucket, authorized_depositor_badge: Option<Proof>) -> Option<Bucket>;
         fn withdraw(&mut self, resource_address: ResourceAddress, amount: Decimal) -> Bucket;
         fn withdraw_non_fungibles(&mut self, resource_address: ResourceAddress, ids: Vec<NonFungibleLocalId>) -> Bucket;
     }
@@ -0,0 +1,62 @@
+use radix_engine_interface::api::ClientCryptoUtilsApi;
+use radix_engine_interface::crypto::*;
+use sbor::rust::prelude::*;
+
+use crate::engine::scrypto_env::ScryptoEnv;
+
+/// Cryptographic utilities, executed natively by the engine.
+///
+/// These are much cheaper than hashing or verifying signatures within the blueprint's own WASM
+/// code, as each call is costed at a fixed (or per-byte) rate.
+#[derive(Debug)]
+pub struct CryptoUtils {}
+
+impl CryptoUtils {
+    /// Computes the Blake2b-256 hash of the given data.
+    pub fn blake2b_256_hash<T: AsRef<[u8]>>(data: T) -> Hash {
+        ScryptoEnv.blake2b_256_hash(data.as_ref().to_vec()).unwrap()
+    }
+
+    /// Computes the Keccak-256 hash of the given data.
+    pub fn keccak256_hash<T: AsRef<[u8]>>(data: T) -> Hash {
+        ScryptoEnv.keccak256_hash(data.as_ref().to_vec()).unwrap()
+    }
+
+    /// Computes the SHA-256 hash of the given data.
+    pub fn sha256_hash<T: AsRef<[u8]>>(data: T) -> Hash {
+        ScryptoEnv.sha256_hash(data.as_ref().to_vec()).unwrap()
+    }
+
+    /// Verifies an ECDSA Secp256k1 signature of the given message hash.
+    pub fn secp256k1_ecdsa_verify(
+        message_hash: &Hash,
+        public_key: &Secp256k1PublicKey,
+        signature: &Secp256k1Signature,
+    ) -> bool {
+        ScryptoEnv
+            .secp256k1_ecdsa_verify(*message_hash, *public_key, *signature)
+            .unwrap()
+    }
+
+    /// Recovers the public key of a (recoverable) ECDSA Secp256k1 signature of the given message
+    /// hash, or returns [`Option::None`] if the signature is invalid.
+    pub fn secp256k1_ecdsa_recover(
+        message_hash: &Hash,
+        signature: &Secp256k1Signature,
+    ) -> Option<Secp256k1PublicKey> {
+        ScryptoEnv
+            .secp256k1_ecdsa_recover(*message_hash, *signature)
+            .unwrap()
+    }
+
+    /// Verifies an Ed25519 signature of the given message hash.
+    pub fn ed25519_verify(
+        message_hash: &Hash,
+        public_key: &Ed25519PublicKey,
+        signature: &Ed25519Signature,
+    ) -> bool {
+        ScryptoEnv
+            .ed25519_verify(*message_hash, *public_key, *signature)
+            .unwrap()
+    }
+}
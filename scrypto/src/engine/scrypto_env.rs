@@ -1,6 +1,8 @@
 use crate::engine::wasm_api::*;
 use radix_engine_common::math::Decimal;
 use radix_engine_common::types::GlobalAddressReservation;
+use radix_engine_interface::api::actor_index_api::ClientActorIndexApi;
+use radix_engine_interface::api::actor_sorted_index_api::SortedKey;
 use radix_engine_interface::api::key_value_entry_api::{
     ClientKeyValueEntryApi, KeyValueEntryHandle,
 };
@@ -8,12 +10,17 @@ use radix_engine_interface::api::key_value_store_api::ClientKeyValueStoreApi;
 use radix_engine_interface::api::object_api::ObjectModuleId;
 use radix_engine_interface::api::system_modules::auth_api::ClientAuthApi;
 use radix_engine_interface::api::{
-    ClientActorApi, ClientCostingApi, ClientFieldLockApi, ClientObjectApi, ObjectHandle,
+    ClientActorApi, ClientActorSortedIndexApi, ClientCostingApi, ClientFieldLockApi,
+    ClientObjectApi, CollectionIndex, ObjectHandle,
+};
+use radix_engine_interface::api::{
+    ClientBlueprintApi, ClientCryptoUtilsApi, ClientTransactionRuntimeApi,
 };
-use radix_engine_interface::api::{ClientBlueprintApi, ClientTransactionRuntimeApi};
 use radix_engine_interface::api::{KVEntry, LockFlags};
 use radix_engine_interface::blueprints::resource::AccessRule;
-use radix_engine_interface::crypto::Hash;
+use radix_engine_interface::crypto::{
+    Ed25519PublicKey, Ed25519Signature, Hash, Secp256k1PublicKey, Secp256k1Signature,
+};
 use radix_engine_interface::data::scrypto::*;
 use radix_engine_interface::types::{BlueprintId, GlobalAddress};
 use radix_engine_interface::types::{Level, LockHandle, NodeId};
@@ -301,6 +308,26 @@ impl ClientKeyValueStoreApi<ClientApiError> for ScryptoEnv {
         });
         Ok(removed)
     }
+
+    fn key_value_store_scan(
+        &mut self,
+        node_id: &NodeId,
+        start_key: Option<Vec<u8>>,
+        limit: u32,
+    ) -> Result<(Vec<(Vec<u8>, Vec<u8>)>, Option<Vec<u8>>), ClientApiError> {
+        let start_key = scrypto_encode(&start_key).unwrap();
+        let scan_result = copy_buffer(unsafe {
+            kv_store_scan(
+                node_id.as_ref().as_ptr(),
+                node_id.as_ref().len(),
+                start_key.as_ptr(),
+                start_key.len(),
+                limit,
+            )
+        });
+
+        scrypto_decode(&scan_result).map_err(ClientApiError::DecodeError)
+    }
 }
 
 impl ClientBlueprintApi<ClientApiError> for ScryptoEnv {
@@ -418,6 +445,129 @@ impl ClientActorApi<ClientApiError> for ScryptoEnv {
     }
 }
 
+impl ClientActorIndexApi<ClientApiError> for ScryptoEnv {
+    fn actor_index_insert(
+        &mut self,
+        object_handle: ObjectHandle,
+        collection_index: CollectionIndex,
+        key: Vec<u8>,
+        buffer: Vec<u8>,
+    ) -> Result<(), ClientApiError> {
+        unsafe {
+            actor_index_insert(
+                object_handle,
+                u32::from(collection_index),
+                key.as_ptr(),
+                key.len(),
+                buffer.as_ptr(),
+                buffer.len(),
+            )
+        };
+
+        Ok(())
+    }
+
+    fn actor_index_remove(
+        &mut self,
+        object_handle: ObjectHandle,
+        collection_index: CollectionIndex,
+        key: Vec<u8>,
+    ) -> Result<Option<Vec<u8>>, ClientApiError> {
+        let removed = copy_buffer(unsafe {
+            actor_index_remove(
+                object_handle,
+                u32::from(collection_index),
+                key.as_ptr(),
+                key.len(),
+            )
+        });
+
+        scrypto_decode(&removed).map_err(ClientApiError::DecodeError)
+    }
+
+    fn actor_index_scan(
+        &mut self,
+        object_handle: ObjectHandle,
+        collection_index: CollectionIndex,
+        count: u32,
+    ) -> Result<Vec<Vec<u8>>, ClientApiError> {
+        let entries = copy_buffer(unsafe {
+            actor_index_scan(object_handle, u32::from(collection_index), count)
+        });
+
+        scrypto_decode(&entries).map_err(ClientApiError::DecodeError)
+    }
+
+    fn actor_index_take(
+        &mut self,
+        object_handle: ObjectHandle,
+        collection_index: CollectionIndex,
+        count: u32,
+    ) -> Result<Vec<Vec<u8>>, ClientApiError> {
+        let entries = copy_buffer(unsafe {
+            actor_index_take(object_handle, u32::from(collection_index), count)
+        });
+
+        scrypto_decode(&entries).map_err(ClientApiError::DecodeError)
+    }
+}
+
+impl ClientActorSortedIndexApi<ClientApiError> for ScryptoEnv {
+    fn actor_sorted_index_insert(
+        &mut self,
+        object_handle: ObjectHandle,
+        collection_index: CollectionIndex,
+        sorted_key: SortedKey,
+        buffer: Vec<u8>,
+    ) -> Result<(), ClientApiError> {
+        unsafe {
+            actor_sorted_index_insert(
+                object_handle,
+                u32::from(collection_index),
+                u32::from(sorted_key.0),
+                sorted_key.1.as_ptr(),
+                sorted_key.1.len(),
+                buffer.as_ptr(),
+                buffer.len(),
+            )
+        };
+
+        Ok(())
+    }
+
+    fn actor_sorted_index_remove(
+        &mut self,
+        object_handle: ObjectHandle,
+        collection_index: CollectionIndex,
+        sorted_key: &SortedKey,
+    ) -> Result<Option<Vec<u8>>, ClientApiError> {
+        let removed = copy_buffer(unsafe {
+            actor_sorted_index_remove(
+                object_handle,
+                u32::from(collection_index),
+                u32::from(sorted_key.0),
+                sorted_key.1.as_ptr(),
+                sorted_key.1.len(),
+            )
+        });
+
+        scrypto_decode(&removed).map_err(ClientApiError::DecodeError)
+    }
+
+    fn actor_sorted_index_scan(
+        &mut self,
+        object_handle: ObjectHandle,
+        collection_index: CollectionIndex,
+        count: u32,
+    ) -> Result<Vec<Vec<u8>>, ClientApiError> {
+        let entries = copy_buffer(unsafe {
+            actor_sorted_index_scan(object_handle, u32::from(collection_index), count)
+        });
+
+        scrypto_decode(&entries).map_err(ClientApiError::DecodeError)
+    }
+}
+
 impl ClientAuthApi<ClientApiError> for ScryptoEnv {
     fn get_auth_zone(&mut self) -> Result<NodeId, ClientApiError> {
         let auth_zone = copy_buffer(unsafe { get_auth_zone() });
@@ -477,6 +627,94 @@ impl ClientTransactionRuntimeApi<ClientApiError> for ScryptoEnv {
     }
 }
 
+impl ClientCryptoUtilsApi<ClientApiError> for ScryptoEnv {
+    fn blake2b_256_hash(&mut self, data: Vec<u8>) -> Result<Hash, ClientApiError> {
+        let hash = copy_buffer(unsafe { crypto_utils_blake2b_256_hash(data.as_ptr(), data.len()) });
+
+        scrypto_decode(&hash).map_err(ClientApiError::DecodeError)
+    }
+
+    fn keccak256_hash(&mut self, data: Vec<u8>) -> Result<Hash, ClientApiError> {
+        let hash = copy_buffer(unsafe { crypto_utils_keccak256_hash(data.as_ptr(), data.len()) });
+
+        scrypto_decode(&hash).map_err(ClientApiError::DecodeError)
+    }
+
+    fn sha256_hash(&mut self, data: Vec<u8>) -> Result<Hash, ClientApiError> {
+        let hash = copy_buffer(unsafe { crypto_utils_sha256_hash(data.as_ptr(), data.len()) });
+
+        scrypto_decode(&hash).map_err(ClientApiError::DecodeError)
+    }
+
+    fn secp256k1_ecdsa_verify(
+        &mut self,
+        message_hash: Hash,
+        public_key: Secp256k1PublicKey,
+        signature: Secp256k1Signature,
+    ) -> Result<bool, ClientApiError> {
+        let message_hash = scrypto_encode(&message_hash).unwrap();
+        let public_key = scrypto_encode(&public_key).unwrap();
+        let signature = scrypto_encode(&signature).unwrap();
+
+        let verified = unsafe {
+            crypto_utils_secp256k1_ecdsa_verify(
+                message_hash.as_ptr(),
+                message_hash.len(),
+                public_key.as_ptr(),
+                public_key.len(),
+                signature.as_ptr(),
+                signature.len(),
+            )
+        };
+
+        Ok(verified != 0)
+    }
+
+    fn secp256k1_ecdsa_recover(
+        &mut self,
+        message_hash: Hash,
+        signature: Secp256k1Signature,
+    ) -> Result<Option<Secp256k1PublicKey>, ClientApiError> {
+        let message_hash = scrypto_encode(&message_hash).unwrap();
+        let signature = scrypto_encode(&signature).unwrap();
+
+        let public_key = copy_buffer(unsafe {
+            crypto_utils_secp256k1_ecdsa_recover(
+                message_hash.as_ptr(),
+                message_hash.len(),
+                signature.as_ptr(),
+                signature.len(),
+            )
+        });
+
+        scrypto_decode(&public_key).map_err(ClientApiError::DecodeError)
+    }
+
+    fn ed25519_verify(
+        &mut self,
+        message_hash: Hash,
+        public_key: Ed25519PublicKey,
+        signature: Ed25519Signature,
+    ) -> Result<bool, ClientApiError> {
+        let message_hash = scrypto_encode(&message_hash).unwrap();
+        let public_key = scrypto_encode(&public_key).unwrap();
+        let signature = scrypto_encode(&signature).unwrap();
+
+        let verified = unsafe {
+            crypto_utils_ed25519_verify(
+                message_hash.as_ptr(),
+                message_hash.len(),
+                public_key.as_ptr(),
+                public_key.len(),
+                signature.as_ptr(),
+                signature.len(),
+            )
+        };
+
+        Ok(verified != 0)
+    }
+}
+
 #[macro_export]
 macro_rules! scrypto_env_native_fn {
     ($($vis:vis $fn:ident $fn_name:ident ($($args:tt)*) -> $rtn:ty { $arg:expr })*) => {
@@ -91,6 +91,14 @@ extern "C" {
         _key_len: usize,
     ) -> Buffer;
 
+    pub fn kv_store_scan(
+        _key_value_store_id_ptr: *const u8,
+        _key_value_store_id_len: usize,
+        _start_key_ptr: *const u8,
+        _start_key_len: usize,
+        _limit: u32,
+    ) -> Buffer;
+
     pub fn kv_entry_get(_key_value_entry_lock_handle: u32) -> Buffer;
 
     pub fn kv_entry_set(
@@ -157,6 +165,55 @@ extern "C" {
     // Releases a lock
     pub fn field_lock_release(handle: u32);
 
+    //===============
+    // Actor Index API
+    //===============
+
+    pub fn actor_index_insert(
+        object_handle: u32,
+        collection_index: u32,
+        key_ptr: *const u8,
+        key_len: usize,
+        value_ptr: *const u8,
+        value_len: usize,
+    );
+
+    pub fn actor_index_remove(
+        object_handle: u32,
+        collection_index: u32,
+        key_ptr: *const u8,
+        key_len: usize,
+    ) -> Buffer;
+
+    pub fn actor_index_scan(object_handle: u32, collection_index: u32, count: u32) -> Buffer;
+
+    pub fn actor_index_take(object_handle: u32, collection_index: u32, count: u32) -> Buffer;
+
+    //===============
+    // Actor Sorted Index API
+    //===============
+
+    pub fn actor_sorted_index_insert(
+        object_handle: u32,
+        collection_index: u32,
+        sort_prefix: u32,
+        key_ptr: *const u8,
+        key_len: usize,
+        value_ptr: *const u8,
+        value_len: usize,
+    );
+
+    pub fn actor_sorted_index_remove(
+        object_handle: u32,
+        collection_index: u32,
+        sort_prefix: u32,
+        key_ptr: *const u8,
+        key_len: usize,
+    ) -> Buffer;
+
+    pub fn actor_sorted_index_scan(object_handle: u32, collection_index: u32, count: u32)
+        -> Buffer;
+
     //===============
     // System API
     //===============
@@ -190,6 +247,41 @@ extern "C" {
     pub fn get_transaction_hash() -> Buffer;
 
     pub fn generate_ruid() -> Buffer;
+
+    //===============
+    // Crypto Utils API
+    //===============
+
+    pub fn crypto_utils_blake2b_256_hash(data_ptr: *const u8, data_len: usize) -> Buffer;
+
+    pub fn crypto_utils_keccak256_hash(data_ptr: *const u8, data_len: usize) -> Buffer;
+
+    pub fn crypto_utils_sha256_hash(data_ptr: *const u8, data_len: usize) -> Buffer;
+
+    pub fn crypto_utils_secp256k1_ecdsa_verify(
+        message_hash_ptr: *const u8,
+        message_hash_len: usize,
+        public_key_ptr: *const u8,
+        public_key_len: usize,
+        signature_ptr: *const u8,
+        signature_len: usize,
+    ) -> u32;
+
+    pub fn crypto_utils_secp256k1_ecdsa_recover(
+        message_hash_ptr: *const u8,
+        message_hash_len: usize,
+        signature_ptr: *const u8,
+        signature_len: usize,
+    ) -> Buffer;
+
+    pub fn crypto_utils_ed25519_verify(
+        message_hash_ptr: *const u8,
+        message_hash_len: usize,
+        public_key_ptr: *const u8,
+        public_key_len: usize,
+        signature_ptr: *const u8,
+        signature_len: usize,
+    ) -> u32;
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -303,6 +395,17 @@ pub unsafe fn kv_store_remove_entry(
     unreachable!()
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+pub unsafe fn kv_store_scan(
+    _key_value_store_id_ptr: *const u8,
+    _key_value_store_id_len: usize,
+    _start_key_ptr: *const u8,
+    _start_key_len: usize,
+    _limit: u32,
+) -> Buffer {
+    unreachable!()
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub unsafe fn call_method(
     _receiver_ptr: *const u8,
@@ -354,6 +457,71 @@ pub unsafe fn field_lock_release(_handle: u32) {
     unreachable!()
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+pub unsafe fn actor_index_insert(
+    _object_handle: u32,
+    _collection_index: u32,
+    _key_ptr: *const u8,
+    _key_len: usize,
+    _value_ptr: *const u8,
+    _value_len: usize,
+) {
+    unreachable!()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub unsafe fn actor_index_remove(
+    _object_handle: u32,
+    _collection_index: u32,
+    _key_ptr: *const u8,
+    _key_len: usize,
+) -> Buffer {
+    unreachable!()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub unsafe fn actor_index_scan(_object_handle: u32, _collection_index: u32, _count: u32) -> Buffer {
+    unreachable!()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub unsafe fn actor_index_take(_object_handle: u32, _collection_index: u32, _count: u32) -> Buffer {
+    unreachable!()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub unsafe fn actor_sorted_index_insert(
+    _object_handle: u32,
+    _collection_index: u32,
+    _sort_prefix: u32,
+    _key_ptr: *const u8,
+    _key_len: usize,
+    _value_ptr: *const u8,
+    _value_len: usize,
+) {
+    unreachable!()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub unsafe fn actor_sorted_index_remove(
+    _object_handle: u32,
+    _collection_index: u32,
+    _sort_prefix: u32,
+    _key_ptr: *const u8,
+    _key_len: usize,
+) -> Buffer {
+    unreachable!()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub unsafe fn actor_sorted_index_scan(
+    _object_handle: u32,
+    _collection_index: u32,
+    _count: u32,
+) -> Buffer {
+    unreachable!()
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub unsafe fn get_global_address() -> Buffer {
     unreachable!()
@@ -425,3 +593,52 @@ pub unsafe fn get_transaction_hash() -> Buffer {
 pub unsafe fn generate_ruid() -> Buffer {
     unreachable!()
 }
+
+#[cfg(not(target_arch = "wasm32"))]
+pub unsafe fn crypto_utils_blake2b_256_hash(_data_ptr: *const u8, _data_len: usize) -> Buffer {
+    unreachable!()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub unsafe fn crypto_utils_keccak256_hash(_data_ptr: *const u8, _data_len: usize) -> Buffer {
+    unreachable!()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub unsafe fn crypto_utils_sha256_hash(_data_ptr: *const u8, _data_len: usize) -> Buffer {
+    unreachable!()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub unsafe fn crypto_utils_secp256k1_ecdsa_verify(
+    _message_hash_ptr: *const u8,
+    _message_hash_len: usize,
+    _public_key_ptr: *const u8,
+    _public_key_len: usize,
+    _signature_ptr: *const u8,
+    _signature_len: usize,
+) -> u32 {
+    unreachable!()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub unsafe fn crypto_utils_secp256k1_ecdsa_recover(
+    _message_hash_ptr: *const u8,
+    _message_hash_len: usize,
+    _signature_ptr: *const u8,
+    _signature_len: usize,
+) -> Buffer {
+    unreachable!()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub unsafe fn crypto_utils_ed25519_verify(
+    _message_hash_ptr: *const u8,
+    _message_hash_len: usize,
+    _public_key_ptr: *const u8,
+    _public_key_len: usize,
+    _signature_ptr: *const u8,
+    _signature_len: usize,
+) -> u32 {
+    unreachable!()
+}
@@ -3,6 +3,7 @@
 //=============
 
 pub use crate::component::*;
+pub use crate::crypto_utils::*;
 pub use crate::engine::*;
 pub use crate::modules::*;
 pub use crate::resource::*;
@@ -23,6 +24,7 @@ pub use num_traits::{
     cast::FromPrimitive, cast::ToPrimitive, identities::One, identities::Zero, pow::Pow,
     sign::Signed,
 };
+pub use radix_engine_interface::api::actor_sorted_index_api::SortedKey;
 pub use radix_engine_interface::api::node_modules::auth::*;
 pub use radix_engine_interface::api::node_modules::metadata::*;
 pub use radix_engine_interface::api::node_modules::*;
@@ -0,0 +1,9 @@
+#[cfg(windows)]
+use colored::*;
+use simulator::rtmfmt;
+
+pub fn main() -> Result<(), rtmfmt::Error> {
+    #[cfg(windows)]
+    control::set_virtual_terminal(true).unwrap();
+    rtmfmt::run()
+}
@@ -118,6 +118,38 @@ pub fn dump_component<T: SubstateDatabase, O: std::io::Write>(
         );
     }
 
+    if !resources.locked_balances.is_empty() {
+        writeln!(output, "{}", "Locked Fungible Resources".green().bold());
+        for (last, (component_address, amount)) in resources.locked_balances.iter().identify_last()
+        {
+            writeln!(
+                output,
+                "{} {}: {}",
+                list_item_prefix(last),
+                component_address.display(&address_bech32_encoder),
+                amount
+            );
+        }
+    }
+
+    if !resources.unknown_locked_balances.is_empty() {
+        writeln!(
+            output,
+            "{}",
+            "Fungible Resources With Unknown Locked Balances (the ledger has no current time and epoch)"
+                .green()
+                .bold()
+        );
+        for (last, resource_address) in resources.unknown_locked_balances.iter().identify_last() {
+            writeln!(
+                output,
+                "{} {}",
+                list_item_prefix(last),
+                resource_address.display(&address_bech32_encoder),
+            );
+        }
+    }
+
     writeln!(output, "{}", "Non-fungibles Resources".green().bold());
     for (last, (component_address, ids)) in resources.non_fungibles.iter().identify_last() {
         writeln!(
@@ -6,6 +6,8 @@ pub mod resim;
 pub mod rtmc;
 /// Radix transaction manifest decompiler CLI.
 pub mod rtmd;
+/// Radix transaction manifest formatter and linter CLI.
+pub mod rtmfmt;
 /// Scrypto CLI.
 pub mod scrypto;
 /// Utility functions.
@@ -68,14 +68,13 @@ use radix_engine::transaction::{ExecutionConfig, FeeReserveConfig};
 use radix_engine::types::*;
 use radix_engine::vm::wasm::*;
 use radix_engine::vm::ScryptoVm;
-use radix_engine_interface::api::ObjectModuleId;
 use radix_engine_interface::blueprints::package::{
-    BlueprintDefinition, BlueprintInterface, BlueprintVersionKey, TypePointer,
-    PACKAGE_SCHEMAS_PARTITION_OFFSET,
+    BlueprintDefinition, BlueprintInterface, BlueprintVersionKey, PACKAGE_SCHEMAS_PARTITION_OFFSET,
 };
 use radix_engine_interface::blueprints::resource::FromPublicKey;
 use radix_engine_interface::crypto::hash;
 use radix_engine_interface::network::NetworkDefinition;
+use radix_engine_queries::query::EventDecoder;
 use radix_engine_store_interface::{
     db_key_mapper::{
         MappedCommittableSubstateDatabase, MappedSubstateDatabase, SpreadPrefixKeyMapper,
@@ -432,55 +431,9 @@ pub fn get_event_schema<S: SubstateDatabase>(
     substate_db: &S,
     event_type_identifier: &EventTypeIdentifier,
 ) -> Option<(LocalTypeIndex, ScryptoSchema)> {
-    let (package_address, schema_pointer) = match event_type_identifier {
-        EventTypeIdentifier(Emitter::Method(node_id, node_module), schema_pointer) => {
-            match node_module {
-                ObjectModuleId::AccessRules => (ACCESS_RULES_MODULE_PACKAGE, *schema_pointer),
-                ObjectModuleId::Royalty => (ROYALTY_MODULE_PACKAGE, *schema_pointer),
-                ObjectModuleId::Metadata => (METADATA_MODULE_PACKAGE, *schema_pointer),
-                ObjectModuleId::Main => {
-                    let type_info = substate_db
-                        .get_mapped::<SpreadPrefixKeyMapper, TypeInfoSubstate>(
-                            node_id,
-                            TYPE_INFO_FIELD_PARTITION,
-                            &TypeInfoField::TypeInfo.into(),
-                        )
-                        .unwrap();
-                    match type_info {
-                        TypeInfoSubstate::Object(ObjectInfo { blueprint_id, .. }) => {
-                            (blueprint_id.package_address, *schema_pointer)
-                        }
-                        _ => return None,
-                    }
-                }
-            }
-        }
-        EventTypeIdentifier(Emitter::Function(node_id, ..), schema_pointer) => (
-            PackageAddress::new_or_panic(node_id.clone().into()),
-            *schema_pointer,
-        ),
-    };
-
-    match schema_pointer {
-        TypePointer::Package(schema_hash, index) => {
-            let schema = substate_db
-                .get_mapped::<SpreadPrefixKeyMapper, KeyValueEntrySubstate<ScryptoSchema>>(
-                    package_address.as_node_id(),
-                    MAIN_BASE_PARTITION
-                        .at_offset(PACKAGE_SCHEMAS_PARTITION_OFFSET)
-                        .unwrap(),
-                    &SubstateKey::Map(scrypto_encode(&schema_hash).unwrap()),
-                )
-                .unwrap()
-                .value
-                .unwrap();
-
-            Some((index, schema))
-        }
-        TypePointer::Instance(..) => {
-            todo!()
-        }
-    }
+    EventDecoder::new(substate_db)
+        .event_schema(event_type_identifier)
+        .ok()
 }
 
 pub fn db_upsert_timestamps(
@@ -2,7 +2,10 @@ use clap::Parser;
 use radix_engine::{types::*, utils::*};
 use std::path::PathBuf;
 use std::str::FromStr;
-use transaction::manifest::{compile, BlobProvider};
+use transaction::manifest::{
+    compile_error_diagnostics, compile_with_source_map, instruction_diagnostics, BlobProvider,
+    FileIncludeProvider, TemplateContext,
+};
 
 /// Radix transaction manifest compiler
 #[derive(Parser, Debug)]
@@ -20,6 +23,10 @@ pub struct Args {
     #[clap(short, long, multiple = true)]
     blobs: Option<Vec<String>>,
 
+    /// The values of the manifest parameters, as `name=value`
+    #[clap(short, long = "param", multiple_occurrences = true)]
+    params: Vec<String>,
+
     /// Input file
     #[clap(required = true)]
     input: PathBuf,
@@ -31,6 +38,8 @@ pub enum Error {
     EncodeError(sbor::EncodeError),
     CompileError(transaction::manifest::CompileError),
     ParseNetworkError(ParseNetworkError),
+    /// A `--param` argument which is not of the form `name=value`.
+    InvalidParam(String),
     InstructionSchemaValidationError(radix_engine::utils::LocatedInstructionSchemaValidationError),
 }
 
@@ -48,10 +57,41 @@ pub fn run() -> Result<(), Error> {
             blobs.push(std::fs::read(path).map_err(Error::IoError)?);
         }
     }
-    let transaction = compile(&content, &network, BlobProvider::new_with_blobs(blobs))
-        .map_err(Error::CompileError)?;
-    validate_call_arguments_to_native_components(&transaction.instructions)
-        .map_err(Error::InstructionSchemaValidationError)?;
+    // Includes are relative to the directory of the input file
+    let include_provider = FileIncludeProvider::new(
+        args.input
+            .parent()
+            .map(|directory| directory.to_path_buf())
+            .unwrap_or_default(),
+    );
+    let mut template_context = TemplateContext::new().with_include_provider(&include_provider);
+    for param in args.params {
+        match param.split_once('=') {
+            Some((name, value)) => template_context = template_context.with_param(name, value),
+            None => return Err(Error::InvalidParam(param)),
+        }
+    }
+    let (transaction, source_map) = compile_with_source_map(
+        &content,
+        &network,
+        BlobProvider::new_with_blobs(blobs),
+        &template_context,
+    )
+    .map_err(|error| {
+        eprint!("{}", compile_error_diagnostics(&content, &error));
+        Error::CompileError(error)
+    })?;
+    validate_call_arguments_to_native_components(&transaction.instructions).map_err(|error| {
+        if let Some(diagnostics) = instruction_diagnostics(
+            &content,
+            &source_map,
+            error.instruction_index,
+            &format!("{:?}", error.cause),
+        ) {
+            eprint!("{}", diagnostics);
+        }
+        Error::InstructionSchemaValidationError(error)
+    })?;
     std::fs::write(
         args.output,
         manifest_encode(&transaction).map_err(Error::EncodeError)?,
@@ -0,0 +1,246 @@
+use radix_engine::types::*;
+use radix_engine::utils::{
+    validate_call_arguments_to_native_components, InstructionSchemaValidationError,
+};
+use radix_engine_interface::blueprints::account::*;
+use serde::Serialize;
+use transaction::builder::{ManifestObjectNames, TransactionManifestV1};
+use transaction::manifest::{analyze_manifest, ManifestIssue};
+use transaction::model::InstructionV1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LintRule {
+    /// A method of a native blueprint which does not exist is called.
+    UnknownNativeMethod,
+    /// A method or function of a native blueprint is called with invalid arguments.
+    InvalidNativeCall,
+    /// A bucket is never consumed (so the transaction would fail).
+    UnusedBucket,
+    /// No fee is locked (so the transaction would be rejected, unless fees are locked by a
+    /// component called by the manifest).
+    MissingLockFee,
+    /// Resources are deposited with `deposit` or `deposit_batch`, which require the owner's
+    /// auth, rather than with `try_deposit_*` (which third parties can call), into an account
+    /// the transaction does not sign for.
+    DepositWithoutTry,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Lint {
+    pub rule: LintRule,
+    /// The index of the instruction the lint is about, if any.
+    pub instruction_index: Option<usize>,
+    pub message: String,
+}
+
+const LOCK_FEE_METHODS: [&str; 4] = [
+    ACCOUNT_LOCK_FEE_IDENT,
+    ACCOUNT_LOCK_CONTINGENT_FEE_IDENT,
+    ACCOUNT_LOCK_FEE_AND_WITHDRAW_IDENT,
+    ACCOUNT_LOCK_FEE_AND_WITHDRAW_NON_FUNGIBLES_IDENT,
+];
+
+/// The account methods requiring the owner's auth, the calls of which mean that the transaction
+/// signs for the account.
+const OWNER_AUTH_METHODS: [&str; 12] = [
+    ACCOUNT_LOCK_FEE_IDENT,
+    ACCOUNT_LOCK_CONTINGENT_FEE_IDENT,
+    ACCOUNT_LOCK_FEE_AND_WITHDRAW_IDENT,
+    ACCOUNT_LOCK_FEE_AND_WITHDRAW_NON_FUNGIBLES_IDENT,
+    ACCOUNT_WITHDRAW_IDENT,
+    ACCOUNT_WITHDRAW_NON_FUNGIBLES_IDENT,
+    ACCOUNT_CREATE_PROOF_IDENT,
+    ACCOUNT_CREATE_PROOF_OF_AMOUNT_IDENT,
+    ACCOUNT_CREATE_PROOF_OF_NON_FUNGIBLES_IDENT,
+    ACCOUNT_BURN_IDENT,
+    ACCOUNT_BURN_NON_FUNGIBLES_IDENT,
+    ACCOUNT_SECURIFY_IDENT,
+];
+
+fn account_address(address: &DynamicGlobalAddress) -> Option<GlobalAddress> {
+    match address {
+        DynamicGlobalAddress::Static(address)
+            if matches!(
+                address.as_node_id().entity_type(),
+                Some(
+                    EntityType::GlobalAccount
+                        | EntityType::GlobalVirtualSecp256k1Account
+                        | EntityType::GlobalVirtualEd25519Account
+                )
+            ) =>
+        {
+            Some(*address)
+        }
+        _ => None,
+    }
+}
+
+/// Lints a compiled manifest, naming its buckets after the given names (e.g. those of the
+/// manifest source).
+pub fn lint_manifest(
+    manifest: &TransactionManifestV1,
+    object_names: &ManifestObjectNames,
+) -> Vec<Lint> {
+    let mut lints = Vec::new();
+
+    if let Err(error) = validate_call_arguments_to_native_components(&manifest.instructions) {
+        let rule = match error.cause {
+            InstructionSchemaValidationError::MethodNotFound(_) => LintRule::UnknownNativeMethod,
+            _ => LintRule::InvalidNativeCall,
+        };
+        lints.push(Lint {
+            rule,
+            instruction_index: Some(error.instruction_index),
+            message: format!("{:?}", error.cause),
+        });
+    }
+
+    // Buckets are only created by the instructions taking from the worktop, in order
+    let bucket_instructions: Vec<usize> = manifest
+        .instructions
+        .iter()
+        .enumerate()
+        .filter(|(_, instruction)| {
+            matches!(
+                instruction,
+                InstructionV1::TakeFromWorktop { .. }
+                    | InstructionV1::TakeNonFungiblesFromWorktop { .. }
+                    | InstructionV1::TakeAllFromWorktop { .. }
+            )
+        })
+        .map(|(index, _)| index)
+        .collect();
+    if let Ok(analysis) = analyze_manifest(&manifest.instructions) {
+        for issue in analysis.issues {
+            if let ManifestIssue::UnusedBucket(bucket) = issue {
+                let name = match object_names.bucket_names.get(&bucket) {
+                    Some(name) => format!("\"{}\"", name),
+                    None => format!("{:?}", bucket),
+                };
+                lints.push(Lint {
+                    rule: LintRule::UnusedBucket,
+                    instruction_index: bucket_instructions.get(bucket.0 as usize).cloned(),
+                    message: format!("Bucket {} is never consumed", name),
+                });
+            }
+        }
+    }
+
+    // Deposits into the accounts the transaction signs for (i.e. uses the owner's auth of
+    // elsewhere in the manifest) are fine, wherever these calls appear in the manifest
+    let signed_accounts: IndexSet<GlobalAddress> = manifest
+        .instructions
+        .iter()
+        .filter_map(|instruction| match instruction {
+            InstructionV1::CallMethod {
+                address,
+                method_name,
+                ..
+            } if OWNER_AUTH_METHODS.contains(&method_name.as_str()) => account_address(address),
+            _ => None,
+        })
+        .collect();
+
+    let mut locks_fee = false;
+    for (index, instruction) in manifest.instructions.iter().enumerate() {
+        if let InstructionV1::CallMethod {
+            address,
+            method_name,
+            ..
+        } = instruction
+        {
+            if LOCK_FEE_METHODS.contains(&method_name.as_str()) {
+                locks_fee = true;
+            }
+            let is_unsigned_account = account_address(address)
+                .map(|address| !signed_accounts.contains(&address))
+                .unwrap_or(false);
+            if is_unsigned_account
+                && (method_name == ACCOUNT_DEPOSIT_IDENT
+                    || method_name == ACCOUNT_DEPOSIT_BATCH_IDENT)
+            {
+                lints.push(Lint {
+                    rule: LintRule::DepositWithoutTry,
+                    instruction_index: Some(index),
+                    message: format!(
+                        "`{}` requires the auth of the account's owner, which the transaction does not sign for; use `try_deposit_or_abort` or `try_deposit_or_refund` (or their batch variants) to deposit into third-party accounts",
+                        method_name
+                    ),
+                });
+            }
+        }
+    }
+    if !locks_fee {
+        lints.push(Lint {
+            rule: LintRule::MissingLockFee,
+            instruction_index: None,
+            message: "No fee is locked by the manifest".to_string(),
+        });
+    }
+
+    lints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use transaction::manifest::compile_with_source_map;
+    use transaction::manifest::{BlobProvider, TemplateContext};
+
+    fn lint(manifest: &str) -> Vec<(LintRule, Option<usize>)> {
+        let (manifest, source_map) = compile_with_source_map(
+            manifest,
+            &NetworkDefinition::simulator(),
+            BlobProvider::default(),
+            &TemplateContext::new(),
+        )
+        .unwrap();
+        lint_manifest(&manifest, source_map.object_names())
+            .into_iter()
+            .map(|lint| (lint.rule, lint.instruction_index))
+            .collect()
+    }
+
+    #[test]
+    fn test_lint_manifest() {
+        assert_eq!(
+            lint(
+                r#"
+CALL_METHOD Address("account_sim1cyvgx33089ukm2pl97pv4max0x40ruvfy4lt60yvya744cve475w0q") "lock_fee" Decimal("10");
+CALL_METHOD Address("account_sim1cyvgx33089ukm2pl97pv4max0x40ruvfy4lt60yvya744cve475w0q") "withdraw" Address("resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxakj8n3") Decimal("1");
+TAKE_ALL_FROM_WORKTOP Address("resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxakj8n3") Bucket("xrd");
+CALL_METHOD Address("account_sim1cyvgx33089ukm2pl97pv4max0x40ruvfy4lt60yvya744cve475w0q") "try_deposit_or_abort" Bucket("xrd");
+"#
+            ),
+            vec![]
+        );
+        assert_eq!(
+            lint(
+                r#"
+CALL_METHOD Address("account_sim1cyvgx33089ukm2pl97pv4max0x40ruvfy4lt60yvya744cve475w0q") "withdraw" Address("resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxakj8n3") Decimal("1");
+TAKE_ALL_FROM_WORKTOP Address("resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxakj8n3") Bucket("unused");
+CALL_METHOD Address("account_sim168qgdkgfqxpnswu38wy6fy5v0q0um52zd0umuely5t9xrf88t3unc0") "deposit_batch" Expression("ENTIRE_WORKTOP");
+CALL_METHOD Address("account_sim1cyvgx33089ukm2pl97pv4max0x40ruvfy4lt60yvya744cve475w0q") "withdraw_everything";
+"#
+            ),
+            vec![
+                (LintRule::UnknownNativeMethod, Some(3)),
+                (LintRule::UnusedBucket, Some(1)),
+                (LintRule::DepositWithoutTry, Some(2)),
+                (LintRule::MissingLockFee, None),
+            ]
+        );
+
+        // The owner's auth of the account is used by the manifest, so it signs for the account
+        assert_eq!(
+            lint(
+                r#"
+CALL_METHOD Address("account_sim1cyvgx33089ukm2pl97pv4max0x40ruvfy4lt60yvya744cve475w0q") "lock_fee" Decimal("10");
+CALL_METHOD Address("account_sim1cyvgx33089ukm2pl97pv4max0x40ruvfy4lt60yvya744cve475w0q") "deposit_batch" Expression("ENTIRE_WORKTOP");
+"#
+            ),
+            vec![]
+        );
+    }
+}
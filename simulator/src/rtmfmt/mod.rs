@@ -0,0 +1,185 @@
+mod lints;
+
+pub use lints::*;
+
+use clap::Parser;
+use radix_engine::types::*;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::str::FromStr;
+use transaction::manifest::{
+    compile_error_diagnostics, compile_with_source_map, format_manifest, FormatError,
+    MockBlobProvider, TemplateContext,
+};
+
+/// Radix transaction manifest formatter and linter
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None, name = "rtmfmt")]
+pub struct Args {
+    /// Network to Use [Simulator | Alphanet | Mainnet]
+    #[clap(short, long)]
+    network: Option<String>,
+
+    /// Only checks that the input files are formatted, instead of printing them formatted
+    #[clap(short, long, action)]
+    check: bool,
+
+    /// Writes the formatted manifests back to the input files, instead of printing them
+    #[clap(short, long, action)]
+    write: bool,
+
+    /// Also lints the manifests
+    #[clap(short, long, action)]
+    lint: bool,
+
+    /// Prints a JSON report of the formatting check and lints, instead of the manifests
+    #[clap(short, long, action)]
+    json: bool,
+
+    /// Input files (manifest templates, i.e. using `PARAM` or `INCLUDE`, can't be formatted and are
+    /// reported as errors)
+    #[clap(required = true, multiple = true)]
+    inputs: Vec<PathBuf>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    IoError(std::io::Error),
+    ParseNetworkError(ParseNetworkError),
+    JsonError(serde_json::Error),
+    /// Some manifests are not formatted (with `--check`), or have lints (with `--lint`).
+    ChecksFailed,
+}
+
+/// The result of formatting and linting a manifest file.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileReport {
+    pub file: PathBuf,
+    /// Whether the file is already formatted
+    pub formatted: bool,
+    /// The error preventing the manifest from being formatted, if any
+    pub error: Option<String>,
+    pub lints: Vec<LocatedLint>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LocatedLint {
+    #[serde(flatten)]
+    pub lint: Lint,
+    /// The (1-indexed) line of the manifest at which the instruction starts, if any.
+    pub line: Option<usize>,
+}
+
+pub fn run() -> Result<(), Error> {
+    let args = Args::parse();
+
+    let network = match args.network {
+        Some(n) => NetworkDefinition::from_str(&n).map_err(Error::ParseNetworkError)?,
+        None => NetworkDefinition::simulator(),
+    };
+
+    let mut reports = Vec::new();
+    for input in args.inputs {
+        let content = std::fs::read_to_string(&input).map_err(Error::IoError)?;
+        let mut report = FileReport {
+            file: input.clone(),
+            formatted: false,
+            error: None,
+            lints: Vec::new(),
+        };
+
+        match format_manifest(&content, &network) {
+            Ok(formatted) => {
+                report.formatted = formatted == content;
+                if args.write && !report.formatted {
+                    std::fs::write(&input, &formatted).map_err(Error::IoError)?;
+                } else if !args.write && !args.check && !args.json {
+                    print!("{}", formatted);
+                }
+            }
+            Err(error) => {
+                report.error = Some(format_error_message(&content, error));
+            }
+        }
+
+        if args.lint && report.error.is_none() {
+            let (manifest, source_map) = compile_with_source_map(
+                &content,
+                &network,
+                MockBlobProvider::new(),
+                &TemplateContext::new(),
+            )
+            .expect("The manifest has already been compiled");
+            for lint in lint_manifest(&manifest, source_map.object_names()) {
+                let line = lint
+                    .instruction_index
+                    .and_then(|index| source_map.instruction_line(index));
+                if !args.json {
+                    match line {
+                        Some(line) => eprintln!(
+                            "{}:{}: {:?}: {}",
+                            input.display(),
+                            line,
+                            lint.rule,
+                            lint.message
+                        ),
+                        None => eprintln!("{}: {:?}: {}", input.display(), lint.rule, lint.message),
+                    }
+                }
+                report.lints.push(LocatedLint { lint, line });
+            }
+        }
+
+        if !args.json {
+            if let Some(error) = &report.error {
+                eprint!("{}: {}", input.display(), error);
+            } else if args.check && !report.formatted {
+                eprintln!("{}: not formatted", input.display());
+            }
+        }
+        reports.push(report);
+    }
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&reports).map_err(Error::JsonError)?
+        );
+    }
+
+    let failed = reports.iter().any(|report| {
+        report.error.is_some() || (args.check && !report.formatted) || !report.lints.is_empty()
+    });
+    if failed {
+        Err(Error::ChecksFailed)
+    } else {
+        Ok(())
+    }
+}
+
+/// Describes why a manifest can't be formatted, as printed after the name of its file.
+fn format_error_message(content: &str, error: FormatError) -> String {
+    match error {
+        FormatError::CompileError(error) => compile_error_diagnostics(content, &error),
+        FormatError::TemplateNotSupported => {
+            "manifest templates (using PARAM or INCLUDE) can't be formatted\n".to_string()
+        }
+        error => format!("{:?}\n", error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_templates_are_reported_as_not_supported() {
+        let content = "PARAM \"count\" U32 1u32;\nCLEAR_AUTH_ZONE;\n";
+        let error = format_manifest(content, &NetworkDefinition::simulator()).unwrap_err();
+
+        assert_eq!(
+            format_error_message(content, error),
+            "manifest templates (using PARAM or INCLUDE) can't be formatted\n"
+        );
+    }
+}
@@ -59,7 +59,7 @@ token_address=`$resim new-token-mutable $minter_badge | awk '/Resource:/ {print
 # Test - transfer non fungible
 non_fungible_create_receipt=`$resim new-simple-badge --name 'TestNonFungible'`
 non_fungible_global_id=`echo "$non_fungible_create_receipt" | awk '/NonFungibleGlobalId:/ {print $NF}'`
-$resim call-method $account2 try_deposit_or_abort "$non_fungible_global_id"
+$resim transfer 1 "${non_fungible_global_id%%:*}" $account2
 
 # Test - mint and transfer (Mintable that requires a `ResourceAddress`)
 $resim mint 777 $token_address --proofs $minter_badge:1
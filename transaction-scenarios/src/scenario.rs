@@ -113,7 +113,7 @@ impl ScenarioCore {
                         builder.call_method(
                             to_account,
                             ACCOUNT_TRY_DEPOSIT_OR_ABORT_IDENT,
-                            manifest_args!(bucket),
+                            manifest_args!(bucket, Option::<ManifestProof>::None),
                         )
                     })
             },
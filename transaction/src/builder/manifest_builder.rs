@@ -25,9 +25,11 @@ use radix_engine_interface::blueprints::identity::{
     IDENTITY_CREATE_ADVANCED_IDENT, IDENTITY_CREATE_IDENT,
 };
 use radix_engine_interface::blueprints::package::{
-    PackageClaimRoyaltiesInput, PackageDefinition, PackagePublishWasmAdvancedManifestInput,
-    PackagePublishWasmManifestInput, PACKAGE_BLUEPRINT, PACKAGE_CLAIM_ROYALTIES_IDENT,
-    PACKAGE_PUBLISH_WASM_ADVANCED_IDENT, PACKAGE_PUBLISH_WASM_IDENT,
+    BlueprintDefinitionInit, BlueprintVersion, PackageClaimRoyaltiesInput, PackageDefinition,
+    PackagePublishWasmAdvancedManifestInput, PackagePublishWasmManifestInput,
+    PackageUpgradeBlueprintManifestInput, PackageUpgradeComponentInput, PACKAGE_BLUEPRINT,
+    PACKAGE_CLAIM_ROYALTIES_IDENT, PACKAGE_PUBLISH_WASM_ADVANCED_IDENT, PACKAGE_PUBLISH_WASM_IDENT,
+    PACKAGE_UPGRADE_BLUEPRINT_IDENT, PACKAGE_UPGRADE_COMPONENT_IDENT,
 };
 use radix_engine_interface::blueprints::resource::ResourceAction::{Burn, Mint};
 use radix_engine_interface::blueprints::resource::*;
@@ -49,6 +51,11 @@ use sbor::rust::string::String;
 use sbor::rust::string::ToString;
 use sbor::rust::vec::Vec;
 
+use super::{
+    ExistingManifestBucket, ExistingManifestProof, ManifestNameRegistry, ManifestObjectNames,
+    PartialManifestStepV1, PartialManifestV1,
+};
+use crate::errors::*;
 use crate::model::*;
 use crate::validation::*;
 
@@ -60,6 +67,11 @@ pub struct ManifestBuilder {
     instructions: Vec<InstructionV1>,
     /// Blobs
     blobs: BTreeMap<Hash, Vec<u8>>,
+    /// Names given to buckets, proofs, address reservations and addresses.
+    registry: ManifestNameRegistry,
+    /// Buckets yielded to and accepted from other partial manifests, with the number of
+    /// instructions preceding them.
+    interface_steps: Vec<(usize, PartialManifestStepV1)>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, ManifestSbor)]
@@ -110,6 +122,8 @@ impl ManifestBuilder {
             id_allocator: ManifestIdAllocator::new(),
             instructions: Vec::new(),
             blobs: BTreeMap::default(),
+            registry: ManifestNameRegistry::new(),
+            interface_steps: Vec::new(),
         }
     }
 
@@ -222,12 +236,107 @@ impl ManifestBuilder {
         then(builder, bucket_id.unwrap())
     }
 
+    /// Takes resource from worktop, into a bucket with the given name.
+    pub fn take_all_from_worktop_named(
+        &mut self,
+        resource_address: ResourceAddress,
+        new_bucket: &str,
+    ) -> &mut Self {
+        let (builder, bucket_id, _) =
+            self.add_instruction(InstructionV1::TakeAllFromWorktop { resource_address });
+        builder
+            .registry
+            .register_bucket(new_bucket, bucket_id.unwrap());
+        builder
+    }
+
+    /// Takes resource from worktop, by amount, into a bucket with the given name.
+    pub fn take_from_worktop_named(
+        &mut self,
+        resource_address: ResourceAddress,
+        amount: Decimal,
+        new_bucket: &str,
+    ) -> &mut Self {
+        let (builder, bucket_id, _) = self.add_instruction(InstructionV1::TakeFromWorktop {
+            amount,
+            resource_address,
+        });
+        builder
+            .registry
+            .register_bucket(new_bucket, bucket_id.unwrap());
+        builder
+    }
+
+    /// Takes resource from worktop, by non-fungible ids, into a bucket with the given name.
+    pub fn take_non_fungibles_from_worktop_named(
+        &mut self,
+        resource_address: ResourceAddress,
+        ids: &BTreeSet<NonFungibleLocalId>,
+        new_bucket: &str,
+    ) -> &mut Self {
+        let (builder, bucket_id, _) =
+            self.add_instruction(InstructionV1::TakeNonFungiblesFromWorktop {
+                ids: ids.clone().into_iter().collect(),
+                resource_address,
+            });
+        builder
+            .registry
+            .register_bucket(new_bucket, bucket_id.unwrap());
+        builder
+    }
+
     /// Adds a bucket of resource to worktop.
-    pub fn return_to_worktop(&mut self, bucket_id: ManifestBucket) -> &mut Self {
+    pub fn return_to_worktop<B: ExistingManifestBucket>(&mut self, bucket: B) -> &mut Self {
+        let bucket_id = bucket.resolve(&self.registry);
         self.add_instruction(InstructionV1::ReturnToWorktop { bucket_id })
             .0
     }
 
+    /// Yields a bucket to the partial manifest accepting the interface point of the given name.
+    ///
+    /// The manifest must then be built with [`Self::build_partial()`].
+    pub fn yield_bucket<B: ExistingManifestBucket>(&mut self, name: &str, bucket: B) -> &mut Self {
+        let bucket_id = bucket.resolve(&self.registry);
+        self.interface_steps.push((
+            self.instructions.len(),
+            PartialManifestStepV1::YieldBucket {
+                name: name.to_owned(),
+                bucket_id,
+            },
+        ));
+        self
+    }
+
+    /// Accepts the bucket yielded by another partial manifest at the interface point of the
+    /// given name.
+    ///
+    /// The manifest must then be built with [`Self::build_partial()`].
+    pub fn accept_bucket<F>(&mut self, name: &str, then: F) -> &mut Self
+    where
+        F: FnOnce(&mut Self, ManifestBucket) -> &mut Self,
+    {
+        let bucket_id = self.add_accept_bucket(name);
+        then(self, bucket_id)
+    }
+
+    /// Accepts the bucket yielded by another partial manifest at the interface point of the
+    /// given name, as a bucket with the given name.
+    pub fn accept_bucket_named(&mut self, name: &str, new_bucket: &str) -> &mut Self {
+        let bucket_id = self.add_accept_bucket(name);
+        self.registry.register_bucket(new_bucket, bucket_id);
+        self
+    }
+
+    fn add_accept_bucket(&mut self, name: &str) -> ManifestBucket {
+        self.interface_steps.push((
+            self.instructions.len(),
+            PartialManifestStepV1::AcceptBucket {
+                name: name.to_owned(),
+            },
+        ));
+        self.id_allocator.new_bucket_id()
+    }
+
     /// Asserts that worktop contains resource.
     pub fn assert_worktop_contains_any(&mut self, resource_address: ResourceAddress) -> &mut Self {
         self.add_instruction(InstructionV1::AssertWorktopContainsAny { resource_address })
@@ -269,8 +378,18 @@ impl ManifestBuilder {
         then(builder, proof_id.unwrap())
     }
 
+    /// Pops the most recent proof from auth zone, into a proof with the given name.
+    pub fn pop_from_auth_zone_named(&mut self, new_proof: &str) -> &mut Self {
+        let (builder, _, proof_id) = self.add_instruction(InstructionV1::PopFromAuthZone {});
+        builder
+            .registry
+            .register_proof(new_proof, proof_id.unwrap());
+        builder
+    }
+
     /// Pushes a proof onto the auth zone
-    pub fn push_to_auth_zone(&mut self, proof_id: ManifestProof) -> &mut Self {
+    pub fn push_to_auth_zone<P: ExistingManifestProof>(&mut self, proof: P) -> &mut Self {
+        let proof_id = proof.resolve(&self.registry);
         self.add_instruction(InstructionV1::PushToAuthZone { proof_id });
         self
     }
@@ -431,8 +550,160 @@ impl ManifestBuilder {
         )
     }
 
+    /// Creates proof from the auth zone, with the given name.
+    pub fn create_proof_from_auth_zone_named(
+        &mut self,
+        resource_address: ResourceAddress,
+        new_proof: &str,
+    ) -> &mut Self {
+        self.add_named_proof_instruction(
+            InstructionV1::CreateProofFromAuthZone { resource_address },
+            new_proof,
+        )
+    }
+
+    /// Creates proof from the auth zone by amount, with the given name.
+    pub fn create_proof_from_auth_zone_of_amount_named(
+        &mut self,
+        resource_address: ResourceAddress,
+        amount: Decimal,
+        new_proof: &str,
+    ) -> &mut Self {
+        self.add_named_proof_instruction(
+            InstructionV1::CreateProofFromAuthZoneOfAmount {
+                amount,
+                resource_address,
+            },
+            new_proof,
+        )
+    }
+
+    /// Creates proof from the auth zone by non-fungible ids, with the given name.
+    pub fn create_proof_from_auth_zone_of_non_fungibles_named(
+        &mut self,
+        resource_address: ResourceAddress,
+        ids: &BTreeSet<NonFungibleLocalId>,
+        new_proof: &str,
+    ) -> &mut Self {
+        self.add_named_proof_instruction(
+            InstructionV1::CreateProofFromAuthZoneOfNonFungibles {
+                ids: ids.clone().into_iter().collect(),
+                resource_address,
+            },
+            new_proof,
+        )
+    }
+
+    /// Creates proof from the auth zone, with the given name.
+    pub fn create_proof_from_auth_zone_of_all_named(
+        &mut self,
+        resource_address: ResourceAddress,
+        new_proof: &str,
+    ) -> &mut Self {
+        self.add_named_proof_instruction(
+            InstructionV1::CreateProofFromAuthZoneOfAll { resource_address },
+            new_proof,
+        )
+    }
+
+    /// Creates proof from a bucket, with the given name.
+    pub fn create_proof_from_bucket_named<B: ExistingManifestBucket>(
+        &mut self,
+        bucket: B,
+        new_proof: &str,
+    ) -> &mut Self {
+        let bucket_id = bucket.resolve(&self.registry);
+        self.add_named_proof_instruction(
+            InstructionV1::CreateProofFromBucket { bucket_id },
+            new_proof,
+        )
+    }
+
+    pub fn create_proof_from_bucket_of_amount_named<B: ExistingManifestBucket>(
+        &mut self,
+        bucket: B,
+        amount: Decimal,
+        new_proof: &str,
+    ) -> &mut Self {
+        let bucket_id = bucket.resolve(&self.registry);
+        self.add_named_proof_instruction(
+            InstructionV1::CreateProofFromBucketOfAmount { bucket_id, amount },
+            new_proof,
+        )
+    }
+
+    pub fn create_proof_from_bucket_of_non_fungibles_named<B: ExistingManifestBucket>(
+        &mut self,
+        bucket: B,
+        ids: BTreeSet<NonFungibleLocalId>,
+        new_proof: &str,
+    ) -> &mut Self {
+        let bucket_id = bucket.resolve(&self.registry);
+        self.add_named_proof_instruction(
+            InstructionV1::CreateProofFromBucketOfNonFungibles {
+                bucket_id,
+                ids: ids.into_iter().collect(),
+            },
+            new_proof,
+        )
+    }
+
+    pub fn create_proof_from_bucket_of_all_named<B: ExistingManifestBucket>(
+        &mut self,
+        bucket: B,
+        new_proof: &str,
+    ) -> &mut Self {
+        let bucket_id = bucket.resolve(&self.registry);
+        self.add_named_proof_instruction(
+            InstructionV1::CreateProofFromBucketOfAll { bucket_id },
+            new_proof,
+        )
+    }
+
+    /// Clones a proof, into a proof with the given name.
+    pub fn clone_proof_named<P: ExistingManifestProof>(
+        &mut self,
+        proof: P,
+        new_proof: &str,
+    ) -> &mut Self {
+        let proof_id = proof.resolve(&self.registry);
+        self.add_named_proof_instruction(InstructionV1::CloneProof { proof_id }, new_proof)
+    }
+
+    fn add_named_proof_instruction(&mut self, inst: InstructionV1, new_proof: &str) -> &mut Self {
+        let (builder, _, proof_id) = self.add_instruction(inst);
+        builder
+            .registry
+            .register_proof(new_proof, proof_id.unwrap());
+        builder
+    }
+
+    /// Allocates a global address, whose reservation and address can later be referred to by the
+    /// given names (see [`Self::address_reservation()`] and [`Self::named_address()`]).
+    pub fn allocate_global_address_named(
+        &mut self,
+        blueprint_id: BlueprintId,
+        new_address_reservation: &str,
+        new_address: &str,
+    ) -> &mut Self {
+        let (builder, mut symbols) =
+            self.add_instruction_advanced(InstructionV1::AllocateGlobalAddress {
+                package_address: blueprint_id.package_address,
+                blueprint_name: blueprint_id.blueprint_name,
+            });
+        builder.registry.register_address_reservation(
+            new_address_reservation,
+            symbols.new_address_reservations.pop().unwrap(),
+        );
+        builder
+            .registry
+            .register_address(new_address, symbols.new_addresses.pop().unwrap());
+        builder
+    }
+
     /// Drops a proof.
-    pub fn drop_proof(&mut self, proof_id: ManifestProof) -> &mut Self {
+    pub fn drop_proof<P: ExistingManifestProof>(&mut self, proof: P) -> &mut Self {
+        let proof_id = proof.resolve(&self.registry);
         self.add_instruction(InstructionV1::DropProof { proof_id })
             .0
     }
@@ -702,14 +973,14 @@ impl ManifestBuilder {
     }
 
     /// Calls a scrypto method where the arguments should be an array of encoded Scrypto value.
-    pub fn call_method<A: Into<GlobalAddress>>(
+    pub fn call_method<A: Into<DynamicGlobalAddress>>(
         &mut self,
         address: A,
         method_name: &str,
         args: ManifestValue,
     ) -> &mut Self {
         self.add_instruction(InstructionV1::CallMethod {
-            address: address.into().into(),
+            address: address.into(),
             method_name: method_name.to_owned(),
             args: args,
         });
@@ -725,6 +996,50 @@ impl ManifestBuilder {
         .0
     }
 
+    /// Adds a new version of a blueprint of a package, with the migrations of the fields of its
+    /// components from previous versions.
+    pub fn upgrade_blueprint<S: ToString>(
+        &mut self,
+        package_address: PackageAddress,
+        blueprint: S,
+        version: BlueprintVersion,
+        code: Vec<u8>,
+        definition: BlueprintDefinitionInit,
+        migrations: BTreeMap<BlueprintVersion, String>,
+    ) -> &mut Self {
+        let code_hash = hash(&code);
+        self.blobs.insert(code_hash, code);
+
+        self.add_instruction(InstructionV1::CallMethod {
+            address: package_address.into(),
+            method_name: PACKAGE_UPGRADE_BLUEPRINT_IDENT.to_string(),
+            args: to_manifest_value_and_unwrap!(&PackageUpgradeBlueprintManifestInput {
+                blueprint: blueprint.to_string(),
+                version,
+                code: ManifestBlobRef(code_hash.0),
+                definition,
+                migrations,
+            }),
+        })
+        .0
+    }
+
+    /// Upgrades a component to the latest version of its blueprint.
+    pub fn upgrade_component(
+        &mut self,
+        package_address: PackageAddress,
+        component_address: ComponentAddress,
+    ) -> &mut Self {
+        self.add_instruction(InstructionV1::CallMethod {
+            address: package_address.into(),
+            method_name: PACKAGE_UPGRADE_COMPONENT_IDENT.to_string(),
+            args: to_manifest_value_and_unwrap!(&PackageUpgradeComponentInput {
+                component: component_address,
+            }),
+        })
+        .0
+    }
+
     pub fn set_component_royalty<S: ToString>(
         &mut self,
         component_address: ComponentAddress,
@@ -927,8 +1242,55 @@ impl ManifestBuilder {
         self
     }
 
+    /// Returns the bucket with the given name.
+    pub fn bucket(&self, name: &str) -> ManifestBucket {
+        self.registry.bucket(name)
+    }
+
+    /// Returns the proof with the given name.
+    pub fn proof(&self, name: &str) -> ManifestProof {
+        self.registry.proof(name)
+    }
+
+    /// Returns the address reservation with the given name.
+    pub fn address_reservation(&self, name: &str) -> ManifestAddressReservation {
+        self.registry.address_reservation(name)
+    }
+
+    /// Returns the named address with the given name, which can be used as the package address
+    /// of [`Self::call_function()`] or the address of [`Self::call_method()`].
+    pub fn named_address(&self, name: &str) -> u32 {
+        self.registry.named_address(name)
+    }
+
+    /// Calls `then` with a lookup of the names given so far, e.g. to pass named buckets as the
+    /// arguments of a call in the middle of a chain of calls.
+    pub fn with_name_lookup<F>(&mut self, then: F) -> &mut Self
+    where
+        F: FnOnce(&mut Self, ManifestNameRegistry) -> &mut Self,
+    {
+        let lookup = self.registry.clone();
+        then(self, lookup)
+    }
+
+    /// Returns the names given to the objects of the manifest, e.g. for
+    /// [`decompile_with_known_naming()`](crate::manifest::decompiler::decompile_with_known_naming).
+    pub fn object_names(&self) -> ManifestObjectNames {
+        self.registry.object_names()
+    }
+
     /// Builds a transaction manifest.
+    ///
+    /// If any names were given to the manifest's objects, the use of buckets, proofs and
+    /// addresses is validated first, and any misuse (e.g. a named bucket used after it has been
+    /// consumed) panics with the names of the objects involved.
     pub fn build(&self) -> TransactionManifestV1 {
+        if !self.interface_steps.is_empty() {
+            panic!("A manifest yielding or accepting buckets must be built with build_partial()");
+        }
+        if !self.registry.is_empty() {
+            self.validate_named_objects();
+        }
         let m = TransactionManifestV1 {
             instructions: self.instructions.clone(),
             blobs: self.blobs.clone(),
@@ -944,6 +1306,44 @@ impl ManifestBuilder {
         m
     }
 
+    /// Builds a partial manifest, to be merged with the partial manifests it yields buckets to
+    /// and accepts buckets from with [`merge_partial_manifests()`](super::merge_partial_manifests).
+    pub fn build_partial(&self) -> PartialManifestV1 {
+        let mut steps = Vec::new();
+        let mut interface_steps = self.interface_steps.iter().peekable();
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            loop {
+                match interface_steps.peek() {
+                    Some((position, step)) if *position == index => {
+                        steps.push(step.clone());
+                        interface_steps.next();
+                    }
+                    _ => break,
+                }
+            }
+            steps.push(PartialManifestStepV1::Instruction(instruction.clone()));
+        }
+        steps.extend(interface_steps.map(|(_, step)| step.clone()));
+        PartialManifestV1 {
+            steps,
+            blobs: self.blobs.clone(),
+        }
+    }
+
+    fn validate_named_objects(&self) {
+        match NotarizedTransactionValidator::validate_instructions_v1(&self.instructions) {
+            Ok(()) => {}
+            Err(TransactionValidationError::IdValidationError(error))
+            | Err(TransactionValidationError::CallDataValidationError(
+                CallDataValidationError::IdValidationError(error),
+            )) => panic!(
+                "Invalid manifest: {}",
+                self.object_names().describe_id_validation_error(&error)
+            ),
+            Err(error) => panic!("Invalid manifest: {:?}", error),
+        }
+    }
+
     /// Creates a token resource with mutable supply.
     pub fn new_token_mutable(
         &mut self,
@@ -1040,6 +1440,13 @@ impl ManifestBuilder {
         })
     }
 
+    /// Burns a bucket of resource.
+    pub fn burn_resource<B: ExistingManifestBucket>(&mut self, bucket: B) -> &mut Self {
+        let bucket_id = bucket.resolve(&self.registry);
+        self.add_instruction(InstructionV1::BurnResource { bucket_id })
+            .0
+    }
+
     pub fn burn_all_from_worktop(&mut self, resource_address: ResourceAddress) -> &mut Self {
         self.take_all_from_worktop(resource_address, |builder, bucket_id| {
             builder
@@ -1215,6 +1622,40 @@ impl ManifestBuilder {
         )
     }
 
+    /// Creates a vesting vault which releases the resources of the bucket on the given schedule.
+    pub fn create_vesting_vault<B: ExistingManifestBucket>(
+        &mut self,
+        owner_role: OwnerRole,
+        bucket: B,
+        schedule: VestingSchedule,
+    ) -> &mut Self {
+        let bucket = bucket.resolve(&self.registry);
+        self.add_instruction(InstructionV1::CallFunction {
+            package_address: RESOURCE_PACKAGE.into(),
+            blueprint_name: VESTING_VAULT_BLUEPRINT.to_string(),
+            function_name: VESTING_VAULT_CREATE_IDENT.to_string(),
+            args: to_manifest_value_and_unwrap!(&VestingVaultCreateManifestInput {
+                owner_role,
+                bucket,
+                schedule,
+            }),
+        })
+        .0
+    }
+
+    /// Withdraws vested resources from a vesting vault.
+    pub fn withdraw_from_vesting_vault(
+        &mut self,
+        vesting_vault_address: ComponentAddress,
+        amount: Decimal,
+    ) -> &mut Self {
+        self.call_method(
+            vesting_vault_address,
+            VESTING_VAULT_WITHDRAW_IDENT,
+            to_manifest_value_and_unwrap!(&VestingVaultWithdrawInput { amount }),
+        )
+    }
+
     /// Creates an account.
     pub fn new_account_advanced(&mut self, owner_role: OwnerRole) -> &mut Self {
         self.add_instruction(InstructionV1::CallFunction {
@@ -1290,6 +1731,27 @@ impl ManifestBuilder {
         .0
     }
 
+    /// Inserts a `lock_fee` call before all the instructions added so far, e.g. once the amount to
+    /// lock was estimated by previewing the manifest built without it.
+    pub fn prepend_lock_fee<A: Into<GlobalAddress>>(
+        &mut self,
+        account: A,
+        amount: Decimal,
+    ) -> &mut Self {
+        let args = to_manifest_value_and_unwrap!(&AccountLockFeeInput { amount });
+
+        // Note: the call creates no buckets nor proofs, so no existing IDs are affected.
+        self.instructions.insert(
+            0,
+            InstructionV1::CallMethod {
+                address: account.into().into(),
+                method_name: ACCOUNT_LOCK_FEE_IDENT.to_string(),
+                args,
+            },
+        );
+        self
+    }
+
     pub fn lock_contingent_fee(&mut self, account: ComponentAddress, amount: Decimal) -> &mut Self {
         let args = to_manifest_value_and_unwrap!(&AccountLockContingentFeeInput { amount });
 
@@ -1442,6 +1904,20 @@ impl ManifestBuilder {
         self
     }
 
+    /// Deposits a bucket of resource into an account.
+    pub fn deposit<B: ExistingManifestBucket>(
+        &mut self,
+        account_address: ComponentAddress,
+        bucket: B,
+    ) -> &mut Self {
+        let bucket_id = bucket.resolve(&self.registry);
+        self.call_method(
+            account_address,
+            ACCOUNT_DEPOSIT_IDENT,
+            manifest_args!(bucket_id),
+        )
+    }
+
     pub fn deposit_batch(&mut self, account_address: ComponentAddress) -> &mut Self {
         self.call_method(
             account_address,
@@ -1466,6 +1942,135 @@ impl ManifestBuilder {
         )
     }
 
+    /// Creates an account locker.
+    pub fn new_account_locker(&mut self, owner_role: OwnerRole) -> &mut Self {
+        self.add_instruction(InstructionV1::CallFunction {
+            package_address: ACCOUNT_PACKAGE.into(),
+            blueprint_name: ACCOUNT_LOCKER_BLUEPRINT.to_string(),
+            function_name: ACCOUNT_LOCKER_INSTANTIATE_IDENT.to_string(),
+            args: to_manifest_value_and_unwrap!(&AccountLockerInstantiateManifestInput {
+                owner_role
+            }),
+        })
+        .0
+    }
+
+    /// Stores a bucket of resource in an account locker for the claimant.
+    pub fn store_in_account_locker<B: ExistingManifestBucket>(
+        &mut self,
+        locker_address: ComponentAddress,
+        claimant: ComponentAddress,
+        bucket: B,
+        try_direct_send: bool,
+    ) -> &mut Self {
+        let bucket = bucket.resolve(&self.registry);
+        self.call_method(
+            locker_address,
+            ACCOUNT_LOCKER_STORE_IDENT,
+            to_manifest_value_and_unwrap!(&AccountLockerStoreManifestInput {
+                claimant,
+                bucket,
+                try_direct_send,
+            }),
+        )
+    }
+
+    /// Stores resources from a bucket in an account locker for each of the claimants.
+    pub fn airdrop_from_account_locker<B: ExistingManifestBucket>(
+        &mut self,
+        locker_address: ComponentAddress,
+        claimants: BTreeMap<ComponentAddress, AccountLockerResources>,
+        bucket: B,
+        try_direct_send: bool,
+    ) -> &mut Self {
+        let bucket = bucket.resolve(&self.registry);
+        self.call_method(
+            locker_address,
+            ACCOUNT_LOCKER_AIRDROP_IDENT,
+            to_manifest_value_and_unwrap!(&AccountLockerAirdropManifestInput {
+                claimants,
+                bucket,
+                try_direct_send,
+            }),
+        )
+    }
+
+    /// Claims resource stored in an account locker for the claimant.
+    pub fn claim_from_account_locker(
+        &mut self,
+        locker_address: ComponentAddress,
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress,
+        amount: Decimal,
+    ) -> &mut Self {
+        self.call_method(
+            locker_address,
+            ACCOUNT_LOCKER_CLAIM_IDENT,
+            to_manifest_value_and_unwrap!(&AccountLockerClaimManifestInput {
+                claimant,
+                resource_address,
+                amount,
+            }),
+        )
+    }
+
+    /// Claims non-fungibles stored in an account locker for the claimant.
+    pub fn claim_non_fungibles_from_account_locker(
+        &mut self,
+        locker_address: ComponentAddress,
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress,
+        ids: &BTreeSet<NonFungibleLocalId>,
+    ) -> &mut Self {
+        self.call_method(
+            locker_address,
+            ACCOUNT_LOCKER_CLAIM_NON_FUNGIBLES_IDENT,
+            to_manifest_value_and_unwrap!(&AccountLockerClaimNonFungiblesManifestInput {
+                claimant,
+                resource_address,
+                ids: ids.clone(),
+            }),
+        )
+    }
+
+    /// Recovers resource stored in an account locker for the claimant.
+    pub fn recover_from_account_locker(
+        &mut self,
+        locker_address: ComponentAddress,
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress,
+        amount: Decimal,
+    ) -> &mut Self {
+        self.call_method(
+            locker_address,
+            ACCOUNT_LOCKER_RECOVER_IDENT,
+            to_manifest_value_and_unwrap!(&AccountLockerRecoverManifestInput {
+                claimant,
+                resource_address,
+                amount,
+            }),
+        )
+    }
+
+    /// Recovers non-fungibles stored in an account locker for the claimant.
+    pub fn recover_non_fungibles_from_account_locker(
+        &mut self,
+        locker_address: ComponentAddress,
+        claimant: ComponentAddress,
+        resource_address: ResourceAddress,
+        ids: &BTreeSet<NonFungibleLocalId>,
+    ) -> &mut Self {
+        self.call_method(
+            locker_address,
+            ACCOUNT_LOCKER_RECOVER_NON_FUNGIBLES_IDENT,
+            to_manifest_value_and_unwrap!(&AccountLockerRecoverNonFungiblesManifestInput {
+                claimant,
+                resource_address,
+                ids: ids.clone(),
+            }),
+        )
+    }
+
     pub fn borrow_mut<F, E>(&mut self, handler: F) -> Result<&mut Self, E>
     where
         F: FnOnce(&mut Self) -> Result<&mut Self, E>,
@@ -1473,3 +2078,135 @@ impl ManifestBuilder {
         handler(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::*;
+    use crate::signing::secp256k1::Secp256k1PrivateKey;
+    use radix_engine_interface::constants::{FAUCET, XRD};
+    use radix_engine_interface::network::NetworkDefinition;
+
+    fn account() -> ComponentAddress {
+        ComponentAddress::virtual_account_from_public_key(
+            &Secp256k1PrivateKey::from_u64(1).unwrap().public_key(),
+        )
+    }
+
+    fn decompile_and_recompile(builder: &ManifestBuilder) -> (String, TransactionManifestV1) {
+        let network = NetworkDefinition::simulator();
+        let manifest = builder.build();
+        let decompiled =
+            decompile_with_known_naming(&manifest.instructions, &network, &builder.object_names())
+                .unwrap();
+        let recompiled = compile(&decompiled, &network, BlobProvider::new()).unwrap();
+        assert_eq!(recompiled.instructions, manifest.instructions);
+        (decompiled, manifest)
+    }
+
+    #[test]
+    fn named_buckets_and_proofs_are_decompiled_with_their_names() {
+        let mut builder = ManifestBuilder::new();
+        builder
+            .call_method(FAUCET, "free", manifest_args!())
+            .take_from_worktop_named(XRD, Decimal::from(10), "xrd")
+            .create_proof_from_bucket_named("xrd", "xrd_proof")
+            .clone_proof_named("xrd_proof", "xrd_proof_clone")
+            .drop_proof("xrd_proof")
+            .push_to_auth_zone("xrd_proof_clone")
+            .deposit(account(), "xrd")
+            .take_all_from_worktop_named(XRD, "rest")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(
+                    account(),
+                    "try_deposit_or_abort",
+                    manifest_args!(lookup.bucket("rest")),
+                )
+            });
+
+        let (decompiled, _) = decompile_and_recompile(&builder);
+
+        assert!(decompiled.contains(r#"Bucket("xrd")"#));
+        assert!(decompiled.contains(r#"Bucket("rest")"#));
+        assert!(decompiled.contains(r#"Proof("xrd_proof")"#));
+        assert!(decompiled.contains(r#"Proof("xrd_proof_clone")"#));
+        assert!(!decompiled.contains("bucket1"));
+    }
+
+    #[test]
+    fn named_addresses_are_usable_as_call_targets() {
+        let mut builder = ManifestBuilder::new();
+        builder
+            .allocate_global_address_named(
+                BlueprintId {
+                    package_address: PACKAGE_PACKAGE,
+                    blueprint_name: PACKAGE_BLUEPRINT.to_owned(),
+                },
+                "package_reservation",
+                "package",
+            )
+            .with_name_lookup(|builder, lookup| {
+                builder
+                    .call_function(
+                        lookup.named_address("package"),
+                        "Blueprint",
+                        "new",
+                        manifest_args!(lookup.address_reservation("package_reservation")),
+                    )
+                    .call_method(lookup.named_address("package"), "method", manifest_args!())
+            });
+
+        let (decompiled, manifest) = decompile_and_recompile(&builder);
+
+        assert!(decompiled.contains(r#"AddressReservation("package_reservation")"#));
+        assert!(decompiled.contains(r#"NamedAddress("package")"#));
+        assert!(matches!(
+            manifest.instructions[2],
+            InstructionV1::CallMethod {
+                address: DynamicGlobalAddress::Named(0),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn generated_names_do_not_clash_with_given_names() {
+        let mut builder = ManifestBuilder::new();
+        builder
+            .call_method(FAUCET, "free", manifest_args!())
+            .take_from_worktop(XRD, Decimal::ONE, |builder, bucket| {
+                builder.return_to_worktop(bucket)
+            })
+            .take_all_from_worktop_named(XRD, "bucket1")
+            .deposit(account(), "bucket1");
+
+        let (decompiled, _) = decompile_and_recompile(&builder);
+
+        assert!(decompiled.contains(r#"Bucket("bucket1_1")"#));
+        assert!(decompiled.contains(r#"Bucket("bucket1")"#));
+    }
+
+    #[test]
+    #[should_panic(expected = "Bucket \"xrd\" does not exist (or has already been consumed)")]
+    fn named_bucket_used_after_being_consumed_is_rejected_on_build() {
+        ManifestBuilder::new()
+            .take_all_from_worktop_named(XRD, "xrd")
+            .deposit(account(), "xrd")
+            .return_to_worktop("xrd")
+            .build();
+    }
+
+    #[test]
+    #[should_panic(expected = "No proof named \"missing\" exists")]
+    fn unknown_name_is_rejected() {
+        ManifestBuilder::new().drop_proof("missing");
+    }
+
+    #[test]
+    #[should_panic(expected = "A bucket named \"xrd\" already exists")]
+    fn duplicate_name_is_rejected() {
+        ManifestBuilder::new()
+            .take_all_from_worktop_named(XRD, "xrd")
+            .take_all_from_worktop_named(XRD, "xrd");
+    }
+}
@@ -0,0 +1,193 @@
+use crate::errors::*;
+use radix_engine_interface::data::manifest::model::*;
+use sbor::rust::hash::Hash;
+use sbor::rust::prelude::*;
+
+/// The names of the buckets, proofs, address reservations and named addresses of a manifest,
+/// keyed by their ids.
+///
+/// These are returned by [`ManifestBuilder::object_names()`](super::ManifestBuilder::object_names),
+/// and can be passed to [`decompile_with_known_naming()`](crate::manifest::decompiler::decompile_with_known_naming)
+/// so that the decompiled manifest uses the same names as the builder.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ManifestObjectNames {
+    pub bucket_names: IndexMap<ManifestBucket, String>,
+    pub proof_names: IndexMap<ManifestProof, String>,
+    pub address_reservation_names: IndexMap<ManifestAddressReservation, String>,
+    pub address_names: IndexMap<u32, String>,
+}
+
+impl ManifestObjectNames {
+    /// Describes an id validation error in terms of the names of the objects involved.
+    pub fn describe_id_validation_error(&self, error: &ManifestIdValidationError) -> String {
+        match error {
+            ManifestIdValidationError::BucketNotFound(bucket) => format!(
+                "Bucket {} does not exist (or has already been consumed)",
+                Self::describe(self.bucket_names.get(bucket), bucket)
+            ),
+            ManifestIdValidationError::ProofNotFound(proof) => format!(
+                "Proof {} does not exist (or has already been consumed)",
+                Self::describe(self.proof_names.get(proof), proof)
+            ),
+            ManifestIdValidationError::BucketLocked(bucket) => format!(
+                "Bucket {} is consumed while a proof of it still exists",
+                Self::describe(self.bucket_names.get(bucket), bucket)
+            ),
+            ManifestIdValidationError::AddressReservationNotFound(reservation) => format!(
+                "Address reservation {} does not exist (or has already been consumed)",
+                Self::describe(self.address_reservation_names.get(reservation), reservation)
+            ),
+            ManifestIdValidationError::AddressNotFound(address) => format!(
+                "Named address {} does not exist",
+                Self::describe(self.address_names.get(address), address)
+            ),
+        }
+    }
+
+    fn describe<T: Debug>(name: Option<&String>, id: &T) -> String {
+        match name {
+            Some(name) => format!("\"{}\"", name),
+            None => format!("{:?}", id),
+        }
+    }
+}
+
+/// The names registered through the name-based methods of the [`ManifestBuilder`](super::ManifestBuilder)
+/// (e.g. [`take_from_worktop_named()`](super::ManifestBuilder::take_from_worktop_named)), which
+/// resolves them to the ids of the objects they were given to.
+///
+/// Names are unique per object kind; resolving an unknown name panics.
+#[derive(Debug, Clone, Default)]
+pub struct ManifestNameRegistry {
+    buckets: IndexMap<String, ManifestBucket>,
+    proofs: IndexMap<String, ManifestProof>,
+    address_reservations: IndexMap<String, ManifestAddressReservation>,
+    addresses: IndexMap<String, u32>,
+}
+
+impl ManifestNameRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buckets.is_empty()
+            && self.proofs.is_empty()
+            && self.address_reservations.is_empty()
+            && self.addresses.is_empty()
+    }
+
+    pub fn register_bucket(&mut self, name: &str, bucket: ManifestBucket) {
+        Self::register(&mut self.buckets, "bucket", name, bucket);
+    }
+
+    pub fn register_proof(&mut self, name: &str, proof: ManifestProof) {
+        Self::register(&mut self.proofs, "proof", name, proof);
+    }
+
+    pub fn register_address_reservation(
+        &mut self,
+        name: &str,
+        reservation: ManifestAddressReservation,
+    ) {
+        Self::register(
+            &mut self.address_reservations,
+            "address reservation",
+            name,
+            reservation,
+        );
+    }
+
+    pub fn register_address(&mut self, name: &str, address: u32) {
+        Self::register(&mut self.addresses, "named address", name, address);
+    }
+
+    pub fn bucket(&self, name: &str) -> ManifestBucket {
+        Self::resolve(&self.buckets, "bucket", name)
+    }
+
+    pub fn proof(&self, name: &str) -> ManifestProof {
+        Self::resolve(&self.proofs, "proof", name)
+    }
+
+    pub fn address_reservation(&self, name: &str) -> ManifestAddressReservation {
+        Self::resolve(&self.address_reservations, "address reservation", name)
+    }
+
+    pub fn named_address(&self, name: &str) -> u32 {
+        Self::resolve(&self.addresses, "named address", name)
+    }
+
+    pub fn object_names(&self) -> ManifestObjectNames {
+        ManifestObjectNames {
+            bucket_names: Self::invert(&self.buckets),
+            proof_names: Self::invert(&self.proofs),
+            address_reservation_names: Self::invert(&self.address_reservations),
+            address_names: Self::invert(&self.addresses),
+        }
+    }
+
+    fn register<T>(names: &mut IndexMap<String, T>, kind: &str, name: &str, id: T) {
+        if names.contains_key(name) {
+            panic!("A {} named \"{}\" already exists", kind, name);
+        }
+        names.insert(name.to_owned(), id);
+    }
+
+    fn resolve<T: Copy>(names: &IndexMap<String, T>, kind: &str, name: &str) -> T {
+        match names.get(name) {
+            Some(id) => *id,
+            None => panic!("No {} named \"{}\" exists", kind, name),
+        }
+    }
+
+    fn invert<T: Copy + Hash + Eq>(names: &IndexMap<String, T>) -> IndexMap<T, String> {
+        names.iter().map(|(name, id)| (*id, name.clone())).collect()
+    }
+}
+
+/// A reference to an existing bucket: either its id, or the name it was given in the builder.
+pub trait ExistingManifestBucket {
+    fn resolve(self, registry: &ManifestNameRegistry) -> ManifestBucket;
+}
+
+impl ExistingManifestBucket for ManifestBucket {
+    fn resolve(self, _: &ManifestNameRegistry) -> ManifestBucket {
+        self
+    }
+}
+
+impl<'a> ExistingManifestBucket for &'a str {
+    fn resolve(self, registry: &ManifestNameRegistry) -> ManifestBucket {
+        registry.bucket(self)
+    }
+}
+
+impl ExistingManifestBucket for String {
+    fn resolve(self, registry: &ManifestNameRegistry) -> ManifestBucket {
+        registry.bucket(&self)
+    }
+}
+
+/// A reference to an existing proof: either its id, or the name it was given in the builder.
+pub trait ExistingManifestProof {
+    fn resolve(self, registry: &ManifestNameRegistry) -> ManifestProof;
+}
+
+impl ExistingManifestProof for ManifestProof {
+    fn resolve(self, _: &ManifestNameRegistry) -> ManifestProof {
+        self
+    }
+}
+
+impl<'a> ExistingManifestProof for &'a str {
+    fn resolve(self, registry: &ManifestNameRegistry) -> ManifestProof {
+        registry.proof(self)
+    }
+}
+
+impl ExistingManifestProof for String {
+    fn resolve(self, registry: &ManifestNameRegistry) -> ManifestProof {
+        registry.proof(&self)
+    }
+}
@@ -1,5 +1,9 @@
 mod manifest_builder;
+mod manifest_namer;
+mod partial_manifest;
 mod transaction_builder;
 
 pub use manifest_builder::*;
+pub use manifest_namer::*;
+pub use partial_manifest::*;
 pub use transaction_builder::*;
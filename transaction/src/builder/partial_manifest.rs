@@ -0,0 +1,607 @@
+use crate::internal_prelude::*;
+
+/// A part of a transaction manifest, authored by one party, which exchanges buckets with the
+/// parts of other parties at named interface points (e.g. one side of an order matched
+/// off-ledger). The parts are merged into a single manifest with [`merge_partial_manifests()`].
+///
+/// Each part numbers its own buckets, proofs, address reservations and named addresses, in the
+/// order they are created - an accepted bucket being created where it is accepted.
+#[derive(Debug, Clone, PartialEq, Eq, ManifestSbor)]
+pub struct PartialManifestV1 {
+    pub steps: Vec<PartialManifestStepV1>,
+    pub blobs: BTreeMap<Hash, Vec<u8>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ManifestSbor)]
+pub enum PartialManifestStepV1 {
+    Instruction(InstructionV1),
+    /// Hands a bucket over to the part accepting the interface point of the given name.
+    YieldBucket {
+        name: String,
+        bucket_id: ManifestBucket,
+    },
+    /// Receives the bucket yielded at the interface point of the given name, as a new bucket.
+    AcceptBucket {
+        name: String,
+    },
+}
+
+/// Merges partial manifests into a single manifest, whose buckets, proofs, address reservations
+/// and named addresses are renumbered in the order they are created.
+///
+/// The instructions of each part are kept in order, and the parts are interleaved only where a
+/// part waits for a bucket yet to be yielded by another: each part runs (in the given order)
+/// until it accepts a bucket which hasn't been yielded yet. Note that the parts share the
+/// worktop and the auth zone.
+///
+/// The merged manifest is validated as the instructions of a notarized transaction.
+pub fn merge_partial_manifests(
+    parts: &[PartialManifestV1],
+) -> Result<TransactionManifestV1, PartialManifestMergeError> {
+    validate_interface_points(parts)?;
+
+    let mut merger = PartialManifestMerger {
+        id_allocator: ManifestIdAllocator::new(),
+        instructions: Vec::new(),
+        yielded_buckets: index_map_new(),
+    };
+    let mut part_ids: Vec<PartIds> = parts.iter().map(|_| PartIds::default()).collect();
+    let mut cursors = vec![0; parts.len()];
+    loop {
+        let mut progressed = false;
+        for (part_index, part) in parts.iter().enumerate() {
+            loop {
+                let step = match part.steps.get(cursors[part_index]) {
+                    Some(PartialManifestStepV1::AcceptBucket { name })
+                        if !merger.yielded_buckets.contains_key(name) =>
+                    {
+                        break;
+                    }
+                    Some(step) => step,
+                    None => break,
+                };
+                merger
+                    .merge_step(step, &mut part_ids[part_index])
+                    .map_err(|error| PartialManifestMergeError::IdValidationError {
+                        part_index,
+                        error,
+                    })?;
+                cursors[part_index] += 1;
+                progressed = true;
+            }
+        }
+
+        let blocked_names: Vec<String> = parts
+            .iter()
+            .zip(cursors.iter())
+            .filter_map(|(part, cursor)| match part.steps.get(*cursor) {
+                Some(PartialManifestStepV1::AcceptBucket { name }) => Some(name.clone()),
+                _ => None,
+            })
+            .collect();
+        if blocked_names.is_empty() {
+            break;
+        }
+        if !progressed {
+            return Err(PartialManifestMergeError::Deadlock(blocked_names));
+        }
+    }
+
+    NotarizedTransactionValidator::validate_instructions_v1(&merger.instructions)
+        .map_err(PartialManifestMergeError::ValidationError)?;
+
+    Ok(TransactionManifestV1 {
+        instructions: merger.instructions,
+        blobs: parts
+            .iter()
+            .flat_map(|part| part.blobs.clone().into_iter())
+            .collect(),
+    })
+}
+
+/// Checks that every bucket yielded is accepted exactly once, and vice versa.
+fn validate_interface_points(parts: &[PartialManifestV1]) -> Result<(), PartialManifestMergeError> {
+    let mut yielded = index_set_new();
+    let mut accepted = index_set_new();
+    for step in parts.iter().flat_map(|part| part.steps.iter()) {
+        let (names, name) = match step {
+            PartialManifestStepV1::YieldBucket { name, .. } => (&mut yielded, name),
+            PartialManifestStepV1::AcceptBucket { name } => (&mut accepted, name),
+            PartialManifestStepV1::Instruction(_) => continue,
+        };
+        if !names.insert(name.clone()) {
+            return Err(PartialManifestMergeError::DuplicateInterfacePoint(
+                name.clone(),
+            ));
+        }
+    }
+    match yielded.symmetric_difference(&accepted).next() {
+        Some(name) => Err(PartialManifestMergeError::UnmatchedInterfacePoint(
+            name.clone(),
+        )),
+        None => Ok(()),
+    }
+}
+
+struct PartialManifestMerger {
+    id_allocator: ManifestIdAllocator,
+    instructions: Vec<InstructionV1>,
+    /// The (renumbered) buckets yielded but not accepted yet
+    yielded_buckets: IndexMap<String, ManifestBucket>,
+}
+
+impl PartialManifestMerger {
+    fn merge_step(
+        &mut self,
+        step: &PartialManifestStepV1,
+        ids: &mut PartIds,
+    ) -> Result<(), ManifestIdValidationError> {
+        match step {
+            PartialManifestStepV1::Instruction(instruction) => {
+                let instruction = ids.renumber_instruction(instruction.clone())?;
+                match &instruction {
+                    InstructionV1::TakeAllFromWorktop { .. }
+                    | InstructionV1::TakeFromWorktop { .. }
+                    | InstructionV1::TakeNonFungiblesFromWorktop { .. } => {
+                        ids.buckets.insert(
+                            ids.id_allocator.new_bucket_id(),
+                            self.id_allocator.new_bucket_id(),
+                        );
+                    }
+                    InstructionV1::PopFromAuthZone { .. }
+                    | InstructionV1::CreateProofFromAuthZone { .. }
+                    | InstructionV1::CreateProofFromAuthZoneOfAmount { .. }
+                    | InstructionV1::CreateProofFromAuthZoneOfNonFungibles { .. }
+                    | InstructionV1::CreateProofFromAuthZoneOfAll { .. }
+                    | InstructionV1::CreateProofFromBucket { .. }
+                    | InstructionV1::CreateProofFromBucketOfAmount { .. }
+                    | InstructionV1::CreateProofFromBucketOfNonFungibles { .. }
+                    | InstructionV1::CreateProofFromBucketOfAll { .. }
+                    | InstructionV1::CloneProof { .. } => {
+                        ids.proofs.insert(
+                            ids.id_allocator.new_proof_id(),
+                            self.id_allocator.new_proof_id(),
+                        );
+                    }
+                    InstructionV1::AllocateGlobalAddress { .. } => {
+                        ids.address_reservations.insert(
+                            ids.id_allocator.new_address_reservation_id(),
+                            self.id_allocator.new_address_reservation_id(),
+                        );
+                        ids.addresses.insert(
+                            ids.id_allocator.new_address_id(),
+                            self.id_allocator.new_address_id(),
+                        );
+                    }
+                    _ => {}
+                }
+                self.instructions.push(instruction);
+            }
+            PartialManifestStepV1::YieldBucket { name, bucket_id } => {
+                // The bucket now belongs to the accepting part
+                let bucket_id = ids
+                    .buckets
+                    .swap_remove(bucket_id)
+                    .ok_or(ManifestIdValidationError::BucketNotFound(*bucket_id))?;
+                self.yielded_buckets.insert(name.clone(), bucket_id);
+            }
+            PartialManifestStepV1::AcceptBucket { name } => {
+                let bucket_id = self
+                    .yielded_buckets
+                    .swap_remove(name)
+                    .expect("Buckets are only accepted once yielded");
+                ids.buckets
+                    .insert(ids.id_allocator.new_bucket_id(), bucket_id);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The ids of a partial manifest, and their renumbered ids in the merged manifest.
+#[derive(Default)]
+struct PartIds {
+    id_allocator: ManifestIdAllocator,
+    buckets: IndexMap<ManifestBucket, ManifestBucket>,
+    proofs: IndexMap<ManifestProof, ManifestProof>,
+    address_reservations: IndexMap<ManifestAddressReservation, ManifestAddressReservation>,
+    addresses: IndexMap<u32, u32>,
+}
+
+impl PartIds {
+    fn bucket(
+        &self,
+        bucket_id: ManifestBucket,
+    ) -> Result<ManifestBucket, ManifestIdValidationError> {
+        self.buckets
+            .get(&bucket_id)
+            .cloned()
+            .ok_or(ManifestIdValidationError::BucketNotFound(bucket_id))
+    }
+
+    fn proof(&self, proof_id: ManifestProof) -> Result<ManifestProof, ManifestIdValidationError> {
+        self.proofs
+            .get(&proof_id)
+            .cloned()
+            .ok_or(ManifestIdValidationError::ProofNotFound(proof_id))
+    }
+
+    fn address_reservation(
+        &self,
+        address_reservation: ManifestAddressReservation,
+    ) -> Result<ManifestAddressReservation, ManifestIdValidationError> {
+        self.address_reservations
+            .get(&address_reservation)
+            .cloned()
+            .ok_or(ManifestIdValidationError::AddressReservationNotFound(
+                address_reservation,
+            ))
+    }
+
+    fn address(&self, address_id: u32) -> Result<u32, ManifestIdValidationError> {
+        self.addresses
+            .get(&address_id)
+            .cloned()
+            .ok_or(ManifestIdValidationError::AddressNotFound(address_id))
+    }
+
+    fn global_address(
+        &self,
+        address: DynamicGlobalAddress,
+    ) -> Result<DynamicGlobalAddress, ManifestIdValidationError> {
+        match address {
+            DynamicGlobalAddress::Static(_) => Ok(address),
+            DynamicGlobalAddress::Named(address_id) => {
+                Ok(DynamicGlobalAddress::Named(self.address(address_id)?))
+            }
+        }
+    }
+
+    fn renumber_instruction(
+        &self,
+        instruction: InstructionV1,
+    ) -> Result<InstructionV1, ManifestIdValidationError> {
+        let instruction = match instruction {
+            InstructionV1::ReturnToWorktop { bucket_id } => InstructionV1::ReturnToWorktop {
+                bucket_id: self.bucket(bucket_id)?,
+            },
+            InstructionV1::PushToAuthZone { proof_id } => InstructionV1::PushToAuthZone {
+                proof_id: self.proof(proof_id)?,
+            },
+            InstructionV1::CreateProofFromBucket { bucket_id } => {
+                InstructionV1::CreateProofFromBucket {
+                    bucket_id: self.bucket(bucket_id)?,
+                }
+            }
+            InstructionV1::CreateProofFromBucketOfAmount { bucket_id, amount } => {
+                InstructionV1::CreateProofFromBucketOfAmount {
+                    bucket_id: self.bucket(bucket_id)?,
+                    amount,
+                }
+            }
+            InstructionV1::CreateProofFromBucketOfNonFungibles { bucket_id, ids } => {
+                InstructionV1::CreateProofFromBucketOfNonFungibles {
+                    bucket_id: self.bucket(bucket_id)?,
+                    ids,
+                }
+            }
+            InstructionV1::CreateProofFromBucketOfAll { bucket_id } => {
+                InstructionV1::CreateProofFromBucketOfAll {
+                    bucket_id: self.bucket(bucket_id)?,
+                }
+            }
+            InstructionV1::BurnResource { bucket_id } => InstructionV1::BurnResource {
+                bucket_id: self.bucket(bucket_id)?,
+            },
+            InstructionV1::CloneProof { proof_id } => InstructionV1::CloneProof {
+                proof_id: self.proof(proof_id)?,
+            },
+            InstructionV1::DropProof { proof_id } => InstructionV1::DropProof {
+                proof_id: self.proof(proof_id)?,
+            },
+            InstructionV1::CallFunction {
+                package_address,
+                blueprint_name,
+                function_name,
+                args,
+            } => InstructionV1::CallFunction {
+                package_address: match package_address {
+                    DynamicPackageAddress::Static(_) => package_address,
+                    DynamicPackageAddress::Named(address_id) => {
+                        DynamicPackageAddress::Named(self.address(address_id)?)
+                    }
+                },
+                blueprint_name,
+                function_name,
+                args: self.renumber_value(args)?,
+            },
+            InstructionV1::CallMethod {
+                address,
+                method_name,
+                args,
+            } => InstructionV1::CallMethod {
+                address: self.global_address(address)?,
+                method_name,
+                args: self.renumber_value(args)?,
+            },
+            InstructionV1::CallRoyaltyMethod {
+                address,
+                method_name,
+                args,
+            } => InstructionV1::CallRoyaltyMethod {
+                address: self.global_address(address)?,
+                method_name,
+                args: self.renumber_value(args)?,
+            },
+            InstructionV1::CallMetadataMethod {
+                address,
+                method_name,
+                args,
+            } => InstructionV1::CallMetadataMethod {
+                address: self.global_address(address)?,
+                method_name,
+                args: self.renumber_value(args)?,
+            },
+            InstructionV1::CallAccessRulesMethod {
+                address,
+                method_name,
+                args,
+            } => InstructionV1::CallAccessRulesMethod {
+                address: self.global_address(address)?,
+                method_name,
+                args: self.renumber_value(args)?,
+            },
+            InstructionV1::CallDirectVaultMethod {
+                address,
+                method_name,
+                args,
+            } => InstructionV1::CallDirectVaultMethod {
+                address,
+                method_name,
+                args: self.renumber_value(args)?,
+            },
+            instruction => instruction,
+        };
+        Ok(instruction)
+    }
+
+    fn renumber_value(
+        &self,
+        value: ManifestValue,
+    ) -> Result<ManifestValue, ManifestIdValidationError> {
+        let value = match value {
+            ManifestValue::Enum {
+                discriminator,
+                fields,
+            } => ManifestValue::Enum {
+                discriminator,
+                fields: self.renumber_values(fields)?,
+            },
+            ManifestValue::Array {
+                element_value_kind,
+                elements,
+            } => ManifestValue::Array {
+                element_value_kind,
+                elements: self.renumber_values(elements)?,
+            },
+            ManifestValue::Tuple { fields } => ManifestValue::Tuple {
+                fields: self.renumber_values(fields)?,
+            },
+            ManifestValue::Map {
+                key_value_kind,
+                value_value_kind,
+                entries,
+            } => {
+                let mut renumbered_entries = Vec::new();
+                for (key, value) in entries {
+                    renumbered_entries
+                        .push((self.renumber_value(key)?, self.renumber_value(value)?));
+                }
+                ManifestValue::Map {
+                    key_value_kind,
+                    value_value_kind,
+                    entries: renumbered_entries,
+                }
+            }
+            ManifestValue::Custom { value } => ManifestValue::Custom {
+                value: match value {
+                    ManifestCustomValue::Address(ManifestAddress::Named(address_id)) => {
+                        ManifestCustomValue::Address(ManifestAddress::Named(
+                            self.address(address_id)?,
+                        ))
+                    }
+                    ManifestCustomValue::Bucket(bucket_id) => {
+                        ManifestCustomValue::Bucket(self.bucket(bucket_id)?)
+                    }
+                    ManifestCustomValue::Proof(proof_id) => {
+                        ManifestCustomValue::Proof(self.proof(proof_id)?)
+                    }
+                    ManifestCustomValue::AddressReservation(address_reservation) => {
+                        ManifestCustomValue::AddressReservation(
+                            self.address_reservation(address_reservation)?,
+                        )
+                    }
+                    value => value,
+                },
+            },
+            value => value,
+        };
+        Ok(value)
+    }
+
+    fn renumber_values(
+        &self,
+        values: Vec<ManifestValue>,
+    ) -> Result<Vec<ManifestValue>, ManifestIdValidationError> {
+        values
+            .into_iter()
+            .map(|value| self.renumber_value(value))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signing::secp256k1::Secp256k1PrivateKey;
+    use radix_engine_interface::constants::XRD;
+    use radix_engine_interface::manifest_args;
+
+    fn account(key: u64) -> ComponentAddress {
+        ComponentAddress::virtual_account_from_public_key(
+            &Secp256k1PrivateKey::from_u64(key).unwrap().public_key(),
+        )
+    }
+
+    fn token() -> ResourceAddress {
+        ResourceAddress::new_or_panic(
+            [EntityType::GlobalFungibleResourceManager as u8; NodeId::LENGTH],
+        )
+    }
+
+    /// The maker offers 10 XRD for 5 tokens, and the taker accepts the offer.
+    fn order(maker: ComponentAddress, taker: ComponentAddress) -> Vec<PartialManifestV1> {
+        let maker_part = ManifestBuilder::new()
+            .withdraw_from_account(maker, XRD, 10.into())
+            .take_from_worktop_named(XRD, 10.into(), "offer")
+            .yield_bucket("offer", "offer")
+            .accept_bucket("payment", |builder, bucket| {
+                builder.call_method(maker, "try_deposit_or_abort", manifest_args!(bucket))
+            })
+            .build_partial();
+        let taker_part = ManifestBuilder::new()
+            .accept_bucket_named("offer", "offer")
+            .withdraw_from_account(taker, token(), 5.into())
+            .take_all_from_worktop_named(token(), "payment")
+            .yield_bucket("payment", "payment")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(
+                    taker,
+                    "try_deposit_or_abort",
+                    manifest_args!(lookup.bucket("offer")),
+                )
+            })
+            .build_partial();
+        vec![maker_part, taker_part]
+    }
+
+    #[test]
+    fn partial_manifests_are_merged_with_renumbered_buckets() {
+        let (maker, taker) = (account(1), account(2));
+
+        let manifest = merge_partial_manifests(&order(maker, taker)).unwrap();
+
+        assert_eq!(manifest.instructions.len(), 6);
+        // The taker's part runs until it deposits the offer (bucket 0), then the maker's part
+        // deposits the payment (bucket 1, taken by the taker)
+        assert_eq!(
+            manifest.instructions[4],
+            InstructionV1::CallMethod {
+                address: taker.into(),
+                method_name: "try_deposit_or_abort".to_string(),
+                args: manifest_args!(ManifestBucket(0)),
+            }
+        );
+        assert_eq!(
+            manifest.instructions[5],
+            InstructionV1::CallMethod {
+                address: maker.into(),
+                method_name: "try_deposit_or_abort".to_string(),
+                args: manifest_args!(ManifestBucket(1)),
+            }
+        );
+    }
+
+    #[test]
+    fn partial_manifests_can_be_exchanged_encoded() {
+        let parts = order(account(1), account(2));
+
+        let encoded = manifest_encode(&parts).unwrap();
+
+        assert_eq!(
+            manifest_decode::<Vec<PartialManifestV1>>(&encoded).unwrap(),
+            parts
+        );
+    }
+
+    #[test]
+    fn unmatched_and_deadlocked_interface_points_are_rejected() {
+        let (maker, taker) = (account(1), account(2));
+        let mut parts = order(maker, taker);
+        parts.pop();
+
+        assert_eq!(
+            merge_partial_manifests(&parts),
+            Err(PartialManifestMergeError::UnmatchedInterfacePoint(
+                "offer".to_string()
+            ))
+        );
+
+        // The maker only yields the offer once paid, and the taker only pays once offered
+        let maker_part = ManifestBuilder::new()
+            .accept_bucket_named("payment", "payment")
+            .deposit(maker, "payment")
+            .withdraw_from_account(maker, XRD, 10.into())
+            .take_all_from_worktop_named(XRD, "offer")
+            .yield_bucket("offer", "offer")
+            .build_partial();
+        parts = order(maker, taker);
+        parts[0] = maker_part;
+
+        assert_eq!(
+            merge_partial_manifests(&parts),
+            Err(PartialManifestMergeError::Deadlock(vec![
+                "payment".to_string(),
+                "offer".to_string()
+            ]))
+        );
+    }
+
+    #[test]
+    fn yielded_bucket_can_no_longer_be_used() {
+        let maker_part = ManifestBuilder::new()
+            .withdraw_from_account(account(1), XRD, 10.into())
+            .take_all_from_worktop_named(XRD, "offer")
+            .yield_bucket("offer", "offer")
+            .return_to_worktop("offer")
+            .build_partial();
+        let taker_part = ManifestBuilder::new()
+            .accept_bucket_named("offer", "offer")
+            .deposit(account(2), "offer")
+            .build_partial();
+
+        assert_eq!(
+            merge_partial_manifests(&[maker_part, taker_part]),
+            Err(PartialManifestMergeError::IdValidationError {
+                part_index: 0,
+                error: ManifestIdValidationError::BucketNotFound(ManifestBucket(0)),
+            })
+        );
+    }
+
+    #[test]
+    fn merged_manifest_is_validated() {
+        // The taker deposits the offer twice
+        let maker_part = ManifestBuilder::new()
+            .withdraw_from_account(account(1), XRD, 10.into())
+            .take_all_from_worktop_named(XRD, "offer")
+            .yield_bucket("offer", "offer")
+            .build_partial();
+        let taker_part = ManifestBuilder::new()
+            .accept_bucket_named("offer", "offer")
+            .deposit(account(2), "offer")
+            .deposit(account(2), "offer")
+            .build_partial();
+
+        assert_eq!(
+            merge_partial_manifests(&[maker_part, taker_part]),
+            Err(PartialManifestMergeError::ValidationError(
+                TransactionValidationError::CallDataValidationError(
+                    CallDataValidationError::IdValidationError(
+                        ManifestIdValidationError::BucketNotFound(ManifestBucket(0))
+                    )
+                )
+            ))
+        );
+    }
+}
@@ -1,12 +1,31 @@
 use crate::model::*;
-use crate::signing::Signer;
+#[cfg(feature = "std")]
+use crate::signing::{encrypt_message, MessageEncryptionError};
+use crate::signing::{
+    sign_with_external_signer, verify_external_signature, ExternalSigner, ExternalSignerError,
+    Signer,
+};
+use crate::validation::recover;
+use radix_engine_interface::crypto::PublicKey;
 
 use super::manifest_builder::TransactionManifestV1;
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeSignaturesError {
+    /// The signatures were made for a different intent than the one being built.
+    IntentMismatch,
+    /// One of the signatures was not made over the intent's hash.
+    InvalidSignature,
+}
+
 pub struct TransactionBuilder {
     manifest: Option<TransactionManifestV1>,
     header: Option<TransactionHeaderV1>,
     message: Option<MessageV1>,
+    /// The intent of a partially signed transaction which is being completed (used as-is, since
+    /// re-creating it from its manifest would not necessarily preserve the order of its blobs, and
+    /// thus its hash).
+    intent: Option<IntentV1>,
     intent_signatures: Vec<SignatureWithPublicKeyV1>,
     notary_signature: Option<SignatureV1>,
 }
@@ -17,26 +36,63 @@ impl TransactionBuilder {
             manifest: None,
             header: None,
             message: None,
+            intent: None,
             intent_signatures: vec![],
             notary_signature: None,
         }
     }
 
+    /// Continues building a transaction from the given partially signed intent (e.g. one created
+    /// by [`Self::build_partially_signed()`] and transferred as payload bytes to another signer).
+    pub fn from_partially_signed(signed_intent: SignedIntentV1) -> Self {
+        let SignedIntentV1 {
+            intent,
+            intent_signatures,
+        } = signed_intent;
+        Self {
+            manifest: Some(TransactionManifestV1::from_intent(&intent)),
+            header: Some(intent.header.clone()),
+            message: Some(intent.message.clone()),
+            intent: Some(intent),
+            intent_signatures: intent_signatures
+                .signatures
+                .into_iter()
+                .map(|signature| signature.0)
+                .collect(),
+            notary_signature: None,
+        }
+    }
+
     pub fn manifest(mut self, manifest: TransactionManifestV1) -> Self {
         self.manifest = Some(manifest);
+        self.intent = None;
         self
     }
 
     pub fn header(mut self, header: TransactionHeaderV1) -> Self {
         self.header = Some(header);
+        self.intent = None;
         self
     }
 
     pub fn message(mut self, message: MessageV1) -> Self {
         self.message = Some(message);
+        self.intent = None;
         self
     }
 
+    /// Sets a message encrypted for the given decryptors (see [`encrypt_message()`]), failing if
+    /// there are no decryptors or any of their public keys is invalid.
+    #[cfg(feature = "std")]
+    pub fn encrypted_message(
+        self,
+        plaintext_message: PlaintextMessageV1,
+        decryptor_public_keys: &[PublicKey],
+    ) -> Result<Self, MessageEncryptionError> {
+        let encrypted_message = encrypt_message(&plaintext_message, decryptor_public_keys)?;
+        Ok(self.message(MessageV1::Encrypted(encrypted_message)))
+    }
+
     pub fn sign<S: Signer>(mut self, signer: &S) -> Self {
         let intent = self.transaction_intent();
         let prepared = intent.prepare().expect("Intent could be prepared");
@@ -45,6 +101,74 @@ impl TransactionBuilder {
         self
     }
 
+    /// Signs the intent with the given [`ExternalSigner`], failing if it does not produce a valid
+    /// signature.
+    pub fn sign_external<S: ExternalSigner + ?Sized>(
+        mut self,
+        signer: &S,
+    ) -> Result<Self, ExternalSignerError> {
+        let intent_hash = self.intent_hash();
+        self.intent_signatures
+            .push(sign_with_external_signer(signer, &intent_hash)?);
+        Ok(self)
+    }
+
+    /// Returns the hash to be signed by the intent signers.
+    /// Together with [`Self::add_intent_signature()`], this allows to defer the signing (e.g. to
+    /// send the hash to a signer answering asynchronously, and only continue building the
+    /// transaction once its signature arrives).
+    pub fn intent_hash(&self) -> IntentHash {
+        self.transaction_intent()
+            .prepare()
+            .expect("Intent could be prepared")
+            .intent_hash()
+    }
+
+    /// Adds an intent signature made over [`Self::intent_hash()`], failing if it does not match the
+    /// given public key.
+    pub fn add_intent_signature(
+        mut self,
+        public_key: PublicKey,
+        signature: SignatureV1,
+    ) -> Result<Self, ExternalSignerError> {
+        let intent_hash = self.intent_hash();
+        self.intent_signatures.push(verify_external_signature(
+            &public_key,
+            &intent_hash,
+            signature,
+        )?);
+        Ok(self)
+    }
+
+    /// Adds the intent signatures of the given partially signed intent (skipping the ones which are
+    /// already present), e.g. to combine the results of several signers signing in parallel.
+    /// Fails (without adding any of them) if one of the signatures does not verify against
+    /// [`Self::intent_hash()`].
+    pub fn merge_signatures(
+        mut self,
+        signed_intent: &SignedIntentV1,
+    ) -> Result<Self, MergeSignaturesError> {
+        if signed_intent.intent != self.transaction_intent() {
+            return Err(MergeSignaturesError::IntentMismatch);
+        }
+        let intent_hash = self.intent_hash();
+        let mut merged_signatures = Vec::new();
+        for signature in &signed_intent.intent_signatures.signatures {
+            let public_key = recover(intent_hash.as_ref(), &signature.0)
+                .ok_or(MergeSignaturesError::InvalidSignature)?;
+            let signature =
+                verify_external_signature(&public_key, &intent_hash, signature.0.signature())
+                    .map_err(|_| MergeSignaturesError::InvalidSignature)?;
+            if !self.intent_signatures.contains(&signature)
+                && !merged_signatures.contains(&signature)
+            {
+                merged_signatures.push(signature);
+            }
+        }
+        self.intent_signatures.extend(merged_signatures);
+        Ok(self)
+    }
+
     pub fn signer_signatures(mut self, sigs: Vec<SignatureWithPublicKeyV1>) -> Self {
         self.intent_signatures.extend(sigs);
         self
@@ -63,6 +187,42 @@ impl TransactionBuilder {
         self
     }
 
+    /// Notarizes the transaction with the given [`ExternalSigner`], failing if it does not produce
+    /// a valid signature.
+    pub fn notarize_external<S: ExternalSigner + ?Sized>(
+        mut self,
+        signer: &S,
+    ) -> Result<Self, ExternalSignerError> {
+        let signed_intent_hash = self.signed_intent_hash();
+        self.notary_signature =
+            Some(sign_with_external_signer(signer, &signed_intent_hash)?.signature());
+        Ok(self)
+    }
+
+    /// Returns the hash to be signed by the notary, i.e. over the intent and all the intent
+    /// signatures collected so far (see [`Self::add_notary_signature()`]).
+    pub fn signed_intent_hash(&self) -> SignedIntentHash {
+        self.signed_transaction_intent()
+            .prepare()
+            .expect("Signed intent could be prepared")
+            .signed_intent_hash()
+    }
+
+    /// Adds a notary signature made over [`Self::signed_intent_hash()`], failing if it does not
+    /// match the notary public key of the header.
+    pub fn add_notary_signature(
+        mut self,
+        signature: SignatureV1,
+    ) -> Result<Self, ExternalSignerError> {
+        let notary_public_key = self.transaction_intent().header.notary_public_key;
+        let signed_intent_hash = self.signed_intent_hash();
+        self.notary_signature = Some(
+            verify_external_signature(&notary_public_key, &signed_intent_hash, signature)?
+                .signature(),
+        );
+        Ok(self)
+    }
+
     pub fn notary_signature(mut self, signature: SignatureV1) -> Self {
         self.notary_signature = Some(signature);
         self
@@ -77,7 +237,19 @@ impl TransactionBuilder {
         }
     }
 
+    /// Builds the intent with all the signatures collected so far, but without the notary
+    /// signature.
+    /// The result can be transferred to further signers (e.g. using
+    /// `TransactionPayload::to_payload_bytes()`), who continue with
+    /// [`Self::from_partially_signed()`].
+    pub fn build_partially_signed(&self) -> SignedIntentV1 {
+        self.signed_transaction_intent()
+    }
+
     fn transaction_intent(&self) -> IntentV1 {
+        if let Some(intent) = &self.intent {
+            return intent.clone();
+        }
         let (instructions, blobs) = self
             .manifest
             .clone()
@@ -114,7 +286,11 @@ mod tests {
 
     use super::*;
     use crate::builder::*;
+    use crate::signing::ed25519::Ed25519PrivateKey;
     use crate::signing::secp256k1::Secp256k1PrivateKey;
+    use crate::signing::CallbackSigner;
+    use crate::validation::{recover, verify};
+    use radix_engine_interface::crypto::{hash, IsHash, PublicKey};
 
     #[test]
     fn notary_as_signatory() {
@@ -145,4 +321,196 @@ mod tests {
             true
         );
     }
+
+    #[test]
+    fn partially_signed_transaction_collects_signatures_of_independent_signers() {
+        let notary_private_key = Secp256k1PrivateKey::from_u64(1).unwrap();
+        let signer_1_private_key = Secp256k1PrivateKey::from_u64(2).unwrap();
+        let signer_2_private_key = Ed25519PrivateKey::from_u64(3).unwrap();
+        let signer_3_private_key = Ed25519PrivateKey::from_u64(4).unwrap();
+
+        let unsigned = TransactionBuilder::new()
+            .header(TransactionHeaderV1 {
+                network_id: NetworkDefinition::simulator().id,
+                start_epoch_inclusive: Epoch::zero(),
+                end_epoch_exclusive: Epoch::of(100),
+                nonce: 5,
+                notary_public_key: notary_private_key.public_key().into(),
+                notary_is_signatory: false,
+                tip_percentage: 5,
+            })
+            .manifest(
+                ManifestBuilder::new()
+                    .clear_auth_zone()
+                    .add_blob(vec![2])
+                    .add_blob(vec![1])
+                    .build(),
+            )
+            .build_partially_signed()
+            .to_payload_bytes()
+            .unwrap();
+
+        // The first signer signs in-process, the second one through a callback
+        let partially_signed = TransactionBuilder::from_partially_signed(
+            SignedIntentV1::from_payload_bytes(&unsigned).unwrap(),
+        )
+        .sign(&signer_1_private_key)
+        .sign_external(&CallbackSigner::new(
+            signer_2_private_key.public_key().into(),
+            |hash| Ok(signer_2_private_key.sign(hash).into()),
+        ))
+        .unwrap()
+        .build_partially_signed()
+        .to_payload_bytes()
+        .unwrap();
+
+        // The third signer signs the unsigned transaction in parallel
+        let separately_signed = TransactionBuilder::from_partially_signed(
+            SignedIntentV1::from_payload_bytes(&unsigned).unwrap(),
+        )
+        .sign(&signer_3_private_key)
+        .build_partially_signed();
+
+        let transaction = TransactionBuilder::from_partially_signed(
+            SignedIntentV1::from_payload_bytes(&partially_signed).unwrap(),
+        )
+        .merge_signatures(&separately_signed)
+        .unwrap()
+        .notarize_external(&notary_private_key)
+        .unwrap()
+        .build();
+
+        let prepared = transaction.prepare().unwrap();
+        let intent_hash = prepared.intent_hash();
+        let signer_public_keys: Vec<PublicKey> = transaction
+            .signed_intent
+            .intent_signatures
+            .signatures
+            .iter()
+            .map(|signature| recover(intent_hash.as_hash(), &signature.0).unwrap())
+            .collect();
+        assert_eq!(
+            signer_public_keys,
+            vec![
+                signer_1_private_key.public_key().into(),
+                signer_2_private_key.public_key().into(),
+                signer_3_private_key.public_key().into(),
+            ]
+        );
+        assert!(verify(
+            prepared.signed_intent_hash().as_hash(),
+            &notary_private_key.public_key().into(),
+            &transaction.notary_signature.0,
+        ));
+    }
+
+    #[test]
+    fn deferred_signatures_are_verified_and_added() {
+        let notary_private_key = Secp256k1PrivateKey::from_u64(1).unwrap();
+        let signer_private_key = Ed25519PrivateKey::from_u64(2).unwrap();
+        let builder = TransactionBuilder::new()
+            .header(TransactionHeaderV1 {
+                network_id: NetworkDefinition::simulator().id,
+                start_epoch_inclusive: Epoch::zero(),
+                end_epoch_exclusive: Epoch::of(100),
+                nonce: 5,
+                notary_public_key: notary_private_key.public_key().into(),
+                notary_is_signatory: false,
+                tip_percentage: 5,
+            })
+            .manifest(ManifestBuilder::new().clear_auth_zone().build());
+
+        // The signer answers later (e.g. from another task), given only the hash
+        let intent_hash = builder.intent_hash();
+        let intent_signature = signer_private_key.sign(&intent_hash).into();
+        let wrong_signature = signer_private_key.sign(&hash("other")).into();
+        assert_eq!(
+            TransactionBuilder::new()
+                .header(builder.header.clone().unwrap())
+                .manifest(builder.manifest.clone().unwrap())
+                .add_intent_signature(signer_private_key.public_key().into(), wrong_signature)
+                .err(),
+            Some(ExternalSignerError::InvalidSignature)
+        );
+        let builder = builder
+            .add_intent_signature(signer_private_key.public_key().into(), intent_signature)
+            .unwrap();
+
+        let signed_intent_hash = builder.signed_intent_hash();
+        let notary_signature = notary_private_key.sign(&signed_intent_hash).into();
+        let transaction = builder
+            .add_notary_signature(notary_signature)
+            .unwrap()
+            .build();
+
+        let prepared = transaction.prepare().unwrap();
+        assert_eq!(prepared.intent_hash(), intent_hash);
+        assert_eq!(prepared.signed_intent_hash(), signed_intent_hash);
+        assert_eq!(
+            recover(
+                intent_hash.as_hash(),
+                &transaction.signed_intent.intent_signatures.signatures[0].0
+            ),
+            Some(signer_private_key.public_key().into())
+        );
+    }
+
+    #[test]
+    fn signatures_of_different_intent_cannot_be_merged() {
+        let private_key = Secp256k1PrivateKey::from_u64(1).unwrap();
+        let builder = |nonce| {
+            TransactionBuilder::new()
+                .header(TransactionHeaderV1 {
+                    network_id: NetworkDefinition::simulator().id,
+                    start_epoch_inclusive: Epoch::zero(),
+                    end_epoch_exclusive: Epoch::of(100),
+                    nonce,
+                    notary_public_key: private_key.public_key().into(),
+                    notary_is_signatory: true,
+                    tip_percentage: 5,
+                })
+                .manifest(ManifestBuilder::new().clear_auth_zone().build())
+        };
+
+        let other_signed = builder(6).sign(&private_key).build_partially_signed();
+        assert_eq!(
+            builder(5).merge_signatures(&other_signed).err(),
+            Some(MergeSignaturesError::IntentMismatch)
+        );
+    }
+
+    #[test]
+    fn signatures_not_made_over_the_intent_hash_cannot_be_merged() {
+        let notary_private_key = Secp256k1PrivateKey::from_u64(1).unwrap();
+        let signer_1_private_key = Ed25519PrivateKey::from_u64(2).unwrap();
+        let signer_2_private_key = Ed25519PrivateKey::from_u64(3).unwrap();
+        let builder = || {
+            TransactionBuilder::new()
+                .header(TransactionHeaderV1 {
+                    network_id: NetworkDefinition::simulator().id,
+                    start_epoch_inclusive: Epoch::zero(),
+                    end_epoch_exclusive: Epoch::of(100),
+                    nonce: 5,
+                    notary_public_key: notary_private_key.public_key().into(),
+                    notary_is_signatory: false,
+                    tip_percentage: 5,
+                })
+                .manifest(ManifestBuilder::new().clear_auth_zone().build())
+        };
+
+        // The other party signed the intent, but also slipped in a signature of something else
+        let mut separately_signed = builder()
+            .sign(&signer_1_private_key)
+            .build_partially_signed();
+        let wrong_signature = signer_2_private_key.sign_with_public_key(&hash("other"));
+        separately_signed
+            .intent_signatures
+            .signatures
+            .push(IntentSignatureV1(wrong_signature));
+
+        assert_eq!(
+            builder().merge_signatures(&separately_signed).err(),
+            Some(MergeSignaturesError::InvalidSignature)
+        );
+    }
 }
@@ -53,6 +53,23 @@ pub enum TransactionValidationError {
     InvalidMessage(InvalidMessageError),
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartialManifestMergeError {
+    /// Several partial manifests yield (or accept) a bucket at the same interface point.
+    DuplicateInterfacePoint(String),
+    /// A bucket is yielded but never accepted at the interface point, or vice versa.
+    UnmatchedInterfacePoint(String),
+    /// Each partial manifest left waits for a bucket at one of these interface points.
+    Deadlock(Vec<String>),
+    /// A partial manifest uses an id it hasn't created (or has already yielded).
+    IdValidationError {
+        part_index: usize,
+        error: ManifestIdValidationError,
+    },
+    /// The merged manifest is invalid.
+    ValidationError(TransactionValidationError),
+}
+
 impl From<PrepareError> for TransactionValidationError {
     fn from(value: PrepareError) -> Self {
         Self::PrepareError(value)
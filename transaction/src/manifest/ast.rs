@@ -266,6 +266,152 @@ pub enum Instruction {
     },
 }
 
+impl Instruction {
+    /// The arguments of the instruction, in the order in which they appear in the manifest.
+    pub fn arguments(&self) -> Vec<&Value> {
+        match self {
+            Instruction::TakeFromWorktop {
+                resource_address,
+                amount,
+                new_bucket,
+            } => vec![resource_address, amount, new_bucket],
+            Instruction::TakeNonFungiblesFromWorktop {
+                resource_address,
+                ids,
+                new_bucket,
+            } => vec![resource_address, ids, new_bucket],
+            Instruction::TakeAllFromWorktop {
+                resource_address,
+                new_bucket,
+            } => vec![resource_address, new_bucket],
+            Instruction::ReturnToWorktop { bucket } | Instruction::BurnResource { bucket } => {
+                vec![bucket]
+            }
+            Instruction::AssertWorktopContains {
+                resource_address,
+                amount,
+            } => vec![resource_address, amount],
+            Instruction::AssertWorktopContainsNonFungibles {
+                resource_address,
+                ids,
+            } => vec![resource_address, ids],
+            Instruction::PopFromAuthZone { new_proof } => vec![new_proof],
+            Instruction::PushToAuthZone { proof } | Instruction::DropProof { proof } => vec![proof],
+            Instruction::ClearAuthZone
+            | Instruction::ClearSignatureProofs
+            | Instruction::DropAllProofs => Vec::new(),
+            Instruction::CreateProofFromAuthZone {
+                resource_address,
+                new_proof,
+            }
+            | Instruction::CreateProofFromAuthZoneOfAll {
+                resource_address,
+                new_proof,
+            } => vec![resource_address, new_proof],
+            Instruction::CreateProofFromAuthZoneOfAmount {
+                resource_address,
+                amount,
+                new_proof,
+            } => vec![resource_address, amount, new_proof],
+            Instruction::CreateProofFromAuthZoneOfNonFungibles {
+                resource_address,
+                ids,
+                new_proof,
+            } => vec![resource_address, ids, new_proof],
+            Instruction::CreateProofFromBucket { bucket, new_proof }
+            | Instruction::CreateProofFromBucketOfAll { bucket, new_proof } => {
+                vec![bucket, new_proof]
+            }
+            Instruction::CreateProofFromBucketOfAmount {
+                bucket,
+                amount,
+                new_proof,
+            } => vec![bucket, amount, new_proof],
+            Instruction::CreateProofFromBucketOfNonFungibles {
+                bucket,
+                ids,
+                new_proof,
+            } => vec![bucket, ids, new_proof],
+            Instruction::CloneProof { proof, new_proof } => vec![proof, new_proof],
+            Instruction::CallFunction {
+                package_address,
+                blueprint_name,
+                function_name,
+                args,
+            } => [package_address, blueprint_name, function_name]
+                .into_iter()
+                .chain(args)
+                .collect(),
+            Instruction::CallMethod {
+                address,
+                method_name,
+                args,
+            }
+            | Instruction::CallRoyaltyMethod {
+                address,
+                method_name,
+                args,
+            }
+            | Instruction::CallMetadataMethod {
+                address,
+                method_name,
+                args,
+            }
+            | Instruction::CallAccessRulesMethod {
+                address,
+                method_name,
+                args,
+            } => [address, method_name].into_iter().chain(args).collect(),
+            Instruction::AllocateGlobalAddress {
+                package_address,
+                blueprint_name,
+                address_reservation,
+                named_address,
+            } => vec![
+                package_address,
+                blueprint_name,
+                address_reservation,
+                named_address,
+            ],
+            Instruction::RecallFromVault { vault_id, args }
+            | Instruction::FreezeVault { vault_id, args }
+            | Instruction::UnfreezeVault { vault_id, args } => {
+                [vault_id].into_iter().chain(args).collect()
+            }
+            Instruction::PublishPackage { args }
+            | Instruction::PublishPackageAdvanced { args }
+            | Instruction::CreateFungibleResource { args }
+            | Instruction::CreateFungibleResourceWithInitialSupply { args }
+            | Instruction::CreateNonFungibleResource { args }
+            | Instruction::CreateNonFungibleResourceWithInitialSupply { args }
+            | Instruction::CreateAccessController { args }
+            | Instruction::CreateIdentity { args }
+            | Instruction::CreateIdentityAdvanced { args }
+            | Instruction::CreateAccount { args }
+            | Instruction::CreateAccountAdvanced { args }
+            | Instruction::CreateValidator { args } => args.iter().collect(),
+            Instruction::SetMetadata { address, args }
+            | Instruction::RemoveMetadata { address, args }
+            | Instruction::LockMetadata { address, args }
+            | Instruction::SetComponentRoyalty { address, args }
+            | Instruction::LockComponentRoyalty { address, args }
+            | Instruction::ClaimComponentRoyalties { address, args }
+            | Instruction::SetOwnerRole { address, args }
+            | Instruction::LockOwnerRole { address, args }
+            | Instruction::SetAndLockOwnerRole { address, args }
+            | Instruction::SetRole { address, args }
+            | Instruction::LockRole { address, args }
+            | Instruction::SetAndLockRole { address, args }
+            | Instruction::MintFungible { address, args }
+            | Instruction::MintNonFungible { address, args }
+            | Instruction::MintRuidNonFungible { address, args }
+            | Instruction::ClaimPackageRoyalties { address, args } => {
+                [address].into_iter().chain(args).collect()
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ValueKind {
     // ==============
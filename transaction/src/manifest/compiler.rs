@@ -1,13 +1,32 @@
 use crate::builder::TransactionManifestV1;
+use crate::manifest::lexer::Span;
+use crate::manifest::template::{resolve_template, TemplateContext};
 use crate::manifest::*;
 use radix_engine_interface::address::AddressBech32Decoder;
 use radix_engine_interface::network::NetworkDefinition;
 
+/// An error compiling a manifest, together with the span of the manifest source it relates to.
+///
+/// Generator errors are reported against the span of the instruction argument they were caused by,
+/// or of the whole instruction if they cannot be attributed to a single argument.
+/// See [`compile_error_diagnostics`] for rendering the error against the source.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CompileError {
-    LexerError(lexer::LexerError),
-    ParserError(parser::ParserError),
-    GeneratorError(generator::GeneratorError),
+    LexerError(lexer::LexerError, Span),
+    ParserError(parser::ParserError, Span),
+    GeneratorError(generator::GeneratorError, Span),
+    TemplateError(template::TemplateError, Span),
+}
+
+impl CompileError {
+    pub fn span(&self) -> Span {
+        match self {
+            CompileError::LexerError(_, span)
+            | CompileError::ParserError(_, span)
+            | CompileError::GeneratorError(_, span)
+            | CompileError::TemplateError(_, span) => *span,
+        }
+    }
 }
 
 pub fn compile<B>(
@@ -15,15 +34,65 @@ pub fn compile<B>(
     network: &NetworkDefinition,
     blobs: B,
 ) -> Result<TransactionManifestV1, CompileError>
+where
+    B: IsBlobProvider,
+{
+    compile_with_source_map(s, network, blobs, &TemplateContext::new())
+        .map(|(manifest, _)| manifest)
+}
+
+/// Compiles a manifest, together with the map from the compiled instructions back to the
+/// manifest source.
+///
+/// The parameters and includes of a manifest template are resolved from the given context (see
+/// [`template`]) before the manifest is parsed.
+pub fn compile_with_source_map<B>(
+    s: &str,
+    network: &NetworkDefinition,
+    blobs: B,
+    template_context: &TemplateContext,
+) -> Result<(TransactionManifestV1, ManifestSourceMap), CompileError>
 where
     B: IsBlobProvider,
 {
     let address_bech32_decoder = AddressBech32Decoder::new(network);
 
-    let tokens = lexer::tokenize(s).map_err(CompileError::LexerError)?;
-    let instructions = parser::Parser::new(tokens, parser::PARSER_MAX_DEPTH)
-        .parse_manifest()
-        .map_err(CompileError::ParserError)?;
-    generator::generate_manifest(&instructions, &address_bech32_decoder, blobs)
-        .map_err(CompileError::GeneratorError)
+    let tokens = lexer::tokenize(s).map_err(|error| {
+        let span = error.span(s);
+        CompileError::LexerError(error, span)
+    })?;
+    let tokens = resolve_template(tokens, template_context)
+        .map_err(|(error, span)| CompileError::TemplateError(error, span))?;
+    let mut parser = parser::Parser::new(tokens, parser::PARSER_MAX_DEPTH);
+    let (instructions, spans): (Vec<_>, Vec<_>) = parser
+        .parse_manifest_with_spans()
+        .map_err(|error| {
+            let span = parser.error_span(&error);
+            CompileError::ParserError(error, span)
+        })?
+        .into_iter()
+        .unzip();
+    let (manifest, name_resolver) =
+        generator::generate_manifest_locating_errors(&instructions, &address_bech32_decoder, blobs)
+            .map_err(|(index, error)| {
+                let span = error
+                    .argument
+                    .and_then(|argument| {
+                        instructions[index]
+                            .arguments()
+                            .into_iter()
+                            .position(|a| core::ptr::eq(a, argument))
+                    })
+                    .map(|position| spans[index].arguments[position])
+                    .unwrap_or(spans[index].instruction);
+                CompileError::GeneratorError(error.error, span)
+            })?;
+
+    Ok((
+        manifest,
+        ManifestSourceMap::new(
+            spans.iter().map(|spans| spans.instruction).collect(),
+            name_resolver.object_names(),
+        ),
+    ))
 }
@@ -1,3 +1,4 @@
+use crate::builder::ManifestObjectNames;
 use crate::data::*;
 use crate::model::*;
 use crate::validation::*;
@@ -91,6 +92,8 @@ pub struct DecompilationContext<'a> {
     pub proof_names: NonIterMap<ManifestProof, String>,
     pub address_reservation_names: NonIterMap<ManifestAddressReservation, String>,
     pub address_names: NonIterMap<u32, String>,
+    /// Names to use instead of generated ones (e.g. the names given in the `ManifestBuilder`).
+    pub known_object_names: Option<&'a ManifestObjectNames>,
 }
 
 impl<'a> DecompilationContext<'a> {
@@ -110,6 +113,17 @@ impl<'a> DecompilationContext<'a> {
         }
     }
 
+    pub fn new_with_known_naming(
+        address_bech32_encoder: &'a AddressBech32Encoder,
+        known_object_names: &'a ManifestObjectNames,
+    ) -> Self {
+        Self {
+            address_bech32_encoder: Some(address_bech32_encoder),
+            known_object_names: Some(known_object_names),
+            ..Default::default()
+        }
+    }
+
     pub fn for_value_display(&'a self) -> ManifestDecompilationDisplayContext<'a> {
         ManifestDecompilationDisplayContext::with_bech32_and_names(
             self.address_bech32_encoder,
@@ -123,32 +137,73 @@ impl<'a> DecompilationContext<'a> {
 
     pub fn new_bucket(&mut self) -> ManifestBucket {
         let id = self.id_allocator.new_bucket_id();
-        let name = format!("bucket{}", self.bucket_names.len() + 1);
+        let name = Self::name_for(
+            self.known_object_names.map(|names| &names.bucket_names),
+            &id,
+            format!("bucket{}", self.bucket_names.len() + 1),
+        );
         self.bucket_names.insert(id, name.clone());
         id
     }
 
     pub fn new_proof(&mut self) -> ManifestProof {
         let id = self.id_allocator.new_proof_id();
-        let name = format!("proof{}", self.proof_names.len() + 1);
+        let name = Self::name_for(
+            self.known_object_names.map(|names| &names.proof_names),
+            &id,
+            format!("proof{}", self.proof_names.len() + 1),
+        );
         self.proof_names.insert(id, name.clone());
         id
     }
 
     pub fn new_address_reservation(&mut self) -> ManifestAddressReservation {
         let id = self.id_allocator.new_address_reservation_id();
-        let name = format!("reservation{}", self.address_reservation_names.len() + 1);
+        let name = Self::name_for(
+            self.known_object_names
+                .map(|names| &names.address_reservation_names),
+            &id,
+            format!("reservation{}", self.address_reservation_names.len() + 1),
+        );
         self.address_reservation_names.insert(id, name.clone());
         id
     }
 
     pub fn new_address(&mut self) -> ManifestAddress {
         let id = self.id_allocator.new_address_id();
-        let name = format!("address{}", self.address_names.len() + 1);
+        let name = Self::name_for(
+            self.known_object_names.map(|names| &names.address_names),
+            &id,
+            format!("address{}", self.address_names.len() + 1),
+        );
         self.address_names.insert(id, name.clone());
         ManifestAddress::Named(id)
     }
 
+    /// Returns the known name of the object, or else the generated name - unless it is already
+    /// known as the name of another object, in which case a suffix is added.
+    fn name_for<K: sbor::rust::hash::Hash + Eq>(
+        known_names: Option<&IndexMap<K, String>>,
+        id: &K,
+        generated_name: String,
+    ) -> String {
+        let known_names = match known_names {
+            Some(known_names) => known_names,
+            None => return generated_name,
+        };
+        if let Some(name) = known_names.get(id) {
+            return name.clone();
+        }
+        let is_known = |name: &String| known_names.values().any(|known| known == name);
+        let mut name = generated_name.clone();
+        let mut suffix = 1;
+        while is_known(&name) {
+            name = format!("{}_{}", generated_name, suffix);
+            suffix += 1;
+        }
+        name
+    }
+
     /// Allocate addresses before transaction, for system transactions only.
     pub fn preallocate_addresses(&mut self, n: u32) {
         for _ in 0..n {
@@ -173,6 +228,25 @@ pub fn decompile(
     Ok(buf)
 }
 
+/// Decompiles the instructions, using the given names (e.g. those given in the `ManifestBuilder`,
+/// see `ManifestBuilder::object_names()`) for the buckets, proofs and addresses they are known
+/// for, and generated names for the others.
+pub fn decompile_with_known_naming(
+    instructions: &[InstructionV1],
+    network: &NetworkDefinition,
+    known_object_names: &ManifestObjectNames,
+) -> Result<String, DecompileError> {
+    let address_bech32_encoder = AddressBech32Encoder::new(network);
+    let mut buf = String::new();
+    let mut context =
+        DecompilationContext::new_with_known_naming(&address_bech32_encoder, known_object_names);
+    for inst in instructions {
+        decompile_instruction(&mut buf, inst, &mut context)?;
+    }
+
+    Ok(buf)
+}
+
 pub fn decompile_instruction<F: fmt::Write>(
     f: &mut F,
     instruction: &InstructionV1,
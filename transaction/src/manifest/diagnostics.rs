@@ -0,0 +1,299 @@
+use crate::builder::ManifestObjectNames;
+use crate::manifest::lexer::Span;
+use crate::manifest::CompileError;
+use sbor::rust::prelude::*;
+
+/// Maps the instructions of a compiled manifest back to the manifest source they were compiled
+/// from, so that e.g. a failure of the instruction at index N can be shown against the original
+/// `.rtm` text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ManifestSourceMap {
+    instruction_spans: Vec<Span>,
+    object_names: ManifestObjectNames,
+}
+
+impl ManifestSourceMap {
+    pub fn new(instruction_spans: Vec<Span>, object_names: ManifestObjectNames) -> Self {
+        Self {
+            instruction_spans,
+            object_names,
+        }
+    }
+
+    /// The names given in the source to the buckets, proofs, address reservations and named
+    /// addresses, e.g. to decompile the manifest with the same names.
+    pub fn object_names(&self) -> &ManifestObjectNames {
+        &self.object_names
+    }
+
+    pub fn len(&self) -> usize {
+        self.instruction_spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instruction_spans.is_empty()
+    }
+
+    /// The span of the compiled instruction at the given index, from the instruction name to its
+    /// terminating semicolon.
+    pub fn instruction_span(&self, instruction_index: usize) -> Option<Span> {
+        self.instruction_spans.get(instruction_index).cloned()
+    }
+
+    /// The (1-indexed) line at which the compiled instruction at the given index starts.
+    pub fn instruction_line(&self, instruction_index: usize) -> Option<usize> {
+        self.instruction_span(instruction_index)
+            .map(|span| span.start.line_number)
+    }
+}
+
+/// Renders a compile error against the manifest source it was returned for, e.g.:
+///
+/// ```text
+/// error: InvalidDecimal("1.2.3")
+///  --> line 2, column 47
+///   |
+/// 2 | TAKE_FROM_WORKTOP Address("resource_sim1...") Decimal("1.2.3") Bucket("bucket");
+///   |                                               ^^^^^^^^^^^^^^^^
+/// ```
+pub fn compile_error_diagnostics(source: &str, error: &CompileError) -> String {
+    let message = match error {
+        CompileError::LexerError(error, _) => format!("{:?}", error),
+        CompileError::ParserError(error, _) => format!("{:?}", error),
+        CompileError::GeneratorError(error, _) => format!("{:?}", error),
+        CompileError::TemplateError(error, _) => format!("{:?}", error),
+    };
+    span_diagnostics(source, error.span(), &message)
+}
+
+/// Renders a message (e.g. an execution failure) against the instruction at the given index of
+/// a manifest compiled with [`compile_with_source_map`](crate::manifest::compiler::compile_with_source_map),
+/// or returns `None` if the source map has no such instruction.
+pub fn instruction_diagnostics(
+    source: &str,
+    source_map: &ManifestSourceMap,
+    instruction_index: usize,
+    message: &str,
+) -> Option<String> {
+    source_map
+        .instruction_span(instruction_index)
+        .map(|span| span_diagnostics(source, span, message))
+}
+
+/// Renders a message against the first line of the given span of the source, underlining the
+/// span with carets.
+pub fn span_diagnostics(source: &str, span: Span, message: &str) -> String {
+    let line_number = span.start.line_number;
+    let line = source
+        .split('\n')
+        .nth(line_number - 1)
+        .unwrap_or("")
+        .trim_end_matches('\r');
+    let line_length = line.chars().count();
+    let start = span.start.line_char_index.min(line_length);
+    let end = if span.end.line_number == line_number {
+        span.end.line_char_index.min(line_length)
+    } else {
+        line_length
+    };
+    // Tabs are kept in the indentation of the carets, so that they line up with the source
+    let indentation: String = line
+        .chars()
+        .take(start)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let carets = "^".repeat(end.saturating_sub(start).max(1));
+    let gutter = " ".repeat(line_number.to_string().len());
+
+    let mut output = String::new();
+    output.push_str(&format!("error: {}\n", message));
+    output.push_str(&format!(
+        "{}--> line {}, column {}\n",
+        gutter,
+        line_number,
+        span.start.line_char_index + 1
+    ));
+    output.push_str(&format!("{} |\n", gutter));
+    output.push_str(&format!("{} | {}\n", line_number, line));
+    output.push_str(&format!("{} | {}{}\n", gutter, indentation, carets));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::compiler::compile_with_source_map;
+    use crate::manifest::generator::GeneratorError;
+    use crate::manifest::lexer::LexerError;
+    use crate::manifest::parser::ParserError;
+    use crate::manifest::template::TemplateContext;
+    use crate::manifest::BlobProvider;
+    use radix_engine_interface::data::manifest::model::ManifestBucket;
+    use radix_engine_interface::network::NetworkDefinition;
+
+    fn compile_error(source: &str) -> CompileError {
+        compile_with_source_map(
+            source,
+            &NetworkDefinition::simulator(),
+            BlobProvider::default(),
+            &TemplateContext::new(),
+        )
+        .unwrap_err()
+    }
+
+    #[test]
+    fn test_source_map_maps_instructions_to_lines() {
+        let source = r#"
+# Lock fees
+CALL_METHOD
+    Address("component_sim1cqvgx33089ukm2pl97pv4max0x40ruvfy4lt60yvya744cvemygpmu")
+    "lock_fee"
+    Decimal("10");
+
+TAKE_ALL_FROM_WORKTOP Address("resource_sim1thvwu8dh6lk4y9mntemkvj25wllq8adq42skzufp4m8wxxuemugnez") Bucket("xrd");
+RETURN_TO_WORKTOP Bucket("xrd");
+"#;
+        let (manifest, source_map) = compile_with_source_map(
+            source,
+            &NetworkDefinition::simulator(),
+            BlobProvider::default(),
+            &TemplateContext::new(),
+        )
+        .unwrap();
+
+        assert_eq!(source_map.len(), manifest.instructions.len());
+        assert_eq!(source_map.instruction_line(0), Some(3));
+        assert_eq!(source_map.instruction_line(1), Some(8));
+        assert_eq!(source_map.instruction_line(2), Some(9));
+        assert_eq!(source_map.instruction_line(3), None);
+        assert_eq!(
+            source_map
+                .object_names()
+                .bucket_names
+                .get(&ManifestBucket(0)),
+            Some(&"xrd".to_string())
+        );
+        let span = source_map.instruction_span(0).unwrap();
+        assert_eq!(span.end.line_number, 6);
+        assert_eq!(span.end.line_char_index, 18);
+        assert_eq!(
+            instruction_diagnostics(source, &source_map, 2, "Bucket not found"),
+            Some(
+                [
+                    "error: Bucket not found",
+                    " --> line 9, column 1",
+                    "  |",
+                    "9 | RETURN_TO_WORKTOP Bucket(\"xrd\");",
+                    "  | ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^",
+                    "",
+                ]
+                .join("\n")
+            )
+        );
+    }
+
+    #[test]
+    fn test_lexer_error_diagnostics() {
+        let source = "CLEAR_AUTH_ZONE;\nCLEAR_AUTH_ZONE ?;";
+        let error = compile_error(source);
+
+        assert!(matches!(
+            error,
+            CompileError::LexerError(LexerError::UnexpectedChar('?', _), _)
+        ));
+        assert_eq!(
+            compile_error_diagnostics(source, &error),
+            [
+                "error: UnexpectedChar('?', Position { full_index: 33, line_number: 2, line_char_index: 16 })",
+                " --> line 2, column 17",
+                "  |",
+                "2 | CLEAR_AUTH_ZONE ?;",
+                "  |                 ^",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_parser_error_diagnostics() {
+        let source = "CLEAR_AUTH_ZONE;\n  DROP_ALL_PROOFS 1u8;";
+        let error = compile_error(source);
+
+        assert!(matches!(
+            error,
+            CompileError::ParserError(ParserError::UnexpectedToken { .. }, _)
+        ));
+        assert_eq!(error.span().start.line_number, 2);
+        assert_eq!(error.span().start.line_char_index, 18);
+        assert_eq!(error.span().end.line_char_index, 21);
+
+        let source = "CLEAR_AUTH_ZONE;\nDROP_ALL_PROOFS";
+        let error = compile_error(source);
+
+        assert!(matches!(
+            error,
+            CompileError::ParserError(ParserError::UnexpectedEof, _)
+        ));
+        assert_eq!(
+            compile_error_diagnostics(source, &error),
+            [
+                "error: UnexpectedEof",
+                " --> line 2, column 16",
+                "  |",
+                "2 | DROP_ALL_PROOFS",
+                "  |                ^",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_generator_error_diagnostics() {
+        let source = "CLEAR_AUTH_ZONE;\n\tASSERT_WORKTOP_CONTAINS Address(\"resource_sim1thvwu8dh6lk4y9mntemkvj25wllq8adq42skzufp4m8wxxuemugnez\") Decimal(\"1.2.3\");";
+        let error = compile_error(source);
+
+        assert_eq!(
+            error,
+            CompileError::GeneratorError(
+                GeneratorError::InvalidDecimal("1.2.3".into()),
+                error.span()
+            )
+        );
+        assert_eq!(error.span().start.line_number, 2);
+        let diagnostics = compile_error_diagnostics(source, &error);
+        let lines: Vec<&str> = diagnostics.lines().collect();
+        assert_eq!(lines[0], "error: InvalidDecimal(\"1.2.3\")");
+        // only the offending argument is underlined, rather than the whole instruction
+        let column = source.lines().nth(1).unwrap().find("Decimal").unwrap();
+        assert_eq!(lines[1], format!(" --> line 2, column {}", column + 1));
+        assert!(lines[3].starts_with("2 | \tASSERT_WORKTOP_CONTAINS"));
+        assert_eq!(
+            lines[4],
+            format!(
+                "  | \t{}{}",
+                " ".repeat(column - 1),
+                "^".repeat("Decimal(\"1.2.3\")".len())
+            )
+        );
+    }
+
+    #[test]
+    fn test_generator_error_diagnostics_of_undefined_bucket() {
+        let source = "CLEAR_AUTH_ZONE;\nRETURN_TO_WORKTOP Bucket(\"missing\");";
+        let error = compile_error(source);
+
+        assert!(matches!(
+            error,
+            CompileError::GeneratorError(GeneratorError::NameResolverError(..), _)
+        ));
+        let diagnostics = compile_error_diagnostics(source, &error);
+        let lines: Vec<&str> = diagnostics.lines().collect();
+        assert_eq!(lines[1], " --> line 2, column 19");
+        assert_eq!(
+            lines[4],
+            format!("  | {}{}", " ".repeat(18), "^".repeat(17))
+        );
+    }
+}
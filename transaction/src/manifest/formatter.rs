@@ -0,0 +1,181 @@
+use crate::manifest::compiler::compile_with_source_map;
+use crate::manifest::decompiler::{decompile_instruction, DecompilationContext};
+use crate::manifest::lexer::{tokenize_with_comments, Comment};
+use crate::manifest::*;
+use radix_engine_interface::address::AddressBech32Encoder;
+use radix_engine_interface::network::NetworkDefinition;
+use sbor::rust::prelude::*;
+
+#[derive(Debug, Clone)]
+pub enum FormatError {
+    CompileError(CompileError),
+    DecompileError(DecompileError),
+    /// Manifest templates can't be formatted, as their parameters and includes would be resolved.
+    TemplateNotSupported,
+}
+
+/// Formats a manifest in the canonical format, i.e. that of the decompiler, keeping the names of
+/// the buckets, proofs, address reservations and named addresses, as well as the comments.
+///
+/// Comments are kept before the instruction they precede or are inside of, and a comment trailing
+/// on the last line of an instruction is kept at the end of its formatted last line. Blank lines
+/// between instructions and comments are kept (collapsed to a single one).
+///
+/// Blobs are not needed: any blob reference is assumed to be valid.
+pub fn format_manifest(s: &str, network: &NetworkDefinition) -> Result<String, FormatError> {
+    let (tokens, comments) = tokenize_with_comments(s).map_err(|error| {
+        let span = error.span(s);
+        FormatError::CompileError(CompileError::LexerError(error, span))
+    })?;
+    if is_template(&tokens) {
+        return Err(FormatError::TemplateNotSupported);
+    }
+    let (manifest, source_map) =
+        compile_with_source_map(s, network, MockBlobProvider::new(), &TemplateContext::new())
+            .map_err(FormatError::CompileError)?;
+
+    let address_bech32_encoder = AddressBech32Encoder::new(network);
+    let mut context = DecompilationContext::new_with_known_naming(
+        &address_bech32_encoder,
+        source_map.object_names(),
+    );
+    let mut formatter = ManifestFormatter::new(s);
+    let mut comments = comments.into_iter().peekable();
+    for (index, instruction) in manifest.instructions.iter().enumerate() {
+        let span = source_map
+            .instruction_span(index)
+            .expect("Every compiled instruction has a span");
+        let mut trailing_comment = None;
+        loop {
+            match comments.peek() {
+                Some(comment) if comment.span.start.full_index < span.end.full_index => {
+                    formatter.push_comment(comments.next().unwrap());
+                }
+                Some(comment) if comment.span.start.line_number == span.end.line_number => {
+                    trailing_comment = comments.next();
+                }
+                _ => break,
+            }
+        }
+        let mut decompiled = String::new();
+        decompile_instruction(&mut decompiled, instruction, &mut context)
+            .map_err(FormatError::DecompileError)?;
+        if let Some(comment) = trailing_comment {
+            decompiled.truncate(decompiled.trim_end().len());
+            decompiled.push_str(&format!(" {}\n", comment.text.trim_end()));
+        }
+        formatter.push(span.start.line_number, span.end.line_number, &decompiled);
+    }
+    for comment in comments {
+        formatter.push_comment(comment);
+    }
+
+    Ok(formatter.output)
+}
+
+struct ManifestFormatter<'s> {
+    source_lines: Vec<&'s str>,
+    /// The (1-indexed) line of the source at which the last pushed element ends
+    last_line_number: Option<usize>,
+    output: String,
+}
+
+impl<'s> ManifestFormatter<'s> {
+    fn new(source: &'s str) -> Self {
+        Self {
+            source_lines: source.split('\n').collect(),
+            last_line_number: None,
+            output: String::new(),
+        }
+    }
+
+    fn push_comment(&mut self, comment: Comment) {
+        let text = format!("{}\n", comment.text.trim_end());
+        self.push(
+            comment.span.start.line_number,
+            comment.span.end.line_number,
+            &text,
+        );
+    }
+
+    fn push(&mut self, start_line_number: usize, end_line_number: usize, text: &str) {
+        if let Some(last_line_number) = self.last_line_number {
+            let has_blank_line = (last_line_number + 1..start_line_number).any(|line_number| {
+                self.source_lines
+                    .get(line_number - 1)
+                    .map_or(false, |line| line.trim().is_empty())
+            });
+            if has_blank_line {
+                self.output.push('\n');
+            }
+        }
+        self.output.push_str(text);
+        self.last_line_number = Some(end_line_number.max(self.last_line_number.unwrap_or(0)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_manifest() {
+        let source = r#"# Lock fees
+CALL_METHOD Address("component_sim1cqvgx33089ukm2pl97pv4max0x40ruvfy4lt60yvya744cvemygpmu")    "lock_fee" Decimal("10") ;
+
+
+TAKE_ALL_FROM_WORKTOP
+  Address("resource_sim1thvwu8dh6lk4y9mntemkvj25wllq8adq42skzufp4m8wxxuemugnez")
+  # The bucket keeps its name
+  Bucket("my_xrd");
+RETURN_TO_WORKTOP Bucket("my_xrd"); # Back to the worktop
+# Done
+"#;
+        let expected = r#"# Lock fees
+CALL_METHOD
+    Address("component_sim1cqvgx33089ukm2pl97pv4max0x40ruvfy4lt60yvya744cvemygpmu")
+    "lock_fee"
+    Decimal("10")
+;
+
+# The bucket keeps its name
+TAKE_ALL_FROM_WORKTOP
+    Address("resource_sim1thvwu8dh6lk4y9mntemkvj25wllq8adq42skzufp4m8wxxuemugnez")
+    Bucket("my_xrd")
+;
+RETURN_TO_WORKTOP
+    Bucket("my_xrd")
+; # Back to the worktop
+# Done
+"#;
+        let network = NetworkDefinition::simulator();
+
+        let formatted = format_manifest(source, &network).unwrap();
+
+        assert_eq!(formatted, expected);
+        assert_eq!(format_manifest(&formatted, &network).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_format_manifest_returns_compile_errors() {
+        let result = format_manifest(
+            "CLEAR_AUTH_ZONE;\nRETURN_TO_WORKTOP Bucket(\"missing\");",
+            &NetworkDefinition::simulator(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(FormatError::CompileError(CompileError::GeneratorError(..)))
+        ));
+    }
+
+    #[test]
+    fn test_format_manifest_rejects_templates() {
+        let result = format_manifest(
+            "PARAM \"count\" U32 1u32;\nCLEAR_AUTH_ZONE;",
+            &NetworkDefinition::simulator(),
+        );
+
+        assert!(matches!(result, Err(FormatError::TemplateNotSupported)));
+    }
+}
@@ -1,4 +1,5 @@
 use super::blob_provider::*;
+use crate::builder::ManifestObjectNames;
 use crate::data::*;
 use crate::errors::*;
 use crate::internal_prelude::TransactionManifestV1;
@@ -60,7 +61,7 @@ use radix_engine_interface::types::InternalAddress;
 use radix_engine_interface::types::ResourceAddress;
 use radix_engine_interface::*;
 use sbor::rust::borrow::Borrow;
-use sbor::rust::collections::BTreeMap;
+use sbor::rust::collections::{BTreeMap, IndexMap};
 use sbor::rust::str::FromStr;
 use sbor::rust::vec;
 use sbor::*;
@@ -221,6 +222,22 @@ impl NameResolver {
             None => Err(NameResolverError::UndefinedNamedAddress(name.into())),
         }
     }
+
+    /// The names of all the objects declared so far, keyed by their ids.
+    pub fn object_names(&self) -> ManifestObjectNames {
+        ManifestObjectNames {
+            bucket_names: Self::invert(&self.named_buckets),
+            proof_names: Self::invert(&self.named_proofs),
+            address_reservation_names: Self::invert(&self.named_address_reservations),
+            address_names: Self::invert(&self.named_addresses),
+        }
+    }
+
+    fn invert<T: Copy + sbor::rust::hash::Hash + Eq>(
+        names: &BTreeMap<String, T>,
+    ) -> IndexMap<T, String> {
+        names.iter().map(|(name, id)| (*id, name.clone())).collect()
+    }
 }
 
 pub fn generate_manifest<B>(
@@ -228,6 +245,51 @@ pub fn generate_manifest<B>(
     address_bech32_decoder: &AddressBech32Decoder,
     blobs: B,
 ) -> Result<TransactionManifestV1, GeneratorError>
+where
+    B: IsBlobProvider,
+{
+    generate_manifest_locating_errors(instructions, address_bech32_decoder, blobs)
+        .map(|(manifest, _)| manifest)
+        .map_err(|(_, error)| error.error)
+}
+
+/// A [`GeneratorError`], together with the argument of the instruction it was caused by (if it
+/// can be attributed to a single argument).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocatedGeneratorError<'a> {
+    pub error: GeneratorError,
+    pub argument: Option<&'a ast::Value>,
+}
+
+impl<'a> From<GeneratorError> for LocatedGeneratorError<'a> {
+    fn from(error: GeneratorError) -> Self {
+        Self {
+            error,
+            argument: None,
+        }
+    }
+}
+
+fn in_argument<'a>(
+    argument: &'a ast::Value,
+) -> impl FnOnce(GeneratorError) -> LocatedGeneratorError<'a> {
+    move |error| LocatedGeneratorError {
+        error,
+        argument: Some(argument),
+    }
+}
+
+/// Same as [`generate_manifest`], but also returns the name resolver (with the names of all the
+/// buckets, proofs, address reservations and named addresses), and any error is returned together
+/// with the index of the instruction it occurred in.
+///
+/// Each instruction is compiled into exactly one [`InstructionV1`], so the index is also that of
+/// the compiled instruction.
+pub fn generate_manifest_locating_errors<'a, B>(
+    instructions: &'a [ast::Instruction],
+    address_bech32_decoder: &AddressBech32Decoder,
+    blobs: B,
+) -> Result<(TransactionManifestV1, NameResolver), (usize, LocatedGeneratorError<'a>)>
 where
     B: IsBlobProvider,
 {
@@ -235,20 +297,26 @@ where
     let mut name_resolver = NameResolver::new();
     let mut output = Vec::new();
 
-    for instruction in instructions {
-        output.push(generate_instruction(
-            instruction,
-            &mut id_validator,
-            &mut name_resolver,
-            address_bech32_decoder,
-            &blobs,
-        )?);
+    for (index, instruction) in instructions.iter().enumerate() {
+        output.push(
+            generate_instruction_locating_errors(
+                instruction,
+                &mut id_validator,
+                &mut name_resolver,
+                address_bech32_decoder,
+                &blobs,
+            )
+            .map_err(|error| (index, error))?,
+        );
     }
 
-    Ok(TransactionManifestV1 {
-        instructions: output,
-        blobs: blobs.blobs(),
-    })
+    Ok((
+        TransactionManifestV1 {
+            instructions: output,
+            blobs: blobs.blobs(),
+        },
+        name_resolver,
+    ))
 }
 
 pub fn generate_instruction<B>(
@@ -258,6 +326,26 @@ pub fn generate_instruction<B>(
     address_bech32_decoder: &AddressBech32Decoder,
     blobs: &B,
 ) -> Result<InstructionV1, GeneratorError>
+where
+    B: IsBlobProvider,
+{
+    generate_instruction_locating_errors(
+        instruction,
+        id_validator,
+        resolver,
+        address_bech32_decoder,
+        blobs,
+    )
+    .map_err(|error| error.error)
+}
+
+fn generate_instruction_locating_errors<'a, B>(
+    instruction: &'a ast::Instruction,
+    id_validator: &mut ManifestValidator,
+    resolver: &mut NameResolver,
+    address_bech32_decoder: &AddressBech32Decoder,
+    blobs: &B,
+) -> Result<InstructionV1, LocatedGeneratorError<'a>>
 where
     B: IsBlobProvider,
 {
@@ -268,14 +356,15 @@ where
             new_bucket,
         } => {
             let bucket_id = id_validator.new_bucket();
-            declare_bucket(new_bucket, resolver, bucket_id)?;
+            declare_bucket(new_bucket, resolver, bucket_id).map_err(in_argument(new_bucket))?;
 
             InstructionV1::TakeFromWorktop {
-                amount: generate_decimal(amount)?,
+                amount: generate_decimal(amount).map_err(in_argument(amount))?,
                 resource_address: generate_resource_address(
                     resource_address,
                     address_bech32_decoder,
-                )?,
+                )
+                .map_err(in_argument(resource_address))?,
             }
         }
         ast::Instruction::TakeNonFungiblesFromWorktop {
@@ -284,14 +373,15 @@ where
             new_bucket,
         } => {
             let bucket_id = id_validator.new_bucket();
-            declare_bucket(new_bucket, resolver, bucket_id)?;
+            declare_bucket(new_bucket, resolver, bucket_id).map_err(in_argument(new_bucket))?;
 
             InstructionV1::TakeNonFungiblesFromWorktop {
-                ids: generate_non_fungible_local_ids(ids)?,
+                ids: generate_non_fungible_local_ids(ids).map_err(in_argument(ids))?,
                 resource_address: generate_resource_address(
                     resource_address,
                     address_bech32_decoder,
-                )?,
+                )
+                .map_err(in_argument(resource_address))?,
             }
         }
         ast::Instruction::TakeAllFromWorktop {
@@ -299,17 +389,18 @@ where
             new_bucket,
         } => {
             let bucket_id = id_validator.new_bucket();
-            declare_bucket(new_bucket, resolver, bucket_id)?;
+            declare_bucket(new_bucket, resolver, bucket_id).map_err(in_argument(new_bucket))?;
 
             InstructionV1::TakeAllFromWorktop {
                 resource_address: generate_resource_address(
                     resource_address,
                     address_bech32_decoder,
-                )?,
+                )
+                .map_err(in_argument(resource_address))?,
             }
         }
         ast::Instruction::ReturnToWorktop { bucket } => {
-            let bucket_id = generate_bucket(bucket, resolver)?;
+            let bucket_id = generate_bucket(bucket, resolver).map_err(in_argument(bucket))?;
             id_validator
                 .drop_bucket(&bucket_id)
                 .map_err(GeneratorError::IdValidationError)?;
@@ -319,26 +410,28 @@ where
             resource_address,
             amount,
         } => InstructionV1::AssertWorktopContains {
-            amount: generate_decimal(amount)?,
-            resource_address: generate_resource_address(resource_address, address_bech32_decoder)?,
+            amount: generate_decimal(amount).map_err(in_argument(amount))?,
+            resource_address: generate_resource_address(resource_address, address_bech32_decoder)
+                .map_err(in_argument(resource_address))?,
         },
         ast::Instruction::AssertWorktopContainsNonFungibles {
             resource_address,
             ids,
         } => InstructionV1::AssertWorktopContainsNonFungibles {
-            resource_address: generate_resource_address(resource_address, address_bech32_decoder)?,
-            ids: generate_non_fungible_local_ids(ids)?,
+            resource_address: generate_resource_address(resource_address, address_bech32_decoder)
+                .map_err(in_argument(resource_address))?,
+            ids: generate_non_fungible_local_ids(ids).map_err(in_argument(ids))?,
         },
         ast::Instruction::PopFromAuthZone { new_proof } => {
             let proof_id = id_validator
                 .new_proof(ProofKind::AuthZoneProof)
                 .map_err(GeneratorError::IdValidationError)?;
-            declare_proof(new_proof, resolver, proof_id)?;
+            declare_proof(new_proof, resolver, proof_id).map_err(in_argument(new_proof))?;
 
             InstructionV1::PopFromAuthZone
         }
         ast::Instruction::PushToAuthZone { proof } => {
-            let proof_id = generate_proof(proof, resolver)?;
+            let proof_id = generate_proof(proof, resolver).map_err(in_argument(proof))?;
             id_validator
                 .drop_proof(&proof_id)
                 .map_err(GeneratorError::IdValidationError)?;
@@ -351,11 +444,12 @@ where
             new_proof,
         } => {
             let resource_address =
-                generate_resource_address(resource_address, address_bech32_decoder)?;
+                generate_resource_address(resource_address, address_bech32_decoder)
+                    .map_err(in_argument(resource_address))?;
             let proof_id = id_validator
                 .new_proof(ProofKind::AuthZoneProof)
                 .map_err(GeneratorError::IdValidationError)?;
-            declare_proof(new_proof, resolver, proof_id)?;
+            declare_proof(new_proof, resolver, proof_id).map_err(in_argument(new_proof))?;
 
             InstructionV1::CreateProofFromAuthZone { resource_address }
         }
@@ -365,12 +459,13 @@ where
             new_proof,
         } => {
             let resource_address =
-                generate_resource_address(resource_address, address_bech32_decoder)?;
-            let amount = generate_decimal(amount)?;
+                generate_resource_address(resource_address, address_bech32_decoder)
+                    .map_err(in_argument(resource_address))?;
+            let amount = generate_decimal(amount).map_err(in_argument(amount))?;
             let proof_id = id_validator
                 .new_proof(ProofKind::AuthZoneProof)
                 .map_err(GeneratorError::IdValidationError)?;
-            declare_proof(new_proof, resolver, proof_id)?;
+            declare_proof(new_proof, resolver, proof_id).map_err(in_argument(new_proof))?;
 
             InstructionV1::CreateProofFromAuthZoneOfAmount {
                 amount,
@@ -383,12 +478,13 @@ where
             new_proof,
         } => {
             let resource_address =
-                generate_resource_address(resource_address, address_bech32_decoder)?;
-            let ids = generate_non_fungible_local_ids(ids)?;
+                generate_resource_address(resource_address, address_bech32_decoder)
+                    .map_err(in_argument(resource_address))?;
+            let ids = generate_non_fungible_local_ids(ids).map_err(in_argument(ids))?;
             let proof_id = id_validator
                 .new_proof(ProofKind::AuthZoneProof)
                 .map_err(GeneratorError::IdValidationError)?;
-            declare_proof(new_proof, resolver, proof_id)?;
+            declare_proof(new_proof, resolver, proof_id).map_err(in_argument(new_proof))?;
 
             InstructionV1::CreateProofFromAuthZoneOfNonFungibles {
                 ids,
@@ -400,11 +496,12 @@ where
             new_proof,
         } => {
             let resource_address =
-                generate_resource_address(resource_address, address_bech32_decoder)?;
+                generate_resource_address(resource_address, address_bech32_decoder)
+                    .map_err(in_argument(resource_address))?;
             let proof_id = id_validator
                 .new_proof(ProofKind::AuthZoneProof)
                 .map_err(GeneratorError::IdValidationError)?;
-            declare_proof(new_proof, resolver, proof_id)?;
+            declare_proof(new_proof, resolver, proof_id).map_err(in_argument(new_proof))?;
 
             InstructionV1::CreateProofFromAuthZoneOfAll { resource_address }
         }
@@ -416,16 +513,16 @@ where
         }
 
         ast::Instruction::CreateProofFromBucket { bucket, new_proof } => {
-            let bucket_id = generate_bucket(bucket, resolver)?;
+            let bucket_id = generate_bucket(bucket, resolver).map_err(in_argument(bucket))?;
             let proof_id = id_validator
                 .new_proof(ProofKind::BucketProof(bucket_id.clone()))
                 .map_err(GeneratorError::IdValidationError)?;
-            declare_proof(new_proof, resolver, proof_id)?;
+            declare_proof(new_proof, resolver, proof_id).map_err(in_argument(new_proof))?;
 
             InstructionV1::CreateProofFromBucket { bucket_id }
         }
         ast::Instruction::BurnResource { bucket } => {
-            let bucket_id = generate_bucket(bucket, resolver)?;
+            let bucket_id = generate_bucket(bucket, resolver).map_err(in_argument(bucket))?;
             id_validator
                 .drop_bucket(&bucket_id)
                 .map_err(GeneratorError::IdValidationError)?;
@@ -437,12 +534,12 @@ where
             amount,
             new_proof,
         } => {
-            let bucket_id = generate_bucket(bucket, resolver)?;
-            let amount = generate_decimal(amount)?;
+            let bucket_id = generate_bucket(bucket, resolver).map_err(in_argument(bucket))?;
+            let amount = generate_decimal(amount).map_err(in_argument(amount))?;
             let proof_id = id_validator
                 .new_proof(ProofKind::BucketProof(bucket_id.clone()))
                 .map_err(GeneratorError::IdValidationError)?;
-            declare_proof(new_proof, resolver, proof_id)?;
+            declare_proof(new_proof, resolver, proof_id).map_err(in_argument(new_proof))?;
 
             InstructionV1::CreateProofFromBucketOfAmount { bucket_id, amount }
         }
@@ -451,36 +548,36 @@ where
             ids,
             new_proof,
         } => {
-            let bucket_id = generate_bucket(bucket, resolver)?;
-            let ids = generate_non_fungible_local_ids(ids)?;
+            let bucket_id = generate_bucket(bucket, resolver).map_err(in_argument(bucket))?;
+            let ids = generate_non_fungible_local_ids(ids).map_err(in_argument(ids))?;
             let proof_id = id_validator
                 .new_proof(ProofKind::BucketProof(bucket_id.clone()))
                 .map_err(GeneratorError::IdValidationError)?;
-            declare_proof(new_proof, resolver, proof_id)?;
+            declare_proof(new_proof, resolver, proof_id).map_err(in_argument(new_proof))?;
 
             InstructionV1::CreateProofFromBucketOfNonFungibles { bucket_id, ids }
         }
         ast::Instruction::CreateProofFromBucketOfAll { bucket, new_proof } => {
-            let bucket_id = generate_bucket(bucket, resolver)?;
+            let bucket_id = generate_bucket(bucket, resolver).map_err(in_argument(bucket))?;
             let proof_id = id_validator
                 .new_proof(ProofKind::BucketProof(bucket_id.clone()))
                 .map_err(GeneratorError::IdValidationError)?;
-            declare_proof(new_proof, resolver, proof_id)?;
+            declare_proof(new_proof, resolver, proof_id).map_err(in_argument(new_proof))?;
 
             InstructionV1::CreateProofFromBucketOfAll { bucket_id }
         }
 
         ast::Instruction::CloneProof { proof, new_proof } => {
-            let proof_id = generate_proof(proof, resolver)?;
+            let proof_id = generate_proof(proof, resolver).map_err(in_argument(proof))?;
             let proof_id2 = id_validator
                 .clone_proof(&proof_id)
                 .map_err(GeneratorError::IdValidationError)?;
-            declare_proof(new_proof, resolver, proof_id2)?;
+            declare_proof(new_proof, resolver, proof_id2).map_err(in_argument(new_proof))?;
 
             InstructionV1::CloneProof { proof_id }
         }
         ast::Instruction::DropProof { proof } => {
-            let proof_id = generate_proof(proof, resolver)?;
+            let proof_id = generate_proof(proof, resolver).map_err(in_argument(proof))?;
             id_validator
                 .drop_proof(&proof_id)
                 .map_err(GeneratorError::IdValidationError)?;
@@ -493,13 +590,13 @@ where
             function_name,
             args,
         } => {
-            let package_address = generate_dynamic_package_address(
-                package_address,
-                address_bech32_decoder,
-                resolver,
-            )?;
-            let blueprint_name = generate_string(&blueprint_name)?;
-            let function_name = generate_string(&function_name)?;
+            let package_address =
+                generate_dynamic_package_address(package_address, address_bech32_decoder, resolver)
+                    .map_err(in_argument(package_address))?;
+            let blueprint_name =
+                generate_string(&blueprint_name).map_err(in_argument(blueprint_name))?;
+            let function_name =
+                generate_string(&function_name).map_err(in_argument(function_name))?;
             let args = generate_args(args, resolver, address_bech32_decoder, blobs)?;
             id_validator
                 .process_call_data(&args)
@@ -518,8 +615,9 @@ where
             args,
         } => {
             let address =
-                generate_dynamic_global_address(address, address_bech32_decoder, resolver)?;
-            let method_name = generate_string(&method_name)?;
+                generate_dynamic_global_address(address, address_bech32_decoder, resolver)
+                    .map_err(in_argument(address))?;
+            let method_name = generate_string(&method_name).map_err(in_argument(method_name))?;
             let args = generate_args(args, resolver, address_bech32_decoder, blobs)?;
             id_validator
                 .process_call_data(&args)
@@ -536,8 +634,9 @@ where
             args,
         } => {
             let address =
-                generate_dynamic_global_address(address, address_bech32_decoder, resolver)?;
-            let method_name = generate_string(&method_name)?;
+                generate_dynamic_global_address(address, address_bech32_decoder, resolver)
+                    .map_err(in_argument(address))?;
+            let method_name = generate_string(&method_name).map_err(in_argument(method_name))?;
             let args = generate_args(args, resolver, address_bech32_decoder, blobs)?;
             id_validator
                 .process_call_data(&args)
@@ -554,8 +653,9 @@ where
             args,
         } => {
             let address =
-                generate_dynamic_global_address(address, address_bech32_decoder, resolver)?;
-            let method_name = generate_string(&method_name)?;
+                generate_dynamic_global_address(address, address_bech32_decoder, resolver)
+                    .map_err(in_argument(address))?;
+            let method_name = generate_string(&method_name).map_err(in_argument(method_name))?;
             let args = generate_args(args, resolver, address_bech32_decoder, blobs)?;
             id_validator
                 .process_call_data(&args)
@@ -572,8 +672,9 @@ where
             args,
         } => {
             let address =
-                generate_dynamic_global_address(address, address_bech32_decoder, resolver)?;
-            let method_name = generate_string(&method_name)?;
+                generate_dynamic_global_address(address, address_bech32_decoder, resolver)
+                    .map_err(in_argument(address))?;
+            let method_name = generate_string(&method_name).map_err(in_argument(method_name))?;
             let args = generate_args(args, resolver, address_bech32_decoder, blobs)?;
             id_validator
                 .process_call_data(&args)
@@ -599,33 +700,40 @@ where
             named_address,
         } => {
             let address_reservation_id = id_validator.new_address_reservation();
-            declare_address_reservation(address_reservation, resolver, address_reservation_id)?;
+            declare_address_reservation(address_reservation, resolver, address_reservation_id)
+                .map_err(in_argument(address_reservation))?;
 
             let address_id = id_validator.new_named_address();
-            declare_named_address(named_address, resolver, address_id)?;
+            declare_named_address(named_address, resolver, address_id)
+                .map_err(in_argument(named_address))?;
 
             InstructionV1::AllocateGlobalAddress {
-                package_address: generate_package_address(package_address, address_bech32_decoder)?,
-                blueprint_name: generate_string(&blueprint_name)?,
+                package_address: generate_package_address(package_address, address_bech32_decoder)
+                    .map_err(in_argument(package_address))?,
+                blueprint_name: generate_string(&blueprint_name)
+                    .map_err(in_argument(blueprint_name))?,
             }
         }
 
         /* direct vault method aliases */
         ast::Instruction::RecallFromVault { vault_id, args } => {
             InstructionV1::CallDirectVaultMethod {
-                address: generate_local_address(vault_id, address_bech32_decoder)?,
+                address: generate_local_address(vault_id, address_bech32_decoder)
+                    .map_err(in_argument(vault_id))?,
                 method_name: VAULT_RECALL_IDENT.to_string(),
                 args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
             }
         }
         ast::Instruction::FreezeVault { vault_id, args } => InstructionV1::CallDirectVaultMethod {
-            address: generate_local_address(vault_id, address_bech32_decoder)?,
+            address: generate_local_address(vault_id, address_bech32_decoder)
+                .map_err(in_argument(vault_id))?,
             method_name: VAULT_FREEZE_IDENT.to_string(),
             args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
         },
         ast::Instruction::UnfreezeVault { vault_id, args } => {
             InstructionV1::CallDirectVaultMethod {
-                address: generate_local_address(vault_id, address_bech32_decoder)?,
+                address: generate_local_address(vault_id, address_bech32_decoder)
+                    .map_err(in_argument(vault_id))?,
                 method_name: VAULT_UNFREEZE_IDENT.to_string(),
                 args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
             }
@@ -707,91 +815,83 @@ where
 
         /* call non-main method aliases */
         ast::Instruction::SetMetadata { address, args } => InstructionV1::CallMetadataMethod {
-            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)?,
+            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)
+                .map_err(in_argument(address))?,
             method_name: METADATA_SET_IDENT.to_string(),
             args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
         },
         ast::Instruction::RemoveMetadata { address, args } => InstructionV1::CallMetadataMethod {
-            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)?,
+            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)
+                .map_err(in_argument(address))?,
             method_name: METADATA_REMOVE_IDENT.to_string(),
             args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
         },
         ast::Instruction::LockMetadata { address, args } => InstructionV1::CallMetadataMethod {
-            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)?,
+            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)
+                .map_err(in_argument(address))?,
             method_name: METADATA_LOCK_IDENT.to_string(),
             args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
         },
         ast::Instruction::SetComponentRoyalty { address, args } => {
             InstructionV1::CallRoyaltyMethod {
-                address: generate_dynamic_global_address(
-                    address,
-                    address_bech32_decoder,
-                    resolver,
-                )?,
+                address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)
+                    .map_err(in_argument(address))?,
                 method_name: COMPONENT_ROYALTY_SET_ROYALTY_IDENT.to_string(),
                 args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
             }
         }
         ast::Instruction::LockComponentRoyalty { address, args } => {
             InstructionV1::CallRoyaltyMethod {
-                address: generate_dynamic_global_address(
-                    address,
-                    address_bech32_decoder,
-                    resolver,
-                )?,
+                address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)
+                    .map_err(in_argument(address))?,
                 method_name: COMPONENT_ROYALTY_LOCK_ROYALTY_IDENT.to_string(),
                 args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
             }
         }
         ast::Instruction::ClaimComponentRoyalties { address, args } => {
             InstructionV1::CallRoyaltyMethod {
-                address: generate_dynamic_global_address(
-                    address,
-                    address_bech32_decoder,
-                    resolver,
-                )?,
+                address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)
+                    .map_err(in_argument(address))?,
                 method_name: COMPONENT_ROYALTY_CLAIM_ROYALTIES_IDENT.to_string(),
                 args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
             }
         }
         ast::Instruction::SetOwnerRole { address, args } => InstructionV1::CallAccessRulesMethod {
-            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)?,
+            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)
+                .map_err(in_argument(address))?,
             method_name: ACCESS_RULES_SET_OWNER_ROLE_IDENT.to_string(),
             args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
         },
         ast::Instruction::LockOwnerRole { address, args } => InstructionV1::CallAccessRulesMethod {
-            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)?,
+            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)
+                .map_err(in_argument(address))?,
             method_name: ACCESS_RULES_LOCK_OWNER_ROLE_IDENT.to_string(),
             args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
         },
         ast::Instruction::SetAndLockOwnerRole { address, args } => {
             InstructionV1::CallAccessRulesMethod {
-                address: generate_dynamic_global_address(
-                    address,
-                    address_bech32_decoder,
-                    resolver,
-                )?,
+                address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)
+                    .map_err(in_argument(address))?,
                 method_name: ACCESS_RULES_SET_AND_LOCK_OWNER_ROLE_IDENT.to_string(),
                 args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
             }
         }
         ast::Instruction::SetRole { address, args } => InstructionV1::CallAccessRulesMethod {
-            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)?,
+            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)
+                .map_err(in_argument(address))?,
             method_name: ACCESS_RULES_SET_ROLE_IDENT.to_string(),
             args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
         },
         ast::Instruction::LockRole { address, args } => InstructionV1::CallAccessRulesMethod {
-            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)?,
+            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)
+                .map_err(in_argument(address))?,
             method_name: ACCESS_RULES_LOCK_ROLE_IDENT.to_string(),
             args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
         },
         ast::Instruction::SetAndLockRole { address, args } => {
             InstructionV1::CallAccessRulesMethod {
-                address: generate_dynamic_global_address(
-                    address,
-                    address_bech32_decoder,
-                    resolver,
-                )?,
+                address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)
+                    .map_err(in_argument(address))?,
                 method_name: ACCESS_RULES_SET_AND_LOCK_ROLE_IDENT.to_string(),
                 args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
             }
@@ -799,22 +899,26 @@ where
 
         /* call main method aliases */
         ast::Instruction::MintFungible { address, args } => InstructionV1::CallMethod {
-            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)?,
+            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)
+                .map_err(in_argument(address))?,
             method_name: FUNGIBLE_RESOURCE_MANAGER_MINT_IDENT.to_string(),
             args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
         },
         ast::Instruction::MintNonFungible { address, args } => InstructionV1::CallMethod {
-            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)?,
+            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)
+                .map_err(in_argument(address))?,
             method_name: NON_FUNGIBLE_RESOURCE_MANAGER_MINT_IDENT.to_string(),
             args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
         },
         ast::Instruction::MintRuidNonFungible { address, args } => InstructionV1::CallMethod {
-            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)?,
+            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)
+                .map_err(in_argument(address))?,
             method_name: NON_FUNGIBLE_RESOURCE_MANAGER_MINT_RUID_IDENT.to_string(),
             args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
         },
         ast::Instruction::ClaimPackageRoyalties { address, args } => InstructionV1::CallMethod {
-            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)?,
+            address: generate_dynamic_global_address(address, address_bech32_decoder, resolver)
+                .map_err(in_argument(address))?,
             method_name: PACKAGE_CLAIM_ROYALTIES_IDENT.to_string(),
             args: generate_args(args, resolver, address_bech32_decoder, blobs)?,
         },
@@ -836,24 +940,21 @@ macro_rules! invalid_type {
     };
 }
 
-fn generate_args<B>(
-    values: &Vec<ast::Value>,
+fn generate_args<'a, B>(
+    values: &'a Vec<ast::Value>,
     resolver: &mut NameResolver,
     address_bech32_decoder: &AddressBech32Decoder,
     blobs: &B,
-) -> Result<ManifestValue, GeneratorError>
+) -> Result<ManifestValue, LocatedGeneratorError<'a>>
 where
     B: IsBlobProvider,
 {
     let mut fields = Vec::new();
     for v in values {
-        fields.push(generate_value(
-            v,
-            None,
-            resolver,
-            address_bech32_decoder,
-            blobs,
-        )?);
+        fields.push(
+            generate_value(v, None, resolver, address_bech32_decoder, blobs)
+                .map_err(in_argument(v))?,
+        );
     }
 
     Ok(ManifestValue::Tuple { fields })
@@ -2193,9 +2294,7 @@ mod tests {
             let manifest = generate_manifest_input_with_given_depth!($depth);
             let address_bech32_decoder = AddressBech32Decoder::new(&NetworkDefinition::simulator());
 
-            let tokens = tokenize(&manifest)
-                .map_err(CompileError::LexerError)
-                .unwrap();
+            let tokens = tokenize(&manifest).unwrap();
 
             let instructions = parser::Parser::new(tokens, $depth)
                 .parse_manifest()
@@ -2217,15 +2316,18 @@ mod tests {
             &NetworkDefinition::simulator(),
             BlobProvider::default(),
         );
-        let expected = CompileError::ParserError(ParserError::MaxDepthExceeded(PARSER_MAX_DEPTH));
+        let expected = ParserError::MaxDepthExceeded(PARSER_MAX_DEPTH);
 
         match result {
             Ok(_) => {
                 panic!("Expected {:?} but no error is thrown", expected);
             }
-            Err(e) => {
+            Err(CompileError::ParserError(e, _)) => {
                 assert_eq!(e, expected);
             }
+            Err(e) => {
+                panic!("Expected {:?} but {:?} is thrown", expected, e);
+            }
         }
     }
 
@@ -56,6 +56,13 @@ pub struct Token {
     pub span: Span,
 }
 
+/// A comment, from its `#` to the end of its line (which is not included).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comment {
+    pub text: String,
+    pub span: Span,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum LexerError {
     UnexpectedEof,
@@ -65,15 +72,46 @@ pub enum LexerError {
     UnknownIdentifier(String, Position),
 }
 
+impl LexerError {
+    /// The span of the given text at which the error was detected.
+    ///
+    /// Lexer errors only record a single position, so the returned span is empty (and, for
+    /// [`LexerError::UnexpectedEof`], at the end of the text).
+    pub fn span(&self, text: &str) -> Span {
+        let position = match self {
+            LexerError::UnexpectedEof => {
+                let mut lexer = Lexer::new(text);
+                while lexer.advance().is_ok() {}
+                lexer.current
+            }
+            LexerError::UnexpectedChar(_, position)
+            | LexerError::InvalidInteger(_, position)
+            | LexerError::InvalidUnicode(_, position)
+            | LexerError::UnknownIdentifier(_, position) => *position,
+        };
+        Span {
+            start: position,
+            end: position,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Lexer {
     /// The input text chars
     text: Vec<char>,
     /// The current position in the text
     current: Position,
+    /// The comments skipped so far
+    comments: Vec<Comment>,
 }
 
 pub fn tokenize(s: &str) -> Result<Vec<Token>, LexerError> {
+    tokenize_with_comments(s).map(|(tokens, _)| tokens)
+}
+
+/// Same as [`tokenize`], but also returns the comments (which are otherwise skipped).
+pub fn tokenize_with_comments(s: &str) -> Result<(Vec<Token>, Vec<Comment>), LexerError> {
     let mut lexer = Lexer::new(s);
     let mut tokens = Vec::new();
     loop {
@@ -83,7 +121,7 @@ pub fn tokenize(s: &str) -> Result<Vec<Token>, LexerError> {
             break;
         }
     }
-    Ok(tokens)
+    Ok((tokens, lexer.comments))
 }
 
 impl Lexer {
@@ -95,6 +133,7 @@ impl Lexer {
                 line_number: 1,
                 line_char_index: 0,
             },
+            comments: Vec::new(),
         }
     }
 
@@ -129,14 +168,9 @@ impl Lexer {
 
     pub fn next_token(&mut self) -> Result<Option<Token>, LexerError> {
         // skip comment and whitespace
-        let mut in_comment = false;
         while !self.is_eof() {
-            if in_comment {
-                if self.advance()? == '\n' {
-                    in_comment = false;
-                }
-            } else if self.peek()? == '#' {
-                in_comment = true;
+            if self.peek()? == '#' {
+                self.skip_comment()?;
             } else if Self::is_whitespace(self.peek()?) {
                 self.advance()?;
             } else {
@@ -165,6 +199,25 @@ impl Lexer {
         .map(Option::from)
     }
 
+    fn skip_comment(&mut self) -> Result<(), LexerError> {
+        let start = self.current;
+        let mut text = String::new();
+        while !self.is_eof() && self.peek()? != '\n' {
+            text.push(self.advance()?);
+        }
+        if text.ends_with('\r') {
+            text.pop();
+        }
+        self.comments.push(Comment {
+            text,
+            span: Span {
+                start,
+                end: self.current,
+            },
+        });
+        Ok(())
+    }
+
     // TODO: consider using DFA
     fn tokenize_number(&mut self) -> Result<Token, LexerError> {
         let start = self.current;
@@ -442,6 +495,21 @@ mod tests {
             "# multiple\n# line\nCALL_FUNCTION",
             vec![TokenKind::Ident("CALL_FUNCTION".to_string()),]
         );
+
+        let (tokens, comments) =
+            tokenize_with_comments("# first\r\nCLEAR_AUTH_ZONE; # second \"#\"\n#").unwrap();
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(
+            comments
+                .iter()
+                .map(|comment| (
+                    comment.text.as_str(),
+                    comment.span.start.line_number,
+                    comment.span.start.line_char_index
+                ))
+                .collect::<Vec<_>>(),
+            vec![("# first", 1, 0), ("# second \"#\"", 2, 17), ("#", 3, 0)]
+        );
     }
 
     #[test]
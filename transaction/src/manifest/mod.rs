@@ -2,15 +2,28 @@ pub mod ast;
 pub mod blob_provider;
 pub mod compiler;
 pub mod decompiler;
+pub mod diagnostics;
 #[cfg(feature = "std")]
 pub mod dumper;
 pub mod e2e;
 pub mod enums;
+pub mod formatter;
 pub mod generator;
 pub mod lexer;
 pub mod parser;
+pub mod static_analysis;
+pub mod template;
 
 pub use blob_provider::*;
-pub use compiler::{compile, CompileError};
-pub use decompiler::{decompile, DecompileError};
+pub use compiler::{compile, compile_with_source_map, CompileError};
+pub use decompiler::{decompile, decompile_with_known_naming, DecompileError};
+pub use diagnostics::*;
 pub use enums::*;
+pub use formatter::{format_manifest, FormatError};
+pub use static_analysis::*;
+#[cfg(feature = "std")]
+pub use template::FileIncludeProvider;
+pub use template::{
+    is_template, resolve_template, IncludeProvider, IsIncludeProvider, TemplateContext,
+    TemplateError,
+};
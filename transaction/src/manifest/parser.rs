@@ -1,6 +1,6 @@
 use crate::manifest::ast::{Instruction, Value, ValueKind};
 use crate::manifest::enums::KNOWN_ENUM_DISCRIMINATORS;
-use crate::manifest::lexer::{Token, TokenKind};
+use crate::manifest::lexer::{Position, Span, Token, TokenKind};
 use radix_engine_interface::data::manifest::MANIFEST_SBOR_V1_MAX_DEPTH;
 
 // For values greater than below it is not possible to encode compiled manifest due to
@@ -386,6 +386,16 @@ pub struct Parser {
     current: usize,
     max_depth: usize,
     stack_depth: usize,
+    /// The spans of the arguments of the instruction being parsed.
+    argument_spans: Vec<Span>,
+}
+
+/// The spans of a parsed instruction: of the whole instruction (from its name to its terminating
+/// semicolon), and of each of its arguments, in the order of [`Instruction::arguments`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstructionSpans {
+    pub instruction: Span,
+    pub arguments: Vec<Span>,
 }
 
 #[macro_export]
@@ -416,6 +426,7 @@ impl Parser {
             current: 0,
             max_depth,
             stack_depth: 0,
+            argument_spans: Vec::new(),
         }
     }
 
@@ -452,23 +463,80 @@ impl Parser {
     }
 
     pub fn parse_manifest(&mut self) -> Result<Vec<Instruction>, ParserError> {
-        let mut instructions = Vec::<Instruction>::new();
+        Ok(self
+            .parse_manifest_with_spans()?
+            .into_iter()
+            .map(|(instruction, _)| instruction)
+            .collect())
+    }
+
+    /// Parses the manifest, together with the spans of each instruction and of its arguments.
+    pub fn parse_manifest_with_spans(
+        &mut self,
+    ) -> Result<Vec<(Instruction, InstructionSpans)>, ParserError> {
+        let mut instructions = Vec::<(Instruction, InstructionSpans)>::new();
 
         while !self.is_eof() {
-            instructions.push(self.parse_instruction()?);
+            let start = self.peek()?.span.start;
+            self.argument_spans.clear();
+            let instruction = self.parse_instruction()?;
+            let end = self.tokens[self.current - 1].span.end;
+            instructions.push((
+                instruction,
+                InstructionSpans {
+                    instruction: Span { start, end },
+                    arguments: self.argument_spans.drain(..).collect(),
+                },
+            ));
         }
 
         Ok(instructions)
     }
 
+    /// The span of the source at which the given error (returned by this parser) was detected:
+    /// the unexpected token, the end of the last token for an unexpected end of file, or else
+    /// the last token consumed before the error was detected.
+    pub fn error_span(&self, error: &ParserError) -> Span {
+        let text_start = Position {
+            full_index: 0,
+            line_number: 1,
+            line_char_index: 0,
+        };
+        let last_span = self.tokens[..self.current]
+            .last()
+            .or(self.tokens.first())
+            .map(|token| token.span)
+            .unwrap_or(Span {
+                start: text_start,
+                end: text_start,
+            });
+        match error {
+            ParserError::UnexpectedToken { actual, .. } => actual.span,
+            ParserError::UnexpectedEof => Span {
+                start: last_span.end,
+                end: last_span.end,
+            },
+            _ => last_span,
+        }
+    }
+
     fn parse_values_till_semicolon(&mut self) -> Result<Vec<Value>, ParserError> {
         let mut values = Vec::new();
         while self.peek()?.kind != TokenKind::Semicolon {
-            values.push(self.parse_value()?);
+            values.push(self.parse_argument()?);
         }
         Ok(values)
     }
 
+    /// Parses a (top-level) argument of an instruction, recording its span.
+    fn parse_argument(&mut self) -> Result<Value, ParserError> {
+        let start = self.peek()?.span.start;
+        let value = self.parse_value()?;
+        let end = self.tokens[self.current - 1].span.end;
+        self.argument_spans.push(Span { start, end });
+        Ok(value)
+    }
+
     pub fn parse_instruction(&mut self) -> Result<Instruction, ParserError> {
         let token = self.advance()?;
         let instruction_ident = match &token.kind {
@@ -487,147 +555,147 @@ impl Parser {
         };
         let instruction = match instruction_ident {
             InstructionIdent::TakeFromWorktop => Instruction::TakeFromWorktop {
-                resource_address: self.parse_value()?,
-                amount: self.parse_value()?,
-                new_bucket: self.parse_value()?,
+                resource_address: self.parse_argument()?,
+                amount: self.parse_argument()?,
+                new_bucket: self.parse_argument()?,
             },
             InstructionIdent::TakeNonFungiblesFromWorktop => {
                 Instruction::TakeNonFungiblesFromWorktop {
-                    resource_address: self.parse_value()?,
-                    ids: self.parse_value()?,
-                    new_bucket: self.parse_value()?,
+                    resource_address: self.parse_argument()?,
+                    ids: self.parse_argument()?,
+                    new_bucket: self.parse_argument()?,
                 }
             }
             InstructionIdent::TakeAllFromWorktop => Instruction::TakeAllFromWorktop {
-                resource_address: self.parse_value()?,
-                new_bucket: self.parse_value()?,
+                resource_address: self.parse_argument()?,
+                new_bucket: self.parse_argument()?,
             },
             InstructionIdent::ReturnToWorktop => Instruction::ReturnToWorktop {
-                bucket: self.parse_value()?,
+                bucket: self.parse_argument()?,
             },
             InstructionIdent::AssertWorktopContains => Instruction::AssertWorktopContains {
-                resource_address: self.parse_value()?,
-                amount: self.parse_value()?,
+                resource_address: self.parse_argument()?,
+                amount: self.parse_argument()?,
             },
             InstructionIdent::AssertWorktopContainsNonFungibles => {
                 Instruction::AssertWorktopContainsNonFungibles {
-                    resource_address: self.parse_value()?,
-                    ids: self.parse_value()?,
+                    resource_address: self.parse_argument()?,
+                    ids: self.parse_argument()?,
                 }
             }
             InstructionIdent::PopFromAuthZone => Instruction::PopFromAuthZone {
-                new_proof: self.parse_value()?,
+                new_proof: self.parse_argument()?,
             },
             InstructionIdent::PushToAuthZone => Instruction::PushToAuthZone {
-                proof: self.parse_value()?,
+                proof: self.parse_argument()?,
             },
             InstructionIdent::ClearAuthZone => Instruction::ClearAuthZone,
             InstructionIdent::CreateProofFromAuthZone => Instruction::CreateProofFromAuthZone {
-                resource_address: self.parse_value()?,
-                new_proof: self.parse_value()?,
+                resource_address: self.parse_argument()?,
+                new_proof: self.parse_argument()?,
             },
             InstructionIdent::CreateProofFromAuthZoneOfAmount => {
                 Instruction::CreateProofFromAuthZoneOfAmount {
-                    resource_address: self.parse_value()?,
-                    amount: self.parse_value()?,
-                    new_proof: self.parse_value()?,
+                    resource_address: self.parse_argument()?,
+                    amount: self.parse_argument()?,
+                    new_proof: self.parse_argument()?,
                 }
             }
             InstructionIdent::CreateProofFromAuthZoneOfNonFungibles => {
                 Instruction::CreateProofFromAuthZoneOfNonFungibles {
-                    resource_address: self.parse_value()?,
-                    ids: self.parse_value()?,
-                    new_proof: self.parse_value()?,
+                    resource_address: self.parse_argument()?,
+                    ids: self.parse_argument()?,
+                    new_proof: self.parse_argument()?,
                 }
             }
             InstructionIdent::CreateProofFromAuthZoneOfAll => {
                 Instruction::CreateProofFromAuthZoneOfAll {
-                    resource_address: self.parse_value()?,
-                    new_proof: self.parse_value()?,
+                    resource_address: self.parse_argument()?,
+                    new_proof: self.parse_argument()?,
                 }
             }
             InstructionIdent::ClearSignatureProofs => Instruction::ClearSignatureProofs,
 
             InstructionIdent::CreateProofFromBucket => Instruction::CreateProofFromBucket {
-                bucket: self.parse_value()?,
-                new_proof: self.parse_value()?,
+                bucket: self.parse_argument()?,
+                new_proof: self.parse_argument()?,
             },
             InstructionIdent::CreateProofFromBucketOfAmount => {
                 Instruction::CreateProofFromBucketOfAmount {
-                    bucket: self.parse_value()?,
-                    amount: self.parse_value()?,
-                    new_proof: self.parse_value()?,
+                    bucket: self.parse_argument()?,
+                    amount: self.parse_argument()?,
+                    new_proof: self.parse_argument()?,
                 }
             }
             InstructionIdent::CreateProofFromBucketOfNonFungibles => {
                 Instruction::CreateProofFromBucketOfNonFungibles {
-                    bucket: self.parse_value()?,
-                    ids: self.parse_value()?,
-                    new_proof: self.parse_value()?,
+                    bucket: self.parse_argument()?,
+                    ids: self.parse_argument()?,
+                    new_proof: self.parse_argument()?,
                 }
             }
             InstructionIdent::CreateProofFromBucketOfAll => {
                 Instruction::CreateProofFromBucketOfAll {
-                    bucket: self.parse_value()?,
-                    new_proof: self.parse_value()?,
+                    bucket: self.parse_argument()?,
+                    new_proof: self.parse_argument()?,
                 }
             }
             InstructionIdent::BurnResource => Instruction::BurnResource {
-                bucket: self.parse_value()?,
+                bucket: self.parse_argument()?,
             },
 
             InstructionIdent::CloneProof => Instruction::CloneProof {
-                proof: self.parse_value()?,
-                new_proof: self.parse_value()?,
+                proof: self.parse_argument()?,
+                new_proof: self.parse_argument()?,
             },
             InstructionIdent::DropProof => Instruction::DropProof {
-                proof: self.parse_value()?,
+                proof: self.parse_argument()?,
             },
             InstructionIdent::CallFunction => Instruction::CallFunction {
-                package_address: self.parse_value()?,
-                blueprint_name: self.parse_value()?,
-                function_name: self.parse_value()?,
+                package_address: self.parse_argument()?,
+                blueprint_name: self.parse_argument()?,
+                function_name: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::CallMethod => Instruction::CallMethod {
-                address: self.parse_value()?,
-                method_name: self.parse_value()?,
+                address: self.parse_argument()?,
+                method_name: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::CallRoyaltyMethod => Instruction::CallRoyaltyMethod {
-                address: self.parse_value()?,
-                method_name: self.parse_value()?,
+                address: self.parse_argument()?,
+                method_name: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::CallMetadataMethod => Instruction::CallMetadataMethod {
-                address: self.parse_value()?,
-                method_name: self.parse_value()?,
+                address: self.parse_argument()?,
+                method_name: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::CallAccessRulesMethod => Instruction::CallAccessRulesMethod {
-                address: self.parse_value()?,
-                method_name: self.parse_value()?,
+                address: self.parse_argument()?,
+                method_name: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::DropAllProofs => Instruction::DropAllProofs,
             InstructionIdent::AllocateGlobalAddress => Instruction::AllocateGlobalAddress {
-                package_address: self.parse_value()?,
-                blueprint_name: self.parse_value()?,
-                address_reservation: self.parse_value()?,
-                named_address: self.parse_value()?,
+                package_address: self.parse_argument()?,
+                blueprint_name: self.parse_argument()?,
+                address_reservation: self.parse_argument()?,
+                named_address: self.parse_argument()?,
             },
 
             /* Call direct vault method aliases */
             InstructionIdent::RecallFromVault => Instruction::RecallFromVault {
-                vault_id: self.parse_value()?,
+                vault_id: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::FreezeVault => Instruction::FreezeVault {
-                vault_id: self.parse_value()?,
+                vault_id: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::UnfreezeVault => Instruction::UnfreezeVault {
-                vault_id: self.parse_value()?,
+                vault_id: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
 
@@ -672,69 +740,69 @@ impl Parser {
 
             /* Call non-main method aliases */
             InstructionIdent::SetMetadata => Instruction::SetMetadata {
-                address: self.parse_value()?,
+                address: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::RemoveMetadata => Instruction::RemoveMetadata {
-                address: self.parse_value()?,
+                address: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::LockMetadata => Instruction::LockMetadata {
-                address: self.parse_value()?,
+                address: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::SetComponentRoyalty => Instruction::SetComponentRoyalty {
-                address: self.parse_value()?,
+                address: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::LockComponentRoyalty => Instruction::LockComponentRoyalty {
-                address: self.parse_value()?,
+                address: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::ClaimComponentRoyalties => Instruction::ClaimComponentRoyalties {
-                address: self.parse_value()?,
+                address: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::SetOwnerRole => Instruction::SetOwnerRole {
-                address: self.parse_value()?,
+                address: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::LockOwnerRole => Instruction::LockOwnerRole {
-                address: self.parse_value()?,
+                address: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::SetAndLockOwnerRole => Instruction::SetAndLockOwnerRole {
-                address: self.parse_value()?,
+                address: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::SetRole => Instruction::SetRole {
-                address: self.parse_value()?,
+                address: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::LockRole => Instruction::LockRole {
-                address: self.parse_value()?,
+                address: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::SetAndLockRole => Instruction::SetAndLockRole {
-                address: self.parse_value()?,
+                address: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
 
             /* Call main method aliases */
             InstructionIdent::MintFungible => Instruction::MintFungible {
-                address: self.parse_value()?,
+                address: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::MintNonFungible => Instruction::MintNonFungible {
-                address: self.parse_value()?,
+                address: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::MintRuidNonFungible => Instruction::MintRuidNonFungible {
-                address: self.parse_value()?,
+                address: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::ClaimPackageRoyalties => Instruction::ClaimPackageRoyalties {
-                address: self.parse_value()?,
+                address: self.parse_argument()?,
                 args: self.parse_values_till_semicolon()?,
             },
             InstructionIdent::CreateValidator => Instruction::CreateValidator {
@@ -932,7 +1000,7 @@ impl Parser {
         }
     }
 
-    fn parse_type(&mut self) -> Result<ValueKind, ParserError> {
+    pub fn parse_type(&mut self) -> Result<ValueKind, ParserError> {
         let token = self.advance()?;
         let the_type = match &token.kind {
             TokenKind::Ident(ident_str) => {
@@ -1191,6 +1259,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_argument_spans() {
+        let source = "TAKE_FROM_WORKTOP\n    Address(\"a\") Decimal(\"1\")\n    Bucket(\"b\");";
+        let mut parser = Parser::new(tokenize(source).unwrap(), PARSER_MAX_DEPTH);
+        let instructions = parser.parse_manifest_with_spans().unwrap();
+
+        assert_eq!(instructions.len(), 1);
+        let (instruction, spans) = &instructions[0];
+        assert_eq!(spans.arguments.len(), instruction.arguments().len());
+        let argument_sources: Vec<&str> = spans
+            .arguments
+            .iter()
+            .map(|span| &source[span.start.full_index..span.end.full_index])
+            .collect();
+        assert_eq!(
+            argument_sources,
+            vec!["Address(\"a\")", "Decimal(\"1\")", "Bucket(\"b\")"]
+        );
+        assert_eq!(spans.arguments[2].start.line_number, 3);
+    }
+
     // Instruction parsing tests have been removed as they're largely outdated (inconsistent with the data model),
     // which may lead developers to invalid syntax.
     //
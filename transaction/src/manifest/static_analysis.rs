@@ -0,0 +1,924 @@
+use crate::data::{transform, TransformHandler};
+use crate::internal_prelude::*;
+use radix_engine_interface::blueprints::account::*;
+use radix_engine_interface::blueprints::consensus_manager::{
+    VALIDATOR_CLAIM_XRD_IDENT, VALIDATOR_STAKE_IDENT, VALIDATOR_UNSTAKE_IDENT,
+};
+use radix_engine_interface::blueprints::pool::{
+    MULTI_RESOURCE_POOL_CONTRIBUTE_IDENT, MULTI_RESOURCE_POOL_REDEEM_IDENT,
+    ONE_RESOURCE_POOL_CONTRIBUTE_IDENT, ONE_RESOURCE_POOL_REDEEM_IDENT,
+    TWO_RESOURCE_POOL_CONTRIBUTE_IDENT, TWO_RESOURCE_POOL_REDEEM_IDENT,
+};
+use radix_engine_interface::data::manifest::model::*;
+use radix_engine_interface::data::manifest::{from_manifest_value, ManifestToRustValueError};
+use radix_engine_interface::data::scrypto::model::{Own, Reference};
+use sbor::rust::convert::Infallible;
+
+/// The kind of a manifest, as far as it can be told from its instructions alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestClass {
+    /// Only moves resources between accounts (through the worktop).
+    Transfer,
+    /// Stakes XRD to validators.
+    ValidatorStake,
+    /// Unstakes from validators.
+    ValidatorUnstake,
+    /// Claims unstaked XRD from validators.
+    ValidatorClaim,
+    /// Contributes resources to pools.
+    PoolContribution,
+    /// Redeems pool units from pools.
+    PoolRedemption,
+    /// Anything else (e.g. calls to blueprints not known to the analysis).
+    General,
+}
+
+/// An amount of a resource, as far as it can be known before execution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceAmount {
+    /// A known amount (of a fungible resource, or of non-fungibles with unknown ids).
+    Amount(Decimal),
+    /// Known non-fungibles.
+    NonFungibles(BTreeSet<NonFungibleLocalId>),
+    /// An amount which depends on the execution (e.g. everything returned by a component).
+    Unknown,
+}
+
+impl ResourceAmount {
+    fn is_empty(&self) -> bool {
+        match self {
+            ResourceAmount::Amount(amount) => amount.is_zero(),
+            ResourceAmount::NonFungibles(ids) => ids.is_empty(),
+            ResourceAmount::Unknown => false,
+        }
+    }
+
+    fn add(self, other: ResourceAmount) -> ResourceAmount {
+        match (self, other) {
+            (ResourceAmount::Amount(amount), ResourceAmount::Amount(other)) => {
+                // Amounts beyond the range of a decimal could not be moved anyway, and are only
+                // possible in manifests which would fail
+                let sum = BnumI384::from(amount.0) + BnumI384::from(other.0);
+                match BnumI256::try_from(sum) {
+                    Ok(sum) => ResourceAmount::Amount(Decimal(sum)),
+                    Err(_) => ResourceAmount::Unknown,
+                }
+            }
+            (ResourceAmount::NonFungibles(mut ids), ResourceAmount::NonFungibles(other)) => {
+                ids.extend(other);
+                ResourceAmount::NonFungibles(ids)
+            }
+            _ => ResourceAmount::Unknown,
+        }
+    }
+}
+
+/// Resources withdrawn from, or deposited into, an account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountResourceMovement {
+    pub account: ComponentAddress,
+    /// The resource, or [`Option::None`] for resources which cannot be known before execution
+    /// (e.g. those returned by a component and deposited with the rest of the worktop).
+    pub resource_address: Option<ResourceAddress>,
+    pub amount: ResourceAmount,
+}
+
+/// A problem found in a manifest which does not prevent the analysis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManifestIssue {
+    /// A bucket is never consumed (so the transaction would fail).
+    UnusedBucket(ManifestBucket),
+    /// A proof is never dropped nor passed to a call (it is dropped at the end of the
+    /// transaction, but most likely by mistake).
+    DanglingProof(ManifestProof),
+    /// An instruction takes more of a resource than the worktop is known to contain (so the
+    /// transaction would fail).
+    InsufficientWorktopBalance {
+        instruction_index: usize,
+        resource_address: ResourceAddress,
+    },
+    /// Resources are known to remain on the worktop at the end (so the transaction would fail).
+    ResourceLeftOnWorktop(ResourceAddress),
+    /// A bucket is consumed while a proof created from it still exists (so the transaction would
+    /// fail, since the proof locks the bucket's resources).
+    LockedBucketConsumed {
+        instruction_index: usize,
+        bucket_id: ManifestBucket,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManifestAnalysisError {
+    IdValidationError {
+        instruction_index: usize,
+        error: ManifestIdValidationError,
+    },
+    InvalidAccountCallArguments {
+        instruction_index: usize,
+        method_name: String,
+        error: ManifestToRustValueError,
+    },
+}
+
+/// The result of the static analysis of a manifest, e.g. to be summarized on the review screen of
+/// a wallet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestAnalysis {
+    pub class: ManifestClass,
+    /// The accounts whose owner role must be satisfied (e.g. by a signature of the owner of a
+    /// virtual account), because the manifest withdraws from them, locks fees, creates proofs, etc.
+    pub accounts_requiring_auth: IndexSet<ComponentAddress>,
+    pub accounts_withdrawn_from: IndexSet<ComponentAddress>,
+    pub accounts_deposited_into: IndexSet<ComponentAddress>,
+    pub withdrawals: Vec<AccountResourceMovement>,
+    pub deposits: Vec<AccountResourceMovement>,
+    pub issues: Vec<ManifestIssue>,
+}
+
+/// Statically analyzes the instructions of a manifest, simulating the contents of the worktop
+/// and of the buckets as far as they can be known without executing the manifest.
+///
+/// Calls to accounts are understood (withdrawals, deposits, fee locking and proofs), as are the
+/// calls to validators and pools used for classification; any other call is assumed to take
+/// the resources passed to it and to return unknown resources to the worktop.
+pub fn analyze_manifest(
+    instructions: &[InstructionV1],
+) -> Result<ManifestAnalysis, ManifestAnalysisError> {
+    let mut analyzer = ManifestAnalyzer::default();
+    for (instruction_index, instruction) in instructions.iter().enumerate() {
+        analyzer.analyze_instruction(instruction_index, instruction)?;
+    }
+    Ok(analyzer.finish())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum CallKind {
+    ValidatorStake,
+    ValidatorUnstake,
+    ValidatorClaim,
+    PoolContribution,
+    PoolRedemption,
+    Other,
+}
+
+#[derive(Default)]
+struct Worktop {
+    known_resources: IndexMap<ResourceAddress, ResourceAmount>,
+    /// Whether the worktop may contain resources other than the known ones (or more of them).
+    has_unknown_resources: bool,
+}
+
+impl Worktop {
+    fn put(&mut self, resource_address: ResourceAddress, amount: ResourceAmount) {
+        let amount = match self.known_resources.remove(&resource_address) {
+            Some(existing) => existing.add(amount),
+            None => amount,
+        };
+        self.known_resources.insert(resource_address, amount);
+    }
+
+    /// Takes the given amount, or returns [`Option::None`] (leaving the worktop unchanged) if it is
+    /// known to be insufficient.
+    fn take(&mut self, resource_address: ResourceAddress, amount: &ResourceAmount) -> Option<()> {
+        // The balance is checked before anything is removed, so that an insufficient take does
+        // not lose track of what is known to be on the worktop.
+        let remaining = match (self.known_resources.get(&resource_address), amount) {
+            (Some(ResourceAmount::Amount(existing)), ResourceAmount::Amount(amount)) => {
+                if existing >= amount {
+                    ResourceAmount::Amount(*existing - *amount)
+                } else if self.has_unknown_resources {
+                    // The known balance is used up, and the rest comes from the unknown resources
+                    ResourceAmount::Unknown
+                } else {
+                    return None;
+                }
+            }
+            (Some(ResourceAmount::NonFungibles(existing)), ResourceAmount::NonFungibles(ids)) => {
+                if !self.has_unknown_resources && !ids.is_subset(existing) {
+                    return None;
+                }
+                ResourceAmount::NonFungibles(existing.difference(ids).cloned().collect())
+            }
+            (Some(_), _) => ResourceAmount::Unknown,
+            (None, _) => {
+                if self.has_unknown_resources {
+                    return Some(());
+                }
+                return None;
+            }
+        };
+        if remaining.is_empty() {
+            self.known_resources.remove(&resource_address);
+        } else {
+            self.known_resources.insert(resource_address, remaining);
+        }
+        Some(())
+    }
+
+    fn take_all(&mut self, resource_address: ResourceAddress) -> ResourceAmount {
+        match self.known_resources.remove(&resource_address) {
+            _ if self.has_unknown_resources => ResourceAmount::Unknown,
+            Some(amount) => amount,
+            None => ResourceAmount::Amount(Decimal::ZERO),
+        }
+    }
+
+    fn drain(&mut self) -> (Vec<(ResourceAddress, ResourceAmount)>, bool) {
+        let resources = self.known_resources.drain(..).collect();
+        let has_unknown_resources = self.has_unknown_resources;
+        self.has_unknown_resources = false;
+        (resources, has_unknown_resources)
+    }
+}
+
+/// The buckets, proofs and expressions passed to a call.
+#[derive(Default)]
+struct CallData {
+    buckets: Vec<ManifestBucket>,
+    proofs: Vec<ManifestProof>,
+    takes_entire_worktop: bool,
+}
+
+impl TransformHandler<Infallible> for CallData {
+    fn replace_bucket(&mut self, b: ManifestBucket) -> Result<Own, Infallible> {
+        self.buckets.push(b);
+        Ok(Own(NodeId([0u8; NodeId::LENGTH])))
+    }
+
+    fn replace_proof(&mut self, p: ManifestProof) -> Result<Own, Infallible> {
+        self.proofs.push(p);
+        Ok(Own(NodeId([0u8; NodeId::LENGTH])))
+    }
+
+    fn replace_address_reservation(
+        &mut self,
+        _r: ManifestAddressReservation,
+    ) -> Result<Own, Infallible> {
+        Ok(Own(NodeId([0u8; NodeId::LENGTH])))
+    }
+
+    fn replace_named_address(&mut self, _a: u32) -> Result<Reference, Infallible> {
+        Ok(Reference(NodeId([0u8; NodeId::LENGTH])))
+    }
+
+    fn replace_expression(&mut self, e: ManifestExpression) -> Result<Vec<Own>, Infallible> {
+        if e == ManifestExpression::EntireWorktop {
+            self.takes_entire_worktop = true;
+        }
+        Ok(Vec::new())
+    }
+
+    fn replace_blob(&mut self, _b: ManifestBlobRef) -> Result<Vec<u8>, Infallible> {
+        Ok(Vec::new())
+    }
+}
+
+#[derive(Default)]
+struct ManifestAnalyzer {
+    id_allocator: ManifestIdAllocator,
+    worktop: Worktop,
+    buckets: IndexMap<ManifestBucket, (ResourceAddress, ResourceAmount)>,
+    /// The named proofs, with the bucket each one was created from (and thus locks).
+    proofs: IndexMap<ManifestProof, Option<ManifestBucket>>,
+    /// The buckets locked by the proofs pushed to the auth zone.
+    auth_zone_bucket_locks: Vec<ManifestBucket>,
+    call_kinds: IndexSet<CallKind>,
+    accounts_requiring_auth: IndexSet<ComponentAddress>,
+    withdrawals: Vec<AccountResourceMovement>,
+    deposits: Vec<AccountResourceMovement>,
+    issues: Vec<ManifestIssue>,
+}
+
+impl ManifestAnalyzer {
+    fn analyze_instruction(
+        &mut self,
+        instruction_index: usize,
+        instruction: &InstructionV1,
+    ) -> Result<(), ManifestAnalysisError> {
+        let id_error = |error| ManifestAnalysisError::IdValidationError {
+            instruction_index,
+            error,
+        };
+        match instruction {
+            InstructionV1::TakeAllFromWorktop { resource_address } => {
+                let amount = self.worktop.take_all(*resource_address);
+                self.new_bucket(*resource_address, amount);
+            }
+            InstructionV1::TakeFromWorktop {
+                resource_address,
+                amount,
+            } => {
+                self.take_from_worktop(
+                    instruction_index,
+                    *resource_address,
+                    ResourceAmount::Amount(*amount),
+                );
+            }
+            InstructionV1::TakeNonFungiblesFromWorktop {
+                resource_address,
+                ids,
+            } => {
+                self.take_from_worktop(
+                    instruction_index,
+                    *resource_address,
+                    ResourceAmount::NonFungibles(ids.iter().cloned().collect()),
+                );
+            }
+            InstructionV1::ReturnToWorktop { bucket_id } => {
+                let (resource_address, amount) = self
+                    .consume_bucket(instruction_index, bucket_id)
+                    .map_err(id_error)?;
+                self.worktop.put(resource_address, amount);
+            }
+            InstructionV1::PopFromAuthZone
+            | InstructionV1::CreateProofFromAuthZone { .. }
+            | InstructionV1::CreateProofFromAuthZoneOfAmount { .. }
+            | InstructionV1::CreateProofFromAuthZoneOfNonFungibles { .. }
+            | InstructionV1::CreateProofFromAuthZoneOfAll { .. } => {
+                self.new_proof(None);
+            }
+            InstructionV1::CreateProofFromBucket { bucket_id }
+            | InstructionV1::CreateProofFromBucketOfAmount { bucket_id, .. }
+            | InstructionV1::CreateProofFromBucketOfNonFungibles { bucket_id, .. }
+            | InstructionV1::CreateProofFromBucketOfAll { bucket_id } => {
+                if !self.buckets.contains_key(bucket_id) {
+                    return Err(id_error(ManifestIdValidationError::BucketNotFound(
+                        *bucket_id,
+                    )));
+                }
+                self.new_proof(Some(*bucket_id));
+            }
+            InstructionV1::CloneProof { proof_id } => {
+                let locked_bucket = self.proofs.get(proof_id).cloned().ok_or(id_error(
+                    ManifestIdValidationError::ProofNotFound(*proof_id),
+                ))?;
+                self.new_proof(locked_bucket);
+            }
+            InstructionV1::PushToAuthZone { proof_id } => {
+                // The proof keeps its bucket locked until the auth zone is cleared
+                if let Some(bucket_id) = self.consume_proof(proof_id).map_err(id_error)? {
+                    self.auth_zone_bucket_locks.push(bucket_id);
+                }
+            }
+            InstructionV1::DropProof { proof_id } => {
+                self.consume_proof(proof_id).map_err(id_error)?;
+            }
+            InstructionV1::DropAllProofs => {
+                self.proofs.clear();
+                self.auth_zone_bucket_locks.clear();
+            }
+            InstructionV1::ClearAuthZone => {
+                self.auth_zone_bucket_locks.clear();
+            }
+            InstructionV1::BurnResource { bucket_id } => {
+                self.consume_bucket(instruction_index, bucket_id)
+                    .map_err(id_error)?;
+            }
+            InstructionV1::AssertWorktopContainsAny { .. }
+            | InstructionV1::AssertWorktopContains { .. }
+            | InstructionV1::AssertWorktopContainsNonFungibles { .. }
+            | InstructionV1::ClearSignatureProofs => {}
+            InstructionV1::CallMethod {
+                address: DynamicGlobalAddress::Static(address),
+                method_name,
+                args,
+            } if Self::is_account(address) => {
+                let account = ComponentAddress::new_or_panic(address.as_node_id().0);
+                let call_data = self
+                    .consume_call_data(instruction_index, args)
+                    .map_err(id_error)?;
+                self.analyze_account_call(
+                    instruction_index,
+                    account,
+                    method_name,
+                    args,
+                    call_data,
+                )?;
+            }
+            InstructionV1::CallMethod {
+                address,
+                method_name,
+                args,
+            } => {
+                self.call_kinds
+                    .insert(Self::classify_call(address, method_name));
+                self.analyze_opaque_call(instruction_index, args)
+                    .map_err(id_error)?;
+            }
+            InstructionV1::CallFunction { args, .. }
+            | InstructionV1::CallRoyaltyMethod { args, .. }
+            | InstructionV1::CallMetadataMethod { args, .. }
+            | InstructionV1::CallAccessRulesMethod { args, .. }
+            | InstructionV1::CallDirectVaultMethod { args, .. } => {
+                self.call_kinds.insert(CallKind::Other);
+                self.analyze_opaque_call(instruction_index, args)
+                    .map_err(id_error)?;
+            }
+            InstructionV1::AllocateGlobalAddress { .. } => {
+                self.id_allocator.new_address_reservation_id();
+                self.id_allocator.new_address_id();
+                self.call_kinds.insert(CallKind::Other);
+            }
+        }
+        Ok(())
+    }
+
+    fn analyze_account_call(
+        &mut self,
+        instruction_index: usize,
+        account: ComponentAddress,
+        method_name: &str,
+        args: &ManifestValue,
+        call_data: Vec<(Option<ResourceAddress>, ResourceAmount)>,
+    ) -> Result<(), ManifestAnalysisError> {
+        let invalid_arguments = |error| ManifestAnalysisError::InvalidAccountCallArguments {
+            instruction_index,
+            method_name: method_name.to_owned(),
+            error,
+        };
+        if !matches!(
+            method_name,
+            ACCOUNT_TRY_DEPOSIT_OR_REFUND_IDENT
+                | ACCOUNT_TRY_DEPOSIT_BATCH_OR_REFUND_IDENT
+                | ACCOUNT_TRY_DEPOSIT_BATCH_OR_REFUND_WITH_BADGE_IDENT
+                | ACCOUNT_TRY_DEPOSIT_OR_ABORT_IDENT
+                | ACCOUNT_TRY_DEPOSIT_BATCH_OR_ABORT_IDENT
+                | ACCOUNT_TRY_DEPOSIT_BATCH_OR_ABORT_WITH_BADGE_IDENT
+        ) {
+            self.accounts_requiring_auth.insert(account);
+        }
+
+        let withdrawn = match method_name {
+            ACCOUNT_WITHDRAW_IDENT => {
+                let input: AccountWithdrawInput =
+                    from_manifest_value(args).map_err(invalid_arguments)?;
+                Some((input.resource_address, ResourceAmount::Amount(input.amount)))
+            }
+            ACCOUNT_WITHDRAW_NON_FUNGIBLES_IDENT => {
+                let input: AccountWithdrawNonFungiblesInput =
+                    from_manifest_value(args).map_err(invalid_arguments)?;
+                Some((
+                    input.resource_address,
+                    ResourceAmount::NonFungibles(input.ids),
+                ))
+            }
+            ACCOUNT_LOCK_FEE_AND_WITHDRAW_IDENT => {
+                let input: AccountLockFeeAndWithdrawInput =
+                    from_manifest_value(args).map_err(invalid_arguments)?;
+                Some((input.resource_address, ResourceAmount::Amount(input.amount)))
+            }
+            ACCOUNT_LOCK_FEE_AND_WITHDRAW_NON_FUNGIBLES_IDENT => {
+                let input: AccountLockFeeAndWithdrawNonFungiblesInput =
+                    from_manifest_value(args).map_err(invalid_arguments)?;
+                Some((
+                    input.resource_address,
+                    ResourceAmount::NonFungibles(input.ids),
+                ))
+            }
+            ACCOUNT_DEPOSIT_IDENT
+            | ACCOUNT_DEPOSIT_BATCH_IDENT
+            | ACCOUNT_TRY_DEPOSIT_OR_REFUND_IDENT
+            | ACCOUNT_TRY_DEPOSIT_BATCH_OR_REFUND_IDENT
+            | ACCOUNT_TRY_DEPOSIT_BATCH_OR_REFUND_WITH_BADGE_IDENT
+            | ACCOUNT_TRY_DEPOSIT_OR_ABORT_IDENT
+            | ACCOUNT_TRY_DEPOSIT_BATCH_OR_ABORT_IDENT
+            | ACCOUNT_TRY_DEPOSIT_BATCH_OR_ABORT_WITH_BADGE_IDENT => {
+                let refundable = matches!(
+                    method_name,
+                    ACCOUNT_TRY_DEPOSIT_OR_REFUND_IDENT
+                        | ACCOUNT_TRY_DEPOSIT_BATCH_OR_REFUND_IDENT
+                        | ACCOUNT_TRY_DEPOSIT_BATCH_OR_REFUND_WITH_BADGE_IDENT
+                );
+                for (resource_address, amount) in call_data {
+                    self.deposits.push(AccountResourceMovement {
+                        account,
+                        resource_address,
+                        amount,
+                    });
+                    // A refund returns (part of) the deposited resources to the worktop.
+                    match resource_address {
+                        Some(resource_address) if refundable => {
+                            self.worktop.put(resource_address, ResourceAmount::Unknown)
+                        }
+                        None if refundable => self.worktop.has_unknown_resources = true,
+                        _ => {}
+                    }
+                }
+                None
+            }
+            ACCOUNT_LOCK_FEE_IDENT
+            | ACCOUNT_LOCK_CONTINGENT_FEE_IDENT
+            | ACCOUNT_CREATE_PROOF_IDENT
+            | ACCOUNT_CREATE_PROOF_OF_AMOUNT_IDENT
+            | ACCOUNT_CREATE_PROOF_OF_NON_FUNGIBLES_IDENT => None,
+            _ => {
+                self.call_kinds.insert(CallKind::Other);
+                self.worktop.has_unknown_resources = true;
+                None
+            }
+        };
+
+        if let Some((resource_address, amount)) = withdrawn {
+            self.withdrawals.push(AccountResourceMovement {
+                account,
+                resource_address: Some(resource_address),
+                amount: amount.clone(),
+            });
+            self.worktop.put(resource_address, amount);
+        }
+        Ok(())
+    }
+
+    /// Analyzes a call to an unknown method or function, which takes everything passed to it
+    /// and may return anything.
+    fn analyze_opaque_call(
+        &mut self,
+        instruction_index: usize,
+        args: &ManifestValue,
+    ) -> Result<(), ManifestIdValidationError> {
+        self.consume_call_data(instruction_index, args)?;
+        self.worktop.has_unknown_resources = true;
+        Ok(())
+    }
+
+    /// Consumes the buckets and proofs passed to a call, and returns the resources passed to it
+    /// (a resource address of [`Option::None`] standing for unknown resources of the worktop).
+    fn consume_call_data(
+        &mut self,
+        instruction_index: usize,
+        args: &ManifestValue,
+    ) -> Result<Vec<(Option<ResourceAddress>, ResourceAmount)>, ManifestIdValidationError> {
+        let mut call_data = CallData::default();
+        let _ = transform(args.clone(), &mut call_data);
+
+        let mut resources = Vec::new();
+        for bucket_id in &call_data.buckets {
+            let (resource_address, amount) = self.consume_bucket(instruction_index, bucket_id)?;
+            resources.push((Some(resource_address), amount));
+        }
+        for proof_id in &call_data.proofs {
+            self.consume_proof(proof_id)?;
+        }
+        if call_data.takes_entire_worktop {
+            let (worktop_resources, has_unknown_resources) = self.worktop.drain();
+            resources.extend(
+                worktop_resources
+                    .into_iter()
+                    .map(|(resource_address, amount)| (Some(resource_address), amount)),
+            );
+            if has_unknown_resources {
+                resources.push((None, ResourceAmount::Unknown));
+            }
+        }
+        Ok(resources)
+    }
+
+    fn take_from_worktop(
+        &mut self,
+        instruction_index: usize,
+        resource_address: ResourceAddress,
+        amount: ResourceAmount,
+    ) {
+        if self.worktop.take(resource_address, &amount).is_none() {
+            self.issues.push(ManifestIssue::InsufficientWorktopBalance {
+                instruction_index,
+                resource_address,
+            });
+        }
+        self.new_bucket(resource_address, amount);
+    }
+
+    fn new_bucket(&mut self, resource_address: ResourceAddress, amount: ResourceAmount) {
+        let bucket_id = self.id_allocator.new_bucket_id();
+        self.buckets.insert(bucket_id, (resource_address, amount));
+    }
+
+    fn consume_bucket(
+        &mut self,
+        instruction_index: usize,
+        bucket_id: &ManifestBucket,
+    ) -> Result<(ResourceAddress, ResourceAmount), ManifestIdValidationError> {
+        let bucket = self
+            .buckets
+            .remove(bucket_id)
+            .ok_or(ManifestIdValidationError::BucketNotFound(*bucket_id))?;
+        let is_locked = self
+            .proofs
+            .values()
+            .any(|locked| locked == &Some(*bucket_id))
+            || self.auth_zone_bucket_locks.contains(bucket_id);
+        if is_locked {
+            self.issues.push(ManifestIssue::LockedBucketConsumed {
+                instruction_index,
+                bucket_id: *bucket_id,
+            });
+        }
+        Ok(bucket)
+    }
+
+    /// Creates a named proof, of the given bucket (if known).
+    fn new_proof(&mut self, locked_bucket: Option<ManifestBucket>) {
+        let proof_id = self.id_allocator.new_proof_id();
+        self.proofs.insert(proof_id, locked_bucket);
+    }
+
+    /// Consumes a named proof, and returns the bucket it locked (if known).
+    fn consume_proof(
+        &mut self,
+        proof_id: &ManifestProof,
+    ) -> Result<Option<ManifestBucket>, ManifestIdValidationError> {
+        self.proofs
+            .remove(proof_id)
+            .ok_or(ManifestIdValidationError::ProofNotFound(*proof_id))
+    }
+
+    fn is_account(address: &GlobalAddress) -> bool {
+        matches!(
+            address.as_node_id().entity_type(),
+            Some(
+                EntityType::GlobalAccount
+                    | EntityType::GlobalVirtualSecp256k1Account
+                    | EntityType::GlobalVirtualEd25519Account
+            )
+        )
+    }
+
+    fn classify_call(address: &DynamicGlobalAddress, method_name: &str) -> CallKind {
+        let entity_type = match address {
+            DynamicGlobalAddress::Static(address) => address.as_node_id().entity_type(),
+            DynamicGlobalAddress::Named(_) => None,
+        };
+        match (entity_type, method_name) {
+            (Some(EntityType::GlobalValidator), VALIDATOR_STAKE_IDENT) => CallKind::ValidatorStake,
+            (Some(EntityType::GlobalValidator), VALIDATOR_UNSTAKE_IDENT) => {
+                CallKind::ValidatorUnstake
+            }
+            (Some(EntityType::GlobalValidator), VALIDATOR_CLAIM_XRD_IDENT) => {
+                CallKind::ValidatorClaim
+            }
+            (Some(EntityType::GlobalOneResourcePool), ONE_RESOURCE_POOL_CONTRIBUTE_IDENT)
+            | (Some(EntityType::GlobalTwoResourcePool), TWO_RESOURCE_POOL_CONTRIBUTE_IDENT)
+            | (Some(EntityType::GlobalMultiResourcePool), MULTI_RESOURCE_POOL_CONTRIBUTE_IDENT) => {
+                CallKind::PoolContribution
+            }
+            (Some(EntityType::GlobalOneResourcePool), ONE_RESOURCE_POOL_REDEEM_IDENT)
+            | (Some(EntityType::GlobalTwoResourcePool), TWO_RESOURCE_POOL_REDEEM_IDENT)
+            | (Some(EntityType::GlobalMultiResourcePool), MULTI_RESOURCE_POOL_REDEEM_IDENT) => {
+                CallKind::PoolRedemption
+            }
+            _ => CallKind::Other,
+        }
+    }
+
+    fn finish(mut self) -> ManifestAnalysis {
+        for bucket_id in self.buckets.keys() {
+            self.issues.push(ManifestIssue::UnusedBucket(*bucket_id));
+        }
+        for proof_id in self.proofs.keys() {
+            self.issues.push(ManifestIssue::DanglingProof(*proof_id));
+        }
+        for (resource_address, amount) in &self.worktop.known_resources {
+            if !amount.is_empty() && amount != &ResourceAmount::Unknown {
+                self.issues
+                    .push(ManifestIssue::ResourceLeftOnWorktop(*resource_address));
+            }
+        }
+
+        let class = match self.call_kinds.len() {
+            0 if !self.withdrawals.is_empty() && !self.deposits.is_empty() => {
+                ManifestClass::Transfer
+            }
+            1 => match self.call_kinds[0] {
+                CallKind::ValidatorStake => ManifestClass::ValidatorStake,
+                CallKind::ValidatorUnstake => ManifestClass::ValidatorUnstake,
+                CallKind::ValidatorClaim => ManifestClass::ValidatorClaim,
+                CallKind::PoolContribution => ManifestClass::PoolContribution,
+                CallKind::PoolRedemption => ManifestClass::PoolRedemption,
+                CallKind::Other => ManifestClass::General,
+            },
+            _ => ManifestClass::General,
+        };
+
+        ManifestAnalysis {
+            class,
+            accounts_requiring_auth: self.accounts_requiring_auth,
+            accounts_withdrawn_from: self
+                .withdrawals
+                .iter()
+                .map(|withdrawal| withdrawal.account)
+                .collect(),
+            accounts_deposited_into: self
+                .deposits
+                .iter()
+                .map(|deposit| deposit.account)
+                .collect(),
+            withdrawals: self.withdrawals,
+            deposits: self.deposits,
+            issues: self.issues,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radix_engine_interface::constants::XRD;
+    use radix_engine_interface::manifest_args;
+    use utils::indexset;
+
+    fn account(key: u64) -> ComponentAddress {
+        ComponentAddress::virtual_account_from_public_key(
+            &Secp256k1PrivateKey::from_u64(key).unwrap().public_key(),
+        )
+    }
+
+    #[test]
+    fn transfer_is_classified_with_its_withdrawals_and_deposits() {
+        let (sender, recipient) = (account(1), account(2));
+        let manifest = ManifestBuilder::new()
+            .lock_fee(sender, 10.into())
+            .withdraw_from_account(sender, XRD, 100.into())
+            .take_from_worktop_named(XRD, 60.into(), "xrd")
+            .with_name_lookup(|builder, lookup| {
+                builder.call_method(
+                    recipient,
+                    ACCOUNT_TRY_DEPOSIT_OR_ABORT_IDENT,
+                    manifest_args!(lookup.bucket("xrd")),
+                )
+            })
+            .deposit_batch(sender)
+            .build();
+
+        let analysis = analyze_manifest(&manifest.instructions).unwrap();
+
+        assert_eq!(analysis.class, ManifestClass::Transfer);
+        assert_eq!(analysis.accounts_requiring_auth, indexset!(sender));
+        assert_eq!(analysis.accounts_withdrawn_from, indexset!(sender));
+        assert_eq!(
+            analysis.accounts_deposited_into,
+            indexset!(recipient, sender)
+        );
+        assert_eq!(
+            analysis.deposits,
+            vec![
+                AccountResourceMovement {
+                    account: recipient,
+                    resource_address: Some(XRD),
+                    amount: ResourceAmount::Amount(60.into()),
+                },
+                AccountResourceMovement {
+                    account: sender,
+                    resource_address: Some(XRD),
+                    amount: ResourceAmount::Amount(40.into()),
+                }
+            ]
+        );
+        assert!(analysis.issues.is_empty());
+    }
+
+    #[test]
+    fn stake_returns_unknown_resources_to_the_worktop() {
+        let staker = account(1);
+        let validator =
+            ComponentAddress::new_or_panic([EntityType::GlobalValidator as u8; NodeId::LENGTH]);
+        let manifest = ManifestBuilder::new()
+            .lock_fee(staker, 10.into())
+            .withdraw_from_account(staker, XRD, 100.into())
+            .take_all_from_worktop(XRD, |builder, bucket| {
+                builder.stake_validator(validator, bucket)
+            })
+            .deposit_batch(staker)
+            .build();
+
+        let analysis = analyze_manifest(&manifest.instructions).unwrap();
+
+        assert_eq!(analysis.class, ManifestClass::ValidatorStake);
+        assert_eq!(
+            analysis.deposits,
+            vec![AccountResourceMovement {
+                account: staker,
+                resource_address: None,
+                amount: ResourceAmount::Unknown,
+            }]
+        );
+        assert!(analysis.issues.is_empty());
+    }
+
+    #[test]
+    fn misused_buckets_and_proofs_are_reported() {
+        let owner = account(1);
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(owner, XRD, 5.into())
+            .take_from_worktop(XRD, 10.into(), |builder, _| builder)
+            .create_proof_from_auth_zone(XRD, |builder, _| builder)
+            .build();
+
+        let analysis = analyze_manifest(&manifest.instructions).unwrap();
+
+        assert_eq!(analysis.class, ManifestClass::General);
+        assert_eq!(
+            analysis.issues,
+            vec![
+                ManifestIssue::InsufficientWorktopBalance {
+                    instruction_index: 1,
+                    resource_address: XRD,
+                },
+                ManifestIssue::UnusedBucket(ManifestBucket(0)),
+                ManifestIssue::DanglingProof(ManifestProof(0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn insufficient_take_leaves_the_known_worktop_balance_unchanged() {
+        let owner = account(1);
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(owner, XRD, 5.into())
+            .take_from_worktop(XRD, 10.into(), |builder, bucket| {
+                builder.burn_resource(bucket)
+            })
+            .take_from_worktop(XRD, 5.into(), |builder, bucket| {
+                builder.burn_resource(bucket)
+            })
+            .build();
+
+        let analysis = analyze_manifest(&manifest.instructions).unwrap();
+
+        assert_eq!(
+            analysis.issues,
+            vec![ManifestIssue::InsufficientWorktopBalance {
+                instruction_index: 1,
+                resource_address: XRD,
+            }]
+        );
+    }
+
+    #[test]
+    fn worktop_balance_beyond_the_decimal_range_is_unknown() {
+        let owner = account(1);
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(owner, XRD, Decimal::MAX)
+            .withdraw_from_account(owner, XRD, Decimal::MAX)
+            .deposit_batch(owner)
+            .build();
+
+        let analysis = analyze_manifest(&manifest.instructions).unwrap();
+
+        assert_eq!(
+            analysis.deposits,
+            vec![AccountResourceMovement {
+                account: owner,
+                resource_address: Some(XRD),
+                amount: ResourceAmount::Unknown,
+            }]
+        );
+    }
+
+    #[test]
+    fn consuming_a_bucket_locked_by_a_proof_is_reported() {
+        let owner = account(1);
+        let manifest = ManifestBuilder::new()
+            .withdraw_from_account(owner, XRD, 10.into())
+            .take_all_from_worktop(XRD, |builder, bucket| {
+                builder
+                    .create_proof_from_bucket_of_all(&bucket, |builder, proof| {
+                        builder.push_to_auth_zone(proof)
+                    })
+                    .burn_resource(bucket)
+            })
+            .take_all_from_worktop(XRD, |builder, bucket| {
+                builder
+                    .create_proof_from_bucket_of_all(&bucket, |builder, proof| {
+                        builder.push_to_auth_zone(proof)
+                    })
+                    .clear_auth_zone()
+                    .burn_resource(bucket)
+            })
+            .build();
+
+        let analysis = analyze_manifest(&manifest.instructions).unwrap();
+
+        assert_eq!(
+            analysis.issues,
+            vec![ManifestIssue::LockedBucketConsumed {
+                instruction_index: 4,
+                bucket_id: ManifestBucket(0),
+            }]
+        );
+    }
+
+    #[test]
+    fn use_of_unknown_bucket_is_an_error() {
+        let result = analyze_manifest(&[InstructionV1::ReturnToWorktop {
+            bucket_id: ManifestBucket(0),
+        }]);
+
+        assert_eq!(
+            result,
+            Err(ManifestAnalysisError::IdValidationError {
+                instruction_index: 0,
+                error: ManifestIdValidationError::BucketNotFound(ManifestBucket(0)),
+            })
+        );
+    }
+}
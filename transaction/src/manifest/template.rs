@@ -0,0 +1,707 @@
+//! Manifest templates, i.e. manifests with parameters and includes, which are resolved (at the
+//! token level) before the manifest is parsed.
+//!
+//! A parameter is declared with its kind, and optionally a default value:
+//!
+//! ```text
+//! PARAM "account" Address;
+//! PARAM "amount" Decimal Decimal("1");
+//! ```
+//!
+//! and is then referred to as `Param("account")` wherever a value is expected.
+//!
+//! Another manifest can be included, with the values of its parameters:
+//!
+//! ```text
+//! INCLUDE "withdraw.rtm" "account" => Param("account"), "amount" => Decimal("10");
+//! ```
+//!
+//! The instructions of an included manifest are reported (e.g. in errors and source maps) at the
+//! span of the `INCLUDE` instruction.
+
+use crate::manifest::ast::ValueKind;
+use crate::manifest::compiler::CompileError;
+use crate::manifest::lexer::{tokenize, Position, Span, Token, TokenKind};
+use crate::manifest::parser::{Parser, ParserError, TokenType, PARSER_MAX_DEPTH};
+use sbor::rust::prelude::*;
+
+pub const PARAM_DIRECTIVE: &str = "PARAM";
+pub const INCLUDE_DIRECTIVE: &str = "INCLUDE";
+pub const PARAM_VALUE_IDENT: &str = "Param";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    ParserError(ParserError),
+    DuplicateParam(String),
+    UndeclaredParam(String),
+    MissingParam(String),
+    /// A value is given for a parameter which the manifest does not declare.
+    UnknownParam(String),
+    InvalidParamValue {
+        name: String,
+        kind: ValueKind,
+    },
+    IncludeNotFound(String),
+    IncludeCycle(String),
+    IncludeError {
+        path: String,
+        error: Box<CompileError>,
+    },
+}
+
+pub trait IsIncludeProvider {
+    /// Returns the source of the manifest at the given path, if it exists.
+    fn get_include(&self, path: &str) -> Option<String>;
+}
+
+/// Provides includes from memory.
+#[derive(Default, Debug, Clone)]
+pub struct IncludeProvider(IndexMap<String, String>);
+
+impl IncludeProvider {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn add_include(&mut self, path: &str, source: &str) {
+        self.0.insert(path.to_string(), source.to_string());
+    }
+}
+
+impl IsIncludeProvider for IncludeProvider {
+    fn get_include(&self, path: &str) -> Option<String> {
+        self.0.get(path).cloned()
+    }
+}
+
+/// Provides includes from files, at paths relative to a base directory.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct FileIncludeProvider {
+    base_directory: std::path::PathBuf,
+}
+
+#[cfg(feature = "std")]
+impl FileIncludeProvider {
+    pub fn new<P: Into<std::path::PathBuf>>(base_directory: P) -> Self {
+        Self {
+            base_directory: base_directory.into(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl IsIncludeProvider for FileIncludeProvider {
+    fn get_include(&self, path: &str) -> Option<String> {
+        std::fs::read_to_string(self.base_directory.join(path)).ok()
+    }
+}
+
+/// The values of the parameters of a manifest template, and the provider of its includes.
+///
+/// Parameter values are given either in the manifest syntax (e.g. `Decimal("1")`), or as the
+/// content of a literal of the declared kind (e.g. `1` for a `Decimal`, or an unquoted string for
+/// a `String`).
+#[derive(Default)]
+pub struct TemplateContext<'a> {
+    params: IndexMap<String, String>,
+    include_provider: Option<&'a dyn IsIncludeProvider>,
+}
+
+impl<'a> TemplateContext<'a> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_param(mut self, name: &str, value: &str) -> Self {
+        self.params.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn with_include_provider(mut self, include_provider: &'a dyn IsIncludeProvider) -> Self {
+        self.include_provider = Some(include_provider);
+        self
+    }
+}
+
+/// Whether the tokens use any of the template features (and so need to be resolved).
+pub fn is_template(tokens: &[Token]) -> bool {
+    tokens.iter().any(|token| match &token.kind {
+        TokenKind::Ident(ident) => {
+            ident == PARAM_DIRECTIVE || ident == INCLUDE_DIRECTIVE || ident == PARAM_VALUE_IDENT
+        }
+        _ => false,
+    })
+}
+
+/// Resolves the parameters and includes of a manifest template, returning the tokens of the
+/// plain manifest.
+pub fn resolve_template(
+    tokens: Vec<Token>,
+    context: &TemplateContext,
+) -> Result<Vec<Token>, (TemplateError, Span)> {
+    let bindings = context
+        .params
+        .iter()
+        .map(|(name, value)| (name.clone(), ParamBinding::Raw(value.clone())))
+        .collect();
+    TemplateResolver {
+        context,
+        include_stack: Vec::new(),
+    }
+    .resolve(tokens, bindings)
+}
+
+enum ParamBinding {
+    /// A value given to the compiler, in any of the formats supported by the [`TemplateContext`]
+    Raw(String),
+    /// A value given to an include
+    Tokens(Vec<Token>),
+}
+
+struct TemplateResolver<'c, 'a> {
+    context: &'c TemplateContext<'a>,
+    include_stack: Vec<String>,
+}
+
+impl<'c, 'a> TemplateResolver<'c, 'a> {
+    fn resolve(
+        &mut self,
+        tokens: Vec<Token>,
+        mut bindings: IndexMap<String, ParamBinding>,
+    ) -> Result<Vec<Token>, (TemplateError, Span)> {
+        let mut params = index_map_new();
+        let mut output = Vec::new();
+        let mut cursor = 0;
+        while cursor < tokens.len() {
+            let at_instruction_start =
+                cursor == 0 || tokens[cursor - 1].kind == TokenKind::Semicolon;
+            match &tokens[cursor].kind {
+                TokenKind::Ident(ident) if at_instruction_start && ident == PARAM_DIRECTIVE => {
+                    cursor = Self::declare_param(&tokens, cursor, &mut bindings, &mut params)?;
+                }
+                TokenKind::Ident(ident) if at_instruction_start && ident == INCLUDE_DIRECTIVE => {
+                    cursor = self.include(&tokens, cursor, &params, &mut output)?;
+                }
+                TokenKind::Ident(ident) if ident == PARAM_VALUE_IDENT => {
+                    cursor = Self::substitute_param(&tokens, cursor, &params, &mut output)?;
+                }
+                _ => {
+                    output.push(tokens[cursor].clone());
+                    cursor += 1;
+                }
+            }
+        }
+
+        match bindings.keys().next() {
+            Some(name) => Err((
+                TemplateError::UnknownParam(name.clone()),
+                tokens
+                    .first()
+                    .map(|token| token.span)
+                    .unwrap_or(Self::empty_span(&[])),
+            )),
+            None => Ok(output),
+        }
+    }
+
+    /// Declares a parameter: `PARAM "name" Kind [default];`
+    fn declare_param(
+        tokens: &[Token],
+        start: usize,
+        bindings: &mut IndexMap<String, ParamBinding>,
+        params: &mut IndexMap<String, Vec<Token>>,
+    ) -> Result<usize, (TemplateError, Span)> {
+        let name = Self::string_at(tokens, start + 1)?;
+        let kind_token = Self::token_at(tokens, start + 2)?;
+        let kind = Parser::new(vec![kind_token.clone()], PARSER_MAX_DEPTH)
+            .parse_type()
+            .map_err(|error| (TemplateError::ParserError(error), kind_token.span))?;
+        let mut cursor = start + 3;
+        let mut default = None;
+        if Self::token_at(tokens, cursor)?.kind != TokenKind::Semicolon {
+            let end = Self::value_end(tokens, cursor)?;
+            default = Some(Self::substitute_params(&tokens[cursor..end], params)?);
+            cursor = end;
+        }
+        Self::expect(tokens, cursor, TokenKind::Semicolon)?;
+        let span = Span {
+            start: tokens[start].span.start,
+            end: tokens[cursor].span.end,
+        };
+
+        if params.contains_key(&name) {
+            return Err((TemplateError::DuplicateParam(name), span));
+        }
+        let value = match bindings.swap_remove(&name) {
+            Some(ParamBinding::Raw(raw)) => Self::raw_param_value(&raw, kind, span),
+            Some(ParamBinding::Tokens(value)) => Some(value),
+            None => match default {
+                Some(default) => Some(default),
+                None => return Err((TemplateError::MissingParam(name), span)),
+            },
+        };
+        match value {
+            Some(value) if Self::is_value_of_kind(&value, kind) => {
+                params.insert(name, value);
+                Ok(cursor + 1)
+            }
+            _ => Err((TemplateError::InvalidParamValue { name, kind }, span)),
+        }
+    }
+
+    /// Includes another manifest: `INCLUDE "path" ["name" => value, ...];`
+    fn include(
+        &mut self,
+        tokens: &[Token],
+        start: usize,
+        params: &IndexMap<String, Vec<Token>>,
+        output: &mut Vec<Token>,
+    ) -> Result<usize, (TemplateError, Span)> {
+        let path = Self::string_at(tokens, start + 1)?;
+        let mut cursor = start + 2;
+        let mut bindings = index_map_new();
+        while Self::token_at(tokens, cursor)?.kind != TokenKind::Semicolon {
+            if !bindings.is_empty() {
+                Self::expect(tokens, cursor, TokenKind::Comma)?;
+                cursor += 1;
+            }
+            let name = Self::string_at(tokens, cursor)?;
+            Self::expect(tokens, cursor + 1, TokenKind::FatArrow)?;
+            let end = Self::value_end(tokens, cursor + 2)?;
+            let value = Self::substitute_params(&tokens[cursor + 2..end], params)?;
+            if bindings
+                .insert(name.clone(), ParamBinding::Tokens(value))
+                .is_some()
+            {
+                return Err((TemplateError::DuplicateParam(name), tokens[cursor].span));
+            }
+            cursor = end;
+        }
+        let span = Span {
+            start: tokens[start].span.start,
+            end: tokens[cursor].span.end,
+        };
+
+        if self.include_stack.contains(&path) {
+            return Err((TemplateError::IncludeCycle(path), span));
+        }
+        let source = match self
+            .context
+            .include_provider
+            .and_then(|include_provider| include_provider.get_include(&path))
+        {
+            Some(source) => source,
+            None => return Err((TemplateError::IncludeNotFound(path), span)),
+        };
+        let included_tokens = tokenize(&source).map_err(|error| {
+            let error_span = error.span(&source);
+            (
+                TemplateError::IncludeError {
+                    path: path.clone(),
+                    error: Box::new(CompileError::LexerError(error, error_span)),
+                },
+                span,
+            )
+        })?;
+        self.include_stack.push(path.clone());
+        let included = self
+            .resolve(included_tokens, bindings)
+            .map_err(|(error, error_span)| {
+                (
+                    TemplateError::IncludeError {
+                        path: path.clone(),
+                        error: Box::new(CompileError::TemplateError(error, error_span)),
+                    },
+                    span,
+                )
+            })?;
+        self.include_stack.pop();
+
+        output.extend(Self::with_span(&included, span));
+        Ok(cursor + 1)
+    }
+
+    /// Substitutes a reference to a parameter: `Param("name")`
+    fn substitute_param(
+        tokens: &[Token],
+        start: usize,
+        params: &IndexMap<String, Vec<Token>>,
+        output: &mut Vec<Token>,
+    ) -> Result<usize, (TemplateError, Span)> {
+        Self::expect(tokens, start + 1, TokenKind::OpenParenthesis)?;
+        let name = Self::string_at(tokens, start + 2)?;
+        Self::expect(tokens, start + 3, TokenKind::CloseParenthesis)?;
+        let span = Span {
+            start: tokens[start].span.start,
+            end: tokens[start + 3].span.end,
+        };
+        match params.get(&name) {
+            Some(value) => {
+                output.extend(Self::with_span(value, span));
+                Ok(start + 4)
+            }
+            None => Err((TemplateError::UndeclaredParam(name), span)),
+        }
+    }
+
+    fn substitute_params(
+        tokens: &[Token],
+        params: &IndexMap<String, Vec<Token>>,
+    ) -> Result<Vec<Token>, (TemplateError, Span)> {
+        let mut output = Vec::new();
+        let mut cursor = 0;
+        while cursor < tokens.len() {
+            match &tokens[cursor].kind {
+                TokenKind::Ident(ident) if ident == PARAM_VALUE_IDENT => {
+                    cursor = Self::substitute_param(tokens, cursor, params, &mut output)?;
+                }
+                _ => {
+                    output.push(tokens[cursor].clone());
+                    cursor += 1;
+                }
+            }
+        }
+        Ok(output)
+    }
+
+    fn raw_param_value(raw: &str, kind: ValueKind, span: Span) -> Option<Vec<Token>> {
+        if let Ok(tokens) = tokenize(raw) {
+            if Self::is_value_of_kind(&tokens, kind) {
+                return Some(Self::with_span(&tokens, span));
+            }
+        }
+
+        let literal = match kind {
+            ValueKind::Bool => raw.parse().ok().map(TokenKind::BoolLiteral),
+            ValueKind::I8 => raw.parse().ok().map(TokenKind::I8Literal),
+            ValueKind::I16 => raw.parse().ok().map(TokenKind::I16Literal),
+            ValueKind::I32 => raw.parse().ok().map(TokenKind::I32Literal),
+            ValueKind::I64 => raw.parse().ok().map(TokenKind::I64Literal),
+            ValueKind::I128 => raw.parse().ok().map(TokenKind::I128Literal),
+            ValueKind::U8 => raw.parse().ok().map(TokenKind::U8Literal),
+            ValueKind::U16 => raw.parse().ok().map(TokenKind::U16Literal),
+            ValueKind::U32 => raw.parse().ok().map(TokenKind::U32Literal),
+            ValueKind::U64 => raw.parse().ok().map(TokenKind::U64Literal),
+            ValueKind::U128 => raw.parse().ok().map(TokenKind::U128Literal),
+            ValueKind::String => Some(TokenKind::StringLiteral(raw.to_string())),
+            _ => None,
+        };
+        if let Some(literal) = literal {
+            return Some(vec![Token {
+                kind: literal,
+                span,
+            }]);
+        }
+
+        // The values of these kinds are constructed from a string
+        let ident = match kind {
+            ValueKind::Address
+            | ValueKind::PackageAddress
+            | ValueKind::ComponentAddress
+            | ValueKind::ResourceAddress => "Address",
+            ValueKind::Expression => "Expression",
+            ValueKind::Blob => "Blob",
+            ValueKind::Decimal => "Decimal",
+            ValueKind::PreciseDecimal => "PreciseDecimal",
+            ValueKind::NonFungibleLocalId => "NonFungibleLocalId",
+            ValueKind::Bytes => "Bytes",
+            ValueKind::NonFungibleGlobalId => "NonFungibleGlobalId",
+            _ => return None,
+        };
+        Some(
+            vec![
+                TokenKind::Ident(ident.to_string()),
+                TokenKind::OpenParenthesis,
+                TokenKind::StringLiteral(raw.to_string()),
+                TokenKind::CloseParenthesis,
+            ]
+            .into_iter()
+            .map(|kind| Token { kind, span })
+            .collect(),
+        )
+    }
+
+    fn is_value_of_kind(tokens: &[Token], kind: ValueKind) -> bool {
+        let mut parser = Parser::new(tokens.to_vec(), PARSER_MAX_DEPTH);
+        match parser.parse_value() {
+            Ok(value) => parser.is_eof() && value.value_kind() == kind.value_kind(),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns the index following the value starting at the given index.
+    fn value_end(tokens: &[Token], start: usize) -> Result<usize, (TemplateError, Span)> {
+        let mut cursor = start + 1;
+        if let TokenKind::Ident(_) = Self::token_at(tokens, start)?.kind {
+            if cursor < tokens.len() && tokens[cursor].kind == TokenKind::LessThan {
+                cursor =
+                    Self::closing(tokens, cursor, TokenKind::LessThan, TokenKind::GreaterThan)? + 1;
+            }
+            if cursor < tokens.len() && tokens[cursor].kind == TokenKind::OpenParenthesis {
+                cursor = Self::closing(
+                    tokens,
+                    cursor,
+                    TokenKind::OpenParenthesis,
+                    TokenKind::CloseParenthesis,
+                )? + 1;
+            }
+        }
+        Ok(cursor)
+    }
+
+    fn closing(
+        tokens: &[Token],
+        start: usize,
+        open: TokenKind,
+        close: TokenKind,
+    ) -> Result<usize, (TemplateError, Span)> {
+        let mut depth = 0;
+        for (cursor, token) in tokens.iter().enumerate().skip(start) {
+            if token.kind == open {
+                depth += 1;
+            } else if token.kind == close {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(cursor);
+                }
+            }
+        }
+        Err(Self::unexpected_eof(tokens))
+    }
+
+    fn token_at(tokens: &[Token], cursor: usize) -> Result<&Token, (TemplateError, Span)> {
+        tokens
+            .get(cursor)
+            .ok_or_else(|| Self::unexpected_eof(tokens))
+    }
+
+    fn string_at(tokens: &[Token], cursor: usize) -> Result<String, (TemplateError, Span)> {
+        let token = Self::token_at(tokens, cursor)?;
+        match &token.kind {
+            TokenKind::StringLiteral(value) => Ok(value.clone()),
+            _ => Err((
+                TemplateError::ParserError(ParserError::UnexpectedToken {
+                    expected: TokenType::Value,
+                    actual: token.clone(),
+                }),
+                token.span,
+            )),
+        }
+    }
+
+    fn expect(
+        tokens: &[Token],
+        cursor: usize,
+        expected: TokenKind,
+    ) -> Result<(), (TemplateError, Span)> {
+        let token = Self::token_at(tokens, cursor)?;
+        if token.kind != expected {
+            return Err((
+                TemplateError::ParserError(ParserError::UnexpectedToken {
+                    expected: TokenType::Exact(expected),
+                    actual: token.clone(),
+                }),
+                token.span,
+            ));
+        }
+        Ok(())
+    }
+
+    fn unexpected_eof(tokens: &[Token]) -> (TemplateError, Span) {
+        (
+            TemplateError::ParserError(ParserError::UnexpectedEof),
+            Self::empty_span(tokens),
+        )
+    }
+
+    /// An empty span at the end of the tokens.
+    fn empty_span(tokens: &[Token]) -> Span {
+        let end = tokens
+            .last()
+            .map(|token| token.span.end)
+            .unwrap_or(Position {
+                full_index: 0,
+                line_number: 1,
+                line_char_index: 0,
+            });
+        Span { start: end, end }
+    }
+
+    fn with_span(tokens: &[Token], span: Span) -> Vec<Token> {
+        tokens
+            .iter()
+            .map(|token| Token {
+                kind: token.kind.clone(),
+                span,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::compiler::{compile, compile_with_source_map};
+    use crate::manifest::decompiler::decompile;
+    use crate::manifest::BlobProvider;
+    use radix_engine_interface::network::NetworkDefinition;
+
+    const ACCOUNT: &str = "account_sim1cyvgx33089ukm2pl97pv4max0x40ruvfy4lt60yvya744cve475w0q";
+    const XRD: &str = "resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxakj8n3";
+
+    fn compile_template(source: &str, context: &TemplateContext) -> Result<String, CompileError> {
+        let network = NetworkDefinition::simulator();
+        compile_with_source_map(source, &network, BlobProvider::default(), context)
+            .map(|(manifest, _)| decompile(&manifest.instructions, &network).unwrap())
+    }
+
+    fn template_error(source: &str, context: &TemplateContext) -> TemplateError {
+        match compile_template(source, context) {
+            Err(CompileError::TemplateError(error, _)) => error,
+            result => panic!("Expected a template error, but got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_params_are_substituted() {
+        let source = r#"
+PARAM "account" Address;
+PARAM "amount" Decimal Decimal("1");
+PARAM "message" String;
+CALL_METHOD Param("account") "withdraw" Address("resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxakj8n3") Param("amount");
+CALL_METHOD Param("account") "try_deposit_batch_or_abort" Expression("ENTIRE_WORKTOP") Param("message");
+"#;
+        let expected = format!(
+            r#"
+CALL_METHOD Address("{ACCOUNT}") "withdraw" Address("{XRD}") Decimal("2.5");
+CALL_METHOD Address("{ACCOUNT}") "try_deposit_batch_or_abort" Expression("ENTIRE_WORKTOP") "hello world";
+"#
+        );
+
+        let context = TemplateContext::new()
+            .with_param("account", ACCOUNT)
+            .with_param("amount", "2.5")
+            .with_param("message", "hello world");
+        assert_eq!(
+            compile_template(source, &context).unwrap(),
+            compile_template(&expected, &TemplateContext::new()).unwrap()
+        );
+
+        // Values can also be given in the manifest syntax, and defaults apply
+        let context = TemplateContext::new()
+            .with_param("account", &format!("Address(\"{}\")", ACCOUNT))
+            .with_param("message", "\"hello world\"");
+        assert_eq!(
+            compile_template(source, &context).unwrap(),
+            compile_template(&expected.replace("2.5", "1"), &TemplateContext::new()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_params_are_rejected() {
+        let source = r#"PARAM "amount" Decimal; CALL_METHOD Address("component_sim1cqvgx33089ukm2pl97pv4max0x40ruvfy4lt60yvya744cvemygpmu") "free" Param("amount");"#;
+
+        assert_eq!(
+            template_error(source, &TemplateContext::new()),
+            TemplateError::MissingParam("amount".to_string())
+        );
+        assert_eq!(
+            template_error(source, &TemplateContext::new().with_param("amount", "1u8")),
+            TemplateError::InvalidParamValue {
+                name: "amount".to_string(),
+                kind: ValueKind::Decimal
+            }
+        );
+        assert_eq!(
+            template_error(
+                source,
+                &TemplateContext::new()
+                    .with_param("amount", "1")
+                    .with_param("amout", "1")
+            ),
+            TemplateError::UnknownParam("amout".to_string())
+        );
+        assert_eq!(
+            template_error(
+                r#"CLEAR_AUTH_ZONE Param("amount");"#,
+                &TemplateContext::new()
+            ),
+            TemplateError::UndeclaredParam("amount".to_string())
+        );
+        // Templates can be compiled without parameters, as long as they all have defaults
+        assert!(compile(
+            r#"PARAM "count" U32 5u32; CALL_METHOD Address("component_sim1cqvgx33089ukm2pl97pv4max0x40ruvfy4lt60yvya744cvemygpmu") "free" Param("count");"#,
+            &NetworkDefinition::simulator(),
+            BlobProvider::default()
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_includes_are_resolved() {
+        let mut includes = IncludeProvider::new();
+        includes.add_include(
+            "withdraw.rtm",
+            r#"
+PARAM "account" Address;
+PARAM "amount" Decimal;
+CALL_METHOD Param("account") "withdraw" Address("resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxakj8n3") Param("amount");
+"#,
+        );
+        includes.add_include("cycle.rtm", r#"INCLUDE "cycle.rtm";"#);
+        let source = r#"
+PARAM "from" Address;
+INCLUDE "withdraw.rtm" "account" => Param("from"), "amount" => Decimal("1");
+INCLUDE "withdraw.rtm" "account" => Param("from"), "amount" => Decimal("2");
+CALL_METHOD Param("from") "try_deposit_batch_or_abort" Expression("ENTIRE_WORKTOP");
+"#;
+        let expected = format!(
+            r#"
+CALL_METHOD Address("{ACCOUNT}") "withdraw" Address("{XRD}") Decimal("1");
+CALL_METHOD Address("{ACCOUNT}") "withdraw" Address("{XRD}") Decimal("2");
+CALL_METHOD Address("{ACCOUNT}") "try_deposit_batch_or_abort" Expression("ENTIRE_WORKTOP");
+"#
+        );
+        let context = TemplateContext::new()
+            .with_param("from", ACCOUNT)
+            .with_include_provider(&includes);
+
+        assert_eq!(
+            compile_template(source, &context).unwrap(),
+            compile_template(&expected, &TemplateContext::new()).unwrap()
+        );
+        let (_, source_map) = compile_with_source_map(
+            source,
+            &NetworkDefinition::simulator(),
+            BlobProvider::default(),
+            &context,
+        )
+        .unwrap();
+        assert_eq!(source_map.instruction_line(0), Some(3));
+        assert_eq!(source_map.instruction_line(1), Some(4));
+        assert_eq!(source_map.instruction_line(2), Some(5));
+
+        assert_eq!(
+            template_error(r#"INCLUDE "missing.rtm";"#, &context),
+            TemplateError::IncludeNotFound("missing.rtm".to_string())
+        );
+        assert!(matches!(
+            template_error(r#"INCLUDE "cycle.rtm";"#, &context),
+            TemplateError::IncludeError { .. }
+        ));
+        match template_error(
+            r#"PARAM "from" Address; INCLUDE "withdraw.rtm" "account" => Param("from");"#,
+            &context,
+        ) {
+            TemplateError::IncludeError { path, error } => {
+                assert_eq!(path, "withdraw.rtm");
+                assert!(matches!(
+                    *error,
+                    CompileError::TemplateError(TemplateError::MissingParam(ref name), _) if name == "amount"
+                ));
+            }
+            error => panic!("Expected an include error, but got {:?}", error),
+        }
+    }
+}
@@ -167,5 +167,5 @@ impl AesWrapped128BitKey {
 
 pub type PreparedMessageV1 = SummarizedRawFullBody<MessageV1>;
 
-// TODO: Add tests with a canonical implementation of message encryption/decryption,
-// and corresponding test vectors for other implementers.
+// Note: the canonical implementation of message encryption/decryption (with test vectors for other
+// implementers) lives in `crate::signing::message_encryption`.
@@ -0,0 +1,278 @@
+use crate::internal_prelude::*;
+use radix_engine_common::prelude::IsHash;
+#[cfg(feature = "std")]
+use sbor::rust::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExternalSignerError {
+    /// The signer could not (or refused to) produce a signature, for the given reason.
+    SigningFailed(String),
+    /// The signer produced something which is not a well-formed signature.
+    MalformedSignature(String),
+    /// The signature does not match the signer's public key and the signed hash.
+    InvalidSignature,
+}
+
+/// A signer whose private key is not necessarily available in this process (e.g. a key held by
+/// another application, a remote service, or a device answering through a user-provided callback).
+///
+/// The signer signs a raw hash - i.e. an `IntentHash` (for intent signatures) or a
+/// `SignedIntentHash` (for the notary signature). An implementation may block until the signature
+/// becomes available (e.g. wait for another task to answer a request sent over a channel).
+/// Signers which must not be waited for (e.g. ones answering through an async API) can instead be
+/// given the hash by `TransactionBuilder::intent_hash()` / `TransactionBuilder::signed_intent_hash()`,
+/// and their signature added later by `TransactionBuilder::add_intent_signature()` /
+/// `TransactionBuilder::add_notary_signature()`.
+pub trait ExternalSigner {
+    fn public_key(&self) -> PublicKey;
+
+    fn sign_hash(&self, message_hash: &Hash) -> Result<SignatureV1, ExternalSignerError>;
+}
+
+impl ExternalSigner for Secp256k1PrivateKey {
+    fn public_key(&self) -> PublicKey {
+        self.public_key().into()
+    }
+
+    fn sign_hash(&self, message_hash: &Hash) -> Result<SignatureV1, ExternalSignerError> {
+        Ok(self.sign(message_hash).into())
+    }
+}
+
+impl ExternalSigner for Ed25519PrivateKey {
+    fn public_key(&self) -> PublicKey {
+        self.public_key().into()
+    }
+
+    fn sign_hash(&self, message_hash: &Hash) -> Result<SignatureV1, ExternalSignerError> {
+        Ok(self.sign(message_hash).into())
+    }
+}
+
+impl ExternalSigner for PrivateKey {
+    fn public_key(&self) -> PublicKey {
+        self.public_key()
+    }
+
+    fn sign_hash(&self, message_hash: &Hash) -> Result<SignatureV1, ExternalSignerError> {
+        Ok(self.sign_without_public_key(message_hash))
+    }
+}
+
+/// Signs the given hash with the given signer, and verifies the returned signature against the
+/// signer's public key (so that a misbehaving signer is caught before the transaction is
+/// submitted).
+pub fn sign_with_external_signer<S: ExternalSigner + ?Sized>(
+    signer: &S,
+    message_hash: &impl IsHash,
+) -> Result<SignatureWithPublicKeyV1, ExternalSignerError> {
+    let signature = signer.sign_hash(message_hash.as_ref())?;
+    verify_external_signature(&signer.public_key(), message_hash, signature)
+}
+
+/// Verifies the given signature (e.g. produced by a signer which was given the hash in advance)
+/// against the given public key and hash, and combines them into a [`SignatureWithPublicKeyV1`].
+pub fn verify_external_signature(
+    public_key: &PublicKey,
+    message_hash: &impl IsHash,
+    signature: SignatureV1,
+) -> Result<SignatureWithPublicKeyV1, ExternalSignerError> {
+    if !verify(message_hash.as_ref(), public_key, &signature) {
+        return Err(ExternalSignerError::InvalidSignature);
+    }
+    match (public_key, signature) {
+        (PublicKey::Secp256k1(_), SignatureV1::Secp256k1(signature)) => Ok(signature.into()),
+        (PublicKey::Ed25519(public_key), SignatureV1::Ed25519(signature)) => {
+            Ok((*public_key, signature).into())
+        }
+        _ => Err(ExternalSignerError::InvalidSignature),
+    }
+}
+
+/// An [`ExternalSigner`] delegating to the given callback.
+pub struct CallbackSigner<F>
+where
+    F: Fn(&Hash) -> Result<SignatureV1, ExternalSignerError>,
+{
+    public_key: PublicKey,
+    callback: F,
+}
+
+impl<F> CallbackSigner<F>
+where
+    F: Fn(&Hash) -> Result<SignatureV1, ExternalSignerError>,
+{
+    pub fn new(public_key: PublicKey, callback: F) -> Self {
+        Self {
+            public_key,
+            callback,
+        }
+    }
+}
+
+impl<F> ExternalSigner for CallbackSigner<F>
+where
+    F: Fn(&Hash) -> Result<SignatureV1, ExternalSignerError>,
+{
+    fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    fn sign_hash(&self, message_hash: &Hash) -> Result<SignatureV1, ExternalSignerError> {
+        (self.callback)(message_hash)
+    }
+}
+
+/// An [`ExternalSigner`] running a local program for each signature.
+///
+/// The program receives the hex-encoded hash (followed by a newline) on its standard input, and is
+/// expected to print the hex-encoded signature (a 65-byte recoverable Secp256k1 signature or a
+/// 64-byte Ed25519 signature, matching the public key) on its standard output and exit with a
+/// success status.
+#[cfg(feature = "std")]
+pub struct SubprocessSigner {
+    public_key: PublicKey,
+    program: String,
+    args: Vec<String>,
+}
+
+#[cfg(feature = "std")]
+impl SubprocessSigner {
+    pub fn new<P: Into<String>, A: Into<String>>(
+        public_key: PublicKey,
+        program: P,
+        args: Vec<A>,
+    ) -> Self {
+        Self {
+            public_key,
+            program: program.into(),
+            args: args.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ExternalSigner for SubprocessSigner {
+    fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    fn sign_hash(&self, message_hash: &Hash) -> Result<SignatureV1, ExternalSignerError> {
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        let signing_failed = |error: std::io::Error| {
+            ExternalSignerError::SigningFailed(format!("{}: {}", self.program, error))
+        };
+
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(signing_failed)?;
+        child
+            .stdin
+            .take()
+            .expect("Child stdin is piped")
+            .write_all(format!("{}\n", hex::encode(message_hash.0)).as_bytes())
+            .map_err(signing_failed)?;
+        let output = child.wait_with_output().map_err(signing_failed)?;
+        if !output.status.success() {
+            return Err(ExternalSignerError::SigningFailed(format!(
+                "{}: {}",
+                self.program, output.status
+            )));
+        }
+
+        let stdout = String::from_utf8(output.stdout)
+            .map_err(|_| ExternalSignerError::MalformedSignature("Not UTF-8".to_owned()))?;
+        let signature = stdout.trim();
+        match self.public_key {
+            PublicKey::Secp256k1(_) => Secp256k1Signature::from_str(signature)
+                .map(Into::into)
+                .map_err(|error| ExternalSignerError::MalformedSignature(format!("{:?}", error))),
+            PublicKey::Ed25519(_) => Ed25519Signature::from_str(signature)
+                .map(Into::into)
+                .map_err(|error| ExternalSignerError::MalformedSignature(format!("{:?}", error))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn private_keys_sign_as_external_signers() {
+        let message_hash = hash("message");
+        let secp256k1_key = Secp256k1PrivateKey::from_u64(1).unwrap();
+        let ed25519_key = Ed25519PrivateKey::from_u64(1).unwrap();
+
+        assert_eq!(
+            sign_with_external_signer(&secp256k1_key, &message_hash),
+            Ok(secp256k1_key.sign_with_public_key(&message_hash))
+        );
+        assert_eq!(
+            sign_with_external_signer(&ed25519_key, &message_hash),
+            Ok(ed25519_key.sign_with_public_key(&message_hash))
+        );
+    }
+
+    #[test]
+    fn callback_signer_signature_is_verified() {
+        let message_hash = hash("message");
+        let private_key = Ed25519PrivateKey::from_u64(1).unwrap();
+        let other_private_key = Ed25519PrivateKey::from_u64(2).unwrap();
+
+        let signer = CallbackSigner::new(private_key.public_key().into(), |message_hash| {
+            Ok(private_key.sign(message_hash).into())
+        });
+        assert_eq!(
+            sign_with_external_signer(&signer, &message_hash),
+            Ok(private_key.sign_with_public_key(&message_hash))
+        );
+
+        let signer = CallbackSigner::new(private_key.public_key().into(), |message_hash| {
+            Ok(other_private_key.sign(message_hash).into())
+        });
+        assert_eq!(
+            sign_with_external_signer(&signer, &message_hash),
+            Err(ExternalSignerError::InvalidSignature)
+        );
+
+        let signer = CallbackSigner::new(private_key.public_key().into(), |_| {
+            Err(ExternalSignerError::SigningFailed("Rejected".to_owned()))
+        });
+        assert_eq!(
+            sign_with_external_signer(&signer, &message_hash),
+            Err(ExternalSignerError::SigningFailed("Rejected".to_owned()))
+        );
+    }
+
+    #[cfg(all(feature = "std", unix))]
+    #[test]
+    fn subprocess_signer_reads_signature_from_stdout() {
+        let message_hash = hash("message");
+        let private_key = Secp256k1PrivateKey::from_u64(1).unwrap();
+        let signature = private_key.sign(&message_hash);
+
+        let signer = SubprocessSigner::new(
+            private_key.public_key().into(),
+            "sh",
+            vec!["-c".to_owned(), format!("read hash && echo {}", signature)],
+        );
+        assert_eq!(
+            sign_with_external_signer(&signer, &message_hash),
+            Ok(signature.into())
+        );
+
+        let signer =
+            SubprocessSigner::new(private_key.public_key().into(), "sh", vec!["-c", "exit 1"]);
+        assert!(matches!(
+            sign_with_external_signer(&signer, &message_hash),
+            Err(ExternalSignerError::SigningFailed(_))
+        ));
+    }
+}
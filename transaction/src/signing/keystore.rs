@@ -0,0 +1,254 @@
+use crate::internal_prelude::*;
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use blake2::digest::consts::U32;
+use blake2::Blake2b;
+use hmac::SimpleHmac;
+
+/// The number of PBKDF2 iterations used by [`EncryptedKeystoreV1::encrypt()`].
+pub const KEYSTORE_DEFAULT_PBKDF2_ITERATIONS: u32 = 100_000;
+/// The length of the random salt used by [`EncryptedKeystoreV1::encrypt()`].
+pub const KEYSTORE_SALT_LENGTH: usize = 16;
+/// The length of the AES-GCM nonce (IV) prefixing the encrypted private key.
+pub const KEYSTORE_AES_NONCE_LENGTH: usize = 12;
+
+/// The function deriving the (256-bit) AES key from the keystore's password.
+#[derive(Debug, Clone, PartialEq, Eq, ManifestSbor)]
+pub enum KeystoreKdfV1 {
+    /// PBKDF2 (RFC-8018) with HMAC-Blake2b-256 as the pseudorandom function.
+    Pbkdf2HmacBlake2b256 { salt: Vec<u8>, iterations: u32 },
+}
+
+impl KeystoreKdfV1 {
+    fn derive_key(&self, password: &str) -> [u8; 32] {
+        match self {
+            KeystoreKdfV1::Pbkdf2HmacBlake2b256 { salt, iterations } => {
+                let mut key = [0u8; 32];
+                pbkdf2::pbkdf2::<SimpleHmac<Blake2b<U32>>>(
+                    password.as_bytes(),
+                    salt,
+                    *iterations,
+                    &mut key,
+                )
+                .expect("HMAC accepts keys of any length");
+                key
+            }
+        }
+    }
+}
+
+/// A private key stored encrypted under a password, e.g. in a file on disk.
+///
+/// The private key bytes are encrypted with AES-256-GCM (and stored as `nonce || ciphertext || tag`),
+/// using a key derived from the password by the [`KeystoreKdfV1`].
+/// The public key is stored in plaintext, so that the keystore can be identified (and used for
+/// e.g. building a transaction header) without the password.
+#[derive(Debug, Clone, PartialEq, Eq, ManifestSbor)]
+pub struct EncryptedKeystoreV1 {
+    pub public_key: PublicKey,
+    pub kdf: KeystoreKdfV1,
+    pub encrypted_private_key: AesGcmPayload,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ManifestSbor)]
+pub enum VersionedEncryptedKeystore {
+    V1(EncryptedKeystoreV1),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeystoreError {
+    EncodeError(EncodeError),
+    DecodeError(DecodeError),
+    IoError(String),
+    /// The private key could not be decrypted (i.e. the password is wrong or the keystore is
+    /// corrupted).
+    DecryptionFailed,
+    InvalidPrivateKey,
+    /// The decrypted private key does not match the keystore's public key.
+    PublicKeyMismatch,
+}
+
+impl EncryptedKeystoreV1 {
+    /// Encrypts the given private key under the given password, with a random salt and nonce and
+    /// the default KDF parameters.
+    #[cfg(feature = "std")]
+    pub fn encrypt(private_key: &PrivateKey, password: &str) -> Self {
+        use rand_core::{OsRng, RngCore};
+
+        let mut salt = vec![0u8; KEYSTORE_SALT_LENGTH];
+        let mut aes_nonce = [0u8; KEYSTORE_AES_NONCE_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut aes_nonce);
+        Self::encrypt_with_parameters(
+            private_key,
+            password,
+            KeystoreKdfV1::Pbkdf2HmacBlake2b256 {
+                salt,
+                iterations: KEYSTORE_DEFAULT_PBKDF2_ITERATIONS,
+            },
+            aes_nonce,
+        )
+    }
+
+    /// Encrypts the given private key under the given password, with the given KDF and nonce.
+    /// Exposed mostly for testing - regular clients should use [`Self::encrypt()`].
+    pub fn encrypt_with_parameters(
+        private_key: &PrivateKey,
+        password: &str,
+        kdf: KeystoreKdfV1,
+        aes_nonce: [u8; KEYSTORE_AES_NONCE_LENGTH],
+    ) -> Self {
+        let private_key_bytes = match private_key {
+            PrivateKey::Secp256k1(private_key) => private_key.to_bytes(),
+            PrivateKey::Ed25519(private_key) => private_key.to_bytes(),
+        };
+        let aes_key = kdf.derive_key(password);
+        let ciphertext = Aes256Gcm::new_from_slice(&aes_key)
+            .unwrap()
+            .encrypt(Nonce::from_slice(&aes_nonce), private_key_bytes.as_slice())
+            .expect("AES-GCM encryption of a short plaintext cannot fail");
+
+        let mut encrypted_private_key = aes_nonce.to_vec();
+        encrypted_private_key.extend(ciphertext);
+        Self {
+            public_key: private_key.public_key(),
+            kdf,
+            encrypted_private_key: AesGcmPayload(encrypted_private_key),
+        }
+    }
+
+    pub fn decrypt(&self, password: &str) -> Result<PrivateKey, KeystoreError> {
+        let encrypted_private_key = &self.encrypted_private_key.0;
+        if encrypted_private_key.len() < KEYSTORE_AES_NONCE_LENGTH {
+            return Err(KeystoreError::DecryptionFailed);
+        }
+        let (aes_nonce, ciphertext) = encrypted_private_key.split_at(KEYSTORE_AES_NONCE_LENGTH);
+        let aes_key = self.kdf.derive_key(password);
+        let private_key_bytes = Aes256Gcm::new_from_slice(&aes_key)
+            .unwrap()
+            .decrypt(Nonce::from_slice(aes_nonce), ciphertext)
+            .map_err(|_| KeystoreError::DecryptionFailed)?;
+
+        let private_key: PrivateKey = match self.public_key {
+            PublicKey::Secp256k1(_) => Secp256k1PrivateKey::from_bytes(&private_key_bytes)
+                .map_err(|_| KeystoreError::InvalidPrivateKey)?
+                .into(),
+            PublicKey::Ed25519(_) => Ed25519PrivateKey::from_bytes(&private_key_bytes)
+                .map_err(|_| KeystoreError::InvalidPrivateKey)?
+                .into(),
+        };
+        if private_key.public_key() != self.public_key {
+            return Err(KeystoreError::PublicKeyMismatch);
+        }
+        Ok(private_key)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, KeystoreError> {
+        manifest_encode(&VersionedEncryptedKeystore::V1(self.clone()))
+            .map_err(KeystoreError::EncodeError)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, KeystoreError> {
+        match manifest_decode(bytes).map_err(KeystoreError::DecodeError)? {
+            VersionedEncryptedKeystore::V1(keystore) => Ok(keystore),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn save_to_file<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), KeystoreError> {
+        std::fs::write(path, self.to_bytes()?)
+            .map_err(|error| KeystoreError::IoError(error.to_string()))
+    }
+
+    #[cfg(feature = "std")]
+    pub fn load_from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, KeystoreError> {
+        let bytes =
+            std::fs::read(path).map_err(|error| KeystoreError::IoError(error.to_string()))?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::copy_u8_array;
+
+    const PASSWORD: &str = "correct horse battery staple";
+
+    fn test_kdf() -> KeystoreKdfV1 {
+        KeystoreKdfV1::Pbkdf2HmacBlake2b256 {
+            salt: (0u8..16).collect(),
+            iterations: 1000,
+        }
+    }
+
+    fn test_aes_nonce() -> [u8; KEYSTORE_AES_NONCE_LENGTH] {
+        copy_u8_array(&(16u8..28).collect::<Vec<u8>>())
+    }
+
+    #[test]
+    fn keystore_matches_test_vector() {
+        let private_key: PrivateKey = Secp256k1PrivateKey::from_u64(1).unwrap().into();
+
+        let keystore = EncryptedKeystoreV1::encrypt_with_parameters(
+            &private_key,
+            PASSWORD,
+            test_kdf(),
+            test_aes_nonce(),
+        );
+
+        assert_eq!(
+            keystore.encrypted_private_key,
+            AesGcmPayload(
+                hex::decode(
+                    "101112131415161718191a1bae677117b48d8dd3fb1661d8f9ccdf34e5001a500615ab6e8ba4ad526f1003ec9cca40d0f29af22adf4a2cacb787e244"
+                )
+                .unwrap()
+            )
+        );
+        assert_eq!(keystore.public_key, private_key.public_key());
+    }
+
+    #[test]
+    fn keystore_roundtrips_through_bytes_and_password() {
+        for private_key in [
+            PrivateKey::from(Secp256k1PrivateKey::from_u64(2).unwrap()),
+            PrivateKey::from(Ed25519PrivateKey::from_u64(2).unwrap()),
+        ] {
+            let keystore = EncryptedKeystoreV1::encrypt_with_parameters(
+                &private_key,
+                PASSWORD,
+                test_kdf(),
+                test_aes_nonce(),
+            );
+            let keystore = EncryptedKeystoreV1::from_bytes(&keystore.to_bytes().unwrap()).unwrap();
+
+            let decrypted = keystore.decrypt(PASSWORD).unwrap();
+            assert_eq!(decrypted.public_key(), private_key.public_key());
+            assert_eq!(
+                keystore.decrypt("wrong password").map(|_| ()),
+                Err(KeystoreError::DecryptionFailed)
+            );
+        }
+    }
+
+    #[test]
+    fn keystore_with_swapped_public_key_is_rejected() {
+        let private_key: PrivateKey = Secp256k1PrivateKey::from_u64(1).unwrap().into();
+        let mut keystore = EncryptedKeystoreV1::encrypt_with_parameters(
+            &private_key,
+            PASSWORD,
+            test_kdf(),
+            test_aes_nonce(),
+        );
+        keystore.public_key = Secp256k1PrivateKey::from_u64(2)
+            .unwrap()
+            .public_key()
+            .into();
+
+        assert_eq!(
+            keystore.decrypt(PASSWORD).map(|_| ()),
+            Err(KeystoreError::PublicKeyMismatch)
+        );
+    }
+}
@@ -0,0 +1,437 @@
+use crate::internal_prelude::*;
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes128Gcm, Nonce};
+use aes_kw::KekAes256;
+use blake2::digest::consts::U32;
+use blake2::Blake2b;
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use curve25519_dalek::scalar::Scalar;
+use hkdf::SimpleHkdf;
+use sha2::{Digest, Sha512};
+use utils::copy_u8_array;
+
+/// The length of the (128-bit) AES-GCM symmetric key.
+pub const MESSAGE_AES_KEY_LENGTH: usize = 16;
+/// The length of the AES-GCM nonce (IV) prefixing the [`AesGcmPayload`].
+pub const MESSAGE_AES_NONCE_LENGTH: usize = 12;
+
+/// All the random inputs of the [`EncryptedMessageV1`] creation.
+/// Exposed mostly for testing (i.e. reproducible test vectors) - regular clients should use
+/// [`encrypt_message()`], which draws them from a secure source of randomness.
+#[derive(Debug, Clone)]
+pub struct MessageEncryptionRandomness {
+    pub aes_key: [u8; MESSAGE_AES_KEY_LENGTH],
+    pub aes_nonce: [u8; MESSAGE_AES_NONCE_LENGTH],
+    /// The ephemeral Secp256k1 private key (only used if there are any Secp256k1 decryptors).
+    pub secp256k1_ephemeral_private_key: [u8; Secp256k1PrivateKey::LENGTH],
+    /// The ephemeral Ed25519 private key (only used if there are any Ed25519 decryptors).
+    pub ed25519_ephemeral_private_key: [u8; Ed25519PrivateKey::LENGTH],
+}
+
+#[cfg(feature = "std")]
+impl MessageEncryptionRandomness {
+    pub fn generate() -> Self {
+        use rand_core::{OsRng, RngCore};
+
+        let mut randomness = Self {
+            aes_key: [0; MESSAGE_AES_KEY_LENGTH],
+            aes_nonce: [0; MESSAGE_AES_NONCE_LENGTH],
+            secp256k1_ephemeral_private_key: [0; Secp256k1PrivateKey::LENGTH],
+            ed25519_ephemeral_private_key: [0; Ed25519PrivateKey::LENGTH],
+        };
+        OsRng.fill_bytes(&mut randomness.aes_key);
+        OsRng.fill_bytes(&mut randomness.aes_nonce);
+        // a random 32-byte value is an invalid Secp256k1 key with a negligible probability
+        loop {
+            OsRng.fill_bytes(&mut randomness.secp256k1_ephemeral_private_key);
+            if Secp256k1PrivateKey::from_bytes(&randomness.secp256k1_ephemeral_private_key).is_ok()
+            {
+                break;
+            }
+        }
+        OsRng.fill_bytes(&mut randomness.ed25519_ephemeral_private_key);
+        randomness
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageEncryptionError {
+    NoDecryptors,
+    InvalidEphemeralPrivateKey,
+    InvalidDecryptorPublicKey(PublicKey),
+    EncodeError(EncodeError),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageDecryptionError {
+    /// The message was not encrypted for the given private key.
+    NotADecryptor,
+    InvalidEphemeralPublicKey,
+    /// The wrapped AES key could not be unwrapped (i.e. its integrity check failed).
+    KeyUnwrapFailed,
+    /// The AES-GCM payload could not be decrypted (i.e. its authentication tag is invalid).
+    PayloadDecryptionFailed,
+    DecodeError(DecodeError),
+}
+
+/// Encrypts the given message for the given decryptors, using the "MultiPartyECIES" scheme
+/// described on [`EncryptedMessageV1`].
+#[cfg(feature = "std")]
+pub fn encrypt_message(
+    plaintext_message: &PlaintextMessageV1,
+    decryptor_public_keys: &[PublicKey],
+) -> Result<EncryptedMessageV1, MessageEncryptionError> {
+    encrypt_message_with_randomness(
+        plaintext_message,
+        decryptor_public_keys,
+        &MessageEncryptionRandomness::generate(),
+    )
+}
+
+/// Encrypts the given message for the given decryptors, like [`encrypt_message()`], but using the
+/// given random inputs.
+/// The decryptors are grouped by curve type, in the order of their first occurrence.
+pub fn encrypt_message_with_randomness(
+    plaintext_message: &PlaintextMessageV1,
+    decryptor_public_keys: &[PublicKey],
+    randomness: &MessageEncryptionRandomness,
+) -> Result<EncryptedMessageV1, MessageEncryptionError> {
+    if decryptor_public_keys.is_empty() {
+        return Err(MessageEncryptionError::NoDecryptors);
+    }
+
+    let plaintext_bytes =
+        manifest_encode(plaintext_message).map_err(MessageEncryptionError::EncodeError)?;
+    let cipher = Aes128Gcm::new_from_slice(&randomness.aes_key).unwrap();
+    let nonce = Nonce::from_slice(&randomness.aes_nonce);
+    let mut payload = randomness.aes_nonce.to_vec();
+    payload.extend(
+        cipher
+            .encrypt(nonce, plaintext_bytes.as_ref())
+            .expect("AES-GCM encryption of an in-memory payload cannot fail"),
+    );
+
+    let secp256k1_ephemeral_private_key =
+        Secp256k1PrivateKey::from_bytes(&randomness.secp256k1_ephemeral_private_key)
+            .map_err(|_| MessageEncryptionError::InvalidEphemeralPrivateKey)?;
+    let ed25519_ephemeral_private_key =
+        Ed25519PrivateKey::from_bytes(&randomness.ed25519_ephemeral_private_key)
+            .map_err(|_| MessageEncryptionError::InvalidEphemeralPrivateKey)?;
+
+    let mut decryptors_by_curve: IndexMap<CurveType, DecryptorsByCurve> = index_map_new();
+    for decryptor_public_key in decryptor_public_keys {
+        let shared_secret = match decryptor_public_key {
+            PublicKey::Secp256k1(public_key) => {
+                secp256k1_shared_secret(&secp256k1_ephemeral_private_key, public_key)
+            }
+            PublicKey::Ed25519(public_key) => {
+                ed25519_shared_secret(&ed25519_ephemeral_private_key, public_key)
+            }
+        }
+        .ok_or(MessageEncryptionError::InvalidDecryptorPublicKey(
+            *decryptor_public_key,
+        ))?;
+        let mut wrapped_key = [0u8; AesWrapped128BitKey::LENGTH];
+        derive_key_encrypting_key(&shared_secret)
+            .wrap(&randomness.aes_key, &mut wrapped_key)
+            .expect("AES-KeyWrap of a 128-bit key cannot fail");
+
+        let decryptors = match decryptors_by_curve
+            .entry(curve_type_of(decryptor_public_key))
+            .or_insert_with(|| match decryptor_public_key {
+                PublicKey::Secp256k1(_) => DecryptorsByCurve::Secp256k1 {
+                    dh_ephemeral_public_key: secp256k1_ephemeral_private_key.public_key(),
+                    decryptors: index_map_new(),
+                },
+                PublicKey::Ed25519(_) => DecryptorsByCurve::Ed25519 {
+                    dh_ephemeral_public_key: ed25519_ephemeral_private_key.public_key(),
+                    decryptors: index_map_new(),
+                },
+            }) {
+            DecryptorsByCurve::Secp256k1 { decryptors, .. } => decryptors,
+            DecryptorsByCurve::Ed25519 { decryptors, .. } => decryptors,
+        };
+        decryptors.insert(
+            PublicKeyFingerprint::from(*decryptor_public_key),
+            AesWrapped128BitKey(wrapped_key),
+        );
+    }
+
+    Ok(EncryptedMessageV1 {
+        encrypted: AesGcmPayload(payload),
+        decryptors_by_curve,
+    })
+}
+
+/// Decrypts the given message using the given decryptor's private key.
+pub fn decrypt_message(
+    encrypted_message: &EncryptedMessageV1,
+    private_key: &PrivateKey,
+) -> Result<PlaintextMessageV1, MessageDecryptionError> {
+    let public_key = private_key.public_key();
+    let fingerprint = PublicKeyFingerprint::from(public_key);
+    let unwrapping_inputs = match (
+        private_key,
+        encrypted_message
+            .decryptors_by_curve
+            .get(&curve_type_of(&public_key)),
+    ) {
+        (
+            PrivateKey::Secp256k1(private_key),
+            Some(DecryptorsByCurve::Secp256k1 {
+                dh_ephemeral_public_key,
+                decryptors,
+            }),
+        ) => decryptors.get(&fingerprint).map(|wrapped_key| {
+            secp256k1_shared_secret(private_key, dh_ephemeral_public_key)
+                .map(|shared_secret| (shared_secret, wrapped_key))
+        }),
+        (
+            PrivateKey::Ed25519(private_key),
+            Some(DecryptorsByCurve::Ed25519 {
+                dh_ephemeral_public_key,
+                decryptors,
+            }),
+        ) => decryptors.get(&fingerprint).map(|wrapped_key| {
+            ed25519_shared_secret(private_key, dh_ephemeral_public_key)
+                .map(|shared_secret| (shared_secret, wrapped_key))
+        }),
+        _ => None,
+    }
+    .ok_or(MessageDecryptionError::NotADecryptor)?;
+    let (shared_secret, wrapped_key) =
+        unwrapping_inputs.ok_or(MessageDecryptionError::InvalidEphemeralPublicKey)?;
+
+    let mut aes_key = [0u8; MESSAGE_AES_KEY_LENGTH];
+    derive_key_encrypting_key(&shared_secret)
+        .unwrap(&wrapped_key.0, &mut aes_key)
+        .map_err(|_| MessageDecryptionError::KeyUnwrapFailed)?;
+
+    let payload = &encrypted_message.encrypted.0;
+    if payload.len() < MESSAGE_AES_NONCE_LENGTH {
+        return Err(MessageDecryptionError::PayloadDecryptionFailed);
+    }
+    let (nonce, ciphertext) = payload.split_at(MESSAGE_AES_NONCE_LENGTH);
+    let plaintext_bytes = Aes128Gcm::new_from_slice(&aes_key)
+        .unwrap()
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| MessageDecryptionError::PayloadDecryptionFailed)?;
+    manifest_decode(&plaintext_bytes).map_err(MessageDecryptionError::DecodeError)
+}
+
+fn curve_type_of(public_key: &PublicKey) -> CurveType {
+    match public_key {
+        PublicKey::Secp256k1(_) => CurveType::Secp256k1,
+        PublicKey::Ed25519(_) => CurveType::Ed25519,
+    }
+}
+
+/// Computes the `KEK = HKDF(hash: Blake2b, secret: shared_secret, salt: [], length: 256 bits)`.
+fn derive_key_encrypting_key(shared_secret: &[u8; 32]) -> KekAes256 {
+    let mut key_encrypting_key = [0u8; 32];
+    SimpleHkdf::<Blake2b<U32>>::new(None, shared_secret)
+        .expand(&[], &mut key_encrypting_key)
+        .expect("256 bits is a valid HKDF output length");
+    KekAes256::from(key_encrypting_key)
+}
+
+/// Returns the `x` coordinate of the Diffie-Hellman shared point (i.e. the ASN1 X9.63 variant).
+fn secp256k1_shared_secret(
+    private_key: &Secp256k1PrivateKey,
+    public_key: &Secp256k1PublicKey,
+) -> Option<[u8; 32]> {
+    let public_key = ::secp256k1::PublicKey::from_slice(&public_key.0).ok()?;
+    let secret_key = ::secp256k1::SecretKey::from_slice(&private_key.to_bytes()).ok()?;
+    let shared_point = ::secp256k1::ecdh::shared_secret_point(&public_key, &secret_key);
+    Some(copy_u8_array(&shared_point[..32]))
+}
+
+/// Returns the `u` coordinate of the X25519 shared point, computed from the Ed25519 keys converted
+/// to their Montgomery form (as per RFC-7748 and RFC-8032).
+fn ed25519_shared_secret(
+    private_key: &Ed25519PrivateKey,
+    public_key: &Ed25519PublicKey,
+) -> Option<[u8; 32]> {
+    let montgomery_point = CompressedEdwardsY(public_key.0)
+        .decompress()?
+        .to_montgomery();
+    let expanded_private_key = Sha512::digest(&private_key.to_bytes());
+    let mut scalar_bytes: [u8; 32] = copy_u8_array(&expanded_private_key[..32]);
+    scalar_bytes[0] &= 248;
+    scalar_bytes[31] &= 127;
+    scalar_bytes[31] |= 64;
+    let shared_point = montgomery_point * Scalar::from_bits(scalar_bytes);
+    Some(shared_point.to_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sbor::rust::str::FromStr;
+
+    fn test_plaintext_message() -> PlaintextMessageV1 {
+        PlaintextMessageV1 {
+            mime_type: "text/plain".to_string(),
+            message: MessageContentsV1::String("Hello Radix!".to_string()),
+        }
+    }
+
+    fn test_randomness() -> MessageEncryptionRandomness {
+        MessageEncryptionRandomness {
+            aes_key: copy_u8_array(&(0..16).collect::<Vec<u8>>()),
+            aes_nonce: copy_u8_array(&(100..112).collect::<Vec<u8>>()),
+            secp256k1_ephemeral_private_key: [0x11; 32],
+            ed25519_ephemeral_private_key: [0x22; 32],
+        }
+    }
+
+    fn fingerprint(hex: &str) -> PublicKeyFingerprint {
+        PublicKeyFingerprint(copy_u8_array(&hex::decode(hex).unwrap()))
+    }
+
+    fn wrapped_key(hex: &str) -> AesWrapped128BitKey {
+        AesWrapped128BitKey(copy_u8_array(&hex::decode(hex).unwrap()))
+    }
+
+    fn test_decryptor_private_keys() -> Vec<PrivateKey> {
+        vec![
+            Secp256k1PrivateKey::from_u64(1).unwrap().into(),
+            Ed25519PrivateKey::from_u64(1).unwrap().into(),
+            Secp256k1PrivateKey::from_u64(2).unwrap().into(),
+            Ed25519PrivateKey::from_u64(2).unwrap().into(),
+        ]
+    }
+
+    /// The test vector for other implementers.
+    /// It was computed independently of this module, in Python: the ECDH on secp256k1, the X25519
+    /// on the birationally-mapped Ed25519 keys, the AES-KeyWrap and the AES-GCM come from the
+    /// `cryptography` package (pyca), and the HKDF is built from `hashlib`'s HMAC-Blake2b-256.
+    fn expected_encrypted_message() -> EncryptedMessageV1 {
+        EncryptedMessageV1 {
+            encrypted: AesGcmPayload(
+                hex::decode(
+                    "6465666768696a6b6c6d6e6f57434ca9d176dd82ef11277ce7a345ce308974f3daccb1369b4fe3f6d89b8fd20d02e25537657006720d1dc7252eda80",
+                )
+                .unwrap(),
+            ),
+            decryptors_by_curve: indexmap!(
+                CurveType::Secp256k1 => DecryptorsByCurve::Secp256k1 {
+                    dh_ephemeral_public_key: Secp256k1PublicKey::from_str(
+                        "034f355bdcb7cc0af728ef3cceb9615d90684bb5b2ca5f859ab0f0b704075871aa",
+                    )
+                    .unwrap(),
+                    decryptors: indexmap!(
+                        fingerprint("a243c1128c2fe737") => wrapped_key(
+                            "d06de6daa2b46c2150b92b484dfa7f2f78ae0b9985d78931",
+                        ),
+                        fingerprint("4d5fc0f41699cd01") => wrapped_key(
+                            "28544649e5d93e4428463a60ce6cc39f27253074e45dc5e7",
+                        ),
+                    ),
+                },
+                CurveType::Ed25519 => DecryptorsByCurve::Ed25519 {
+                    dh_ephemeral_public_key: Ed25519PublicKey::from_str(
+                        "a09aa5f47a6759802ff955f8dc2d2a14a5c99d23be97f864127ff9383455a4f0",
+                    )
+                    .unwrap(),
+                    decryptors: indexmap!(
+                        fingerprint("f91fa8df2486c9ea") => wrapped_key(
+                            "937bf897fdddaf72eb93f6465bbbc44885e2e098b20f7e33",
+                        ),
+                        fingerprint("b403cf0557293778") => wrapped_key(
+                            "b5171da3911f89070c97c7930b40702f20a424065ae81587",
+                        ),
+                    ),
+                },
+            ),
+        }
+    }
+
+    #[test]
+    fn encryption_matches_test_vector() {
+        let decryptor_public_keys = test_decryptor_private_keys()
+            .iter()
+            .map(|private_key| private_key.public_key())
+            .collect::<Vec<_>>();
+
+        let encrypted_message = encrypt_message_with_randomness(
+            &test_plaintext_message(),
+            &decryptor_public_keys,
+            &test_randomness(),
+        )
+        .unwrap();
+
+        assert_eq!(encrypted_message, expected_encrypted_message());
+    }
+
+    #[test]
+    fn test_vector_can_be_decrypted_by_each_decryptor() {
+        let encrypted_message = expected_encrypted_message();
+
+        for private_key in test_decryptor_private_keys() {
+            assert_eq!(
+                decrypt_message(&encrypted_message, &private_key),
+                Ok(test_plaintext_message())
+            );
+        }
+    }
+
+    #[test]
+    fn message_cannot_be_decrypted_by_non_decryptor_or_when_tampered() {
+        let mut encrypted_message = expected_encrypted_message();
+        let non_decryptor: PrivateKey = Secp256k1PrivateKey::from_u64(3).unwrap().into();
+        let decryptor: PrivateKey = Ed25519PrivateKey::from_u64(1).unwrap().into();
+
+        assert_eq!(
+            decrypt_message(&encrypted_message, &non_decryptor),
+            Err(MessageDecryptionError::NotADecryptor)
+        );
+
+        let last_byte = encrypted_message.encrypted.0.last_mut().unwrap();
+        *last_byte ^= 0xFF;
+        assert_eq!(
+            decrypt_message(&encrypted_message, &decryptor),
+            Err(MessageDecryptionError::PayloadDecryptionFailed)
+        );
+    }
+
+    #[test]
+    fn secp256k1_shared_secret_with_private_key_one_is_x_coordinate_of_public_key() {
+        let private_key = Secp256k1PrivateKey::from_u64(1).unwrap();
+        let public_key = Secp256k1PrivateKey::from_u64(7).unwrap().public_key();
+
+        let shared_secret = secp256k1_shared_secret(&private_key, &public_key).unwrap();
+
+        assert_eq!(shared_secret[..], public_key.0[1..]);
+    }
+
+    #[test]
+    fn key_wrapping_matches_rfc_3394_test_vector() {
+        // RFC 3394, section 4.3: "Wrap 128 bits of Key Data with a 256-bit KEK"
+        let key_encrypting_key: [u8; 32] = copy_u8_array(&(0..32).collect::<Vec<u8>>());
+        let key_data = hex::decode("00112233445566778899AABBCCDDEEFF").unwrap();
+
+        let mut wrapped_key = [0u8; AesWrapped128BitKey::LENGTH];
+        KekAes256::from(key_encrypting_key)
+            .wrap(&key_data, &mut wrapped_key)
+            .unwrap();
+
+        assert_eq!(
+            wrapped_key.to_vec(),
+            hex::decode("64E8C3F9CE0F5BA263E9777905818A2A93C8191E7D6E8AE7").unwrap()
+        );
+    }
+
+    #[test]
+    fn encrypted_message_round_trips() {
+        let decryptor: PrivateKey = Ed25519PrivateKey::from_u64(7).unwrap().into();
+
+        let encrypted_message =
+            encrypt_message(&test_plaintext_message(), &[decryptor.public_key()]).unwrap();
+
+        assert_eq!(
+            decrypt_message(&encrypted_message, &decryptor),
+            Ok(test_plaintext_message())
+        );
+    }
+}
@@ -1,5 +1,11 @@
 pub mod ed25519;
+mod external_signer;
+mod keystore;
+mod message_encryption;
 pub mod secp256k1;
 mod signer;
 
+pub use external_signer::*;
+pub use keystore::*;
+pub use message_encryption::*;
 pub use signer::*;